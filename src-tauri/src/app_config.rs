@@ -130,6 +130,9 @@ pub struct InstalledSkill {
     /// README URL
     #[serde(skip_serializing_if = "Option::is_none")]
     pub readme_url: Option<String>,
+    /// 非 GitHub 仓库来源的下载地址（如 release 压缩包），仓库信息此时为空
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_url: Option<String>,
     /// 应用启用状态
     pub apps: SkillApps,
     /// 安装时间（Unix 时间戳）
@@ -147,7 +150,7 @@ pub struct UnmanagedSkill {
     /// 描述
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
-    /// 在哪些应用目录中发现（如 ["claude", "codex"]）
+    /// 在哪些应用目录中发现（如 ["claude", "codex"]；仅存在于 SSOT 时为 "ssot"）
     pub found_in: Vec<String>,
 }
 
@@ -465,6 +468,42 @@ impl MultiAppConfig {
         Ok(config)
     }
 
+    /// 从任意路径读取旧版 config.json（仅解析与结构迁移，不写回磁盘）
+    ///
+    /// 用于 `config migrate`：将备份/旧文件中的 v2 配置合并到当前数据库。
+    pub fn load_legacy_file(path: &std::path::Path) -> Result<Self, AppError> {
+        let content = std::fs::read_to_string(path).map_err(|e| AppError::io(path, e))?;
+        let content = content.trim_start_matches('\u{feff}');
+        let value: serde_json::Value =
+            serde_json::from_str(content).map_err(|e| AppError::json(path, e))?;
+
+        let is_v1 = value.as_object().is_some_and(|map| {
+            map.get("providers").is_some_and(|v| v.is_object())
+                && map.get("current").is_some_and(|v| v.is_string())
+                && !map.contains_key("apps")
+        });
+        if is_v1 {
+            return Err(AppError::localized(
+                "config.unsupported_v1",
+                "检测到旧版 v1 配置格式，无法迁移。请先使用 v3.2.x 版本将其升级为 v2 结构。",
+                "Detected legacy v1 config, which cannot be migrated. Upgrade it to v2 with v3.2.x first.",
+            ));
+        }
+
+        let mut config: Self =
+            serde_json::from_value(value).map_err(|e| AppError::json(path, e))?;
+
+        for app in ["claude", "codex", "gemini"] {
+            config.apps.entry(app.to_string()).or_default();
+        }
+        config.migrate_mcp_to_unified()?;
+        if let Some(old_claude_snippet) = config.claude_common_config_snippet.take() {
+            config.common_config_snippets.claude = Some(old_claude_snippet);
+        }
+
+        Ok(config)
+    }
+
     /// 保存配置到文件
     pub fn save(&self) -> Result<(), AppError> {
         let config_path = get_app_config_path();
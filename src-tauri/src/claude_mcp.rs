@@ -36,13 +36,13 @@ fn ensure_mcp_override_migrated() {
     }
 
     if let Some(parent) = new_path.parent() {
-        if let Err(err) = fs::create_dir_all(parent) {
+        if let Err(err) = crate::dry_run::create_dir_all(parent) {
             log::warn!("创建 MCP 目录失败: {err}");
             return;
         }
     }
 
-    match fs::copy(&legacy_path, &new_path) {
+    match crate::dry_run::copy(&legacy_path, &new_path) {
         Ok(_) => {
             log::info!(
                 "已根据覆盖目录复制 MCP 配置: {} -> {}",
@@ -72,7 +72,7 @@ fn read_json_value(path: &Path) -> Result<Value, AppError> {
 
 fn write_json_value(path: &Path, value: &Value) -> Result<(), AppError> {
     if let Some(parent) = path.parent() {
-        fs::create_dir_all(parent).map_err(|e| AppError::io(parent, e))?;
+        crate::dry_run::create_dir_all(parent).map_err(|e| AppError::io(parent, e))?;
     }
     let json =
         serde_json::to_string_pretty(value).map_err(|e| AppError::JsonSerialize { source: e })?;
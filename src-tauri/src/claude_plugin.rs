@@ -22,7 +22,7 @@ pub fn claude_config_path() -> Result<PathBuf, AppError> {
 pub fn ensure_claude_dir_exists() -> Result<PathBuf, AppError> {
     let dir = claude_dir()?;
     if !dir.exists() {
-        fs::create_dir_all(&dir).map_err(|e| AppError::io(&dir, e))?;
+        crate::dry_run::create_dir_all(&dir).map_err(|e| AppError::io(&dir, e))?;
     }
     Ok(dir)
 }
@@ -80,7 +80,8 @@ pub fn write_claude_config() -> Result<bool, AppError> {
     if changed || !path.exists() {
         let serialized = serde_json::to_string_pretty(&obj)
             .map_err(|e| AppError::JsonSerialize { source: e })?;
-        fs::write(&path, format!("{serialized}\n")).map_err(|e| AppError::io(&path, e))?;
+        crate::dry_run::write(&path, format!("{serialized}\n"))
+            .map_err(|e| AppError::io(&path, e))?;
         Ok(true)
     } else {
         Ok(false)
@@ -114,7 +115,7 @@ pub fn clear_claude_config() -> Result<bool, AppError> {
 
     let serialized =
         serde_json::to_string_pretty(&value).map_err(|e| AppError::JsonSerialize { source: e })?;
-    fs::write(&path, format!("{serialized}\n")).map_err(|e| AppError::io(&path, e))?;
+    crate::dry_run::write(&path, format!("{serialized}\n")).map_err(|e| AppError::io(&path, e))?;
     Ok(true)
 }
 
@@ -0,0 +1,514 @@
+use clap::Subcommand;
+use serde::Serialize;
+use std::path::PathBuf;
+
+use crate::app_config::AppType;
+use crate::cli::terminal::ensure_interactive;
+use crate::cli::ui::{
+    create_table, custom_theme_color, default_theme_color, emit, json_output, out,
+    parse_theme_color,
+};
+use crate::error::AppError;
+use crate::services::live_backup;
+use crate::services::{McpService, ProviderService, SkillService};
+use crate::settings::{
+    get_app_override_dir, get_post_switch_hook, get_theme_color, resolve_override_path,
+    set_app_override_dir, set_post_switch_hook, set_theme_color,
+};
+use crate::store::AppState;
+use colored::Colorize;
+
+#[derive(Subcommand)]
+pub enum AppCommand {
+    /// Show each app's config dir, install status and provider/skill/MCP counts
+    List,
+    /// Manage config directory overrides (e.g. a custom CLAUDE_CONFIG_DIR)
+    #[command(subcommand)]
+    Override(AppOverrideCommand),
+    /// Manage per-app highlight colors
+    #[command(subcommand)]
+    Theme(AppThemeCommand),
+    /// Manage commands run after provider events
+    #[command(subcommand)]
+    Hook(AppHookCommand),
+    /// Snapshot the app's live config files (e.g. ~/.claude/settings.json)
+    Backup {
+        /// List existing snapshots instead of creating one
+        #[arg(long)]
+        list: bool,
+    },
+    /// Put the app's live config files back from a snapshot
+    Restore {
+        /// Snapshot ID (from `app backup --list`); prompts when omitted
+        #[arg(long)]
+        backup: Option<String>,
+        /// Skip the confirmation prompt
+        #[arg(short = 'y', long)]
+        yes: bool,
+    },
+}
+
+/// Provider events that can trigger a hook
+#[derive(Debug, Clone, Copy, clap::ValueEnum)]
+pub enum HookEvent {
+    /// After `provider switch` writes the live config
+    Switch,
+}
+
+#[derive(Subcommand)]
+pub enum AppHookCommand {
+    /// Show configured hooks
+    Show,
+    /// Set the command for an event ({app} and {id} are replaced with shell-quoted values)
+    Set {
+        #[arg(value_enum)]
+        event: HookEvent,
+        /// Shell command template, e.g. "systemctl --user restart my-proxy"
+        command: String,
+    },
+    /// Remove the hook of an event
+    Clear {
+        #[arg(value_enum)]
+        event: HookEvent,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum AppThemeCommand {
+    /// Show the highlight color of each app
+    Show,
+    /// Set the highlight color of an app
+    Set {
+        /// Target app
+        #[arg(value_enum, value_name = "APP")]
+        target: AppType,
+        /// Color name (e.g. magenta, bright-cyan) or hex (#rrggbb)
+        color: String,
+    },
+    /// Reset the highlight color of an app to the default
+    Clear {
+        /// Target app
+        #[arg(value_enum, value_name = "APP")]
+        target: AppType,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum AppOverrideCommand {
+    /// Show config directories for all apps
+    Show,
+    /// Override the config directory of an app
+    Set {
+        /// Target app
+        #[arg(value_enum, value_name = "APP")]
+        target: AppType,
+        /// Config directory (supports ~)
+        path: String,
+        /// Create the directory if it does not exist
+        #[arg(long)]
+        create: bool,
+    },
+    /// Remove the config directory override of an app
+    Clear {
+        /// Target app
+        #[arg(value_enum, value_name = "APP")]
+        target: AppType,
+    },
+}
+
+pub fn execute(cmd: AppCommand, app: Option<AppType>) -> Result<(), AppError> {
+    let app_type = app.unwrap_or(AppType::Claude);
+    match cmd {
+        AppCommand::List => list_apps(),
+        AppCommand::Override(cmd) => match cmd {
+            AppOverrideCommand::Show => show_overrides(),
+            AppOverrideCommand::Set {
+                target,
+                path,
+                create,
+            } => set_override(&target, &path, create),
+            AppOverrideCommand::Clear { target } => clear_override(&target),
+        },
+        AppCommand::Theme(cmd) => match cmd {
+            AppThemeCommand::Show => show_themes(),
+            AppThemeCommand::Set { target, color } => set_theme(&target, &color),
+            AppThemeCommand::Clear { target } => clear_theme(&target),
+        },
+        AppCommand::Hook(cmd) => match cmd {
+            AppHookCommand::Show => show_hooks(),
+            AppHookCommand::Set { event, command } => set_hook(event, &command),
+            AppHookCommand::Clear { event } => clear_hook(event),
+        },
+        AppCommand::Backup { list: true } => list_live_backups(&app_type),
+        AppCommand::Backup { list: false } => backup_live_config(&app_type),
+        AppCommand::Restore { backup, yes } => {
+            restore_live_config(&app_type, backup.as_deref(), yes)
+        }
+    }
+}
+
+fn backup_live_config(app: &AppType) -> Result<(), AppError> {
+    let backup = live_backup::create(app)?;
+    if json_output() {
+        return emit(&backup);
+    }
+    out::success(&format!(
+        "✓ Backed up {} live config: {}",
+        app.as_str(),
+        backup.id
+    ));
+    for file in &backup.files {
+        println!("  {}", file.path.display());
+    }
+    println!("Location: {}", backup.dir.display());
+    Ok(())
+}
+
+fn list_live_backups(app: &AppType) -> Result<(), AppError> {
+    let backups = live_backup::list(app)?;
+    if json_output() {
+        return emit(&backups);
+    }
+    if backups.is_empty() {
+        out::info(&format!(
+            "No live config backups for {}; create one with `cc-switch app backup --app {}`",
+            app.as_str(),
+            app.as_str()
+        ));
+        return Ok(());
+    }
+    let mut table = create_table();
+    table.set_header(vec!["ID", "Created", "Files"]);
+    for b in &backups {
+        let files: Vec<String> = b.files.iter().map(|f| f.name.clone()).collect();
+        table.add_row(vec![b.id.clone(), b.created_at.clone(), files.join(", ")]);
+    }
+    println!("{table}");
+    Ok(())
+}
+
+fn restore_live_config(app: &AppType, id: Option<&str>, yes: bool) -> Result<(), AppError> {
+    let id = match id {
+        Some(id) => id.to_string(),
+        None => {
+            let backups = live_backup::list(app)?;
+            if backups.is_empty() {
+                out::info(&format!("No live config backups for {}.", app.as_str()));
+                return Ok(());
+            }
+            let choices: Vec<String> = backups
+                .iter()
+                .map(|b| {
+                    let files: Vec<&str> = b.files.iter().map(|f| f.name.as_str()).collect();
+                    format!("{} ({})", b.id, files.join(", "))
+                })
+                .collect();
+            ensure_interactive("selecting a snapshot", Some("--backup <id>"))?;
+            let selection = inquire::Select::new("Select a snapshot to restore:", choices)
+                .prompt()
+                .map_err(|e| AppError::Message(format!("Prompt failed: {e}")))?;
+            selection
+                .split_whitespace()
+                .next()
+                .unwrap_or_default()
+                .to_string()
+        }
+    };
+
+    if !yes {
+        ensure_interactive("the restore confirmation", Some("--yes"))?;
+        let confirm = inquire::Confirm::new(&format!(
+            "Overwrite {}'s live config files with snapshot '{id}'?",
+            app.as_str()
+        ))
+        .with_default(false)
+        .prompt()
+        .map_err(|e| AppError::Message(format!("Prompt failed: {e}")))?;
+        if !confirm {
+            out::info("Cancelled.");
+            return Ok(());
+        }
+    }
+
+    let pre_restore = live_backup::restore(app, &id)?;
+    out::success(&format!(
+        "✓ Restored {} live config from '{id}'",
+        app.as_str()
+    ));
+    if let Some(snapshot) = pre_restore {
+        out::info(&format!("  Pre-restore backup: {}", snapshot.id));
+    }
+    Ok(())
+}
+
+fn default_config_dir(app: &AppType) -> String {
+    match app {
+        AppType::Claude => "~/.claude",
+        AppType::Codex => "~/.codex",
+        AppType::Gemini => "~/.gemini",
+    }
+    .to_string()
+}
+
+/// `app list` 中单个应用的概况
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct AppOverview {
+    app: String,
+    config_dir: String,
+    config_dir_exists: bool,
+    overridden: bool,
+    /// CLI 可执行文件路径（PATH 中找不到时为 None）
+    binary: Option<String>,
+    current_provider: Option<String>,
+    providers: usize,
+    skills: usize,
+    mcp_servers: usize,
+}
+
+fn resolved_config_dir(app: &AppType) -> PathBuf {
+    match app {
+        AppType::Claude => crate::config::get_claude_config_dir(),
+        AppType::Codex => crate::codex_config::get_codex_config_dir(),
+        AppType::Gemini => crate::gemini_config::get_gemini_dir(),
+    }
+}
+
+fn list_apps() -> Result<(), AppError> {
+    let state = AppState::try_new()?;
+    let skills = SkillService::list_installed()?;
+    let servers = McpService::get_all_servers(&state)?;
+
+    let mut overviews = Vec::new();
+    for app in [AppType::Claude, AppType::Codex, AppType::Gemini] {
+        let config_dir = resolved_config_dir(&app);
+        let current = ProviderService::current(&state, app.clone())?;
+        overviews.push(AppOverview {
+            app: app.as_str().to_string(),
+            config_dir_exists: config_dir.is_dir(),
+            config_dir: config_dir.display().to_string(),
+            overridden: get_app_override_dir(&app).is_some(),
+            binary: which::which(app.as_str())
+                .ok()
+                .map(|p| p.display().to_string()),
+            current_provider: Some(current).filter(|id| !id.is_empty()),
+            providers: ProviderService::list(&state, app.clone())?.len(),
+            skills: skills
+                .iter()
+                .filter(|s| s.apps.is_enabled_for(&app))
+                .count(),
+            mcp_servers: servers
+                .values()
+                .filter(|s| s.apps.is_enabled_for(&app))
+                .count(),
+        });
+    }
+
+    if json_output() {
+        emit(&overviews)?;
+        return Ok(());
+    }
+
+    let mut table = create_table();
+    table.set_header(vec![
+        "App",
+        "Config Dir",
+        "Binary",
+        "Current",
+        "Providers",
+        "Skills",
+        "MCP",
+    ]);
+    for o in &overviews {
+        let mut dir = o.config_dir.clone();
+        if o.overridden {
+            dir.push_str(" (override)");
+        }
+        if !o.config_dir_exists {
+            dir.push_str(" (missing)");
+        }
+        table.add_row(vec![
+            o.app.clone(),
+            dir,
+            o.binary.clone().unwrap_or_else(|| "not found".to_string()),
+            o.current_provider
+                .clone()
+                .unwrap_or_else(|| "-".to_string()),
+            o.providers.to_string(),
+            o.skills.to_string(),
+            o.mcp_servers.to_string(),
+        ]);
+    }
+
+    out::highlight("Apps");
+    println!("{}", table);
+    Ok(())
+}
+
+fn show_overrides() -> Result<(), AppError> {
+    let mut table = create_table();
+    table.set_header(vec!["App", "Config Dir", "Source"]);
+    for app in [AppType::Claude, AppType::Codex, AppType::Gemini] {
+        let (dir, source) = match get_app_override_dir(&app) {
+            Some(dir) => (dir.display().to_string(), "override"),
+            None => (default_config_dir(&app), "default"),
+        };
+        table.add_row(vec![app.as_str().to_string(), dir, source.to_string()]);
+    }
+
+    out::highlight("Config Directory Overrides");
+    println!("{}", table);
+    Ok(())
+}
+
+fn set_override(app: &AppType, raw: &str, create: bool) -> Result<(), AppError> {
+    let raw = raw.trim();
+    if raw.is_empty() {
+        return Err(AppError::InvalidInput(
+            "Path cannot be empty (use `app override clear` to remove an override)".to_string(),
+        ));
+    }
+
+    let path = resolve_override_path(raw);
+    if !path.exists() {
+        if !create {
+            return Err(AppError::InvalidInput(format!(
+                "Directory does not exist: {} (pass --create to create it)",
+                path.display()
+            )));
+        }
+        crate::dry_run::create_dir_all(&path).map_err(|e| AppError::io(&path, e))?;
+    } else if !path.is_dir() {
+        return Err(AppError::InvalidInput(format!(
+            "Not a directory: {}",
+            path.display()
+        )));
+    }
+
+    set_app_override_dir(app, Some(raw.to_string()))?;
+    out::success(&format!(
+        "✓ {} config directory set to {}",
+        app.as_str(),
+        path.display()
+    ));
+    Ok(())
+}
+
+fn clear_override(app: &AppType) -> Result<(), AppError> {
+    if get_app_override_dir(app).is_none() {
+        out::info(&format!("No override configured for {}.", app.as_str()));
+        return Ok(());
+    }
+
+    set_app_override_dir(app, None)?;
+    out::success(&format!(
+        "✓ {} config directory reset to {}",
+        app.as_str(),
+        default_config_dir(app)
+    ));
+    Ok(())
+}
+
+fn default_theme_name(app: &AppType) -> &'static str {
+    match app {
+        AppType::Claude => "bright-cyan",
+        AppType::Codex => "bright-green",
+        AppType::Gemini => "bright-magenta",
+    }
+}
+
+fn show_themes() -> Result<(), AppError> {
+    let mut table = create_table();
+    table.set_header(vec!["App", "Color", "Source", "Preview"]);
+    for app in [AppType::Claude, AppType::Codex, AppType::Gemini] {
+        let (color, source) = match get_theme_color(&app) {
+            Some(raw) if parse_theme_color(&raw).is_some() => (raw, "custom"),
+            Some(raw) => (format!("{raw} (invalid)"), "default"),
+            None => (default_theme_name(&app).to_string(), "default"),
+        };
+        let accent = custom_theme_color(&app).unwrap_or_else(|| default_theme_color(&app));
+        table.add_row(vec![
+            app.as_str().to_string(),
+            color,
+            source.to_string(),
+            app.as_str().color(accent).bold().to_string(),
+        ]);
+    }
+
+    out::highlight("App Theme Colors");
+    println!("{}", table);
+    Ok(())
+}
+
+fn set_theme(app: &AppType, raw: &str) -> Result<(), AppError> {
+    let raw = raw.trim();
+    if parse_theme_color(raw).is_none() {
+        return Err(AppError::InvalidInput(format!(
+            "Invalid color: '{raw}' (use a name like magenta / bright-cyan, or #rrggbb)"
+        )));
+    }
+
+    set_theme_color(app, Some(raw.to_string()))?;
+    out::success(&format!(
+        "✓ {} highlight color set to {}",
+        app.as_str(),
+        raw
+    ));
+    Ok(())
+}
+
+fn clear_theme(app: &AppType) -> Result<(), AppError> {
+    if get_theme_color(app).is_none() {
+        out::info(&format!("No custom color configured for {}.", app.as_str()));
+        return Ok(());
+    }
+
+    set_theme_color(app, None)?;
+    out::success(&format!(
+        "✓ {} highlight color reset to default",
+        app.as_str()
+    ));
+    Ok(())
+}
+
+fn show_hooks() -> Result<(), AppError> {
+    let mut table = create_table();
+    table.set_header(vec!["Event", "Command"]);
+    table.add_row(vec![
+        "switch".to_string(),
+        get_post_switch_hook().unwrap_or_else(|| "(none)".to_string()),
+    ]);
+
+    out::highlight("Hooks");
+    println!("{}", table);
+    Ok(())
+}
+
+fn set_hook(event: HookEvent, command: &str) -> Result<(), AppError> {
+    let command = command.trim();
+    if command.is_empty() {
+        return Err(AppError::InvalidInput(
+            "Command cannot be empty (use `app hook clear` to remove a hook)".to_string(),
+        ));
+    }
+
+    match event {
+        HookEvent::Switch => set_post_switch_hook(Some(command.to_string()))?,
+    }
+    out::success(&format!("✓ switch hook set: {command}"));
+    Ok(())
+}
+
+fn clear_hook(event: HookEvent) -> Result<(), AppError> {
+    match event {
+        HookEvent::Switch => {
+            if get_post_switch_hook().is_none() {
+                out::info("No switch hook configured.");
+                return Ok(());
+            }
+            set_post_switch_hook(None)?;
+        }
+    }
+    out::success("✓ switch hook cleared");
+    Ok(())
+}
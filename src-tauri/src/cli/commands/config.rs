@@ -4,32 +4,73 @@ use std::path::{Path, PathBuf};
 
 use crate::app_config::AppType;
 use crate::cli::i18n::texts;
-use crate::cli::ui::{error, highlight, info, success, to_json};
+use crate::cli::terminal::ensure_interactive;
+use crate::cli::ui::{
+    create_table, emit, error, format_bytes, highlight, info, json_output, out,
+    redact_secrets_in_json, reveal_secrets, success, to_json,
+};
 use crate::error::AppError;
-use crate::services::ConfigService;
+use crate::services::{BackupInfo, BackupMeta, ConfigService, ConflictStrategy, SkillService};
 use crate::store::AppState;
 
 #[derive(Subcommand)]
 pub enum ConfigCommand {
-    /// Show current configuration
-    Show,
-    /// Show configuration file path
+    /// Show current configuration (secrets masked by default)
+    Show {
+        /// Mask token-like values (default)
+        #[arg(long, conflicts_with = "full")]
+        redacted: bool,
+        /// Show secrets in full (same as --reveal-secrets for this command)
+        #[arg(long)]
+        full: bool,
+        /// Only show what differs from a fresh default config and skills index
+        #[arg(long)]
+        diff_default: bool,
+    },
+    /// Show config, settings, skills, cache and per-app live config paths
     Path,
     /// Export configuration to file
     Export {
         /// Output file path
         file: PathBuf,
+        /// Encrypt the export with a passphrase (prompted, or CC_SWITCH_EXPORT_PASSPHRASE)
+        #[arg(long)]
+        encrypted: bool,
     },
     /// Import configuration from file
     Import {
         /// Input file path
         file: PathBuf,
+        /// Preview what would change without modifying anything
+        #[arg(long)]
+        dry_run: bool,
+        /// Upsert records into the current database instead of replacing it
+        #[arg(long)]
+        merge: bool,
+        /// With --merge: keep (skip) or overwrite existing records with the same id
+        #[arg(long, value_enum, requires = "merge", default_value_t = ConflictStrategy::Skip)]
+        on_conflict: ConflictStrategy,
+        /// Skip the confirmation prompt
+        #[arg(short, long)]
+        yes: bool,
     },
     /// Create a backup of current configuration
     Backup {
         /// Optional custom name for the backup
         #[arg(long)]
         name: Option<String>,
+
+        /// Gzip the backup into <id>.sql.gz
+        #[arg(long)]
+        compress: bool,
+
+        /// Free-form description stored in <id>.meta.json
+        #[arg(long)]
+        description: Option<String>,
+
+        /// Tag the backup (repeatable, e.g. --tag pre-upgrade)
+        #[arg(long = "tag", value_name = "TAG")]
+        tags: Vec<String>,
     },
     /// Restore from a backup
     Restore {
@@ -40,11 +81,45 @@ pub enum ConfigCommand {
         /// External file path to restore from
         #[arg(long, conflicts_with = "backup")]
         file: Option<PathBuf>,
+
+        /// Preview what would change without modifying anything
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Only list backups carrying this tag
+        #[arg(long, conflicts_with_all = ["backup", "file"])]
+        tag: Option<String>,
+
+        /// Skip the confirmation prompt
+        #[arg(short, long)]
+        yes: bool,
+    },
+    /// Merge a legacy config.json into the current database
+    Migrate {
+        /// Legacy config.json to migrate (archived as <file>.migrated afterwards)
+        file: PathBuf,
+
+        /// How to handle entries whose id already exists in the database
+        #[arg(long, value_enum, default_value_t = ConflictStrategy::Skip)]
+        on_conflict: ConflictStrategy,
+    },
+    /// Detect stored schema versions and apply pending migrations (e.g. after a self-update)
+    Upgrade {
+        /// Only report what is pending; change nothing
+        #[arg(long)]
+        check: bool,
     },
     /// Validate configuration file
     Validate,
+    /// Compact the database (VACUUM + PRAGMA optimize)
+    #[command(alias = "optimize")]
+    Vacuum,
     /// Reset to default configuration
-    Reset,
+    Reset {
+        /// Skip the confirmation prompt
+        #[arg(short, long)]
+        yes: bool,
+    },
 
     /// Manage common configuration snippet (per app)
     #[command(subcommand)]
@@ -68,6 +143,10 @@ pub enum CommonConfigCommand {
         /// Apply to current provider immediately
         #[arg(long)]
         apply: bool,
+
+        /// Skip the built-in per-app schema validation
+        #[arg(long)]
+        no_validate: bool,
     },
     /// Clear common config snippet
     Clear {
@@ -79,16 +158,54 @@ pub enum CommonConfigCommand {
 
 pub fn execute(cmd: ConfigCommand, app: Option<AppType>) -> Result<(), AppError> {
     match cmd {
-        ConfigCommand::Show => show_config(),
+        ConfigCommand::Show {
+            full, diff_default, ..
+        } => {
+            if diff_default {
+                show_config_diff_default(full)
+            } else {
+                show_config(full)
+            }
+        }
         ConfigCommand::Path => show_path(),
-        ConfigCommand::Export { file } => export_config(&file),
-        ConfigCommand::Import { file } => import_config(&file),
-        ConfigCommand::Backup { name } => backup_config(name.as_deref()),
-        ConfigCommand::Restore { backup, file } => {
-            restore_config(backup.as_deref(), file.as_deref())
+        ConfigCommand::Export { file, encrypted } => export_config(&file, encrypted),
+        ConfigCommand::Import {
+            file,
+            dry_run,
+            merge,
+            on_conflict,
+            yes,
+        } => {
+            if merge {
+                merge_import_config(&file, on_conflict, dry_run)
+            } else {
+                import_config(&file, dry_run, yes)
+            }
         }
+        ConfigCommand::Backup {
+            name,
+            compress,
+            description,
+            tags,
+        } => backup_config(name.as_deref(), compress, description, tags),
+        ConfigCommand::Restore {
+            backup,
+            file,
+            dry_run,
+            tag,
+            yes,
+        } => restore_config(
+            backup.as_deref(),
+            file.as_deref(),
+            dry_run,
+            tag.as_deref(),
+            yes,
+        ),
+        ConfigCommand::Migrate { file, on_conflict } => migrate_config(&file, on_conflict),
+        ConfigCommand::Upgrade { check } => upgrade_config(check),
         ConfigCommand::Validate => validate_config(),
-        ConfigCommand::Reset => reset_config(),
+        ConfigCommand::Vacuum => vacuum_database(),
+        ConfigCommand::Reset { yes } => reset_config(yes),
         ConfigCommand::Common(cmd) => execute_common(cmd, app.unwrap_or(AppType::Claude)),
     }
 }
@@ -97,27 +214,196 @@ fn get_state() -> Result<AppState, AppError> {
     AppState::try_new()
 }
 
-fn show_config() -> Result<(), AppError> {
+fn migrate_config(file: &Path, strategy: ConflictStrategy) -> Result<(), AppError> {
+    if !file.exists() {
+        return Err(AppError::Message(format!(
+            "File '{}' not found",
+            file.display()
+        )));
+    }
+
+    let state = get_state()?;
+    let report = ConfigService::migrate_legacy_config(file, strategy, &state)?;
+
+    out::success(&format!("✓ Migrated legacy config from {}", file.display()));
+    println!("  Providers:   {}", report.providers);
+    println!("  Prompts:     {}", report.prompts);
+    println!("  MCP servers: {}", report.mcp_servers);
+    if report.skipped > 0 {
+        out::info(&format!(
+            "  Skipped {} existing entries (use --on-conflict overwrite to replace them)",
+            report.skipped
+        ));
+    }
+    if let Some(archived) = report.archived_to {
+        out::info(&format!("  Archived source to {}", archived.display()));
+    }
+
+    Ok(())
+}
+
+fn upgrade_config(check: bool) -> Result<(), AppError> {
+    let report = ConfigService::upgrade(!check)?;
+    if json_output() {
+        return emit(&report);
+    }
+
+    let stored = report
+        .database_version
+        .map_or_else(|| "none".to_string(), |v| format!("v{v}"));
+    println!("Database:     {}", report.database_path.display());
+    println!(
+        "Schema:       {stored} (this build: v{})",
+        report.target_version
+    );
+    if let Some(version) = report.skills_index_version {
+        println!("skills.json:  index v{version} (legacy, imported when the database is created)");
+    }
+    if report.legacy_config {
+        println!("config.json:  legacy (imported when the database is created)");
+    }
+    println!();
+
+    if !report.is_pending() {
+        out::success("✓ Configuration is up to date");
+        return Ok(());
+    }
+    let mut steps = match report.database_version {
+        Some(from) => (from..report.target_version)
+            .map(|v| format!("database v{v} → v{}", v + 1))
+            .collect::<Vec<_>>(),
+        None => vec![format!("create database at v{}", report.target_version)],
+    };
+    if report.skills_index_version.is_some() {
+        steps.push("import legacy skills.json".to_string());
+    }
+    if report.legacy_config {
+        steps.push("import legacy config.json".to_string());
+    }
+
+    if report.applied {
+        out::success("✓ Upgrade complete");
+    } else {
+        out::info("Pending:");
+    }
+    for step in &steps {
+        println!("  - {step}");
+    }
+    if !report.applied {
+        out::info("Run `cc-switch config upgrade` to apply.");
+    }
+    if let Some(backup) = &report.backup_path {
+        out::info(&format!(
+            "Backup of the previous database: {}",
+            backup.display()
+        ));
+    }
+    Ok(())
+}
+
+fn show_config(full: bool) -> Result<(), AppError> {
     let state = get_state()?;
     let config = state.config.read()?;
 
-    println!("{}", highlight("Current Configuration"));
+    // Secrets masked unless --full / --reveal-secrets, in every output format
+    let (value, redacted) = display_value(&*config, full)?;
+    if json_output() {
+        // 结构化输出保持 stdout 干净，提示写到 stderr
+        if redacted && !out::quiet() {
+            eprintln!("{}", REDACTED_NOTE);
+        }
+        return emit(&value);
+    }
+
+    out::highlight("Current Configuration");
     println!("{}", "=".repeat(50));
     println!();
 
-    // Display in pretty JSON format
-    let json = to_json(&*config).map_err(|e| AppError::Message(e.to_string()))?;
+    let json = to_json(&value).map_err(|e| AppError::Message(e.to_string()))?;
     println!("{}", json);
+    if redacted {
+        println!();
+        out::info(REDACTED_NOTE);
+    }
+
+    Ok(())
+}
+
+fn show_config_diff_default(full: bool) -> Result<(), AppError> {
+    let state = get_state()?;
+    let deviations = ConfigService::diff_from_default(&*state.config.read()?)?;
+
+    let (value, redacted) = display_value(&deviations, full)?;
+    if json_output() {
+        if redacted && !out::quiet() {
+            eprintln!("{}", REDACTED_NOTE);
+        }
+        return emit(&value);
+    }
+
+    out::highlight("Changes from Default Configuration");
+    println!("{}", "=".repeat(50));
+    if deviations.is_empty() {
+        out::info("No changes: the configuration matches a fresh default.");
+        return Ok(());
+    }
+    for section in &deviations.sections {
+        println!("\n{}", highlight(&section.section));
+        for id in &section.added {
+            println!("  {} {}", success("+"), id);
+        }
+        for id in &section.removed {
+            println!("  {} {}", error("-"), id);
+        }
+        for id in &section.changed {
+            println!("  {} {}", info("~"), id);
+        }
+    }
+    for key in ["config", "skills"] {
+        if value[key].as_object().is_some_and(|map| !map.is_empty()) {
+            println!("\n{}", highlight(key));
+            let json = to_json(&value[key]).map_err(|e| AppError::Message(e.to_string()))?;
+            println!("{}", json);
+        }
+    }
+    if redacted {
+        println!();
+        out::info(REDACTED_NOTE);
+    }
 
     Ok(())
 }
 
+const REDACTED_NOTE: &str = "Secrets are masked; pass --full to show raw values";
+
+/// 序列化用于展示的副本；除非 `--full` / `--reveal-secrets`，否则脱敏。返回值与是否已脱敏
+fn display_value<T: serde::Serialize>(
+    value: &T,
+    full: bool,
+) -> Result<(serde_json::Value, bool), AppError> {
+    let mut value = serde_json::to_value(value).map_err(|e| AppError::Message(e.to_string()))?;
+    let redacted = !(full || reveal_secrets());
+    if redacted {
+        redact_secrets_in_json(&mut value);
+    }
+    Ok((value, redacted))
+}
+
 fn execute_common(cmd: CommonConfigCommand, app_type: AppType) -> Result<(), AppError> {
     match cmd {
         CommonConfigCommand::Show => show_common(app_type),
-        CommonConfigCommand::Set { json, file, apply } => {
-            set_common(app_type, json.as_deref(), file.as_deref(), apply)
-        }
+        CommonConfigCommand::Set {
+            json,
+            file,
+            apply,
+            no_validate,
+        } => set_common(
+            app_type,
+            json.as_deref(),
+            file.as_deref(),
+            apply,
+            !no_validate,
+        ),
         CommonConfigCommand::Clear { apply } => clear_common(app_type, apply),
     }
 }
@@ -127,7 +413,7 @@ fn show_common(app_type: AppType) -> Result<(), AppError> {
     let config = state.config.read()?;
     let snippet = config.common_config_snippets.get(&app_type).cloned();
 
-    println!("{}", highlight(texts::config_common_snippet_title()));
+    out::highlight(texts::config_common_snippet_title());
     println!("{}", "=".repeat(50));
     println!("App: {}", app_type.as_str());
     println!();
@@ -137,7 +423,7 @@ fn show_common(app_type: AppType) -> Result<(), AppError> {
             println!("{}", s);
         }
         _ => {
-            println!("{}", info(texts::config_common_snippet_none_set()));
+            out::info(texts::config_common_snippet_none_set());
         }
     }
 
@@ -149,6 +435,7 @@ fn set_common(
     json_text: Option<&str>,
     file: Option<&Path>,
     apply: bool,
+    validate: bool,
 ) -> Result<(), AppError> {
     let raw = if let Some(text) = json_text {
         text.to_string()
@@ -167,6 +454,9 @@ fn set_common(
             texts::common_config_snippet_not_object().to_string(),
         ));
     }
+    if validate {
+        crate::services::validate_common_config_snippet(&app_type, &value)?;
+    }
 
     let pretty = serde_json::to_string_pretty(&value)
         .map_err(|e| AppError::Message(texts::failed_to_serialize_json(&e.to_string())))?;
@@ -178,17 +468,13 @@ fn set_common(
     }
     state.save()?;
 
-    println!(
-        "{}",
-        success(&texts::config_common_snippet_set_for_app(app_type.as_str()))
-    );
+    out::success(&texts::config_common_snippet_set_for_app(app_type.as_str()));
 
     if apply {
         apply_common_to_current(&state, app_type)?;
     } else {
-        println!(
-            "{}",
-            info("Tip: run `cc-switch provider switch <id>` to re-apply settings to the live config.")
+        out::info(
+            "Tip: run `cc-switch provider switch <id>` to re-apply settings to the live config.",
         );
     }
 
@@ -203,20 +489,16 @@ fn clear_common(app_type: AppType, apply: bool) -> Result<(), AppError> {
     }
     state.save()?;
 
-    println!(
-        "{}",
-        success(&format!(
-            "✓ Common config snippet cleared for app '{}'",
-            app_type.as_str()
-        ))
-    );
+    out::success(&format!(
+        "✓ Common config snippet cleared for app '{}'",
+        app_type.as_str()
+    ));
 
     if apply {
         apply_common_to_current(&state, app_type)?;
     } else {
-        println!(
-            "{}",
-            info("Tip: run `cc-switch provider switch <id>` to re-apply settings to the live config.")
+        out::info(
+            "Tip: run `cc-switch provider switch <id>` to re-apply settings to the live config.",
         );
     }
 
@@ -228,25 +510,75 @@ fn apply_common_to_current(state: &AppState, app_type: AppType) -> Result<(), Ap
 
     let current_id = ProviderService::current(state, app_type.clone())?;
     if current_id.trim().is_empty() {
-        println!("{}", info("No current provider; nothing to apply."));
+        out::info("No current provider; nothing to apply.");
         return Ok(());
     }
 
     ProviderService::switch(state, app_type, &current_id)?;
-    println!("{}", success("✓ Applied to live config."));
+    out::success("✓ Applied to live config.");
     Ok(())
 }
 
+/// 某个应用的 live 配置文件（按当前目录覆盖解析）
+fn app_live_paths(app_type: &AppType) -> Vec<PathBuf> {
+    match app_type {
+        AppType::Claude => vec![
+            crate::config::get_claude_settings_path(),
+            crate::config::get_claude_mcp_path(),
+        ],
+        AppType::Codex => vec![
+            crate::codex_config::get_codex_config_path(),
+            crate::codex_config::get_codex_auth_path(),
+        ],
+        AppType::Gemini => vec![
+            crate::gemini_config::get_gemini_env_path(),
+            crate::gemini_config::get_gemini_settings_path(),
+        ],
+    }
+}
+
 fn show_path() -> Result<(), AppError> {
     let config_dir = crate::config::get_app_config_dir();
     let db_path = config_dir.join("cc-switch.db");
-    let legacy_config_path = config_dir.join("config.json");
+    let legacy_config_path = crate::config::get_app_config_path();
+    let settings_path = crate::settings::get_settings_path();
+    let backup_dir = config_dir.join("backups");
+    let skills_dir = SkillService::get_ssot_dir()?;
+    let cache_dir = crate::config::get_app_cache_dir();
+
+    let apps = [AppType::Claude, AppType::Codex, AppType::Gemini];
+    if json_output() {
+        let mut app_paths = serde_json::Map::new();
+        for app_type in &apps {
+            app_paths.insert(
+                app_type.as_str().to_string(),
+                serde_json::json!({
+                    "liveConfig": app_live_paths(app_type),
+                    "skillsDir": SkillService::get_app_skills_dir(app_type)?,
+                }),
+            );
+        }
+        return emit(&serde_json::json!({
+            "configDir": config_dir,
+            "database": db_path,
+            "databaseExists": db_path.exists(),
+            "legacyConfig": legacy_config_path,
+            "settings": settings_path,
+            "backupsDir": backup_dir,
+            "skillsDir": skills_dir,
+            "cacheDir": cache_dir,
+            "apps": app_paths,
+        }));
+    }
 
-    println!("{}", highlight("Configuration Paths"));
+    out::highlight("Configuration Paths");
     println!("{}", "=".repeat(50));
     println!("DB file:      {}", db_path.display());
     println!("Legacy JSON:  {}", legacy_config_path.display());
     println!("Config dir:   {}", config_dir.display());
+    println!("Settings:     {}", settings_path.display());
+    println!("Skills dir:   {}", skills_dir.display());
+    println!("Cache dir:    {}", cache_dir.display());
 
     // Check if DB file exists
     if db_path.exists() {
@@ -258,11 +590,10 @@ fn show_path() -> Result<(), AppError> {
         }
     } else {
         println!("\n{} Database file does not exist", error("✗"));
-        println!("{}", info("Run cc-switch once to create the database."));
+        out::info("Run cc-switch once to create the database.");
     }
 
     // Show backup directory
-    let backup_dir = config_dir.join("backups");
     if backup_dir.exists() {
         if let Ok(entries) = fs::read_dir(&backup_dir) {
             let count = entries.filter_map(|e| e.ok()).count();
@@ -271,17 +602,67 @@ fn show_path() -> Result<(), AppError> {
         }
     }
 
+    println!();
+    for app_type in &apps {
+        let live: Vec<String> = app_live_paths(app_type)
+            .iter()
+            .map(|p| p.display().to_string())
+            .collect();
+        println!(
+            "{:<13} {}",
+            format!("{}:", app_type.as_str()),
+            live.join(", ")
+        );
+    }
+
     Ok(())
 }
 
-fn export_config(file: &PathBuf) -> Result<(), AppError> {
-    println!(
-        "{}",
-        info(&format!("Exporting configuration to {}...", file.display()))
-    );
+/// 非交互场景可通过该环境变量提供加密导出/导入口令
+const EXPORT_PASSPHRASE_ENV: &str = "CC_SWITCH_EXPORT_PASSPHRASE";
+
+fn read_passphrase(confirm: bool) -> Result<String, AppError> {
+    if let Some(passphrase) = std::env::var(EXPORT_PASSPHRASE_ENV)
+        .ok()
+        .filter(|p| !p.is_empty())
+    {
+        return Ok(passphrase);
+    }
+
+    ensure_interactive(
+        "the passphrase prompt",
+        Some(&format!("the passphrase via {EXPORT_PASSPHRASE_ENV}")),
+    )?;
+    let mut prompt = inquire::Password::new("Passphrase:")
+        .with_display_mode(inquire::PasswordDisplayMode::Masked);
+    if confirm {
+        prompt = prompt.with_custom_confirmation_message("Confirm passphrase:");
+    } else {
+        prompt = prompt.without_confirmation();
+    }
+    prompt
+        .prompt()
+        .map_err(|e| AppError::Message(format!("Prompt failed: {}", e)))
+}
+
+/// 加密导出文件返回口令，明文文件返回 `None`
+fn passphrase_for_import(file: &Path) -> Result<Option<String>, AppError> {
+    if !ConfigService::is_encrypted_export(file)? {
+        return Ok(None);
+    }
+    out::info(&format!("{} is encrypted.", file.display()));
+    read_passphrase(false).map(Some)
+}
+
+fn export_config(file: &PathBuf, encrypted: bool) -> Result<(), AppError> {
+    out::info(&format!("Exporting configuration to {}...", file.display()));
 
     // Check if target file already exists
     if file.exists() {
+        ensure_interactive(
+            "the overwrite confirmation",
+            Some("a path that does not exist yet"),
+        )?;
         let confirm = inquire::Confirm::new(&format!(
             "File '{}' already exists. Overwrite?",
             file.display()
@@ -291,35 +672,37 @@ fn export_config(file: &PathBuf) -> Result<(), AppError> {
         .map_err(|e| AppError::Message(format!("Prompt failed: {}", e)))?;
 
         if !confirm {
-            println!("{}", info("Cancelled."));
+            out::info("Cancelled.");
             return Ok(());
         }
     }
 
     // Ensure parent directory exists
     if let Some(parent) = file.parent() {
-        fs::create_dir_all(parent).map_err(|e| AppError::io(parent, e))?;
+        crate::dry_run::create_dir_all(parent).map_err(|e| AppError::io(parent, e))?;
     }
 
     // Export configuration
-    ConfigService::export_config_to_path(file)?;
-
-    println!(
-        "{}",
-        success(&format!("✓ Configuration exported to {}", file.display()))
-    );
+    if encrypted {
+        let passphrase = read_passphrase(true)?;
+        ConfigService::export_config_encrypted_to_path(file, &passphrase)?;
+        out::success(&format!(
+            "✓ Encrypted configuration exported to {}",
+            file.display()
+        ));
+    } else {
+        ConfigService::export_config_to_path(file)?;
+        out::success(&format!("✓ Configuration exported to {}", file.display()));
+    }
 
     Ok(())
 }
 
-fn import_config(file: &PathBuf) -> Result<(), AppError> {
-    println!(
-        "{}",
-        info(&format!(
-            "Importing configuration from {}...",
-            file.display()
-        ))
-    );
+fn import_config(file: &PathBuf, dry_run: bool, yes: bool) -> Result<(), AppError> {
+    out::info(&format!(
+        "Importing configuration from {}...",
+        file.display()
+    ));
 
     // Check if source file exists
     if !file.exists() {
@@ -329,119 +712,274 @@ fn import_config(file: &PathBuf) -> Result<(), AppError> {
         )));
     }
 
+    let passphrase = passphrase_for_import(file)?;
+
+    if dry_run {
+        return preview_import(file, passphrase.as_deref());
+    }
+
     // Confirm import
     println!();
-    println!("{}", highlight("Warning:"));
+    out::highlight("Warning:");
     println!("This will replace your current database with the imported SQL backup.");
     println!("A backup will be created automatically.");
     println!();
 
-    let confirm = inquire::Confirm::new("Continue with import?")
-        .with_default(false)
-        .prompt()
-        .map_err(|e| AppError::Message(format!("Prompt failed: {}", e)))?;
+    if !yes {
+        ensure_interactive("the import confirmation", Some("--yes"))?;
+        let confirm = inquire::Confirm::new("Continue with import?")
+            .with_default(false)
+            .prompt()
+            .map_err(|e| AppError::Message(format!("Prompt failed: {}", e)))?;
 
-    if !confirm {
-        println!("{}", info("Cancelled."));
-        return Ok(());
+        if !confirm {
+            out::info("Cancelled.");
+            return Ok(());
+        }
     }
 
     // Perform import
     let state = get_state()?;
-    let backup_id = ConfigService::import_config_from_path(file, &state)?;
+    let backup_id = ConfigService::import_config_from_path_with_passphrase(
+        file,
+        passphrase.as_deref(),
+        &state,
+    )?;
 
-    println!(
-        "{}",
-        success(&format!("✓ Configuration imported from {}", file.display()))
-    );
+    out::success(&format!("✓ Configuration imported from {}", file.display()));
     if !backup_id.is_empty() {
-        println!("{}", info(&format!("  Backup created: {}", backup_id)));
+        out::info(&format!("  Backup created: {}", backup_id));
     }
     println!();
-    println!(
-        "{}",
-        info("Note: Restart your CLI clients to apply the changes.")
-    );
+    out::info("Note: Restart your CLI clients to apply the changes.");
 
     Ok(())
 }
 
-fn backup_config(custom_name: Option<&str>) -> Result<(), AppError> {
+/// `--merge`：按 id 新增或覆盖条目，保留导入文件中没有的内容
+fn merge_import_config(
+    file: &Path,
+    strategy: ConflictStrategy,
+    dry_run: bool,
+) -> Result<(), AppError> {
+    if !file.exists() {
+        return Err(AppError::Message(format!(
+            "File '{}' not found",
+            file.display()
+        )));
+    }
+
+    let passphrase = passphrase_for_import(file)?;
+    let state = get_state()?;
+    let report = ConfigService::merge_import_from_path(
+        file,
+        passphrase.as_deref(),
+        strategy,
+        dry_run,
+        &state,
+    )?;
+
+    if json_output() {
+        emit(&report)?;
+        return Ok(());
+    }
+
+    let mut table = create_table();
+    table.set_header(vec![
+        "Section",
+        "+Added",
+        "~Updated",
+        "Skipped",
+        "Unchanged",
+    ]);
+    for section in &report.sections {
+        table.add_row(vec![
+            section.section.clone(),
+            section.added.len().to_string(),
+            section.updated.len().to_string(),
+            section.skipped.len().to_string(),
+            section.unchanged.to_string(),
+        ]);
+    }
+    println!("{}", table);
+
+    let skipped: usize = report.sections.iter().map(|s| s.skipped.len()).sum();
+    if skipped > 0 && strategy == ConflictStrategy::Skip {
+        out::info(&format!(
+            "Kept {} existing entries that differ (use --on-conflict overwrite to replace them)",
+            skipped
+        ));
+    }
+    for section in report.sections.iter().filter(|s| !s.missing.is_empty()) {
+        out::warning(&format!(
+            "{}: not imported, files missing on this machine: {}",
+            section.section,
+            section.missing.join(", ")
+        ));
+    }
+
+    if dry_run {
+        out::info("Dry run: nothing was changed.");
+        return Ok(());
+    }
+    let merged = report
+        .sections
+        .iter()
+        .any(|s| !s.added.is_empty() || !s.updated.is_empty());
+    if !merged {
+        out::info("Nothing to merge: the current configuration already has every entry.");
+        return Ok(());
+    }
+    out::success(&format!("✓ Configuration merged from {}", file.display()));
+    if let Some(backup_id) = report.backup_id {
+        out::info(&format!("  Backup created: {}", backup_id));
+    }
+    out::info("Note: Restart your CLI clients to apply the changes.");
+
+    Ok(())
+}
+
+/// `--dry-run`：按分区打印导入后会发生的变化，不修改数据库
+fn preview_import(file: &Path, passphrase: Option<&str>) -> Result<(), AppError> {
+    let state = get_state()?;
+    let sections = ConfigService::preview_import_from_path(file, passphrase, &state)?;
+
+    if json_output() {
+        emit(&sections)?;
+        return Ok(());
+    }
+
+    let mut table = create_table();
+    table.set_header(vec![
+        "Section", "Current", "Incoming", "+Added", "-Removed", "~Changed",
+    ]);
+    for section in &sections {
+        table.add_row(vec![
+            section.section.clone(),
+            section.current.to_string(),
+            section.incoming.to_string(),
+            section.added.len().to_string(),
+            section.removed.len().to_string(),
+            section.changed.len().to_string(),
+        ]);
+    }
+    println!("{}", table);
+
+    let changed: Vec<_> = sections.iter().filter(|s| !s.is_unchanged()).collect();
+    if changed.is_empty() {
+        out::info("No changes: the file matches the current configuration.");
+    }
+    for section in changed {
+        println!("\n{}", highlight(&section.section));
+        for id in &section.added {
+            println!("  {} {}", success("+"), id);
+        }
+        for id in &section.removed {
+            println!("  {} {}", error("-"), id);
+        }
+        for id in &section.changed {
+            println!("  {} {}", info("~"), id);
+        }
+    }
+    println!();
+    out::info("Dry run: nothing was modified.");
+
+    Ok(())
+}
+
+fn backup_config(
+    custom_name: Option<&str>,
+    compress: bool,
+    description: Option<String>,
+    tags: Vec<String>,
+) -> Result<(), AppError> {
     let config_path = crate::config::get_app_config_path();
 
     if let Some(name) = custom_name {
-        println!(
-            "{}",
-            info(&format!("Creating backup with name '{}'...", name))
-        );
+        out::info(&format!("Creating backup with name '{}'...", name));
     } else {
-        println!("{}", info("Creating backup of current configuration..."));
+        out::info("Creating backup of current configuration...");
     }
 
-    let backup_id = ConfigService::create_backup(&config_path, custom_name.map(|s| s.to_string()))?;
+    let meta = BackupMeta {
+        description: description
+            .map(|d| d.trim().to_string())
+            .filter(|d| !d.is_empty()),
+        tags: tags
+            .iter()
+            .map(|t| t.trim().to_string())
+            .filter(|t| !t.is_empty())
+            .collect(),
+    };
+    let backup_id = ConfigService::create_backup_with_meta(
+        &config_path,
+        custom_name.map(|s| s.to_string()),
+        compress,
+        &meta,
+    )?;
 
     if backup_id.is_empty() {
         println!("{}", error("Failed to create backup."));
     } else {
-        let backup_dir = config_path.parent().unwrap().join("backups");
-        let backup_file = backup_dir.join(format!("{}.sql", backup_id));
+        let backup_file = ConfigService::backup_file_path(&backup_id)?;
 
-        println!("{}", success(&format!("✓ Backup created: {}", backup_id)));
+        out::success(&format!("✓ Backup created: {}", backup_id));
         println!("Location: {}", backup_file.display());
     }
 
     Ok(())
 }
 
-fn restore_config(backup_id: Option<&str>, file_path: Option<&Path>) -> Result<(), AppError> {
+fn restore_config(
+    backup_id: Option<&str>,
+    file_path: Option<&Path>,
+    dry_run: bool,
+    tag: Option<&str>,
+    yes: bool,
+) -> Result<(), AppError> {
     let config_path = crate::config::get_app_config_path();
 
     // 情况1：指定了备份 ID
     if let Some(id) = backup_id {
-        println!("{}", info(&format!("Restoring from backup '{}'...", id)));
+        out::info(&format!("Restoring from backup '{}'...", id));
 
-        let confirm =
-            inquire::Confirm::new("This will replace your current configuration. Continue?")
-                .with_default(false)
-                .prompt()
-                .map_err(|e| AppError::Message(format!("Prompt failed: {}", e)))?;
+        if dry_run {
+            return preview_import(&ConfigService::backup_file_path(id)?, None);
+        }
 
-        if !confirm {
-            println!("{}", info("Cancelled."));
-            return Ok(());
+        if !yes {
+            ensure_interactive("the restore confirmation", Some("--yes"))?;
+            let confirm =
+                inquire::Confirm::new("This will replace your current configuration. Continue?")
+                    .with_default(false)
+                    .prompt()
+                    .map_err(|e| AppError::Message(format!("Prompt failed: {}", e)))?;
+
+            if !confirm {
+                out::info("Cancelled.");
+                return Ok(());
+            }
         }
 
         let state = get_state()?;
         let pre_restore_backup = ConfigService::restore_from_backup_id(id, &state)?;
 
-        println!(
-            "{}",
-            success(&format!("✓ Configuration restored from backup '{}'", id))
-        );
+        out::success(&format!("✓ Configuration restored from backup '{}'", id));
         if !pre_restore_backup.is_empty() {
-            println!(
-                "{}",
-                info(&format!("  Pre-restore backup: {}", pre_restore_backup))
-            );
+            out::info(&format!("  Pre-restore backup: {}", pre_restore_backup));
         }
         println!();
-        println!(
-            "{}",
-            info("Note: Restart your CLI clients to apply the changes.")
-        );
+        out::info("Note: Restart your CLI clients to apply the changes.");
 
         return Ok(());
     }
 
     // 情况2：指定了文件路径
     if let Some(file) = file_path {
-        println!(
-            "{}",
-            info(&format!(
-                "Restoring configuration from {}...",
-                file.display()
-            ))
-        );
+        out::info(&format!(
+            "Restoring configuration from {}...",
+            file.display()
+        ));
 
         if !file.exists() {
             return Err(AppError::Message(format!(
@@ -450,54 +988,66 @@ fn restore_config(backup_id: Option<&str>, file_path: Option<&Path>) -> Result<(
             )));
         }
 
+        let passphrase = passphrase_for_import(file)?;
+
+        if dry_run {
+            return preview_import(file, passphrase.as_deref());
+        }
+
         println!();
-        println!("{}", highlight("Warning:"));
+        out::highlight("Warning:");
         println!("This will replace your current database with the SQL backup file.");
         println!("A backup of the current state will be created first.");
         println!();
 
-        let confirm = inquire::Confirm::new(texts::config_restore_confirm_prompt())
-            .with_default(false)
-            .prompt()
-            .map_err(|e| AppError::Message(format!("Prompt failed: {}", e)))?;
+        if !yes {
+            ensure_interactive("the restore confirmation", Some("--yes"))?;
+            let confirm = inquire::Confirm::new(texts::config_restore_confirm_prompt())
+                .with_default(false)
+                .prompt()
+                .map_err(|e| AppError::Message(format!("Prompt failed: {}", e)))?;
 
-        if !confirm {
-            println!("{}", info("Cancelled."));
-            return Ok(());
+            if !confirm {
+                out::info("Cancelled.");
+                return Ok(());
+            }
         }
 
         let state = get_state()?;
-        let pre_restore_backup = ConfigService::import_config_from_path(file, &state)?;
+        let pre_restore_backup = ConfigService::import_config_from_path_with_passphrase(
+            file,
+            passphrase.as_deref(),
+            &state,
+        )?;
 
-        println!(
-            "{}",
-            success(&format!("✓ Configuration restored from {}", file.display()))
-        );
+        out::success(&format!("✓ Configuration restored from {}", file.display()));
         if !pre_restore_backup.is_empty() {
-            println!(
-                "{}",
-                info(&format!("  Pre-restore backup: {}", pre_restore_backup))
-            );
+            out::info(&format!("  Pre-restore backup: {}", pre_restore_backup));
         }
         println!();
-        println!(
-            "{}",
-            info("Note: Restart your CLI clients to apply the changes.")
-        );
+        out::info("Note: Restart your CLI clients to apply the changes.");
 
         return Ok(());
     }
 
     // 情况3：无参数，显示交互式列表
-    println!("{}", highlight(texts::available_backups()));
+    out::highlight(texts::available_backups());
     println!("{}", "=".repeat(50));
 
-    let backups = ConfigService::list_backups(&config_path)?;
+    let mut backups = ConfigService::list_backups(&config_path)?;
+    if let Some(tag) = tag {
+        backups.retain(|b| b.meta.has_tag(tag));
+    }
 
     if backups.is_empty() {
         println!();
-        println!("{}", info(texts::no_backups_found()));
-        println!("{}", info(texts::create_backup_first_hint()));
+        match tag {
+            Some(tag) => out::info(&texts::no_backups_tagged(tag)),
+            None => {
+                out::info(texts::no_backups_found());
+                out::info(texts::create_backup_first_hint());
+            }
+        }
         return Ok(());
     }
 
@@ -505,11 +1055,9 @@ fn restore_config(backup_id: Option<&str>, file_path: Option<&Path>) -> Result<(
     println!("{}", texts::found_backups(backups.len()));
     println!();
 
-    let choices: Vec<String> = backups
-        .iter()
-        .map(|b| format!("{} - {}", b.display_name, b.id))
-        .collect();
+    let choices: Vec<String> = backups.iter().map(BackupInfo::label).collect();
 
+    ensure_interactive("selecting a backup", Some("--backup <id> or --file <path>"))?;
     let selection = inquire::Select::new(texts::select_backup_to_restore(), choices)
         .prompt()
         .map_err(|_| AppError::Message(texts::selection_cancelled().to_string()))?;
@@ -519,43 +1067,41 @@ fn restore_config(backup_id: Option<&str>, file_path: Option<&Path>) -> Result<(
         .find(|b| selection.contains(&b.id))
         .ok_or_else(|| AppError::Message(texts::invalid_selection().to_string()))?;
 
+    if dry_run {
+        return preview_import(&ConfigService::backup_file_path(&selected_backup.id)?, None);
+    }
+
     println!();
-    println!("{}", highlight(texts::warning_title()));
+    out::highlight(texts::warning_title());
     println!("{}", texts::config_restore_warning_replace());
     println!("{}", texts::config_restore_warning_pre_backup());
     println!();
 
-    let confirm = inquire::Confirm::new(texts::config_restore_confirm_prompt())
-        .with_default(false)
-        .prompt()
-        .map_err(|e| AppError::Message(format!("Prompt failed: {}", e)))?;
+    if !yes {
+        ensure_interactive("the restore confirmation", Some("--yes"))?;
+        let confirm = inquire::Confirm::new(texts::config_restore_confirm_prompt())
+            .with_default(false)
+            .prompt()
+            .map_err(|e| AppError::Message(format!("Prompt failed: {}", e)))?;
 
-    if !confirm {
-        println!("{}", info(texts::cancelled()));
-        return Ok(());
+        if !confirm {
+            out::info(texts::cancelled());
+            return Ok(());
+        }
     }
 
     let state = get_state()?;
     let pre_restore_backup = ConfigService::restore_from_backup_id(&selected_backup.id, &state)?;
 
-    println!(
-        "{}",
-        success(&format!(
-            "✓ Configuration restored from: {}",
-            selected_backup.display_name
-        ))
-    );
+    out::success(&format!(
+        "✓ Configuration restored from: {}",
+        selected_backup.display_name
+    ));
     if !pre_restore_backup.is_empty() {
-        println!(
-            "{}",
-            info(&format!("  Pre-restore backup: {}", pre_restore_backup))
-        );
+        out::info(&format!("  Pre-restore backup: {}", pre_restore_backup));
     }
     println!();
-    println!(
-        "{}",
-        info("Note: Restart your CLI clients to apply the changes.")
-    );
+    out::info("Note: Restart your CLI clients to apply the changes.");
 
     Ok(())
 }
@@ -564,7 +1110,7 @@ fn validate_config() -> Result<(), AppError> {
     let config_dir = crate::config::get_app_config_dir();
     let db_path = config_dir.join("cc-switch.db");
 
-    println!("{}", info("Validating database..."));
+    out::info("Validating database...");
     println!();
 
     if !db_path.exists() {
@@ -579,6 +1125,21 @@ fn validate_config() -> Result<(), AppError> {
     let db = crate::Database::init()?;
     println!("{} Database schema is readable", success("✓"));
 
+    let problems = db.integrity_check()?;
+    if !problems.is_empty() {
+        println!("{}", error("✗ Database integrity check failed"));
+        for problem in problems.iter().take(20) {
+            println!("  {}", problem);
+        }
+        println!();
+        out::info("Restore a backup with: cc-switch config restore");
+        return Err(AppError::Database(format!(
+            "Integrity check reported {} problem(s)",
+            problems.len()
+        )));
+    }
+    println!("{} Database integrity check passed", success("✓"));
+
     // Show some stats
     let claude_count = db.get_all_providers("claude")?.len();
     let codex_count = db.get_all_providers("codex")?.len();
@@ -587,7 +1148,7 @@ fn validate_config() -> Result<(), AppError> {
     let skills_count = db.get_all_installed_skills()?.len();
 
     println!();
-    println!("{}", highlight("Database Summary:"));
+    out::highlight("Database Summary:");
     println!("Claude providers:  {}", claude_count);
     println!("Codex providers:   {}", codex_count);
     println!("Gemini providers:  {}", gemini_count);
@@ -595,31 +1156,56 @@ fn validate_config() -> Result<(), AppError> {
     println!("Skills installed:  {}", skills_count);
 
     println!();
-    println!("{}", success("✓ Database validation passed"));
+    out::success("✓ Database validation passed");
+
+    Ok(())
+}
+
+fn vacuum_database() -> Result<(), AppError> {
+    let db_path = crate::config::get_app_config_dir().join("cc-switch.db");
+    if !db_path.exists() {
+        return Err(AppError::Message(format!(
+            "Database file does not exist: {}",
+            db_path.display()
+        )));
+    }
+
+    let db = crate::Database::init()?;
+    let (before, after) = db.vacuum()?;
+    let saved = before.saturating_sub(after);
+
+    out::success("✓ Database optimized");
+    println!("Before: {}", format_bytes(before));
+    println!("After:  {}", format_bytes(after));
+    println!("Saved:  {}", format_bytes(saved));
 
     Ok(())
 }
 
-fn reset_config() -> Result<(), AppError> {
-    println!("{}", highlight("Reset Configuration"));
+fn reset_config(yes: bool) -> Result<(), AppError> {
+    out::highlight("Reset Configuration");
     println!("{}", "=".repeat(50));
     println!();
-    println!("{}", highlight("Warning:"));
+    out::highlight("Warning:");
     println!("This will delete your current configuration and create a fresh default one.");
     println!("All your providers, MCP servers, and settings will be lost.");
     println!();
-    println!("{}", info("Consider creating a backup first:"));
+    out::info("Consider creating a backup first:");
     println!("  cc-switch config backup");
     println!();
 
-    let confirm = inquire::Confirm::new("Are you sure you want to reset to default configuration?")
-        .with_default(false)
-        .prompt()
-        .map_err(|e| AppError::Message(format!("Prompt failed: {}", e)))?;
+    if !yes {
+        ensure_interactive("the reset confirmation", Some("--yes"))?;
+        let confirm =
+            inquire::Confirm::new("Are you sure you want to reset to default configuration?")
+                .with_default(false)
+                .prompt()
+                .map_err(|e| AppError::Message(format!("Prompt failed: {}", e)))?;
 
-    if !confirm {
-        println!("{}", info("Cancelled."));
-        return Ok(());
+        if !confirm {
+            out::info("Cancelled.");
+            return Ok(());
+        }
     }
 
     // Create a backup before reset (SQL)
@@ -629,20 +1215,124 @@ fn reset_config() -> Result<(), AppError> {
     // Delete the database file
     let db_path = crate::config::get_app_config_dir().join("cc-switch.db");
     if db_path.exists() {
-        fs::remove_file(&db_path).map_err(|e| AppError::io(&db_path, e))?;
+        crate::dry_run::remove_file(&db_path).map_err(|e| AppError::io(&db_path, e))?;
     }
 
     // Recreate empty DB
     let _ = crate::Database::init()?;
 
-    println!("{}", success("✓ Configuration reset to defaults"));
+    out::success("✓ Configuration reset to defaults");
     if !backup_id.is_empty() {
-        println!("{}", info(&format!("  Backup created: {}", backup_id)));
-        println!(
-            "{}",
-            info("  You can restore it later using: cc-switch config restore")
-        );
+        out::info(&format!("  Backup created: {}", backup_id));
+        out::info("  You can restore it later using: cc-switch config restore");
     }
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app_config::{McpServer, MultiAppConfig};
+    use crate::provider::Provider;
+    use crate::services::config::{diff_configs, prune_defaults, DefaultDeviations};
+    use serde_json::json;
+
+    /// Codex OAuth 令牌与 MCP 服务器密钥
+    fn config_with_secrets() -> MultiAppConfig {
+        let mut config = MultiAppConfig::default();
+        let codex = Provider::with_id(
+            "codex-oauth".to_string(),
+            "Codex OAuth".to_string(),
+            json!({
+                "auth": {
+                    "OPENAI_API_KEY": null,
+                    "tokens": {
+                        "id_token": "eyJhbGciOi-id-token-1111",
+                        "access_token": "eyJhbGciOi-access-2222",
+                        "refresh_token": "rt-refresh-token-3333",
+                        "account_id": "acct-visible"
+                    }
+                },
+                "config": ""
+            }),
+            None,
+        );
+        config
+            .get_manager_mut(&AppType::Codex)
+            .expect("codex manager")
+            .providers
+            .insert(codex.id.clone(), codex);
+        config
+            .mcp
+            .servers
+            .get_or_insert_with(Default::default)
+            .insert(
+                "github".to_string(),
+                McpServer {
+                    id: "github".to_string(),
+                    name: "GitHub".to_string(),
+                    server: json!({
+                        "type": "http",
+                        "url": "https://api.example.com/mcp",
+                        "headers": { "Authorization": "Bearer ghp-header-4444" },
+                        "env": { "GITHUB_PERSONAL_ACCESS_TOKEN": "ghp-env-token-5555" }
+                    }),
+                    apps: Default::default(),
+                    description: None,
+                    homepage: None,
+                    docs: None,
+                    tags: Vec::new(),
+                },
+            );
+        config
+    }
+
+    fn assert_masked(value: &serde_json::Value) {
+        let text = value.to_string();
+        for secret in [
+            "eyJhbGciOi-id-token-1111",
+            "eyJhbGciOi-access-2222",
+            "rt-refresh-token-3333",
+            "Bearer ghp-header-4444",
+            "ghp-env-token-5555",
+        ] {
+            assert!(!text.contains(secret), "{secret} leaked: {text}");
+        }
+        assert!(text.contains("acct-visible"), "{text}");
+        assert!(text.contains("https://api.example.com/mcp"), "{text}");
+    }
+
+    #[test]
+    fn config_show_masks_codex_tokens_and_mcp_secrets() {
+        let config = config_with_secrets();
+        let (value, redacted) = display_value(&config, false).expect("serialize config");
+        assert!(redacted);
+        assert_masked(&value);
+        assert_eq!(
+            value["codex"]["providers"]["codex-oauth"]["settingsConfig"]["auth"]["tokens"]
+                ["refresh_token"],
+            "****3333"
+        );
+
+        let (value, redacted) = display_value(&config, true).expect("serialize config");
+        assert!(!redacted);
+        assert!(value.to_string().contains("rt-refresh-token-3333"));
+    }
+
+    #[test]
+    fn config_diff_default_masks_codex_tokens_and_mcp_secrets() {
+        let config = config_with_secrets();
+        let default = MultiAppConfig::default();
+        let to_value = |c: &MultiAppConfig| serde_json::to_value(c).expect("serialize config");
+        let deviations = DefaultDeviations {
+            sections: diff_configs(&default, &config),
+            config: prune_defaults(&to_value(&config), &to_value(&default))
+                .expect("config differs from default"),
+            skills: json!({}),
+        };
+        let (value, redacted) = display_value(&deviations, false).expect("serialize diff");
+        assert!(redacted);
+        assert_masked(&value);
+    }
+}
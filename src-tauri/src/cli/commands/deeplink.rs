@@ -0,0 +1,243 @@
+use clap::Subcommand;
+use serde_json::Value;
+
+use crate::cli::runtime::run_async;
+use crate::cli::terminal::ensure_interactive;
+use crate::cli::ui::{display_secret, out};
+use crate::deeplink::{
+    build_server_spec, import_mcp_from_deeplink, import_provider_from_deeplink,
+    import_skill_from_deeplink, parse_and_merge_config, parse_deeplink_url, register_url_scheme,
+    unregister_url_scheme, DeepLinkImportRequest,
+};
+use crate::error::AppError;
+use crate::store::AppState;
+
+#[derive(Subcommand)]
+pub enum DeeplinkCommand {
+    /// Import the resource described by a ccswitch:// URL
+    Handle {
+        /// Deep link URL (ccswitch://v1/import?...)
+        url: String,
+        /// Import without asking for confirmation
+        #[arg(short, long)]
+        yes: bool,
+    },
+    /// Register cc-switch as the OS handler for ccswitch:// URLs
+    Register,
+    /// Remove the ccswitch:// URL handler registration
+    Unregister,
+}
+
+pub fn execute(cmd: DeeplinkCommand) -> Result<(), AppError> {
+    match cmd {
+        DeeplinkCommand::Handle { url, yes } => handle(&url, yes),
+        DeeplinkCommand::Register => register(),
+        DeeplinkCommand::Unregister => unregister(),
+    }
+}
+
+fn handle(url: &str, yes: bool) -> Result<(), AppError> {
+    let request = parse_deeplink_url(url)?;
+    let request = match request.resource.as_str() {
+        "provider" => parse_and_merge_config(&request)?,
+        _ => request,
+    };
+
+    // 先构建摘要：无效的 config/args 在确认前就报错
+    let rows = summary_rows(&request)?;
+    out::highlight("Deep link import");
+    for (label, value) in rows {
+        println!("  {:<10} {}", format!("{label}:"), value);
+    }
+    println!();
+
+    if !yes {
+        ensure_interactive("the import confirmation", Some("--yes"))?;
+        let confirm = inquire::Confirm::new(&format!("Import this {}?", request.resource))
+            .with_default(false)
+            .prompt()
+            .map_err(|e| AppError::Message(format!("Prompt failed: {}", e)))?;
+
+        if !confirm {
+            out::info("Cancelled.");
+            return Ok(());
+        }
+    }
+
+    match request.resource.as_str() {
+        "provider" => {
+            let app = request.app.clone().unwrap_or_default();
+            let state = AppState::try_new()?;
+            let id = import_provider_from_deeplink(&state, request)?;
+            out::success(&format!("✓ Imported {app} provider '{id}'"));
+        }
+        "mcp" => {
+            let state = AppState::try_new()?;
+            let id = import_mcp_from_deeplink(&state, request)?;
+            out::success(&format!("✓ Imported MCP server '{id}'"));
+        }
+        "skill" => {
+            let installed = run_async(import_skill_from_deeplink(request))?;
+            out::success(&format!("✓ Installed skill '{}'", installed.directory));
+        }
+        other => {
+            return Err(AppError::InvalidInput(format!(
+                "Unsupported resource type: {other}"
+            )))
+        }
+    }
+
+    Ok(())
+}
+
+fn register() -> Result<(), AppError> {
+    let exe = std::env::current_exe()
+        .map_err(|e| AppError::Message(format!("Failed to locate cc-switch executable: {e}")))?;
+    let changes = register_url_scheme(&exe)?;
+
+    out::success(&format!(
+        "✓ Registered {} as the ccswitch:// handler",
+        exe.display()
+    ));
+    for change in changes {
+        println!("  - {change}");
+    }
+    out::info("To undo, run: cc-switch deeplink unregister");
+    Ok(())
+}
+
+fn unregister() -> Result<(), AppError> {
+    let changes = unregister_url_scheme()?;
+    if changes.is_empty() {
+        out::info("No ccswitch:// handler registration found.");
+        return Ok(());
+    }
+
+    out::success("✓ Removed the ccswitch:// handler");
+    for change in changes {
+        println!("  - {change}");
+    }
+    Ok(())
+}
+
+/// 确认前展示的摘要行；MCP 以最终生效的 server spec 为准（含内联 `config`），密钥值不展示
+fn summary_rows(request: &DeepLinkImportRequest) -> Result<Vec<(&'static str, String)>, AppError> {
+    let mut rows: Vec<(&str, Option<String>)> = vec![("Resource", Some(request.resource.clone()))];
+    match request.resource.as_str() {
+        "provider" => rows.extend([
+            ("App", request.app.clone()),
+            ("Name", request.name.clone()),
+            ("Endpoint", request.endpoint.clone()),
+            ("API Key", request.api_key.as_deref().map(display_secret)),
+            ("Homepage", request.homepage.clone()),
+            ("Model", request.model.clone()),
+            ("Enable", request.enabled.map(|v| v.to_string())),
+        ]),
+        "mcp" => {
+            let spec = build_server_spec(request)?;
+            let text = |key: &str| spec.get(key).and_then(Value::as_str).map(str::to_string);
+            let args = spec.get("args").and_then(Value::as_array).map(|args| {
+                args.iter()
+                    .map(|a| a.as_str().map_or_else(|| a.to_string(), str::to_string))
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            });
+            rows.extend([
+                ("Name", request.name.clone()),
+                ("Type", text("type")),
+                ("Command", text("command")),
+                ("Args", args),
+                ("URL", text("url")),
+                ("Env keys", object_keys(&spec, "env")),
+                ("Headers", object_keys(&spec, "headers")),
+                ("Apps", request.apps.clone()),
+            ]);
+        }
+        "skill" => rows.extend([
+            ("Repo", request.repo.clone()),
+            ("Directory", request.directory.clone()),
+            ("Branch", request.branch.clone()),
+            ("Apps", request.apps.clone()),
+        ]),
+        _ => {}
+    }
+
+    Ok(rows
+        .into_iter()
+        .filter_map(|(label, value)| value.filter(|v| !v.is_empty()).map(|v| (label, v)))
+        .collect())
+}
+
+/// Only show keys of `env` / `headers`; values may hold secrets.
+fn object_keys(spec: &Value, field: &str) -> Option<String> {
+    spec.get(field)
+        .and_then(Value::as_object)
+        .map(|map| map.keys().cloned().collect::<Vec<_>>().join(", "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use base64::prelude::*;
+
+    fn mcp_request() -> DeepLinkImportRequest {
+        DeepLinkImportRequest {
+            version: "v1".to_string(),
+            resource: "mcp".to_string(),
+            name: Some("Fetch".to_string()),
+            ..Default::default()
+        }
+    }
+
+    fn row<'a>(rows: &'a [(&str, String)], label: &str) -> Option<&'a str> {
+        rows.iter()
+            .find(|(l, _)| *l == label)
+            .map(|(_, v)| v.as_str())
+    }
+
+    #[test]
+    fn mcp_summary_shows_command_from_inline_config() {
+        let config = serde_json::json!({
+            "type": "stdio",
+            "command": "npx",
+            "args": ["-y", "mcp-server-fetch"],
+            "env": { "API_TOKEN": "secret-value" }
+        });
+        let request = DeepLinkImportRequest {
+            config: Some(BASE64_STANDARD.encode(config.to_string())),
+            ..mcp_request()
+        };
+
+        let rows = summary_rows(&request).expect("summary");
+        assert_eq!(row(&rows, "Command"), Some("npx"));
+        assert_eq!(row(&rows, "Args"), Some("-y mcp-server-fetch"));
+        assert_eq!(row(&rows, "Env keys"), Some("API_TOKEN"));
+        assert!(rows.iter().all(|(_, v)| !v.contains("secret-value")));
+    }
+
+    #[test]
+    fn mcp_summary_query_params_override_inline_config() {
+        let config = serde_json::json!({ "command": "node", "args": ["old.js"] });
+        let request = DeepLinkImportRequest {
+            config: Some(BASE64_STANDARD.encode(config.to_string())),
+            command: Some("uvx".to_string()),
+            args: Some("mcp-server-git,--verbose".to_string()),
+            env: Some("GITHUB_TOKEN=ghp_x,LOG=1".to_string()),
+            ..mcp_request()
+        };
+
+        let rows = summary_rows(&request).expect("summary");
+        assert_eq!(row(&rows, "Command"), Some("uvx"));
+        assert_eq!(row(&rows, "Args"), Some("mcp-server-git --verbose"));
+        assert_eq!(row(&rows, "Env keys"), Some("GITHUB_TOKEN, LOG"));
+    }
+
+    #[test]
+    fn mcp_summary_rejects_invalid_inline_config() {
+        let request = DeepLinkImportRequest {
+            config: Some(BASE64_STANDARD.encode("[1, 2]")),
+            ..mcp_request()
+        };
+        assert!(summary_rows(&request).is_err());
+    }
+}
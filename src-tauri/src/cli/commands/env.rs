@@ -1,33 +1,268 @@
 use crate::app_config::AppType;
-use crate::cli::ui::{create_table, error, highlight, info, success};
+use crate::cli::terminal::ensure_interactive;
+use crate::cli::ui::{
+    create_table, display_secret, emit, error, is_sensitive_key, json_output, out,
+};
 use crate::error::AppError;
-use crate::services::env_checker;
+use crate::provider::Provider;
+use crate::services::{env_checker, env_manager, ProviderService};
+use crate::store::AppState;
 use clap::Subcommand;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
 
 #[derive(Subcommand)]
 pub enum EnvCommand {
     /// Check for environment variable conflicts
-    Check,
+    Check {
+        /// Remove all detected conflicts (a backup is created first)
+        #[arg(long)]
+        fix: bool,
+        /// Skip the confirmation prompt (with --fix)
+        #[arg(short, long, requires = "fix")]
+        yes: bool,
+    },
     /// List all relevant environment variables
     List,
+    /// Manage backups created when environment variables are removed
+    #[command(subcommand)]
+    Backup(EnvBackupCommand),
+    /// Re-apply the variables saved in an env backup
+    Restore {
+        /// Backup ID (timestamp from `env backup list`); prompts when omitted
+        id: Option<String>,
+    },
+    /// Set a variable: print the export line, or write it into a shell rc file with --write
+    Set {
+        /// Variable name
+        key: String,
+        /// Value
+        value: String,
+        /// Update (or append) `export KEY="VALUE"` in this file; a backup is taken first
+        #[arg(long, value_name = "FILE")]
+        write: Option<PathBuf>,
+    },
+    /// Unset a variable: print how, or remove its assignments from a shell rc file with --write
+    Unset {
+        /// Variable name
+        key: String,
+        /// Remove every assignment of KEY from this file; a backup is taken first
+        #[arg(long, value_name = "FILE")]
+        write: Option<PathBuf>,
+    },
+    /// Compare the env of two stored providers (secrets masked)
+    Diff {
+        /// First provider ID
+        a: String,
+        /// Second provider ID
+        b: String,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum EnvBackupCommand {
+    /// List env backups and the variables they contain
+    List,
 }
 
 pub fn execute(cmd: EnvCommand, app: Option<AppType>) -> Result<(), AppError> {
     let app_type = app.unwrap_or(AppType::Claude);
 
     match cmd {
-        EnvCommand::Check => check_conflicts(app_type),
+        EnvCommand::Check { fix, yes } => check_conflicts(app_type, fix, yes),
         EnvCommand::List => list_env_vars(app_type),
+        EnvCommand::Backup(EnvBackupCommand::List) => list_env_backups(),
+        EnvCommand::Restore { id } => restore_env_backup(id.as_deref()),
+        EnvCommand::Set { key, value, write } => set_env(&key, &value, write.as_deref()),
+        EnvCommand::Unset { key, write } => unset_env(&key, write.as_deref()),
+        EnvCommand::Diff { a, b } => diff_provider_env(app_type, &a, &b),
     }
 }
 
-fn check_conflicts(app_type: AppType) -> Result<(), AppError> {
-    let app_str = app_type.as_str();
+fn set_env(key: &str, value: &str, write: Option<&Path>) -> Result<(), AppError> {
+    env_manager::validate_env_key(key).map_err(AppError::InvalidInput)?;
+    let Some(file) = write else {
+        if cfg!(windows) {
+            out::info("Run this to persist the variable for new shells:");
+            println!("  setx {key} \"{value}\"");
+        } else {
+            out::info("Add this line to your shell rc file (e.g. ~/.zshrc), or re-run with --write <file>:");
+            println!("  {}", env_manager::export_line(key, value));
+        }
+        return Ok(());
+    };
+
+    let path = crate::settings::resolve_override_path(&file.to_string_lossy());
+    let edit = env_manager::set_env_in_file(&path, key, value)
+        .map_err(|e| AppError::Message(format!("Failed to set {key}: {e}")))?;
+    report_rc_edit(&edit, &format!("✓ Set {key} in {}", edit.path))
+}
+
+fn unset_env(key: &str, write: Option<&Path>) -> Result<(), AppError> {
+    env_manager::validate_env_key(key).map_err(AppError::InvalidInput)?;
+    let Some(file) = write else {
+        if cfg!(windows) {
+            out::info("Run this to remove the variable for new shells:");
+            println!("  reg delete HKCU\\Environment /v {key} /f");
+        } else {
+            out::info(&format!(
+                "Remove the lines assigning {key} from your shell rc file, or re-run with --write <file>."
+            ));
+            println!("  unset {key}   # current shell only");
+        }
+        return Ok(());
+    };
+
+    let path = crate::settings::resolve_override_path(&file.to_string_lossy());
+    let edit = env_manager::unset_env_in_file(&path, key)
+        .map_err(|e| AppError::Message(format!("Failed to unset {key}: {e}")))?;
+    report_rc_edit(&edit, &format!("✓ Removed {key} from {}", edit.path))
+}
+
+fn report_rc_edit(edit: &env_manager::RcFileEdit, done: &str) -> Result<(), AppError> {
+    if json_output() {
+        return emit(edit);
+    }
+    if !edit.changed {
+        out::info(&format!("{} is already up to date.", edit.path));
+        return Ok(());
+    }
+    out::success(done);
+    if let Some(backup) = &edit.backup_path {
+        println!("  Backup: {backup}");
+    }
+    out::info("Open a new shell (or `source` the file) for the change to take effect.");
+    Ok(())
+}
+
+/// `env diff` 中一个不同的键
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct EnvDiffEntry {
+    key: String,
+    status: EnvDiffStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    a: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    b: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum EnvDiffStatus {
+    OnlyA,
+    OnlyB,
+    Changed,
+}
+
+/// 供应商的环境变量：Claude / Gemini 取 `settingsConfig.env`，Codex 取 `settingsConfig.auth`
+fn provider_env(provider: &Provider, app_type: &AppType) -> BTreeMap<String, String> {
+    let section = match app_type {
+        AppType::Codex => "auth",
+        AppType::Claude | AppType::Gemini => "env",
+    };
+    provider
+        .settings_config
+        .get(section)
+        .and_then(Value::as_object)
+        .map(|map| {
+            map.iter()
+                .map(|(key, value)| {
+                    let value = match value {
+                        Value::String(s) => s.clone(),
+                        other => other.to_string(),
+                    };
+                    (key.clone(), value)
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// 按键名排序的差异（相同的键不列出）
+fn diff_env(a: &BTreeMap<String, String>, b: &BTreeMap<String, String>) -> Vec<EnvDiffEntry> {
+    let mut keys: Vec<&String> = a.keys().chain(b.keys()).collect();
+    keys.sort();
+    keys.dedup();
+    keys.into_iter()
+        .filter_map(|key| {
+            let (left, right) = (a.get(key), b.get(key));
+            let status = match (left, right) {
+                (Some(_), None) => EnvDiffStatus::OnlyA,
+                (None, Some(_)) => EnvDiffStatus::OnlyB,
+                (Some(x), Some(y)) if x != y => EnvDiffStatus::Changed,
+                _ => return None,
+            };
+            Some(EnvDiffEntry {
+                key: key.clone(),
+                status,
+                a: left.cloned(),
+                b: right.cloned(),
+            })
+        })
+        .collect()
+}
+
+fn diff_provider_env(app_type: AppType, a: &str, b: &str) -> Result<(), AppError> {
+    let state = AppState::try_new()?;
+    let providers = ProviderService::list(&state, app_type.clone())?;
+    let lookup = |id: &str| {
+        providers.get(id).ok_or_else(|| {
+            AppError::Message(format!(
+                "Provider '{}' not found for {}",
+                id,
+                app_type.as_str()
+            ))
+        })
+    };
+    let env_a = provider_env(lookup(a)?, &app_type);
+    let env_b = provider_env(lookup(b)?, &app_type);
 
+    let mut entries = diff_env(&env_a, &env_b);
+    for entry in &mut entries {
+        if is_sensitive_key(&entry.key) {
+            entry.a = entry.a.as_deref().map(display_secret);
+            entry.b = entry.b.as_deref().map(display_secret);
+        }
+    }
+
+    if json_output() {
+        return emit(&serde_json::json!({ "a": a, "b": b, "differences": entries }));
+    }
+
+    if entries.is_empty() {
+        out::success(&format!("✓ Env of '{a}' and '{b}' is identical"));
+        return Ok(());
+    }
+
+    let mut table = create_table();
+    table.set_header(vec!["Key", a, b]);
+    let missing = || "(missing)".to_string();
+    for entry in &entries {
+        table.add_row(vec![
+            entry.key.clone(),
+            entry.a.clone().unwrap_or_else(missing),
+            entry.b.clone().unwrap_or_else(missing),
+        ]);
+    }
+    println!("{}", table);
+
+    let count = |status| entries.iter().filter(|e| e.status == status).count();
     println!(
-        "\n{}",
-        highlight(&format!("Checking Environment Variables for {}", app_str))
+        "\n{} only in '{a}', {} only in '{b}', {} with different values",
+        count(EnvDiffStatus::OnlyA),
+        count(EnvDiffStatus::OnlyB),
+        count(EnvDiffStatus::Changed)
     );
+    Ok(())
+}
+
+fn check_conflicts(app_type: AppType, fix: bool, yes: bool) -> Result<(), AppError> {
+    let app_str = app_type.as_str();
+
+    out::highlight(&format!("\nChecking Environment Variables for {}", app_str));
     println!("{}", "═".repeat(60));
 
     // 检测冲突
@@ -35,17 +270,14 @@ fn check_conflicts(app_type: AppType) -> Result<(), AppError> {
         .map_err(|e| AppError::Message(format!("Failed to check environment variables: {}", e)))?;
 
     if conflicts.is_empty() {
-        println!(
+        out::success(&format!(
             "\n{}",
-            success("✓ No environment variable conflicts detected")
-        );
-        println!(
-            "{}",
-            info(&format!(
-                "Your {} configuration should work correctly.",
-                app_str
-            ))
-        );
+            "✓ No environment variable conflicts detected"
+        ));
+        out::info(&format!(
+            "Your {} configuration should work correctly.",
+            app_str
+        ));
         return Ok(());
     }
 
@@ -63,8 +295,10 @@ fn check_conflicts(app_type: AppType) -> Result<(), AppError> {
     table.set_header(vec!["Variable", "Value", "Source Type", "Source Location"]);
 
     for conflict in &conflicts {
-        // 截断过长的值
-        let value_display = if conflict.var_value.len() > 30 {
+        // 敏感值脱敏，其余截断过长的值
+        let value_display = if is_sensitive_key(&conflict.var_name) {
+            display_secret(&conflict.var_value)
+        } else if conflict.var_value.len() > 30 {
             format!("{}...", &conflict.var_value[..27])
         } else {
             conflict.var_value.clone()
@@ -80,14 +314,65 @@ fn check_conflicts(app_type: AppType) -> Result<(), AppError> {
 
     println!("{}", table);
     println!();
-    println!(
-        "{}",
-        info("These environment variables may override CC-Switch's configuration.")
-    );
-    println!(
-        "{}",
-        info("Please manually remove them from your shell config files or system settings.")
-    );
+    out::info("These environment variables may override CC-Switch's configuration.");
+
+    if fix {
+        return fix_conflicts(conflicts, yes);
+    }
+
+    out::info("Please manually remove them from your shell config files or system settings.");
+    out::info("Or run `cc-switch env check --fix` to remove them all (with a backup).");
+
+    Ok(())
+}
+
+fn fix_conflicts(conflicts: Vec<env_checker::EnvConflict>, yes: bool) -> Result<(), AppError> {
+    if !yes {
+        ensure_interactive("the removal confirmation", Some("--yes"))?;
+        let confirm = inquire::Confirm::new(&format!(
+            "Remove all {} variable(s)? A backup will be created first.",
+            conflicts.len()
+        ))
+        .with_default(false)
+        .prompt()
+        .map_err(|e| AppError::Message(format!("Prompt failed: {}", e)))?;
+
+        if !confirm {
+            out::info("Cancelled.");
+            return Ok(());
+        }
+    }
+
+    // Unix 下进程环境变量无法由本进程删除，只能提示用户手动 unset
+    let process_only: Vec<String> = if cfg!(target_os = "windows") {
+        Vec::new()
+    } else {
+        conflicts
+            .iter()
+            .filter(|c| c.source_type == "system")
+            .map(|c| c.var_name.clone())
+            .collect()
+    };
+
+    let (backup, removed) = env_manager::delete_env_vars(conflicts)
+        .map_err(|e| AppError::Message(format!("Failed to remove environment variables: {}", e)))?;
+
+    out::success(&format!("\n✓ Removed {} environment variable(s)", removed));
+    out::info(&format!("  Backup: {}", backup.backup_path));
+    out::info(&format!(
+        "  Undo with: cc-switch env restore {}",
+        backup.timestamp
+    ));
+    if !process_only.is_empty() {
+        println!(
+            "{}",
+            error(&format!(
+                "Set only in the current process environment; unset them in your shell: {}",
+                process_only.join(", ")
+            ))
+        );
+    }
+    out::info("Open a new shell (or re-login on Windows) for the changes to take effect.");
 
     Ok(())
 }
@@ -95,10 +380,7 @@ fn check_conflicts(app_type: AppType) -> Result<(), AppError> {
 fn list_env_vars(app_type: AppType) -> Result<(), AppError> {
     let app_str = app_type.as_str();
 
-    println!(
-        "\n{}",
-        highlight(&format!("Environment Variables for {}", app_str))
-    );
+    out::highlight(&format!("\nEnvironment Variables for {}", app_str));
     println!("{}", "═".repeat(60));
 
     // 获取所有相关环境变量
@@ -106,7 +388,7 @@ fn list_env_vars(app_type: AppType) -> Result<(), AppError> {
         .map_err(|e| AppError::Message(format!("Failed to list environment variables: {}", e)))?;
 
     if conflicts.is_empty() {
-        println!("\n{}", info("No related environment variables found."));
+        out::info("\nNo related environment variables found.");
         return Ok(());
     }
 
@@ -116,11 +398,16 @@ fn list_env_vars(app_type: AppType) -> Result<(), AppError> {
     table.set_header(vec!["Variable", "Value", "Source Type", "Source Location"]);
 
     for conflict in &conflicts {
+        let value = if is_sensitive_key(&conflict.var_name) {
+            display_secret(&conflict.var_value)
+        } else {
+            conflict.var_value.clone()
+        };
         table.add_row(vec![
-            conflict.var_name.as_str(),
-            conflict.var_value.as_str(),
-            conflict.source_type.as_str(),
-            conflict.source_path.as_str(),
+            conflict.var_name.clone(),
+            value,
+            conflict.source_type.clone(),
+            conflict.source_path.clone(),
         ]);
     }
 
@@ -128,3 +415,117 @@ fn list_env_vars(app_type: AppType) -> Result<(), AppError> {
 
     Ok(())
 }
+
+fn load_env_backups() -> Result<Vec<env_manager::BackupInfo>, AppError> {
+    env_manager::list_backups()
+        .map_err(|e| AppError::Message(format!("Failed to list env backups: {}", e)))
+}
+
+fn backup_var_names(backup: &env_manager::BackupInfo) -> String {
+    backup
+        .conflicts
+        .iter()
+        .map(|c| c.var_name.as_str())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn list_env_backups() -> Result<(), AppError> {
+    let backups = load_env_backups()?;
+
+    if backups.is_empty() {
+        out::info("No env backups found.");
+        return Ok(());
+    }
+
+    let mut table = create_table();
+    table.set_header(vec!["ID", "Variables", "Path"]);
+    for backup in &backups {
+        table.add_row(vec![
+            backup.timestamp.clone(),
+            backup_var_names(backup),
+            backup.backup_path.clone(),
+        ]);
+    }
+    println!("{}", table);
+
+    Ok(())
+}
+
+fn restore_env_backup(id: Option<&str>) -> Result<(), AppError> {
+    let backups = load_env_backups()?;
+    if backups.is_empty() {
+        out::info("No env backups found.");
+        return Ok(());
+    }
+
+    let backup = match id {
+        Some(id) => backups
+            .into_iter()
+            .find(|b| b.timestamp == id || b.backup_path.ends_with(&format!("{id}.json")))
+            .ok_or_else(|| AppError::Message(format!("Env backup '{}' not found", id)))?,
+        None => {
+            let options: Vec<String> = backups
+                .iter()
+                .map(|b| format!("{}  ({})", b.timestamp, backup_var_names(b)))
+                .collect();
+            ensure_interactive("selecting an env backup", Some("a backup id"))?;
+            let choice = inquire::Select::new("Select an env backup to restore:", options.clone())
+                .prompt()
+                .map_err(|e| AppError::Message(format!("Prompt failed: {}", e)))?;
+            let index = options.iter().position(|o| *o == choice).unwrap_or(0);
+            backups.into_iter().nth(index).ok_or_else(|| {
+                AppError::Message("Selected env backup no longer exists".to_string())
+            })?
+        }
+    };
+
+    env_manager::restore_from_backup(backup.backup_path.clone())
+        .map_err(|e| AppError::Message(format!("Failed to restore env backup: {}", e)))?;
+
+    out::success(&format!("✓ Restored env backup {}", backup.timestamp));
+    for conflict in &backup.conflicts {
+        println!("  {} -> {}", conflict.var_name, conflict.source_path);
+    }
+    out::info("Open a new shell (or re-login on Windows) for the variables to take effect.");
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn env(pairs: &[(&str, &str)]) -> BTreeMap<String, String> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn diff_env_reports_missing_and_changed_keys_only() {
+        let a = env(&[
+            ("ANTHROPIC_BASE_URL", "https://a.example.com"),
+            ("ANTHROPIC_MODEL", "opus"),
+            ("SHARED", "1"),
+        ]);
+        let b = env(&[
+            ("ANTHROPIC_AUTH_TOKEN", "sk-b"),
+            ("ANTHROPIC_MODEL", "sonnet"),
+            ("SHARED", "1"),
+        ]);
+        let statuses: Vec<(String, EnvDiffStatus)> = diff_env(&a, &b)
+            .into_iter()
+            .map(|entry| (entry.key, entry.status))
+            .collect();
+        assert_eq!(
+            statuses,
+            vec![
+                ("ANTHROPIC_AUTH_TOKEN".to_string(), EnvDiffStatus::OnlyB),
+                ("ANTHROPIC_BASE_URL".to_string(), EnvDiffStatus::OnlyA),
+                ("ANTHROPIC_MODEL".to_string(), EnvDiffStatus::Changed),
+            ]
+        );
+    }
+}
@@ -1,17 +1,35 @@
 use clap::Subcommand;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 
 use crate::app_config::{AppType, McpApps, McpServer};
-use crate::cli::ui::{create_table, error, highlight, info, success};
+use crate::cli::runtime::run_async;
+use crate::cli::terminal::ensure_interactive;
+use crate::cli::ui::{create_table, emit, error, info, json_output, out, success};
 use crate::error::AppError;
-use crate::services::McpService;
+use crate::services::{ConflictStrategy, McpImportOutcome, McpProbeStatus, McpService};
 use crate::store::AppState;
 
 #[derive(Subcommand)]
 pub enum McpCommand {
     /// List all MCP servers
     List,
-    /// Add a new MCP server (interactive)
-    Add,
+    /// Add MCP server(s): opens an editor, or parses --json / --file / --claude-config
+    Add {
+        /// Server definition, e.g. '{"name":"fs","command":"npx","args":[..],"env":{..}}'
+        #[arg(long, conflicts_with_all = ["file", "claude_config"])]
+        json: Option<String>,
+        /// Read the server definition from a JSON file
+        #[arg(long, conflicts_with = "claude_config")]
+        file: Option<PathBuf>,
+        /// Import every server from a Claude-style `mcpServers` config block
+        #[arg(long, value_name = "FILE")]
+        claude_config: Option<PathBuf>,
+        /// How to handle servers whose id already exists (default: refuse and list them)
+        #[arg(long, value_enum)]
+        on_conflict: Option<ConflictStrategy>,
+    },
     /// Edit an MCP server
     Edit {
         /// Server ID to edit
@@ -21,6 +39,9 @@ pub enum McpCommand {
     Delete {
         /// Server ID to delete
         id: String,
+        /// Skip the confirmation prompt
+        #[arg(short, long)]
+        yes: bool,
     },
     /// Enable an MCP server for specific app(s)
     Enable {
@@ -32,6 +53,21 @@ pub enum McpCommand {
         /// Server ID to disable
         id: String,
     },
+    /// Probe MCP server(s): spawn / connect and perform the initialize handshake
+    Test {
+        /// Server ID to test
+        #[arg(required_unless_present = "all", conflicts_with = "all")]
+        id: Option<String>,
+        /// Test every configured server
+        #[arg(long)]
+        all: bool,
+        /// Per-server handshake timeout in seconds
+        #[arg(long, default_value_t = 10)]
+        timeout: u64,
+        /// Maximum number of servers probed at the same time
+        #[arg(long, default_value_t = 4)]
+        concurrency: usize,
+    },
     /// Validate a command is in PATH
     Validate {
         /// Command to validate
@@ -39,8 +75,19 @@ pub enum McpCommand {
     },
     /// Sync MCP configuration to live files
     Sync,
-    /// Import MCP servers from live configuration
-    Import,
+    /// Import MCP servers from live configuration, or from a JSON file made by `mcp export`
+    Import {
+        /// JSON file to import (omit to import from the app's live config)
+        file: Option<PathBuf>,
+        /// How to handle servers whose id already exists (file import only)
+        #[arg(long, value_enum, default_value_t = ConflictStrategy::Skip)]
+        on_conflict: ConflictStrategy,
+    },
+    /// Export all MCP servers to a JSON file
+    Export {
+        /// Output file path
+        file: PathBuf,
+    },
 }
 
 pub fn execute(cmd: McpCommand, app: Option<AppType>) -> Result<(), AppError> {
@@ -48,14 +95,35 @@ pub fn execute(cmd: McpCommand, app: Option<AppType>) -> Result<(), AppError> {
 
     match cmd {
         McpCommand::List => list_servers(app_type),
-        McpCommand::Add => add_server(app_type),
+        McpCommand::Add {
+            json,
+            file,
+            claude_config,
+            on_conflict,
+        } => match (json, file.or(claude_config)) {
+            (None, None) => add_server(app_type),
+            (json, file) => {
+                add_servers_from_snippet(app_type, json.as_deref(), file.as_deref(), on_conflict)
+            }
+        },
         McpCommand::Edit { id } => edit_server(app_type, &id),
-        McpCommand::Delete { id } => delete_server(&id),
+        McpCommand::Delete { id, yes } => delete_server(&id, yes),
         McpCommand::Enable { id } => enable_server(app_type, &id),
         McpCommand::Disable { id } => disable_server(app_type, &id),
+        McpCommand::Test {
+            id,
+            timeout,
+            concurrency,
+            ..
+        } => test_servers(id.as_deref(), timeout, concurrency),
         McpCommand::Validate { command } => validate_command(&command),
         McpCommand::Sync => sync_servers(),
-        McpCommand::Import => import_servers(app_type),
+        McpCommand::Import { file: None, .. } => import_servers(app_type),
+        McpCommand::Import {
+            file: Some(file),
+            on_conflict,
+        } => import_servers_from_file(&file, on_conflict),
+        McpCommand::Export { file } => export_servers(&file),
     }
 }
 
@@ -68,7 +136,7 @@ fn list_servers(app_type: AppType) -> Result<(), AppError> {
     let servers = McpService::get_all_servers(&state)?;
 
     if servers.is_empty() {
-        println!("{}", info("No MCP servers found."));
+        out::info("No MCP servers found.");
         println!("Use 'cc-switch mcp add' or 'cc-switch mcp import' to add servers.");
         return Ok(());
     }
@@ -110,7 +178,7 @@ fn list_servers(app_type: AppType) -> Result<(), AppError> {
     Ok(())
 }
 
-fn delete_server(id: &str) -> Result<(), AppError> {
+fn delete_server(id: &str, yes: bool) -> Result<(), AppError> {
     let state = get_state()?;
 
     // 检查服务器是否存在
@@ -120,7 +188,7 @@ fn delete_server(id: &str) -> Result<(), AppError> {
         .ok_or_else(|| AppError::Message(format!("MCP server '{}' not found", id)))?;
 
     // 显示将要删除的服务器信息
-    println!("{}", highlight("Server to be deleted:"));
+    out::highlight("Server to be deleted:");
     println!("ID:   {}", id);
     println!("Name: {}", server.name);
 
@@ -151,29 +219,29 @@ fn delete_server(id: &str) -> Result<(), AppError> {
     println!();
 
     // 确认删除
-    let confirm = inquire::Confirm::new(&format!(
-        "Are you sure you want to delete MCP server '{}'?",
-        id
-    ))
-    .with_default(false)
-    .prompt()
-    .map_err(|e| AppError::Message(format!("Prompt failed: {}", e)))?;
-
-    if !confirm {
-        println!("{}", info("Cancelled."));
-        return Ok(());
+    if !yes {
+        ensure_interactive("the delete confirmation", Some("--yes"))?;
+        let confirm = inquire::Confirm::new(&format!(
+            "Are you sure you want to delete MCP server '{}'?",
+            id
+        ))
+        .with_default(false)
+        .prompt()
+        .map_err(|e| AppError::Message(format!("Prompt failed: {}", e)))?;
+
+        if !confirm {
+            out::info("Cancelled.");
+            return Ok(());
+        }
     }
 
     // 执行删除
     let deleted = McpService::delete_server(&state, id)?;
 
     if deleted {
-        println!("{}", success(&format!("✓ Deleted MCP server '{}'", id)));
+        out::success(&format!("✓ Deleted MCP server '{}'", id));
         if !enabled_apps.is_empty() {
-            println!(
-                "{}",
-                info(&format!("  Removed from: {}", enabled_apps.join(", ")))
-            );
+            out::info(&format!("  Removed from: {}", enabled_apps.join(", ")));
         }
     } else {
         println!("{}", error(&format!("Failed to delete server '{}'", id)));
@@ -195,14 +263,8 @@ fn enable_server(app_type: AppType, id: &str) -> Result<(), AppError> {
     // 执行启用
     McpService::toggle_app(&state, id, app_type, true)?;
 
-    println!(
-        "{}",
-        success(&format!("✓ Enabled MCP server '{}' for {}", id, app_str))
-    );
-    println!(
-        "{}",
-        info("Note: Configuration has been synced to live file.")
-    );
+    out::success(&format!("✓ Enabled MCP server '{}' for {}", id, app_str));
+    out::info("Note: Configuration has been synced to live file.");
 
     Ok(())
 }
@@ -220,14 +282,8 @@ fn disable_server(app_type: AppType, id: &str) -> Result<(), AppError> {
     // 执行禁用
     McpService::toggle_app(&state, id, app_type, false)?;
 
-    println!(
-        "{}",
-        success(&format!("✓ Disabled MCP server '{}' for {}", id, app_str))
-    );
-    println!(
-        "{}",
-        info("Note: Configuration has been removed from live file.")
-    );
+    out::success(&format!("✓ Disabled MCP server '{}' for {}", id, app_str));
+    out::info("Note: Configuration has been removed from live file.");
 
     Ok(())
 }
@@ -235,15 +291,12 @@ fn disable_server(app_type: AppType, id: &str) -> Result<(), AppError> {
 fn sync_servers() -> Result<(), AppError> {
     let state = get_state()?;
 
-    println!("{}", info("Syncing all enabled MCP servers..."));
+    out::info("Syncing all enabled MCP servers...");
 
     McpService::sync_all_enabled(&state)?;
 
-    println!("{}", success("✓ All MCP servers synced successfully"));
-    println!(
-        "{}",
-        info("Note: Live configuration files have been updated.")
-    );
+    out::success("✓ All MCP servers synced successfully");
+    out::info("Note: Live configuration files have been updated.");
 
     Ok(())
 }
@@ -252,13 +305,10 @@ fn import_servers(app_type: AppType) -> Result<(), AppError> {
     let state = get_state()?;
     let app_str = app_type.as_str().to_string();
 
-    println!(
-        "{}",
-        info(&format!(
-            "Importing MCP servers from {} live config...",
-            app_str
-        ))
-    );
+    out::info(&format!(
+        "Importing MCP servers from {} live config...",
+        app_str
+    ));
 
     let count = match app_type {
         AppType::Claude => McpService::import_from_claude(&state)?,
@@ -267,27 +317,100 @@ fn import_servers(app_type: AppType) -> Result<(), AppError> {
     };
 
     if count > 0 {
-        println!(
-            "{}",
-            success(&format!(
-                "✓ Imported {} MCP server(s) from {}",
-                count, app_str
-            ))
-        );
-        println!(
-            "{}",
-            info("Note: Servers have been added to unified configuration.")
-        );
+        out::success(&format!(
+            "✓ Imported {} MCP server(s) from {}",
+            count, app_str
+        ));
+        out::info("Note: Servers have been added to unified configuration.");
     } else {
-        println!(
-            "{}",
-            info(&format!("No new MCP servers found in {} config.", app_str))
-        );
+        out::info(&format!("No new MCP servers found in {} config.", app_str));
     }
 
     Ok(())
 }
 
+fn export_servers(file: &Path) -> Result<(), AppError> {
+    let state = get_state()?;
+    let count = McpService::export_to_file(&state, file)?;
+    out::success(&format!(
+        "✓ Exported {} MCP server(s) to {}",
+        count,
+        file.display()
+    ));
+    Ok(())
+}
+
+fn import_servers_from_file(file: &Path, strategy: ConflictStrategy) -> Result<(), AppError> {
+    let state = get_state()?;
+    let results = McpService::import_from_file(&state, file, strategy)?;
+
+    if results.is_empty() {
+        out::info("No MCP servers found in file.");
+        return Ok(());
+    }
+
+    let mut imported = 0;
+    for (id, outcome) in &results {
+        match outcome {
+            McpImportOutcome::Added => {
+                imported += 1;
+                println!("{} {} (added)", success("✓"), id);
+            }
+            McpImportOutcome::Overwritten => {
+                imported += 1;
+                println!("{} {} (overwritten)", success("✓"), id);
+            }
+            McpImportOutcome::Skipped => {
+                println!("{} {} (exists, skipped)", info("-"), id);
+            }
+            McpImportOutcome::Invalid(reason) => {
+                println!("{} {}: {}", error("✗"), id, reason);
+            }
+        }
+    }
+
+    println!();
+    out::info(&format!(
+        "Imported {} of {} server(s) from {}",
+        imported,
+        results.len(),
+        file.display()
+    ));
+    Ok(())
+}
+
+fn add_servers_from_snippet(
+    app_type: AppType,
+    json_text: Option<&str>,
+    file: Option<&Path>,
+    on_conflict: Option<ConflictStrategy>,
+) -> Result<(), AppError> {
+    let raw = match (json_text, file) {
+        (Some(text), _) => text.to_string(),
+        (None, Some(path)) => fs::read_to_string(path).map_err(|e| AppError::io(path, e))?,
+        (None, None) => {
+            return Err(AppError::InvalidInput(
+                "either --json or --file is required".to_string(),
+            ))
+        }
+    };
+    let value: serde_json::Value = serde_json::from_str(&raw)
+        .map_err(|e| AppError::InvalidInput(format!("invalid JSON: {e}")))?;
+    let servers = McpService::servers_from_snippet(&value, &app_type)?;
+
+    let state = get_state()?;
+    for (id, outcome) in McpService::add_from_snippet(&state, servers, on_conflict)? {
+        match outcome {
+            McpImportOutcome::Added => out::success(&format!("✓ {id} (added)")),
+            McpImportOutcome::Overwritten => out::success(&format!("✓ {id} (overwritten)")),
+            McpImportOutcome::Skipped => out::info(&format!("- {id} (exists, skipped)")),
+            McpImportOutcome::Invalid(reason) => out::warning(&format!("✗ {id}: {reason}")),
+        }
+    }
+    out::info("Tip: Use 'cc-switch mcp list' to view all servers.");
+    Ok(())
+}
+
 fn add_server(_app_type: AppType) -> Result<(), AppError> {
     let state = get_state()?;
 
@@ -310,8 +433,8 @@ fn add_server(_app_type: AppType) -> Result<(), AppError> {
     let initial = serde_json::to_string_pretty(&template)
         .map_err(|e| AppError::Message(format!("failed to serialize template: {e}")))?;
 
-    println!("{}", highlight("Add New MCP Server"));
-    println!("{}", info("Opening external editor..."));
+    out::highlight("Add New MCP Server");
+    out::info("Opening external editor...");
     let edited =
         edit::edit(&initial).map_err(|e| AppError::Message(format!("editor failed: {e}")))?;
 
@@ -325,11 +448,8 @@ fn add_server(_app_type: AppType) -> Result<(), AppError> {
 
     McpService::upsert_server(&state, server)?;
 
-    println!("{}", success("✓ MCP server saved"));
-    println!(
-        "{}",
-        info("Tip: Use 'cc-switch mcp list' to view all servers.")
-    );
+    out::success("✓ MCP server saved");
+    out::info("Tip: Use 'cc-switch mcp list' to view all servers.");
     Ok(())
 }
 
@@ -345,13 +465,13 @@ fn edit_server(_app_type: AppType, id: &str) -> Result<(), AppError> {
     let initial = serde_json::to_string_pretty(&existing)
         .map_err(|e| AppError::Message(format!("failed to serialize server: {e}")))?;
 
-    println!("{}", info(&format!("Editing MCP server '{}'...", id)));
-    println!("{}", info("Opening external editor..."));
+    out::info(&format!("Editing MCP server '{}'...", id));
+    out::info("Opening external editor...");
     let edited =
         edit::edit(&initial).map_err(|e| AppError::Message(format!("editor failed: {e}")))?;
 
     if edited.trim_end() == initial.trim_end() {
-        println!("{}", info("No changes detected."));
+        out::info("No changes detected.");
         return Ok(());
     }
 
@@ -366,28 +486,88 @@ fn edit_server(_app_type: AppType, id: &str) -> Result<(), AppError> {
 
     McpService::upsert_server(&state, server)?;
 
-    println!("{}", success("✓ MCP server updated"));
+    out::success("✓ MCP server updated");
+    Ok(())
+}
+
+fn test_servers(id: Option<&str>, timeout: u64, concurrency: usize) -> Result<(), AppError> {
+    let state = get_state()?;
+    let mut servers = McpService::get_all_servers(&state)?;
+    let targets: Vec<McpServer> = match id {
+        Some(id) => vec![servers
+            .remove(id)
+            .ok_or_else(|| AppError::InvalidInput(format!("MCP server '{id}' not found")))?],
+        None => servers.into_values().collect(),
+    };
+    let json = json_output();
+    if targets.is_empty() && !json {
+        out::info("No MCP servers configured.");
+        return Ok(());
+    }
+
+    if !json {
+        out::info(&format!("Testing {} MCP server(s)...", targets.len()));
+    }
+    let results = run_async(async {
+        Ok(
+            McpService::probe_servers(targets, Duration::from_secs(timeout.max(1)), concurrency)
+                .await,
+        )
+    })?;
+
+    let failed = results
+        .iter()
+        .filter(|r| r.status != McpProbeStatus::Passed)
+        .count();
+
+    if json {
+        emit(&results)?;
+    } else {
+        let mut table = create_table();
+        table.set_header(vec!["ID", "Transport", "Result", "Time", "Detail"]);
+        for r in &results {
+            let result = if r.status == McpProbeStatus::Passed {
+                success("✓ pass")
+            } else {
+                error(&format!("✗ {}", r.status.as_str()))
+            };
+            table.add_row(vec![
+                r.id.clone(),
+                r.transport.clone(),
+                result,
+                r.latency_ms
+                    .map(|ms| format!("{ms} ms"))
+                    .unwrap_or_else(|| "-".to_string()),
+                r.detail.clone().unwrap_or_default(),
+            ]);
+        }
+        println!("{}", table);
+    }
+
+    if failed > 0 {
+        return Err(AppError::Message(format!(
+            "{failed} of {} MCP server(s) failed",
+            results.len()
+        )));
+    }
+    if !json {
+        out::success(&format!("✓ All {} MCP server(s) passed", results.len()));
+    }
     Ok(())
 }
 
 fn validate_command(command: &str) -> Result<(), AppError> {
-    println!("{}", info(&format!("Validating command '{}'...", command)));
+    out::info(&format!("Validating command '{}'...", command));
 
     // 检查命令是否在 PATH 中
     if which::which(command).is_ok() {
-        println!(
-            "{}",
-            success(&format!("✓ Command '{}' is available in PATH", command))
-        );
+        out::success(&format!("✓ Command '{}' is available in PATH", command));
     } else {
         println!(
             "{}",
             error(&format!("✗ Command '{}' not found in PATH", command))
         );
-        println!(
-            "{}",
-            info("Make sure the command is installed and accessible.")
-        );
+        out::info("Make sure the command is installed and accessible.");
     }
 
     Ok(())
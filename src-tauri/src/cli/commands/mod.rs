@@ -1,7 +1,12 @@
+pub mod app;
 pub mod config;
+pub mod deeplink;
 pub mod env;
 pub mod mcp;
 pub mod prompts;
 pub mod provider;
 pub mod provider_input;
+pub mod settings;
 pub mod skills;
+pub mod update;
+pub mod version;
@@ -1,7 +1,9 @@
 use clap::Subcommand;
+use std::path::{Path, PathBuf};
 
 use crate::app_config::AppType;
-use crate::cli::ui::{create_table, highlight, info, success};
+use crate::cli::terminal::ensure_interactive;
+use crate::cli::ui::{create_table, highlight, info, out, success};
 use crate::error::AppError;
 use crate::prompt::Prompt;
 use crate::services::PromptService;
@@ -31,12 +33,31 @@ pub enum PromptsCommand {
     Delete {
         /// Prompt preset ID
         id: String,
+        /// Skip the confirmation prompt
+        #[arg(short, long)]
+        yes: bool,
     },
     /// Show prompt content
     Show {
         /// Prompt preset ID
         id: String,
     },
+    /// Import every .md file in a directory as a prompt preset (name = file name)
+    Import {
+        /// Directory containing markdown files
+        dir: PathBuf,
+        /// Also import from subdirectories
+        #[arg(short, long)]
+        recursive: bool,
+    },
+    /// Export every prompt preset to <dir>/<id>.md (re-import keeps the ids)
+    Export {
+        /// Target directory (created if missing)
+        dir: PathBuf,
+        /// Overwrite existing files
+        #[arg(long)]
+        force: bool,
+    },
 }
 
 pub fn execute(cmd: PromptsCommand, app: Option<AppType>) -> Result<(), AppError> {
@@ -49,8 +70,10 @@ pub fn execute(cmd: PromptsCommand, app: Option<AppType>) -> Result<(), AppError
         PromptsCommand::Deactivate => deactivate_prompt(app_type),
         PromptsCommand::Create => create_prompt(app_type),
         PromptsCommand::Edit { id } => edit_prompt(app_type, &id),
-        PromptsCommand::Delete { id } => delete_prompt(app_type, &id),
+        PromptsCommand::Delete { id, yes } => delete_prompt(app_type, &id, yes),
         PromptsCommand::Show { id } => show_prompt(app_type, &id),
+        PromptsCommand::Import { dir, recursive } => import_prompts(app_type, &dir, recursive),
+        PromptsCommand::Export { dir, force } => export_prompts(app_type, &dir, force),
     }
 }
 
@@ -63,7 +86,7 @@ fn list_prompts(app_type: AppType) -> Result<(), AppError> {
     let prompts = PromptService::get_prompts(&state, app_type.clone())?;
 
     if prompts.is_empty() {
-        println!("{}", info("No prompt presets found."));
+        out::info("No prompt presets found.");
         println!("Use 'cc-switch prompts create' to create a new prompt preset.");
         return Ok(());
     }
@@ -140,7 +163,7 @@ fn show_current(app_type: AppType) -> Result<(), AppError> {
                 })
                 .unwrap_or_else(|| "Unknown".to_string());
 
-            println!("{}", highlight("Current Active Prompt"));
+            out::highlight("Current Active Prompt");
             println!("{}", "=".repeat(50));
             println!("ID:          {}", id);
             println!("Name:        {}", prompt.name);
@@ -150,7 +173,7 @@ fn show_current(app_type: AppType) -> Result<(), AppError> {
             println!("Updated:     {}", updated);
             println!("App:         {}", app_type.as_str());
             println!();
-            println!("{}", highlight("Content Preview:"));
+            out::highlight("Content Preview:");
             println!("{}", "-".repeat(50));
 
             // 显示内容预览（前 10 行）
@@ -162,11 +185,11 @@ fn show_current(app_type: AppType) -> Result<(), AppError> {
 
             if lines.len() > 10 {
                 println!("...");
-                println!("{}", info(&format!("({} more lines)", lines.len() - 10)));
+                out::info(&format!("({} more lines)", lines.len() - 10));
             }
         }
         None => {
-            println!("{}", info("No active prompt preset."));
+            out::info("No active prompt preset.");
             println!("Use 'cc-switch prompts activate <id>' to activate a prompt.");
         }
     }
@@ -190,21 +213,15 @@ fn activate_prompt(app_type: AppType, id: &str) -> Result<(), AppError> {
     // 执行激活
     PromptService::enable_prompt(&state, app_type, id)?;
 
-    println!(
-        "{}",
-        success(&format!("✓ Activated prompt preset '{}'", id))
-    );
-    println!("{}", info(&format!("  Application: {}", app_str)));
+    out::success(&format!("✓ Activated prompt preset '{}'", id));
+    out::info(&format!("  Application: {}", app_str));
     println!();
-    println!(
-        "{}",
-        info("Note: The prompt has been synced to the live configuration file.")
-    );
+    out::info("Note: The prompt has been synced to the live configuration file.");
 
     Ok(())
 }
 
-fn delete_prompt(app_type: AppType, id: &str) -> Result<(), AppError> {
+fn delete_prompt(app_type: AppType, id: &str, yes: bool) -> Result<(), AppError> {
     let state = get_state()?;
 
     // 检查 prompt 是否存在
@@ -222,7 +239,7 @@ fn delete_prompt(app_type: AppType, id: &str) -> Result<(), AppError> {
     }
 
     // 显示将要删除的 prompt 信息
-    println!("{}", highlight("Prompt to be deleted:"));
+    out::highlight("Prompt to be deleted:");
     println!("ID:   {}", id);
     println!("Name: {}", prompt.name);
     if let Some(desc) = &prompt.description {
@@ -231,23 +248,26 @@ fn delete_prompt(app_type: AppType, id: &str) -> Result<(), AppError> {
     println!();
 
     // 确认删除
-    let confirm = inquire::Confirm::new(&format!(
-        "Are you sure you want to delete prompt preset '{}'?",
-        id
-    ))
-    .with_default(false)
-    .prompt()
-    .map_err(|e| AppError::Message(format!("Prompt failed: {}", e)))?;
-
-    if !confirm {
-        println!("{}", info("Cancelled."));
-        return Ok(());
+    if !yes {
+        ensure_interactive("the delete confirmation", Some("--yes"))?;
+        let confirm = inquire::Confirm::new(&format!(
+            "Are you sure you want to delete prompt preset '{}'?",
+            id
+        ))
+        .with_default(false)
+        .prompt()
+        .map_err(|e| AppError::Message(format!("Prompt failed: {}", e)))?;
+
+        if !confirm {
+            out::info("Cancelled.");
+            return Ok(());
+        }
     }
 
     // 执行删除
     PromptService::delete_prompt(&state, app_type, id)?;
 
-    println!("{}", success(&format!("✓ Deleted prompt preset '{}'", id)));
+    out::success(&format!("✓ Deleted prompt preset '{}'", id));
 
     Ok(())
 }
@@ -269,7 +289,7 @@ fn show_prompt(app_type: AppType, id: &str) -> Result<(), AppError> {
         })
         .unwrap_or_else(|| "Unknown".to_string());
 
-    println!("{}", highlight(&format!("Prompt Preset: {}", prompt.name)));
+    out::highlight(&format!("Prompt Preset: {}", prompt.name));
     println!("{}", "=".repeat(50));
     println!("ID:          {}", id);
     println!("Name:        {}", prompt.name);
@@ -286,7 +306,7 @@ fn show_prompt(app_type: AppType, id: &str) -> Result<(), AppError> {
     );
     println!("Updated:     {}", updated);
     println!();
-    println!("{}", highlight("Content:"));
+    out::highlight("Content:");
     println!("{}", "-".repeat(50));
     println!("{}", prompt.content);
     println!("{}", "-".repeat(50));
@@ -307,8 +327,8 @@ fn create_prompt(_app_type: AppType) -> Result<(), AppError> {
     let name = format!("Prompt {}", chrono::Local::now().format("%Y-%m-%d %H:%M"));
     let initial = "# Write your prompt here\n";
 
-    println!("{}", highlight("Create New Prompt Preset"));
-    println!("{}", info("Opening external editor..."));
+    out::highlight("Create New Prompt Preset");
+    out::info("Opening external editor...");
 
     let edited =
         edit::edit(initial).map_err(|e| AppError::Message(format!("editor failed: {e}")))?;
@@ -326,11 +346,51 @@ fn create_prompt(_app_type: AppType) -> Result<(), AppError> {
 
     PromptService::upsert_prompt(&state, _app_type.clone(), &id, prompt)?;
 
-    println!("{}", success(&format!("✓ Created prompt preset '{id}'")));
-    println!(
-        "{}",
-        info("Tip: Use 'cc-switch prompts list' to view all presets.")
-    );
+    out::success(&format!("✓ Created prompt preset '{id}'"));
+    out::info("Tip: Use 'cc-switch prompts list' to view all presets.");
+    Ok(())
+}
+
+fn import_prompts(app_type: AppType, dir: &Path, recursive: bool) -> Result<(), AppError> {
+    let state = get_state()?;
+    let report = PromptService::import_from_dir(&state, app_type.clone(), dir, recursive)?;
+
+    if report.added.is_empty() && report.updated.is_empty() {
+        out::info(&format!("No markdown files found in {}.", dir.display()));
+        return Ok(());
+    }
+    for id in &report.added {
+        println!("{} {} (added)", success("✓"), id);
+    }
+    for id in &report.updated {
+        println!("{} {} (updated)", success("✓"), id);
+    }
+    out::success(&format!(
+        "✓ Imported {} prompt(s), updated {} for {}",
+        report.added.len(),
+        report.updated.len(),
+        app_type.as_str()
+    ));
+    Ok(())
+}
+
+fn export_prompts(app_type: AppType, dir: &Path, force: bool) -> Result<(), AppError> {
+    let state = get_state()?;
+    let written = PromptService::export_to_dir(&state, app_type.clone(), dir, force)?;
+
+    if written.is_empty() {
+        out::info("No prompt presets to export.");
+        return Ok(());
+    }
+    for path in &written {
+        println!("{} {}", success("✓"), path.display());
+    }
+    out::success(&format!(
+        "✓ Exported {} prompt(s) for {} to {}",
+        written.len(),
+        app_type.as_str(),
+        dir.display()
+    ));
     Ok(())
 }
 
@@ -349,19 +409,13 @@ fn deactivate_prompt(app_type: AppType) -> Result<(), AppError> {
             // Deactivate the current prompt
             PromptService::disable_prompt(&state, app_type.clone(), &id)?;
 
-            println!(
-                "{}",
-                success(&format!("✓ Deactivated prompt preset '{}'", id))
-            );
-            println!("{}", info(&format!("  Application: {}", app_type.as_str())));
+            out::success(&format!("✓ Deactivated prompt preset '{}'", id));
+            out::info(&format!("  Application: {}", app_type.as_str()));
             println!();
-            println!(
-                "{}",
-                info("Note: The live configuration file has been cleared.")
-            );
+            out::info("Note: The live configuration file has been cleared.");
         }
         None => {
-            println!("{}", info("No active prompt to deactivate."));
+            out::info("No active prompt to deactivate.");
             println!("Use 'cc-switch prompts activate <id>' to activate a prompt preset.");
         }
     }
@@ -378,14 +432,14 @@ fn edit_prompt(_app_type: AppType, id: &str) -> Result<(), AppError> {
         )));
     };
 
-    println!("{}", info(&format!("Editing prompt preset '{}'...", id)));
-    println!("{}", info("Opening external editor..."));
+    out::info(&format!("Editing prompt preset '{}'...", id));
+    out::info("Opening external editor...");
 
     let edited = edit::edit(&prompt.content)
         .map_err(|e| AppError::Message(format!("editor failed: {e}")))?;
 
     if edited.trim_end() == prompt.content.trim_end() {
-        println!("{}", info("No changes detected."));
+        out::info("No changes detected.");
         return Ok(());
     }
 
@@ -398,6 +452,6 @@ fn edit_prompt(_app_type: AppType, id: &str) -> Result<(), AppError> {
 
     PromptService::upsert_prompt(&state, _app_type.clone(), id, prompt)?;
 
-    println!("{}", success(&format!("✓ Updated prompt preset '{id}'")));
+    out::success(&format!("✓ Updated prompt preset '{id}'"));
     Ok(())
 }
@@ -1,4 +1,5 @@
-use clap::Subcommand;
+use clap::{Subcommand, ValueEnum};
+use serde::Serialize;
 
 use crate::app_config::AppType;
 use crate::cli::commands::provider_input::{
@@ -7,10 +8,19 @@ use crate::cli::commands::provider_input::{
     ProviderAddMode,
 };
 use crate::cli::i18n::texts;
-use crate::cli::ui::{create_table, error, highlight, info, success, warning};
+use crate::cli::runtime::run_async;
+use crate::cli::terminal::{ensure_interactive, read_secret};
+use crate::cli::ui::{
+    create_table, display_secret, emit, error, format_bool, highlight, info, is_sensitive_key,
+    json_output, out, redact_secret, redact_secrets_in_json, reveal_secrets,
+};
 use crate::error::AppError;
 use crate::provider::Provider;
-use crate::services::{ProviderService, SpeedtestService};
+use crate::services::provider::{parse_time_bound, pick_fastest_endpoint};
+use crate::services::{
+    EndpointLatency, EndpointSelection, ProviderModels, ProviderService, SpeedtestService,
+    SwitchHookOutput,
+};
 use crate::store::AppState;
 use inquire::{Confirm, Select, Text};
 
@@ -21,85 +31,473 @@ fn supports_official_provider(app_type: &AppType) -> bool {
 #[derive(Subcommand)]
 pub enum ProviderCommand {
     /// List all providers
-    List,
+    List {
+        /// Sort order (defaults to the stored sort index, then creation time)
+        #[arg(long, value_enum)]
+        sort: Option<ProviderSort>,
+        /// Only show providers whose name, id or API URL contains this text (case-insensitive)
+        #[arg(long, value_name = "TEXT")]
+        filter: Option<String>,
+    },
     /// Show current provider
     Current,
+    /// Show the full stored configuration of a provider
+    Show {
+        /// Provider ID or name to show
+        id: String,
+        /// Print API keys and tokens in full instead of redacting them
+        #[arg(long)]
+        reveal_key: bool,
+    },
     /// Switch to a provider
     Switch {
-        /// Provider ID to switch to
+        /// Provider ID or name to switch to (with `--app all`: provider name, matched per app)
         id: String,
+        /// Remember the previous provider so `restore-session` can switch back
+        #[arg(long)]
+        temporary: bool,
+        /// Print the live config that would be written, without changing anything
+        #[arg(long, conflicts_with = "temporary")]
+        dry_run: bool,
+        /// Skip the post-switch hook (see `app hook`)
+        #[arg(long)]
+        no_hook: bool,
+    },
+    /// Revert the last `switch --temporary` for the selected app
+    RestoreSession,
+    /// Add a new provider (interactive without flags)
+    Add {
+        /// Provider display name (switches to non-interactive mode)
+        #[arg(long, requires_all = ["endpoint", "api_key"])]
+        name: Option<String>,
+        /// API endpoint (base URL)
+        #[arg(long, value_name = "URL", requires = "name")]
+        endpoint: Option<String>,
+        /// API key / auth token
+        #[arg(long, requires = "name")]
+        api_key: Option<String>,
+        /// Default model
+        #[arg(long, requires = "name")]
+        model: Option<String>,
     },
-    /// Add a new provider (interactive)
-    Add,
     /// Edit a provider
     Edit {
         /// Provider ID to edit
         id: String,
     },
     /// Delete a provider
+    #[command(alias = "remove")]
     Delete {
-        /// Provider ID to delete
-        id: String,
+        /// Provider ID or name to delete
+        #[arg(required_unless_present = "all", conflicts_with = "all")]
+        id: Option<String>,
+        /// Delete every provider of the selected app
+        #[arg(long)]
+        all: bool,
+        /// Skip the confirmation prompt
+        #[arg(short, long)]
+        yes: bool,
+        /// Also delete the provider currently in use (with --all)
+        #[arg(long, requires = "all")]
+        force: bool,
     },
     /// Duplicate a provider
     Duplicate {
         /// Provider ID to duplicate
         id: String,
     },
-    /// Test provider endpoint speed
+    /// Test provider endpoint speed (all endpoints; picks the fastest when auto-select is on)
+    #[command(alias = "test")]
     Speedtest {
-        /// Provider ID to test
+        /// Provider ID or name to test
+        id: String,
+    },
+    /// List or manage a provider's endpoints
+    Endpoints {
+        /// Provider ID
+        id: String,
+        #[command(subcommand)]
+        action: Option<ProviderEndpointsCommand>,
+    },
+    /// Set model fields of a provider in place
+    SetModel {
+        /// Provider ID to update
         id: String,
+        /// Main model (ANTHROPIC_MODEL / Codex `model` / GEMINI_MODEL)
+        #[arg(long)]
+        model: Option<String>,
+        /// Default Haiku model (Claude only)
+        #[arg(long)]
+        haiku: Option<String>,
+        /// Default Sonnet model (Claude only)
+        #[arg(long)]
+        sonnet: Option<String>,
+        /// Default Opus model (Claude only)
+        #[arg(long)]
+        opus: Option<String>,
+    },
+    /// Replace a provider's API key (prompted, or read from stdin when piped)
+    SetKey {
+        /// Provider ID to update
+        id: String,
+        /// Store the key in the OS keychain and keep only a `keyring:<name>` reference
+        #[arg(long)]
+        keyring: bool,
+        /// Keychain entry name (default: <app>-<id>)
+        #[arg(long, value_name = "NAME", requires = "keyring")]
+        name: Option<String>,
+    },
+    /// Pin providers to the top of every listing, in the given order
+    Reorder {
+        /// Provider IDs, comma-separated (unlisted providers keep their relative order)
+        #[arg(value_delimiter = ',', required = true)]
+        ids: Vec<String>,
+    },
+    /// Check every stored provider for a valid endpoint, a token and a required model
+    Validate,
+    /// Show provider switches, newest first
+    History {
+        /// Only switches at or after this time (RFC3339 or YYYY-MM-DD, local midnight)
+        #[arg(long, value_name = "TIME")]
+        since: Option<String>,
+        /// Only switches before this time (exclusive; RFC3339 or YYYY-MM-DD)
+        #[arg(long, value_name = "TIME")]
+        until: Option<String>,
+        /// Maximum number of entries to show
+        #[arg(long)]
+        limit: Option<usize>,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum ProviderEndpointsCommand {
+    /// List endpoints (default)
+    List,
+    /// Add an alternate endpoint
+    Add {
+        /// Endpoint URL
+        url: String,
+    },
+    /// Remove an alternate endpoint
+    #[command(alias = "rm")]
+    Remove {
+        /// Endpoint URL
+        url: String,
+    },
+    /// Make an endpoint the one written to the live config
+    Use {
+        /// Endpoint URL
+        url: String,
+    },
+    /// Turn latency-based auto-selection on `switch` / `speedtest` on or off
+    AutoSelect {
+        /// on | off
+        #[arg(
+            action = clap::ArgAction::Set,
+            value_parser = clap::builder::BoolishValueParser::new()
+        )]
+        enabled: bool,
     },
 }
 
+/// `provider list --sort` 的排序方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ProviderSort {
+    /// By display name (case-insensitive)
+    Name,
+    /// By provider id
+    Id,
+    /// Most recently added first
+    Recent,
+}
+
+/// 排序供应商列表；`None` 为默认顺序（先 sort_index，再创建时间）
+pub(crate) fn sort_providers<K: AsRef<str>, P: std::borrow::Borrow<Provider>>(
+    list: &mut [(K, P)],
+    sort: Option<ProviderSort>,
+) {
+    list.sort_by(|(id_a, a), (id_b, b)| {
+        let (id_a, id_b) = (id_a.as_ref(), id_b.as_ref());
+        let (a, b) = (a.borrow(), b.borrow());
+        match sort {
+            Some(ProviderSort::Name) => a
+                .name
+                .to_lowercase()
+                .cmp(&b.name.to_lowercase())
+                .then_with(|| id_a.cmp(id_b)),
+            Some(ProviderSort::Id) => id_a.cmp(id_b),
+            Some(ProviderSort::Recent) => b
+                .added_at
+                .or(b.created_at.map(normalize_created_at))
+                .cmp(&a.added_at.or(a.created_at.map(normalize_created_at))),
+            None => match (a.sort_index, b.sort_index) {
+                (Some(idx_a), Some(idx_b)) => idx_a.cmp(&idx_b),
+                (Some(_), None) => std::cmp::Ordering::Less,
+                (None, Some(_)) => std::cmp::Ordering::Greater,
+                (None, None) => a.created_at.cmp(&b.created_at),
+            },
+        }
+    });
+}
+
+/// created_at 历史上混有毫秒与秒，统一换算为秒以便与 added_at 比较
+fn normalize_created_at(ts: i64) -> i64 {
+    if ts > 100_000_000_000 {
+        ts / 1000
+    } else {
+        ts
+    }
+}
+
+/// 名称、ID 或 API 地址包含 `filter`（忽略大小写）
+pub(crate) fn provider_matches_filter(
+    id: &str,
+    provider: &Provider,
+    app_type: &AppType,
+    filter: &str,
+) -> bool {
+    let needle = filter.trim().to_lowercase();
+    if needle.is_empty() {
+        return true;
+    }
+    id.to_lowercase().contains(&needle)
+        || provider.name.to_lowercase().contains(&needle)
+        || extract_api_url(&provider.settings_config, app_type)
+            .is_some_and(|url| url.to_lowercase().contains(&needle))
+}
+
 pub fn execute(cmd: ProviderCommand, app: Option<AppType>) -> Result<(), AppError> {
     let app_type = app.unwrap_or(AppType::Claude);
 
     match cmd {
-        ProviderCommand::List => list_providers(app_type),
+        ProviderCommand::List { sort, filter } => list_providers(app_type, sort, filter.as_deref()),
         ProviderCommand::Current => show_current(app_type),
-        ProviderCommand::Switch { id } => switch_provider(app_type, &id),
-        ProviderCommand::Add => add_provider(app_type),
+        ProviderCommand::Show { id, reveal_key } => show_provider(app_type, &id, reveal_key),
+        ProviderCommand::Switch {
+            id,
+            temporary,
+            dry_run,
+            no_hook,
+        } => {
+            if dry_run {
+                preview_switch(app_type, &id)
+            } else {
+                switch_provider(app_type, &id, temporary, !no_hook)
+            }
+        }
+        ProviderCommand::RestoreSession => restore_session(app_type),
+        ProviderCommand::Add {
+            name: Some(name),
+            endpoint: Some(endpoint),
+            api_key: Some(api_key),
+            model,
+        } => add_provider_from_flags(app_type, &name, &endpoint, &api_key, model.as_deref()),
+        ProviderCommand::Add { .. } => add_provider(app_type),
         ProviderCommand::Edit { id } => edit_provider(app_type, &id),
-        ProviderCommand::Delete { id } => delete_provider(app_type, &id),
+        ProviderCommand::Delete {
+            id,
+            all,
+            yes,
+            force,
+        } => match id {
+            Some(id) if !all => delete_provider(app_type, &id, yes),
+            _ => delete_all_providers(app_type, yes, force),
+        },
         ProviderCommand::Duplicate { id } => duplicate_provider(app_type, &id),
         ProviderCommand::Speedtest { id } => speedtest_provider(app_type, &id),
+        ProviderCommand::Endpoints { id, action } => manage_endpoints(
+            app_type,
+            &id,
+            action.unwrap_or(ProviderEndpointsCommand::List),
+        ),
+        ProviderCommand::SetModel {
+            id,
+            model,
+            haiku,
+            sonnet,
+            opus,
+        } => set_provider_model(
+            app_type,
+            &id,
+            ProviderModels {
+                model,
+                haiku_model: haiku,
+                sonnet_model: sonnet,
+                opus_model: opus,
+            },
+        ),
+        ProviderCommand::SetKey { id, keyring, name } => {
+            set_provider_key(app_type, &id, keyring.then_some(name))
+        }
+        ProviderCommand::Reorder { ids } => reorder_providers(app_type, &ids),
+        ProviderCommand::Validate => validate_providers(app_type),
+        ProviderCommand::History {
+            since,
+            until,
+            limit,
+        } => show_switch_history(app_type, since.as_deref(), until.as_deref(), limit),
     }
 }
 
+fn show_switch_history(
+    app_type: AppType,
+    since: Option<&str>,
+    until: Option<&str>,
+    limit: Option<usize>,
+) -> Result<(), AppError> {
+    let since = since.map(parse_time_bound).transpose()?;
+    let until = until.map(parse_time_bound).transpose()?;
+    let state = get_state()?;
+    let records = ProviderService::switch_history(&state, app_type.clone(), since, until, limit)?;
+    let providers = ProviderService::list(&state, app_type.clone())?;
+    let name_of = |id: &str| providers.get(id).map(|p| p.name.clone());
+    let local_time = |ms: i64| {
+        chrono::DateTime::from_timestamp_millis(ms)
+            .map(|t| {
+                t.with_timezone(&chrono::Local)
+                    .format("%Y-%m-%d %H:%M:%S")
+                    .to_string()
+            })
+            .unwrap_or_else(|| "N/A".to_string())
+    };
+
+    if json_output() {
+        let rows: Vec<_> = records
+            .iter()
+            .map(|r| {
+                serde_json::json!({
+                    "providerId": r.provider_id,
+                    "name": name_of(&r.provider_id),
+                    "switchedAt": r.switched_at,
+                })
+            })
+            .collect();
+        return emit(&rows);
+    }
+
+    if records.is_empty() {
+        out::info(&format!(
+            "No provider switches recorded for {}.",
+            app_type.as_str()
+        ));
+        return Ok(());
+    }
+    let mut table = create_table();
+    table.set_header(vec!["Time", "ID", "Name"]);
+    for r in &records {
+        table.add_row(vec![
+            local_time(r.switched_at),
+            r.provider_id.clone(),
+            name_of(&r.provider_id).unwrap_or_else(|| "(deleted)".to_string()),
+        ]);
+    }
+    println!("{table}");
+    Ok(())
+}
+
+/// 任一供应商有问题时返回错误（非零退出）
+fn validate_providers(app_type: AppType) -> Result<(), AppError> {
+    let state = get_state()?;
+    let results = ProviderService::validate_providers(&state, app_type.clone())?;
+    let invalid = results.iter().filter(|r| !r.is_valid()).count();
+
+    if json_output() {
+        emit(&results)?;
+    } else if results.is_empty() {
+        out::info(&format!(
+            "No providers configured for {}.",
+            app_type.as_str()
+        ));
+    } else {
+        let mut table = create_table();
+        table.set_header(vec!["ID", "Name", "Status", "Issues"]);
+        for r in &results {
+            let status = if r.is_valid() {
+                "✓ ok"
+            } else {
+                "✗ invalid"
+            };
+            table.add_row(vec![
+                r.id.clone(),
+                r.name.clone(),
+                status.to_string(),
+                r.issues.join("\n"),
+            ]);
+        }
+        println!("{table}");
+    }
+
+    if invalid > 0 {
+        return Err(AppError::localized(
+            "provider.validation_failed",
+            format!("{invalid}/{} 个供应商配置有误", results.len()),
+            format!("{invalid} of {} provider(s) are invalid", results.len()),
+        ));
+    }
+    if !json_output() && !results.is_empty() {
+        out::success(&format!("✓ All {} provider(s) are valid", results.len()));
+    }
+    Ok(())
+}
+
+fn reorder_providers(app_type: AppType, ids: &[String]) -> Result<(), AppError> {
+    let state = get_state()?;
+    let order = ProviderService::reorder(&state, app_type.clone(), ids)?;
+    if json_output() {
+        return emit(&order);
+    }
+
+    let providers = ProviderService::list(&state, app_type.clone())?;
+    out::success(&format!("✓ Saved provider order for {}", app_type.as_str()));
+    for (position, id) in order.iter().enumerate() {
+        let name = providers
+            .get(id)
+            .map(|p| p.name.as_str())
+            .unwrap_or(id.as_str());
+        println!("  {:>2}. {} ({})", position + 1, name, id);
+    }
+    Ok(())
+}
+
 fn get_state() -> Result<AppState, AppError> {
     AppState::try_new()
 }
 
-fn list_providers(app_type: AppType) -> Result<(), AppError> {
+fn list_providers(
+    app_type: AppType,
+    sort: Option<ProviderSort>,
+    filter: Option<&str>,
+) -> Result<(), AppError> {
     let state = get_state()?;
     let app_str = app_type.as_str().to_string();
     let providers = ProviderService::list(&state, app_type.clone())?;
     let current_id = ProviderService::current(&state, app_type.clone())?;
 
     if providers.is_empty() {
-        println!("{}", info("No providers found."));
+        out::info("No providers found.");
         println!("{}", texts::no_providers_hint());
         return Ok(());
     }
 
+    let mut provider_list: Vec<_> = providers
+        .into_iter()
+        .filter(|(id, provider)| {
+            filter.is_none_or(|f| provider_matches_filter(id, provider, &app_type, f))
+        })
+        .collect();
+    if provider_list.is_empty() {
+        out::info(&format!(
+            "No providers match '{}'.",
+            filter.unwrap_or_default()
+        ));
+        return Ok(());
+    }
+    sort_providers(&mut provider_list, sort);
+
     // 创建表格
     let mut table = create_table();
     table.set_header(vec!["", "ID", "Name", "API URL"]);
 
-    // 按创建时间排序
-    let mut provider_list: Vec<_> = providers.into_iter().collect();
-    provider_list.sort_by(|(_, a), (_, b)| {
-        // 先按 sort_index，再按创建时间
-        match (a.sort_index, b.sort_index) {
-            (Some(idx_a), Some(idx_b)) => idx_a.cmp(&idx_b),
-            (Some(_), None) => std::cmp::Ordering::Less,
-            (None, Some(_)) => std::cmp::Ordering::Greater,
-            (None, None) => a.created_at.cmp(&b.created_at),
-        }
-    });
-
     for (id, provider) in provider_list {
         let current_marker = if id == current_id { "✓" } else { " " };
         let api_url = extract_api_url(&provider.settings_config, &app_type)
@@ -129,11 +527,11 @@ fn show_current(app_type: AppType) -> Result<(), AppError> {
         .get(&current_id)
         .ok_or_else(|| AppError::Message(format!("Current provider '{}' not found", current_id)))?;
 
-    println!("{}", highlight("Current Provider"));
+    out::highlight("Current Provider");
     println!("{}", "═".repeat(60));
 
     // 基本信息
-    println!("\n{}", highlight(texts::basic_info_section_header()));
+    out::highlight(&format!("\n{}", texts::basic_info_section_header()));
     println!("  ID:       {}", current_id);
     println!(
         "  {}:     {}",
@@ -151,7 +549,7 @@ fn show_current(app_type: AppType) -> Result<(), AppError> {
         let config = extract_claude_config(&provider.settings_config);
 
         // API 配置
-        println!("\n{}", highlight(texts::api_config_section_header()));
+        out::highlight(&format!("\n{}", texts::api_config_section_header()));
         println!(
             "  Base URL: {}",
             config.base_url.unwrap_or_else(|| "N/A".to_string())
@@ -162,7 +560,7 @@ fn show_current(app_type: AppType) -> Result<(), AppError> {
         );
 
         // 模型配置
-        println!("\n{}", highlight(texts::model_config_section_header()));
+        out::highlight(&format!("\n{}", texts::model_config_section_header()));
         println!(
             "  {}:   {}",
             texts::main_model_label_with_colon(),
@@ -182,7 +580,7 @@ fn show_current(app_type: AppType) -> Result<(), AppError> {
         );
     } else {
         // Codex/Gemini 应用只显示 API URL
-        println!("\n{}", highlight("API 配置 / API Configuration"));
+        out::highlight("\nAPI 配置 / API Configuration");
         let api_url = extract_api_url(&provider.settings_config, &app_type)
             .unwrap_or_else(|| "N/A".to_string());
         println!("  API URL:  {}", api_url);
@@ -193,8 +591,218 @@ fn show_current(app_type: AppType) -> Result<(), AppError> {
     Ok(())
 }
 
-fn switch_provider(app_type: AppType, id: &str) -> Result<(), AppError> {
+fn show_provider(app_type: AppType, id: &str, reveal_key: bool) -> Result<(), AppError> {
     let state = get_state()?;
+    let id: &str = &ProviderService::resolve_provider(&state, app_type.clone(), id)?;
+    let current_id = ProviderService::current(&state, app_type.clone())?;
+    let providers = ProviderService::list(&state, app_type.clone())?;
+    let provider = providers.get(id).ok_or_else(|| {
+        AppError::Message(format!(
+            "Provider '{}' not found for {}",
+            id,
+            app_type.as_str()
+        ))
+    })?;
+
+    if json_output() {
+        let mut value =
+            serde_json::to_value(provider).map_err(|e| AppError::JsonSerialize { source: e })?;
+        if !reveal_key && !reveal_secrets() {
+            redact_secrets_in_json(&mut value);
+        }
+        emit(&value)?;
+        return Ok(());
+    }
+
+    let or_na = |value: Option<String>| value.unwrap_or_else(|| "N/A".to_string());
+
+    out::highlight(&format!("Provider: {}", provider.name));
+    println!("{}", "═".repeat(60));
+    println!("  ID:            {}", provider.id);
+    println!("  App:           {}", app_type.as_str());
+    println!("  Current:       {}", format_bool(id == current_id));
+    if let Some(category) = &provider.category {
+        println!("  Category:      {}", category);
+    }
+    if let Some(website) = &provider.website_url {
+        println!("  Website:       {}", website);
+    }
+    if let Some(notes) = &provider.notes {
+        println!("  Notes:         {}", notes);
+    }
+    println!(
+        "  Failover:      {}",
+        format_bool(provider.in_failover_queue)
+    );
+    let format_time = |ts: Option<i64>| {
+        ts.and_then(|ts| chrono::DateTime::from_timestamp(ts, 0))
+            .map(|t| {
+                t.with_timezone(&chrono::Local)
+                    .format("%Y-%m-%d %H:%M:%S")
+                    .to_string()
+            })
+            .unwrap_or_else(|| "N/A".to_string())
+    };
+    println!("  Added:         {}", format_time(provider.added_at));
+    println!("  Updated:       {}", format_time(provider.updated_at));
+
+    out::highlight(&format!("\n{}", texts::api_config_section_header()));
+    println!(
+        "  Endpoint:      {}",
+        or_na(extract_api_url(&provider.settings_config, &app_type))
+    );
+    let custom_endpoints = provider
+        .meta
+        .as_ref()
+        .map(|meta| {
+            let mut urls: Vec<_> = meta.custom_endpoints.keys().cloned().collect();
+            urls.sort();
+            urls
+        })
+        .unwrap_or_default();
+    for url in custom_endpoints {
+        println!("  Alt endpoint:  {}", url);
+    }
+    let api_key = extract_api_key(&provider.settings_config, &app_type).map(|key| {
+        if reveal_key {
+            key
+        } else {
+            display_secret(&key)
+        }
+    });
+    println!("  API Key:       {}", or_na(api_key));
+
+    let models = ProviderModels::from_settings(&app_type, &provider.settings_config);
+    out::highlight(&format!("\n{}", texts::model_config_section_header()));
+    println!(
+        "  Model:         {}",
+        models.model.unwrap_or_else(|| "default".to_string())
+    );
+    if matches!(app_type, AppType::Claude) {
+        for (label, value) in [
+            ("Haiku", models.haiku_model),
+            ("Sonnet", models.sonnet_model),
+            ("Opus", models.opus_model),
+        ] {
+            println!(
+                "  {:<14} {}",
+                format!("{label}:"),
+                value.unwrap_or_else(|| "default".to_string())
+            );
+        }
+    }
+
+    if let Some(meta) = &provider.meta {
+        out::highlight("\nMeta");
+        let usage = meta
+            .usage_script
+            .as_ref()
+            .map(|script| {
+                let template = script.template_type.as_deref().unwrap_or("custom");
+                format!(
+                    "{} ({}, {})",
+                    format_bool(script.enabled),
+                    template,
+                    script.language
+                )
+            })
+            .unwrap_or_else(|| "none".to_string());
+        println!("  Usage script:  {}", usage);
+        println!(
+            "  Daily limit:   {}",
+            or_na(meta.limit_daily_usd.as_ref().map(|v| format!("${v}")))
+        );
+        println!(
+            "  Monthly limit: {}",
+            or_na(meta.limit_monthly_usd.as_ref().map(|v| format!("${v}")))
+        );
+        if let Some(multiplier) = &meta.cost_multiplier {
+            println!("  Cost x:        {}", multiplier);
+        }
+        if let Some(auto_select) = meta.endpoint_auto_select {
+            println!("  Auto endpoint: {}", format_bool(auto_select));
+        }
+    }
+
+    println!("\n{}", "─".repeat(60));
+    Ok(())
+}
+
+fn preview_switch(app_type: AppType, id: &str) -> Result<(), AppError> {
+    let state = get_state()?;
+    let id: &str = &ProviderService::resolve_provider(&state, app_type.clone(), id)?;
+    let previews = ProviderService::preview_switch(&state, app_type.clone(), id)?;
+
+    if json_output() {
+        let files: Vec<serde_json::Value> = previews
+            .iter()
+            .map(|p| {
+                serde_json::json!({
+                    "path": p.path.display().to_string(),
+                    "content": redact_live_content(&p.content),
+                })
+            })
+            .collect();
+        emit(&files)?;
+        return Ok(());
+    }
+
+    out::highlight(&format!(
+        "Dry run: switching {} to '{}' would write:",
+        app_type.as_str(),
+        id
+    ));
+    for preview in &previews {
+        out::info(&format!("\n── {}", preview.path.display()));
+        println!("{}", redact_live_content(&preview.content));
+    }
+    if !crate::sync_policy::should_sync_live(&app_type) {
+        out::warning(&format!(
+            "\n{}",
+            &texts::live_sync_skipped_uninitialized_warning(app_type.as_str())
+        ));
+    }
+    out::info("\nNo files were changed.");
+    Ok(())
+}
+
+/// 预览内容中的密钥默认脱敏：JSON 按字段处理，TOML / .env 按 `key = value` 行处理
+fn redact_live_content(content: &str) -> String {
+    if reveal_secrets() {
+        return content.to_string();
+    }
+    if let Ok(mut value) = serde_json::from_str::<serde_json::Value>(content) {
+        redact_secrets_in_json(&mut value);
+        return serde_json::to_string_pretty(&value).unwrap_or_else(|_| content.to_string());
+    }
+
+    content
+        .lines()
+        .map(|line| match line.split_once('=') {
+            Some((key, value)) if is_sensitive_key(key.trim()) => {
+                let raw = value.trim();
+                let secret = redact_secret(raw.trim_matches('"'));
+                let sep = if key.ends_with(' ') { "= " } else { "=" };
+                if raw.starts_with('"') {
+                    format!("{key}{sep}\"{secret}\"")
+                } else {
+                    format!("{key}{sep}{secret}")
+                }
+            }
+            _ => line.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn switch_provider(
+    app_type: AppType,
+    id: &str,
+    temporary: bool,
+    run_hook: bool,
+) -> Result<(), AppError> {
+    let state = get_state()?;
+    let id: &str = &ProviderService::resolve_provider(&state, app_type.clone(), id)?;
     let app_str = app_type.as_str().to_string();
     let skip_live_sync = !crate::sync_policy::should_sync_live(&app_type);
 
@@ -204,27 +812,249 @@ fn switch_provider(app_type: AppType, id: &str) -> Result<(), AppError> {
         return Err(AppError::Message(format!("Provider '{}' not found", id)));
     }
 
+    // 开启 endpointAutoSelect 时，先测速并把最快的可达端点写入配置
+    match run_async(ProviderService::auto_select_endpoint(
+        &state,
+        app_type.clone(),
+        id,
+    )) {
+        Ok(Some(selection)) => print_endpoint_selection(&selection),
+        Ok(None) => {}
+        Err(e) => out::warning(&format!("Endpoint auto-select skipped: {e}")),
+    }
+
     // 执行切换
-    ProviderService::switch(&state, app_type, id)?;
+    let (temporary_switch, hook_output) = if temporary {
+        let marker = ProviderService::switch_temporary(&state, app_type.clone(), id)?;
+        let hook_output = run_hook
+            .then(|| ProviderService::run_switch_hook(&app_type, id))
+            .flatten();
+        (Some(marker), hook_output)
+    } else {
+        let hook_output =
+            ProviderService::switch_with_hook(&state, app_type.clone(), id, run_hook)?;
+        // 永久切换后，之前的临时切换记录不再适用
+        ProviderService::clear_temporary_switch(&app_type)?;
+        (None, hook_output)
+    };
 
-    println!("{}", success(&format!("✓ Switched to provider '{}'", id)));
-    println!("{}", info(&format!("  Application: {}", app_str)));
+    out::success(&format!("✓ Switched to provider '{}'", id));
+    out::info(&format!("  Application: {}", app_str));
+    if let Some(marker) = temporary_switch {
+        let previous = if marker.previous_id.is_empty() {
+            "(none)"
+        } else {
+            marker.previous_id.as_str()
+        };
+        out::info(&format!(
+            "  Temporary: run `cc-switch --app {} provider restore-session` to switch back to '{}'",
+            app_str, previous
+        ));
+    }
     if skip_live_sync {
-        println!(
-            "{}",
-            warning(&texts::live_sync_skipped_uninitialized_warning(&app_str))
-        );
+        out::warning(&texts::live_sync_skipped_uninitialized_warning(&app_str));
     }
-    println!(
+    if let Some(hook_output) = hook_output {
+        print_switch_hook_output(&hook_output);
+    }
+    out::info(&format!(
         "\n{}",
-        info("Note: Restart your CLI client to apply the changes.")
-    );
+        "Note: Restart your CLI client to apply the changes."
+    ));
+
+    Ok(())
+}
+
+/// `--app all` 下单个应用的切换结果
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct AppSwitchResult {
+    app: AppType,
+    /// switched / current / dry_run / no_match / ambiguous / failed
+    status: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    provider_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    detail: Option<String>,
+}
+
+/// `--app all`：目前只有 `provider switch <名称>` 支持
+pub fn execute_all_apps(cmd: ProviderCommand) -> Result<(), AppError> {
+    match cmd {
+        ProviderCommand::Switch {
+            id: name,
+            temporary,
+            dry_run,
+            no_hook,
+        } => switch_all_apps(&name, temporary, dry_run, !no_hook),
+        _ => Err(crate::cli::app_all_unsupported()),
+    }
+}
+
+/// 按名称（忽略大小写）在每个应用中查找并切换；各应用的 id 往往不同
+fn switch_all_apps(
+    name: &str,
+    temporary: bool,
+    dry_run: bool,
+    run_hook: bool,
+) -> Result<(), AppError> {
+    let state = get_state()?;
+    let needle = name.trim();
+    let mut results = Vec::new();
+
+    for app_type in [AppType::Claude, AppType::Codex, AppType::Gemini] {
+        let providers = ProviderService::list(&state, app_type.clone())?;
+        let mut matches: Vec<&str> = providers
+            .iter()
+            .filter(|(_, p)| p.name.trim().eq_ignore_ascii_case(needle))
+            .map(|(id, _)| id.as_str())
+            .collect();
+        matches.sort_unstable();
+
+        let result = |status, provider_id: Option<&str>, detail: Option<String>| AppSwitchResult {
+            app: app_type.clone(),
+            status,
+            provider_id: provider_id.map(str::to_string),
+            detail,
+        };
+        let id = match matches.as_slice() {
+            [] => {
+                results.push(result("no_match", None, None));
+                continue;
+            }
+            [id] => *id,
+            ids => {
+                results.push(result("ambiguous", None, Some(ids.join(", "))));
+                continue;
+            }
+        };
+        if dry_run {
+            results.push(result("dry_run", Some(id), None));
+            continue;
+        }
+        if ProviderService::current(&state, app_type.clone())? == id {
+            results.push(result("current", Some(id), None));
+            continue;
+        }
+
+        let outcome = if temporary {
+            ProviderService::switch_temporary(&state, app_type.clone(), id).map(|_| {
+                run_hook
+                    .then(|| ProviderService::run_switch_hook(&app_type, id))
+                    .flatten()
+            })
+        } else {
+            ProviderService::switch_with_hook(&state, app_type.clone(), id, run_hook)
+                .and_then(|hook| ProviderService::clear_temporary_switch(&app_type).map(|_| hook))
+        };
+        match outcome {
+            Ok(hook_output) => {
+                if let Some(hook_output) = hook_output.filter(|_| !json_output()) {
+                    print_switch_hook_output(&hook_output);
+                }
+                results.push(result("switched", Some(id), None));
+            }
+            Err(e) => results.push(result("failed", Some(id), Some(e.to_string()))),
+        }
+    }
+
+    if dry_run && !json_output() {
+        for r in &results {
+            if let Some(id) = r.provider_id.as_deref() {
+                out::highlight(&format!("[{}]", r.app.as_str()));
+                preview_switch(r.app.clone(), id)?;
+            }
+        }
+    }
+
+    if json_output() {
+        emit(&results)?;
+    } else {
+        let mut table = create_table();
+        table.set_header(vec!["App", "Result", "Provider", "Detail"]);
+        for r in &results {
+            let status = match r.status {
+                "switched" => "✓ switched",
+                "current" => "already current",
+                "no_match" => "no match",
+                "ambiguous" => "ambiguous name",
+                "dry_run" => "would switch",
+                _ => "✗ failed",
+            };
+            table.add_row(vec![
+                r.app.as_str().to_string(),
+                status.to_string(),
+                r.provider_id.clone().unwrap_or_default(),
+                r.detail.clone().unwrap_or_default(),
+            ]);
+        }
+        println!("{table}");
+    }
+
+    let failed = results.iter().filter(|r| r.status == "failed").count();
+    if failed > 0 {
+        return Err(AppError::localized(
+            "provider.switch_all_failed",
+            format!("{failed} 个应用切换失败"),
+            format!("Switching failed for {failed} app(s)"),
+        ));
+    }
+    if results
+        .iter()
+        .all(|r| matches!(r.status, "no_match" | "ambiguous"))
+    {
+        return Err(AppError::localized(
+            "provider.name_not_found",
+            format!("没有应用可以唯一匹配名为 '{needle}' 的供应商"),
+            format!("No app has exactly one provider named '{needle}'"),
+        ));
+    }
+    if !dry_run && results.iter().any(|r| r.status == "switched") {
+        out::info("\nNote: Restart your CLI clients to apply the changes.");
+    }
+    Ok(())
+}
+
+/// 展示 post-switch 钩子的输出；失败仅警告（切换已完成，不回滚）
+pub(crate) fn print_switch_hook_output(hook: &SwitchHookOutput) {
+    out::info(&format!("  Hook: {}", hook.command));
+    if !out::quiet() {
+        for line in hook.output.lines() {
+            println!("    {line}");
+        }
+    }
+    if !hook.success {
+        let code = hook
+            .exit_code
+            .map(|c| c.to_string())
+            .unwrap_or_else(|| "none".to_string());
+        out::warning(&format!(
+            "  Post-switch hook failed (exit code {code}); the switch was kept."
+        ));
+    }
+}
+
+fn restore_session(app_type: AppType) -> Result<(), AppError> {
+    let state = get_state()?;
+    let app_str = app_type.as_str().to_string();
+
+    match ProviderService::restore_session(&state, app_type)? {
+        None => out::info(&format!("No temporary switch to restore for {}.", app_str)),
+        Some(previous) if previous.is_empty() => {
+            out::info("Temporary switch cleared (there was no previous provider to restore).")
+        }
+        Some(previous) => {
+            out::success(&format!("✓ Restored provider '{}'", previous));
+            out::info(&format!("  Application: {}", app_str));
+        }
+    }
 
     Ok(())
 }
 
-fn delete_provider(app_type: AppType, id: &str) -> Result<(), AppError> {
+fn delete_provider(app_type: AppType, id: &str, yes: bool) -> Result<(), AppError> {
     let state = get_state()?;
+    let id: &str = &ProviderService::resolve_provider(&state, app_type.clone(), id)?;
 
     // 检查是否是当前 provider
     let current_id = ProviderService::current(&state, app_type.clone())?;
@@ -236,32 +1066,85 @@ fn delete_provider(app_type: AppType, id: &str) -> Result<(), AppError> {
     }
 
     // 确认删除
-    let confirm = inquire::Confirm::new(&format!(
-        "Are you sure you want to delete provider '{}'?",
-        id
-    ))
-    .with_default(false)
-    .prompt()
-    .map_err(|e| AppError::Message(format!("Prompt failed: {}", e)))?;
+    if !yes {
+        ensure_interactive("the delete confirmation", Some("--yes"))?;
+        let confirm = inquire::Confirm::new(&format!(
+            "Are you sure you want to delete provider '{}'?",
+            id
+        ))
+        .with_default(false)
+        .prompt()
+        .map_err(|e| AppError::Message(format!("Prompt failed: {}", e)))?;
 
-    if !confirm {
-        println!("{}", info("Cancelled."));
-        return Ok(());
+        if !confirm {
+            out::info("Cancelled.");
+            return Ok(());
+        }
     }
 
     // 执行删除
     ProviderService::delete(&state, app_type, id)?;
 
-    println!("{}", success(&format!("✓ Deleted provider '{}'", id)));
+    out::success(&format!("✓ Deleted provider '{}'", id));
+
+    Ok(())
+}
+
+fn delete_all_providers(app_type: AppType, yes: bool, force: bool) -> Result<(), AppError> {
+    let state = get_state()?;
+    let app_str = app_type.as_str().to_string();
+
+    let providers = ProviderService::list(&state, app_type.clone())?;
+    let count = providers.len();
+    if count == 0 {
+        out::info(&format!("No providers found for {}.", app_str));
+        return Ok(());
+    }
+
+    // 提前拒绝，避免用户确认后才报错
+    let current_id = ProviderService::current(&state, app_type.clone())?;
+    if !force && providers.contains_key(&current_id) {
+        return Err(AppError::Message(format!(
+            "Provider '{}' is currently in use; pass --force to delete it as well",
+            current_id
+        )));
+    }
+
+    if !yes {
+        ensure_interactive("the delete confirmation", Some("--yes"))?;
+        let confirm = Confirm::new(&format!(
+            "Delete all {} provider(s) for {}? This cannot be undone.",
+            count, app_str
+        ))
+        .with_default(false)
+        .prompt()
+        .map_err(|e| AppError::Message(format!("Prompt failed: {}", e)))?;
+
+        if !confirm {
+            out::info("Cancelled.");
+            return Ok(());
+        }
+    }
+
+    let removed = ProviderService::delete_all(&state, app_type, force)?;
+
+    out::success(&format!(
+        "✓ Deleted {} provider(s) for {}",
+        removed, app_str
+    ));
 
     Ok(())
 }
 
 fn add_provider(app_type: AppType) -> Result<(), AppError> {
+    ensure_interactive(
+        "the provider wizard",
+        Some("--name, --endpoint and --api-key"),
+    )?;
     // Disable bracketed paste mode to work around inquire dropping paste events
     crate::cli::terminal::disable_bracketed_paste_mode_best_effort();
 
-    println!("{}", highlight("Add New Provider"));
+    out::highlight("Add New Provider");
     println!("{}", "=".repeat(50));
 
     let add_mode = if supports_official_provider(&app_type) {
@@ -274,7 +1157,7 @@ fn add_provider(app_type: AppType) -> Result<(), AppError> {
             Ok(_selected) => ProviderAddMode::ThirdParty,
             Err(inquire::error::InquireError::OperationCanceled)
             | Err(inquire::error::InquireError::OperationInterrupted) => {
-                println!("{}", info(texts::cancelled()));
+                out::info(texts::cancelled());
                 return Ok(());
             }
             Err(e) => {
@@ -313,7 +1196,7 @@ fn add_provider(app_type: AppType) -> Result<(), AppError> {
         _ => prompt_basic_fields(None)?,
     };
     let id = generate_provider_id(&name, &existing_ids);
-    println!("{}", info(&texts::generated_id_message(&id)));
+    out::info(&texts::generated_id_message(&id));
 
     // 3. 收集配置
     let settings_config = prompt_settings_config_for_add(&app_type, add_mode)?;
@@ -337,12 +1220,15 @@ fn add_provider(app_type: AppType) -> Result<(), AppError> {
         website_url,
         category: None,
         created_at: Some(current_timestamp()),
+        added_at: None,
+        updated_at: None,
         sort_index: optional.sort_index,
         notes: optional.notes,
         icon: None,
         icon_color: None,
         meta: None,
         in_failover_queue: false,
+        extra: Default::default(),
     };
 
     // 6. 显示摘要并确认
@@ -352,7 +1238,7 @@ fn add_provider(app_type: AppType) -> Result<(), AppError> {
         .prompt()
         .map_err(|e| AppError::Message(texts::input_failed_error(&e.to_string())))?
     {
-        println!("{}", info(texts::cancelled()));
+        out::info(texts::cancelled());
         return Ok(());
     }
 
@@ -360,35 +1246,88 @@ fn add_provider(app_type: AppType) -> Result<(), AppError> {
     ProviderService::add(&state, app_type.clone(), provider)?;
 
     // 8. 成功消息
-    println!(
+    out::success(&format!(
         "\n{}",
-        success(&texts::entity_added_success(texts::entity_provider(), &id))
-    );
+        &texts::entity_added_success(texts::entity_provider(), &id)
+    ));
 
     Ok(())
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+/// 非交互添加：按应用生成 settings_config，校验端点并生成唯一 id
+fn add_provider_from_flags(
+    app_type: AppType,
+    name: &str,
+    endpoint: &str,
+    api_key: &str,
+    model: Option<&str>,
+) -> Result<(), AppError> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(AppError::InvalidInput(
+            texts::provider_name_empty_error().to_string(),
+        ));
+    }
+    let endpoint = endpoint.trim();
+    crate::deeplink::validate_url(endpoint, "endpoint")?;
+    let api_key = api_key.trim();
+    if api_key.is_empty() {
+        return Err(AppError::InvalidInput(
+            "API key cannot be empty".to_string(),
+        ));
+    }
 
-    #[test]
-    fn supports_official_provider_is_codex_only() {
-        assert!(supports_official_provider(&AppType::Codex));
-        assert!(!supports_official_provider(&AppType::Claude));
-        assert!(!supports_official_provider(&AppType::Gemini));
+    let state = AppState::try_new()?;
+    let existing_ids: Vec<String> = ProviderService::list(&state, app_type.clone())?
+        .into_keys()
+        .collect();
+    let id = generate_provider_id(name, &existing_ids);
+
+    let provider = Provider {
+        id: id.clone(),
+        name: name.to_string(),
+        settings_config: crate::deeplink::build_settings_config(
+            &app_type,
+            endpoint,
+            api_key,
+            model.filter(|m| !m.trim().is_empty()),
+        ),
+        website_url: crate::deeplink::infer_homepage_from_endpoint(endpoint),
+        category: None,
+        created_at: Some(current_timestamp()),
+        added_at: None,
+        updated_at: None,
+        sort_index: None,
+        notes: None,
+        icon: None,
+        icon_color: None,
+        meta: None,
+        in_failover_queue: false,
+        extra: Default::default(),
+    };
+    ProviderService::add(&state, app_type.clone(), provider)?;
+
+    if json_output() {
+        let value = serde_json::json!({ "app": app_type.as_str(), "id": id });
+        emit(&value)?;
+    } else {
+        out::success(&texts::entity_added_success(texts::entity_provider(), &id));
     }
+    Ok(())
 }
 
 fn edit_provider(app_type: AppType, id: &str) -> Result<(), AppError> {
+    ensure_interactive("the provider editor", None)?;
     // Disable bracketed paste mode to work around inquire dropping paste events
     crate::cli::terminal::disable_bracketed_paste_mode_best_effort();
 
-    println!("{}", highlight(&format!("Edit Provider: {}", id)));
-    println!("{}", "=".repeat(50));
-
     // 1. 加载并验证供应商存在
     let state = AppState::try_new()?;
+    let id: &str = &ProviderService::resolve_provider(&state, app_type.clone(), id)?;
+
+    out::highlight(&format!("Edit Provider: {}", id));
+    println!("{}", "=".repeat(50));
+
     let config = state.config.read().unwrap();
     let manager = config
         .get_manager(&app_type)
@@ -405,12 +1344,12 @@ fn edit_provider(app_type: AppType, id: &str) -> Result<(), AppError> {
     drop(config);
 
     // 2. 显示当前配置
-    println!("\n{}", highlight(texts::current_config_header()));
+    out::highlight(&format!("\n{}", texts::current_config_header()));
     display_provider_summary(&original, &app_type);
     println!();
 
     // 3. 全量编辑各字段（使用当前值作为默认）
-    println!("{}", info(texts::edit_fields_instruction()));
+    out::info(texts::edit_fields_instruction());
 
     // 调用 prompt_basic_fields 来处理基本字段输入（自动使用 initial_value）
     let (name, website_url) = prompt_basic_fields(Some(&original))?;
@@ -445,23 +1384,26 @@ fn edit_provider(app_type: AppType, id: &str) -> Result<(), AppError> {
         website_url,
         category: None,
         created_at: original.created_at,
+        added_at: original.added_at,
+        updated_at: original.updated_at,
         sort_index: optional.sort_index,
         notes: optional.notes,
         icon: None,
         icon_color: None,
         meta: original.meta,                           // 保留元数据
         in_failover_queue: original.in_failover_queue, // 保留故障转移状态
+        extra: original.extra,                         // 保留未识别字段
     };
 
     // 7. 显示修改摘要并确认
-    println!("\n{}", highlight(texts::updated_config_header()));
+    out::highlight(&format!("\n{}", texts::updated_config_header()));
     display_provider_summary(&updated, &app_type);
     if !Confirm::new(&texts::confirm_update_entity(texts::entity_provider()))
         .with_default(false)
         .prompt()
         .map_err(|e| AppError::Message(texts::input_failed_error(&e.to_string())))?
     {
-        println!("{}", info(texts::cancelled()));
+        out::info(texts::cancelled());
         return Ok(());
     }
 
@@ -469,25 +1411,120 @@ fn edit_provider(app_type: AppType, id: &str) -> Result<(), AppError> {
     ProviderService::update(&state, app_type.clone(), updated)?;
 
     // 9. 成功消息
-    println!(
+    out::success(&format!(
         "\n{}",
-        success(&texts::entity_updated_success(texts::entity_provider(), id))
-    );
+        &texts::entity_updated_success(texts::entity_provider(), id)
+    ));
     if is_current {
-        println!("{}", warning(texts::current_provider_synced_warning()));
+        out::warning(texts::current_provider_synced_warning());
     }
 
     Ok(())
 }
 
-fn duplicate_provider(_app_type: AppType, id: &str) -> Result<(), AppError> {
-    println!("{}", info(&format!("Duplicating provider '{}'...", id)));
+fn duplicate_provider(app_type: AppType, id: &str) -> Result<(), AppError> {
+    let state = get_state()?;
+    let id: &str = &ProviderService::resolve_provider(&state, app_type.clone(), id)?;
+    out::info(&format!("Duplicating provider '{}'...", id));
     println!("{}", error("Provider duplication is not yet implemented."));
     Ok(())
 }
 
+/// `keyring` 为 Some 时密钥存入系统钥匙串，内层为自定义条目名
+fn set_provider_key(
+    app_type: AppType,
+    id: &str,
+    keyring: Option<Option<String>>,
+) -> Result<(), AppError> {
+    let state = get_state()?;
+    let id: &str = &ProviderService::resolve_provider(&state, app_type.clone(), id)?;
+
+    let key = read_secret("API key")?;
+    let value = match keyring {
+        Some(name) => {
+            let name = name
+                .map(|n| n.trim().to_string())
+                .filter(|n| !n.is_empty())
+                .unwrap_or_else(|| format!("{}-{}", app_type.as_str(), id));
+            crate::keyring_store::store_secret(&name, &key)?;
+            out::info(&format!("Stored the key in the OS keychain as '{name}'"));
+            crate::keyring_store::reference(&name)
+        }
+        None => key,
+    };
+
+    let field = ProviderService::set_api_key(&state, app_type.clone(), id, &value)?;
+    out::success(&texts::entity_updated_success(texts::entity_provider(), id));
+    out::info(&format!("  {field}: {}", display_secret(&value)));
+    if ProviderService::current(&state, app_type)? == id {
+        out::warning(&format!("\n{}", texts::current_provider_synced_warning()));
+    }
+    Ok(())
+}
+
+fn set_provider_model(app_type: AppType, id: &str, models: ProviderModels) -> Result<(), AppError> {
+    let state = get_state()?;
+    let id: &str = &ProviderService::resolve_provider(&state, app_type.clone(), id)?;
+    let is_current = ProviderService::current(&state, app_type.clone())? == id;
+
+    let updated = ProviderService::set_models(&state, app_type.clone(), id, &models)?;
+    let mapping = ProviderModels::from_settings(&app_type, &updated.settings_config);
+
+    out::success(&texts::entity_updated_success(texts::entity_provider(), id));
+    out::highlight(&format!("\n{}", texts::model_config_section_header()));
+    let display = |value: Option<String>| value.unwrap_or_else(|| "default".to_string());
+    println!(
+        "  {}:   {}",
+        texts::main_model_label_with_colon(),
+        display(mapping.model)
+    );
+    if matches!(app_type, AppType::Claude) {
+        println!("  Haiku:    {}", display(mapping.haiku_model));
+        println!("  Sonnet:   {}", display(mapping.sonnet_model));
+        println!("  Opus:     {}", display(mapping.opus_model));
+    }
+    if is_current {
+        out::warning(&format!("\n{}", texts::current_provider_synced_warning()));
+    }
+
+    Ok(())
+}
+
+fn latency_cells(result: &EndpointLatency) -> (String, String) {
+    let latency = if let Some(latency) = result.latency {
+        format!("{} ms", latency)
+    } else if result.error.is_some() {
+        "Failed".to_string()
+    } else {
+        "Timeout".to_string()
+    };
+    let status = result
+        .status
+        .map(|s| s.to_string())
+        .unwrap_or_else(|| "N/A".to_string());
+    (latency, status)
+}
+
+fn print_endpoint_selection(selection: &EndpointSelection) {
+    match (&selection.chosen, selection.changed) {
+        (Some(url), true) => {
+            let latency = selection
+                .results
+                .iter()
+                .find(|r| r.url.trim_end_matches('/') == url)
+                .and_then(|r| r.latency)
+                .map(|ms| format!(" ({ms} ms)"))
+                .unwrap_or_default();
+            out::success(&format!("✓ Auto-selected endpoint {url}{latency}"));
+        }
+        (Some(url), false) => out::info(&format!("  Fastest endpoint already in use: {url}")),
+        (None, _) => out::warning("No endpoint was reachable; keeping the current one."),
+    }
+}
+
 fn speedtest_provider(app_type: AppType, id: &str) -> Result<(), AppError> {
     let state = get_state()?;
+    let id: &str = &ProviderService::resolve_provider(&state, app_type.clone(), id)?;
 
     // Get provider by ID
     let providers = ProviderService::list(&state, app_type.clone())?;
@@ -495,54 +1532,145 @@ fn speedtest_provider(app_type: AppType, id: &str) -> Result<(), AppError> {
         .get(id)
         .ok_or_else(|| AppError::Message(format!("Provider '{}' not found", id)))?;
 
-    // Extract API URL
-    let api_url = extract_api_url(&provider.settings_config, &app_type)
-        .ok_or_else(|| AppError::Message(format!("No API URL configured for provider '{}'", id)))?;
+    let endpoints = ProviderService::list_endpoints(&state, app_type.clone(), id)?;
+    if endpoints.is_empty() {
+        return Err(AppError::Message(format!(
+            "No API URL configured for provider '{}'",
+            id
+        )));
+    }
+    let auto_select = ProviderService::endpoint_auto_select(&state, app_type.clone(), id)?;
 
-    println!(
-        "{}",
-        info(&format!("Testing provider '{}'...", provider.name))
-    );
-    println!("{}", info(&format!("Endpoint: {}", api_url)));
+    out::info(&format!("Testing provider '{}'...", provider.name));
     println!();
 
-    // Run speedtest asynchronously
-    let runtime = tokio::runtime::Runtime::new()
-        .map_err(|e| AppError::Message(format!("Failed to create async runtime: {}", e)))?;
-
-    let results = runtime
-        .block_on(async { SpeedtestService::test_endpoints(vec![api_url.clone()], None).await })?;
+    let urls = endpoints.iter().map(|e| e.url.clone()).collect();
+    let results = run_async(SpeedtestService::test_endpoints(urls, None))?;
 
-    // Display results
-    if let Some(result) = results.first() {
-        let mut table = create_table();
-        table.set_header(vec!["Endpoint", "Latency", "Status"]);
-
-        let latency_str = if let Some(latency) = result.latency {
-            format!("{} ms", latency)
-        } else if result.error.is_some() {
-            "Failed".to_string()
-        } else {
-            "Timeout".to_string()
-        };
+    let mut table = create_table();
+    table.set_header(vec!["", "Endpoint", "Latency", "Status"]);
+    for result in &results {
+        let primary = endpoints
+            .iter()
+            .any(|e| e.primary && e.url == result.url.trim_end_matches('/'));
+        let (latency, status) = latency_cells(result);
+        table.add_row(vec![
+            if primary { "*" } else { "" }.to_string(),
+            result.url.clone(),
+            latency,
+            status,
+        ]);
+    }
+    println!("{}", table);
 
-        let status_str = result
-            .status
-            .map(|s| s.to_string())
-            .unwrap_or_else(|| "N/A".to_string());
+    for result in results.iter().filter(|r| r.error.is_some()) {
+        println!(
+            "{}",
+            error(&format!(
+                "Error ({}): {}",
+                result.url,
+                result.error.as_deref().unwrap_or_default()
+            ))
+        );
+    }
 
-        table.add_row(vec![result.url.clone(), latency_str, status_str]);
+    if auto_select && endpoints.len() > 1 {
+        if let Some(fastest) = pick_fastest_endpoint(&results) {
+            let url = fastest.url.trim_end_matches('/').to_string();
+            if endpoints.iter().any(|e| e.primary && e.url == url) {
+                out::info(&format!("  Fastest endpoint already in use: {url}"));
+            } else {
+                ProviderService::use_endpoint(&state, app_type, id, &url)?;
+                out::success(&format!(
+                    "✓ Auto-selected endpoint {url} ({} ms)",
+                    fastest.latency.unwrap_or_default()
+                ));
+            }
+        }
+    } else if results.iter().any(|r| r.latency.is_some()) {
+        out::success("\n✓ Speedtest completed successfully");
+    }
 
-        println!("{}", table);
+    Ok(())
+}
 
-        // Show error details if any
-        if let Some(err) = &result.error {
-            println!("\n{}", error(&format!("Error: {}", err)));
-        } else if result.latency.is_some() {
-            println!("\n{}", success("✓ Speedtest completed successfully"));
+fn manage_endpoints(
+    app_type: AppType,
+    id: &str,
+    action: ProviderEndpointsCommand,
+) -> Result<(), AppError> {
+    let state = get_state()?;
+    let id: &str = &ProviderService::resolve_provider(&state, app_type.clone(), id)?;
+    match action {
+        ProviderEndpointsCommand::List => {
+            let endpoints = ProviderService::list_endpoints(&state, app_type.clone(), id)?;
+            let auto_select = ProviderService::endpoint_auto_select(&state, app_type, id)?;
+            if json_output() {
+                let value = serde_json::json!({
+                    "endpointAutoSelect": auto_select,
+                    "endpoints": endpoints,
+                });
+                emit(&value)?;
+                return Ok(());
+            }
+            if endpoints.is_empty() {
+                out::info(&format!("No endpoints configured for provider '{id}'."));
+                return Ok(());
+            }
+            let mut table = create_table();
+            table.set_header(vec!["", "Endpoint", "Last used"]);
+            for endpoint in endpoints {
+                let last_used = endpoint
+                    .last_used
+                    .and_then(chrono::DateTime::from_timestamp_millis)
+                    .map(|t| t.format("%Y-%m-%d %H:%M").to_string())
+                    .unwrap_or_else(|| "-".to_string());
+                table.add_row(vec![
+                    if endpoint.primary { "*" } else { "" }.to_string(),
+                    endpoint.url,
+                    last_used,
+                ]);
+            }
+            println!("{}", table);
+            println!("Auto-select: {}", format_bool(auto_select));
+        }
+        ProviderEndpointsCommand::Add { url } => {
+            crate::deeplink::validate_url(url.trim(), "endpoint")?;
+            ProviderService::add_custom_endpoint(&state, app_type, id, url.clone())?;
+            out::success(&format!("✓ Added endpoint {} to '{id}'", url.trim()));
+        }
+        ProviderEndpointsCommand::Remove { url } => {
+            let normalized = url.trim().trim_end_matches('/');
+            let endpoints = ProviderService::list_endpoints(&state, app_type.clone(), id)?;
+            if endpoints.iter().any(|e| e.primary && e.url == normalized) {
+                return Err(AppError::InvalidInput(format!(
+                    "{normalized} is the endpoint in use; switch with `provider endpoints {id} use <url>` first"
+                )));
+            }
+            if !endpoints.iter().any(|e| e.url == normalized) {
+                return Err(AppError::InvalidInput(format!(
+                    "Endpoint {normalized} not found for provider '{id}'"
+                )));
+            }
+            ProviderService::remove_custom_endpoint(&state, app_type, id, url.clone())?;
+            out::success(&format!("✓ Removed endpoint {normalized} from '{id}'"));
+        }
+        ProviderEndpointsCommand::Use { url } => {
+            crate::deeplink::validate_url(url.trim(), "endpoint")?;
+            ProviderService::use_endpoint(&state, app_type, id, &url)?;
+            out::success(&format!(
+                "✓ Provider '{id}' now uses {}",
+                url.trim().trim_end_matches('/')
+            ));
+        }
+        ProviderEndpointsCommand::AutoSelect { enabled } => {
+            ProviderService::set_endpoint_auto_select(&state, app_type, id, enabled)?;
+            out::success(&format!(
+                "✓ Endpoint auto-select {} for '{id}'",
+                if enabled { "enabled" } else { "disabled" }
+            ));
         }
     }
-
     Ok(())
 }
 
@@ -576,6 +1704,20 @@ fn extract_api_url(settings_config: &serde_json::Value, app_type: &AppType) -> O
     }
 }
 
+/// 提取各应用的 API Key 原文
+fn extract_api_key(settings_config: &serde_json::Value, app_type: &AppType) -> Option<String> {
+    let (section, keys): (&str, &[&str]) = match app_type {
+        AppType::Claude => ("env", &["ANTHROPIC_AUTH_TOKEN", "ANTHROPIC_API_KEY"]),
+        AppType::Codex => ("auth", &["OPENAI_API_KEY"]),
+        AppType::Gemini => ("env", &["GEMINI_API_KEY", "GOOGLE_API_KEY"]),
+    };
+    let section = settings_config.get(section)?;
+    keys.iter()
+        .find_map(|key| section.get(*key).and_then(|v| v.as_str()))
+        .filter(|key| !key.is_empty())
+        .map(|key| key.to_string())
+}
+
 /// Claude 配置信息
 #[derive(Default)]
 struct ClaudeConfig {
@@ -597,7 +1739,7 @@ fn extract_claude_config(settings_config: &serde_json::Value) -> ClaudeConfig {
                 .get("ANTHROPIC_AUTH_TOKEN")
                 .or_else(|| env.get("ANTHROPIC_API_KEY"))
                 .and_then(|v| v.as_str())
-                .map(|s| mask_api_key(s)),
+                .map(display_secret),
             base_url: env
                 .get("ANTHROPIC_BASE_URL")
                 .and_then(|v| v.as_str())
@@ -624,15 +1766,6 @@ fn extract_claude_config(settings_config: &serde_json::Value) -> ClaudeConfig {
     }
 }
 
-/// 将 API Key 脱敏显示（显示前8位 + ...）
-fn mask_api_key(key: &str) -> String {
-    if key.len() > 8 {
-        format!("{}...", &key[..8])
-    } else {
-        key.to_string()
-    }
-}
-
 /// 简化模型名称（去掉日期后缀）
 /// 例如：claude-3-5-sonnet-20241022 -> claude-3-5-sonnet
 fn simplify_model_name(name: &str) -> String {
@@ -645,3 +1778,89 @@ fn simplify_model_name(name: &str) -> String {
     }
     name.to_string()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn supports_official_provider_is_codex_only() {
+        assert!(supports_official_provider(&AppType::Codex));
+        assert!(!supports_official_provider(&AppType::Claude));
+        assert!(!supports_official_provider(&AppType::Gemini));
+    }
+
+    #[test]
+    fn flag_settings_config_matches_each_app_layout() {
+        let claude = crate::deeplink::build_settings_config(
+            &AppType::Claude,
+            "https://api.example.com",
+            "sk-test",
+            Some("claude-sonnet"),
+        );
+        assert_eq!(
+            extract_api_url(&claude, &AppType::Claude).as_deref(),
+            Some("https://api.example.com")
+        );
+        assert_eq!(claude["env"]["ANTHROPIC_MODEL"], json!("claude-sonnet"));
+
+        let codex = crate::deeplink::build_settings_config(
+            &AppType::Codex,
+            "https://api.example.com/v1/",
+            "sk-test",
+            None,
+        );
+        assert_eq!(codex["auth"]["OPENAI_API_KEY"], json!("sk-test"));
+        assert!(codex["config"]
+            .as_str()
+            .unwrap_or_default()
+            .contains("base_url = \"https://api.example.com/v1\""));
+    }
+
+    fn provider(id: &str, name: &str, url: &str, created_at: i64) -> (String, Provider) {
+        let mut provider = Provider::with_id(
+            id.to_string(),
+            name.to_string(),
+            json!({ "env": { "ANTHROPIC_BASE_URL": url } }),
+            None,
+        );
+        provider.created_at = Some(created_at);
+        (id.to_string(), provider)
+    }
+
+    #[test]
+    fn sort_and_filter_providers() {
+        let mut list = vec![
+            provider("b", "zeta", "https://api.example.com", 1),
+            provider("c", "Alpha", "https://relay.internal", 3),
+            provider("a", "mid", "https://api.example.com", 2),
+        ];
+        let ids = |list: &[(String, Provider)]| {
+            list.iter()
+                .map(|(id, _)| id.as_str())
+                .collect::<Vec<_>>()
+                .join(",")
+        };
+
+        sort_providers(&mut list, Some(ProviderSort::Name));
+        assert_eq!(ids(&list), "c,a,b");
+        sort_providers(&mut list, Some(ProviderSort::Id));
+        assert_eq!(ids(&list), "a,b,c");
+        sort_providers(&mut list, Some(ProviderSort::Recent));
+        assert_eq!(ids(&list), "c,a,b");
+
+        let matching: Vec<_> = list
+            .iter()
+            .filter(|(id, p)| provider_matches_filter(id, p, &AppType::Claude, "RELAY"))
+            .map(|(id, _)| id.as_str())
+            .collect();
+        assert_eq!(matching, vec!["c"]);
+        assert!(provider_matches_filter(
+            "b",
+            &list[2].1,
+            &AppType::Claude,
+            "Zet"
+        ));
+    }
+}
@@ -3,6 +3,7 @@
 
 use crate::app_config::AppType;
 use crate::cli::i18n::texts;
+use crate::cli::ui::display_secret;
 use crate::error::AppError;
 use crate::provider::Provider;
 use colored::Colorize;
@@ -676,7 +677,7 @@ pub fn display_provider_summary(provider: &Provider, app_type: &AppType) {
                     println!(
                         "  {}: {}",
                         texts::api_key_display_label(),
-                        mask_api_key(api_key)
+                        display_secret(api_key)
                     );
                 }
                 if let Some(base_url) = env.get("ANTHROPIC_BASE_URL").and_then(|v| v.as_str()) {
@@ -693,7 +694,7 @@ pub fn display_provider_summary(provider: &Provider, app_type: &AppType) {
                     println!(
                         "  {}: {}",
                         texts::api_key_display_label(),
-                        mask_api_key(api_key)
+                        display_secret(api_key)
                     );
                 }
             }
@@ -711,7 +712,7 @@ pub fn display_provider_summary(provider: &Provider, app_type: &AppType) {
                     println!(
                         "  {}: {}",
                         texts::api_key_display_label(),
-                        mask_api_key(api_key)
+                        display_secret(api_key)
                     );
                 }
                 if let Some(base_url) = env
@@ -771,11 +772,3 @@ fn detect_gemini_auth_type(value: Option<&Value>) -> Option<String> {
     }
     None
 }
-
-/// 遮蔽 API Key 显示（用于摘要显示）
-fn mask_api_key(key: &str) -> String {
-    if key.len() <= 8 {
-        return "***".to_string();
-    }
-    format!("{}...{}", &key[..4], &key[key.len() - 4..])
-}
@@ -0,0 +1,62 @@
+use clap::Subcommand;
+
+use crate::cli::ui::{create_table, out};
+use crate::error::AppError;
+use crate::settings::{get_setting_value, get_settings, set_setting_value, SettingKey};
+
+#[derive(Subcommand)]
+pub enum SettingsCommand {
+    /// List all known settings and their current values
+    List,
+    /// Show the value of a setting
+    Get {
+        /// Setting key (e.g. skillSyncMethod)
+        key: String,
+    },
+    /// Validate and update a setting
+    Set {
+        /// Setting key (e.g. skillSyncMethod)
+        key: String,
+        /// New value
+        value: String,
+    },
+}
+
+pub fn execute(cmd: SettingsCommand) -> Result<(), AppError> {
+    match cmd {
+        SettingsCommand::List => list_settings(),
+        SettingsCommand::Get { key } => get_setting(&key),
+        SettingsCommand::Set { key, value } => set_setting(&key, &value),
+    }
+}
+
+fn list_settings() -> Result<(), AppError> {
+    let settings = get_settings();
+
+    let mut table = create_table();
+    table.set_header(vec!["Key", "Value", "Allowed"]);
+    for key in SettingKey::ALL {
+        table.add_row(vec![
+            key.as_str().to_string(),
+            key.get(&settings),
+            key.value_hint().to_string(),
+        ]);
+    }
+
+    out::highlight("Settings (~/.cc-switch/settings.json)");
+    println!("{}", table);
+    Ok(())
+}
+
+fn get_setting(key: &str) -> Result<(), AppError> {
+    let key = SettingKey::parse(key)?;
+    println!("{}", get_setting_value(key));
+    Ok(())
+}
+
+fn set_setting(key: &str, value: &str) -> Result<(), AppError> {
+    let key = SettingKey::parse(key)?;
+    set_setting_value(key, value)?;
+    out::success(&format!("✓ {} = {}", key.as_str(), get_setting_value(key)));
+    Ok(())
+}
@@ -1,31 +1,116 @@
 use clap::Subcommand;
-use std::future::Future;
+use notify::{RecursiveMode, Watcher};
+use std::collections::BTreeSet;
+use std::io::IsTerminal;
+use std::path::{Path, PathBuf};
+use std::sync::{mpsc, Arc};
+use std::time::Duration;
 
 use crate::app_config::AppType;
-use crate::cli::ui::{create_table, highlight, info, success};
+use crate::cli::runtime::run_async;
+use crate::cli::terminal::{ensure_interactive, read_secret};
+use crate::cli::ui::{colors, create_table, emit, format_bytes, json_output, out};
 use crate::error::AppError;
-use crate::services::skill::{SkillRepo, SyncMethod};
+use crate::services::skill::{
+    InstallOptions, InstallOutcome, SkillProgress, SkillReadmeSource, SkillRepo, SyncMethod,
+};
 use crate::services::SkillService;
 
+/// `skills install --progress`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum ProgressMode {
+    Text,
+    /// JSON Lines: `{"event":"download","skill":"foo","pct":42}`
+    Json,
+}
+
 #[derive(Subcommand)]
 pub enum SkillsCommand {
     /// List installed skills (from ~/.cc-switch/skills.json)
-    List,
-    /// Discover available skills (from enabled repos)
+    List {
+        /// Show at most N skills
+        #[arg(long, value_name = "N")]
+        limit: Option<usize>,
+        /// Skip the first M skills (sorted by name)
+        #[arg(long, value_name = "M", default_value_t = 0)]
+        offset: usize,
+        /// Print only the total number of installed skills
+        #[arg(long, conflicts_with_all = ["limit", "offset"])]
+        count: bool,
+    },
+    /// Summarize installed skills, per-app enablement, SSOT disk usage and repos
+    Stats,
+    /// Discover available skills (from enabled repos; with --json, a JSON array
+    /// including installed flags and repo origin)
     #[command(alias = "search")]
     Discover {
         /// Optional query filter (matches name/directory)
         query: Option<String>,
+        /// Override HTTP and repo download timeouts for this run (seconds)
+        #[arg(long, value_name = "SECS", value_parser = clap::value_parser!(u64).range(1..))]
+        timeout: Option<u64>,
+        /// Also run the `skillDiscoveryCommand` setting and merge its JSON output
+        #[arg(long)]
+        allow_external_discovery: bool,
     },
     /// Install a skill (SSOT -> app skills dir)
     Install {
         /// Skill directory name or full key (owner/name:directory)
-        spec: String,
+        #[arg(required_unless_present_any = ["url", "from_file"])]
+        spec: Option<String>,
+        /// Install every spec listed in a file (one per line, `#` comments allowed)
+        #[arg(long, value_name = "PATH", conflicts_with_all = ["spec", "url"])]
+        from_file: Option<PathBuf>,
+        /// Maximum number of installs running at the same time (with --from-file)
+        #[arg(long, value_name = "N", default_value_t = 4, requires = "from_file")]
+        parallel: usize,
+        /// Progress output: human-readable summary, or one JSON object per event on stdout
+        #[arg(long, value_enum, default_value_t = ProgressMode::Text, requires = "from_file")]
+        progress: ProgressMode,
+        /// Install from a release archive (.zip / .tar.gz / .tgz) instead of a repo
+        #[arg(
+            long,
+            value_name = "URL",
+            conflicts_with = "spec",
+            requires = "directory"
+        )]
+        url: Option<String>,
+        /// Skill directory inside the archive (used with --url)
+        #[arg(long, value_name = "DIR", requires = "url")]
+        directory: Option<String>,
+        /// On a directory conflict with another repo, install as <directory>-<owner>; also
+        /// bypasses the repo cache and re-downloads (with --url: replace an existing directory)
+        #[arg(long)]
+        force: bool,
+        /// Fail unless SKILL.md has valid front matter with a name
+        #[arg(long)]
+        strict: bool,
+        /// Override HTTP and repo download timeouts for this run (seconds)
+        #[arg(long, value_name = "SECS", value_parser = clap::value_parser!(u64).range(1..))]
+        timeout: Option<u64>,
+        /// Non-interactive: exit 0 quietly if already installed, rename on --force conflicts without asking
+        #[arg(short = 'y', long)]
+        yes: bool,
+        /// Enable the skill for the selected app and sync it (default)
+        #[arg(long, overrides_with = "no_enable")]
+        enable: bool,
+        /// Only copy into the SSOT with every app disabled; activate later with `skills enable`
+        #[arg(long, overrides_with = "enable")]
+        no_enable: bool,
+        /// Abort unless the downloaded archive has this SHA-256 (skips the repo cache)
+        #[arg(long, value_name = "HASH", conflicts_with = "from_file")]
+        sha256: Option<String>,
+        /// Also resolve the skill from the `skillDiscoveryCommand` catalog
+        #[arg(long, conflicts_with_all = ["from_file", "url"])]
+        allow_external_discovery: bool,
     },
     /// Uninstall a skill (remove from SSOT and app dirs)
     Uninstall {
         /// Skill directory or id
         spec: String,
+        /// Only unmanage: remove from app dirs and the index but keep ~/.cc-switch/skills/<dir>
+        #[arg(long)]
+        keep_ssot: bool,
     },
     /// Enable a skill for the selected app
     Enable {
@@ -37,8 +122,41 @@ pub enum SkillsCommand {
         /// Skill directory or id
         spec: String,
     },
+    /// Enable or disable every installed skill for the selected app
+    ToggleAll {
+        /// Enable all skills
+        #[arg(long, conflicts_with = "disable", required_unless_present = "disable")]
+        enable: bool,
+        /// Disable all skills
+        #[arg(long)]
+        disable: bool,
+        /// Skip the confirmation prompt
+        #[arg(short, long)]
+        yes: bool,
+    },
     /// Sync enabled skills to app skills dirs
-    Sync,
+    Sync {
+        /// Override the sync method for this run only (stored setting is unchanged)
+        #[arg(long, value_enum)]
+        method: Option<SyncMethod>,
+    },
+    /// Watch the SSOT skills dir and re-sync changed skills to enabled apps
+    Watch,
+    /// Remove SSOT skill dirs that are not referenced by skills.json
+    Prune {
+        /// Only list orphaned dirs without deleting them
+        #[arg(long)]
+        dry_run: bool,
+        /// Delete without asking for confirmation
+        #[arg(short, long)]
+        yes: bool,
+    },
+    /// Consolidate skills whose directory names differ only by case
+    Normalize {
+        /// Only report what would be consolidated
+        #[arg(long)]
+        dry_run: bool,
+    },
     /// Scan unmanaged skills in app skills dirs
     ScanUnmanaged,
     /// Import unmanaged skills from app skills dirs into SSOT
@@ -48,8 +166,22 @@ pub enum SkillsCommand {
     },
     /// Show skill information
     Info {
-        /// Skill directory or id
+        /// Skill directory or id (any discoverable key with --readme)
         spec: String,
+        /// Print the skill's SKILL.md (or README.md): from the SSOT when installed, else fetched
+        #[arg(long)]
+        readme: bool,
+        /// Print the readme verbatim instead of rendering it for the terminal
+        #[arg(long, requires = "readme")]
+        raw: bool,
+        /// Override HTTP and repo download timeouts for this run (seconds)
+        #[arg(
+            long,
+            value_name = "SECS",
+            requires = "readme",
+            value_parser = clap::value_parser!(u64).range(1..)
+        )]
+        timeout: Option<u64>,
     },
     /// Get or set the skills sync method (auto|symlink|copy)
     SyncMethod {
@@ -58,7 +190,7 @@ pub enum SkillsCommand {
         method: Option<SyncMethod>,
     },
     /// Manage skill repositories
-    #[command(subcommand)]
+    #[command(subcommand, visible_alias = "repo")]
     Repos(SkillReposCommand),
 }
 
@@ -70,52 +202,177 @@ pub enum SkillReposCommand {
     Add {
         /// Repository (GitHub URL or owner/name[@branch])
         url: String,
+        /// Branch to track (defaults to the repo's main/master, probed on add)
+        #[arg(long)]
+        branch: Option<String>,
+        /// Private repo: prompt for a GitHub token (read from stdin when piped) and keep it in the system keychain
+        #[arg(long)]
+        private: bool,
     },
     /// Remove a repository
     Remove {
         /// Repository (GitHub URL or owner/name)
         url: String,
     },
+    /// Enable a repository for discovery
+    Enable {
+        /// Repository (GitHub URL or owner/name)
+        url: String,
+    },
+    /// Disable a repository (kept in the list, skipped by discover)
+    Disable {
+        /// Repository (GitHub URL or owner/name)
+        url: String,
+    },
+    /// Add or update repositories from a JSON/TOML manifest ({owner,name,branch,enabled})
+    Import {
+        /// Manifest file (.toml, otherwise JSON)
+        file: PathBuf,
+    },
+    /// Write all repositories to a JSON/TOML manifest
+    Export {
+        /// Output file (.toml, otherwise JSON)
+        file: PathBuf,
+    },
+    /// Check a repository is reachable and list its skills, without adding it
+    Test {
+        /// Repository (GitHub URL or owner/name[@branch])
+        url: String,
+        /// Branch to test (defaults to the repo's main/master)
+        #[arg(long)]
+        branch: Option<String>,
+        /// Download a fresh copy even if the repo is cached
+        #[arg(long)]
+        refresh: bool,
+        /// Override HTTP and repo download timeouts for this run (seconds)
+        #[arg(long, value_name = "SECS", value_parser = clap::value_parser!(u64).range(1..))]
+        timeout: Option<u64>,
+    },
+    /// Re-download cached repositories (all enabled repos, or the given one)
+    Refresh {
+        /// Repository (GitHub URL or owner/name); defaults to every enabled repo
+        url: Option<String>,
+    },
 }
 
 pub fn execute(cmd: SkillsCommand, app: Option<AppType>) -> Result<(), AppError> {
     let app_type = app.clone().unwrap_or(AppType::Claude);
 
     match cmd {
-        SkillsCommand::List => list_installed(),
-        SkillsCommand::Discover { query } => discover_skills(query.as_deref()),
-        SkillsCommand::Install { spec } => install_skill(&app_type, &spec),
-        SkillsCommand::Uninstall { spec } => uninstall_skill(&spec),
+        SkillsCommand::List {
+            limit,
+            offset,
+            count,
+        } => list_installed(limit, offset, count),
+        SkillsCommand::Stats => show_skill_stats(),
+        SkillsCommand::Discover {
+            query,
+            timeout,
+            allow_external_discovery,
+        } => discover_skills(query.as_deref(), timeout, allow_external_discovery),
+        SkillsCommand::Install {
+            spec,
+            from_file,
+            parallel,
+            progress,
+            url,
+            directory,
+            force,
+            strict,
+            timeout,
+            yes,
+            no_enable,
+            sha256,
+            allow_external_discovery,
+            ..
+        } => {
+            let options = InstallOptions {
+                force,
+                strict,
+                no_enable,
+            };
+            if let Some(path) = from_file {
+                return install_skills_from_file(
+                    &app_type, &path, options, timeout, parallel, progress,
+                );
+            }
+            match (url, directory) {
+                (Some(url), Some(directory)) => install_skill_from_url(
+                    &app_type,
+                    &url,
+                    &directory,
+                    options,
+                    timeout,
+                    sha256.as_deref(),
+                ),
+                _ => install_skill(
+                    &app_type,
+                    spec.as_deref().unwrap_or_default(),
+                    options,
+                    timeout,
+                    yes,
+                    sha256.as_deref(),
+                    allow_external_discovery,
+                ),
+            }
+        }
+        SkillsCommand::Uninstall { spec, keep_ssot } => uninstall_skill(&spec, keep_ssot),
         SkillsCommand::Enable { spec } => toggle_skill(&app_type, &spec, true),
         SkillsCommand::Disable { spec } => toggle_skill(&app_type, &spec, false),
-        SkillsCommand::Sync => sync_skills(app.as_ref()),
+        SkillsCommand::ToggleAll { enable, yes, .. } => toggle_all_skills(&app_type, enable, yes),
+        SkillsCommand::Sync { method } => sync_skills(app.as_ref(), method),
+        SkillsCommand::Watch => watch_skills(app.as_ref()),
+        SkillsCommand::Prune { dry_run, yes } => prune_skills(dry_run, yes),
+        SkillsCommand::Normalize { dry_run } => normalize_skills(dry_run),
         SkillsCommand::ScanUnmanaged => scan_unmanaged(),
         SkillsCommand::ImportFromApps { directories } => import_from_apps(directories),
-        SkillsCommand::Info { spec } => show_skill_info(&spec),
+        SkillsCommand::Info {
+            spec,
+            readme,
+            raw,
+            timeout,
+        } => {
+            if readme {
+                show_skill_readme(&spec, raw, timeout)
+            } else {
+                show_skill_info(&spec)
+            }
+        }
         SkillsCommand::SyncMethod { method } => sync_method(method),
         SkillsCommand::Repos(repos_cmd) => execute_repos(repos_cmd),
     }
 }
 
-fn run_async<T>(fut: impl Future<Output = Result<T, AppError>>) -> Result<T, AppError> {
-    tokio::runtime::Builder::new_current_thread()
-        .enable_all()
-        .build()
-        .map_err(|e| AppError::Message(format!("Failed to create runtime: {e}")))?
-        .block_on(fut)
-}
-
-fn list_installed() -> Result<(), AppError> {
+fn list_installed(limit: Option<usize>, offset: usize, count: bool) -> Result<(), AppError> {
     let skills = SkillService::list_installed()?;
+    let total = skills.len();
 
-    if skills.is_empty() {
-        println!("{}", info("No installed skills found."));
+    if count {
+        println!("{total}");
         return Ok(());
     }
 
+    let page: Vec<_> = skills
+        .into_iter()
+        .skip(offset)
+        .take(limit.unwrap_or(usize::MAX))
+        .collect();
+    if json_output() {
+        return emit(&page);
+    }
+    if total == 0 {
+        out::info("No installed skills found.");
+        return Ok(());
+    }
+    if page.is_empty() {
+        out::info(&format!("No skills at offset {offset} (total: {total})."));
+        return Ok(());
+    }
+    let shown = page.len();
+
     let mut table = create_table();
     table.set_header(vec!["Directory", "Name", "Claude", "Codex", "Gemini"]);
-    for skill in skills {
+    for skill in page {
         table.add_row(vec![
             skill.directory,
             skill.name,
@@ -126,11 +383,34 @@ fn list_installed() -> Result<(), AppError> {
     }
 
     println!("{}", table);
+    if shown < total {
+        out::info(&format!(
+            "Showing {}-{} of {total}",
+            offset + 1,
+            offset + shown
+        ));
+    }
     Ok(())
 }
 
-fn discover_skills(query: Option<&str>) -> Result<(), AppError> {
-    let service = SkillService::new()?;
+fn discover_skills(
+    query: Option<&str>,
+    timeout: Option<u64>,
+    allow_external: bool,
+) -> Result<(), AppError> {
+    let mut service = SkillService::with_timeout(timeout)?;
+    if allow_external {
+        service = service.with_external_discovery(query);
+    } else if crate::settings::get_skill_discovery_command().is_some() {
+        let note =
+            "skillDiscoveryCommand is set but skipped; pass --allow-external-discovery to run it.";
+        // 结构化输出保持 stdout 干净，提示写到 stderr
+        if !json_output() {
+            out::info(note);
+        } else if !out::quiet() {
+            eprintln!("{note}");
+        }
+    }
     let mut skills = run_async(service.list_skills())?;
 
     if let Some(query) = query.map(str::trim).filter(|q| !q.is_empty()) {
@@ -140,8 +420,13 @@ fn discover_skills(query: Option<&str>) -> Result<(), AppError> {
         });
     }
 
+    // 空结果同样输出 `[]`，便于脚本解析
+    if json_output() {
+        return emit(&skills);
+    }
+
     if skills.is_empty() {
-        println!("{}", info("No skills found."));
+        out::info("No skills found.");
         return Ok(());
     }
 
@@ -158,50 +443,494 @@ fn discover_skills(query: Option<&str>) -> Result<(), AppError> {
     Ok(())
 }
 
-fn install_skill(app_type: &AppType, spec: &str) -> Result<(), AppError> {
-    let service = SkillService::new()?;
-    let installed = run_async(service.install(spec, app_type))?;
-    println!(
-        "{}",
-        success(&format!(
-            "✓ Installed skill '{}' (enabled for {})",
+/// 指定 `--sha256` 时要求下载的压缩包摘要一致
+fn checked_service(timeout: Option<u64>, sha256: Option<&str>) -> Result<SkillService, AppError> {
+    let service = SkillService::with_timeout(timeout)?;
+    match sha256 {
+        Some(hash) => service.with_sha256(hash),
+        None => Ok(service),
+    }
+}
+
+/// 退出码：新安装 / 已安装均为 0；目录冲突且未指定 `--force` 时返回错误（非 0）
+fn install_skill(
+    app_type: &AppType,
+    spec: &str,
+    options: InstallOptions,
+    timeout: Option<u64>,
+    yes: bool,
+    sha256: Option<&str>,
+    allow_external: bool,
+) -> Result<(), AppError> {
+    let mut service = checked_service(timeout, sha256)?;
+    if allow_external {
+        service = service.with_external_discovery(Some(spec));
+    }
+
+    // --force 会把冲突目录改名安装；交互终端下先确认，--yes 或非终端直接处理。
+    // 确认后复用同一份解析结果安装，避免重复解析与联网发现
+    let plan = run_async(service.plan_install(spec, options))?;
+    if plan.renamed && options.force && !yes && std::io::stdin().is_terminal() {
+        ensure_interactive("the rename confirmation", Some("--yes"))?;
+        let confirm = inquire::Confirm::new(&format!(
+            "Directory conflicts with another repo's skill; install as '{}'?",
+            plan.directory
+        ))
+        .with_default(true)
+        .prompt()
+        .map_err(|e| AppError::Message(format!("Prompt failed: {}", e)))?;
+        if !confirm {
+            out::info("Cancelled.");
+            return Ok(());
+        }
+    }
+
+    let (installed, outcome) = run_async(service.install_planned(plan, app_type, options))?;
+    let note = enablement_note(app_type, options);
+    match outcome {
+        InstallOutcome::AlreadyInstalled if yes => {}
+        InstallOutcome::AlreadyInstalled => out::info(&format!(
+            "Skill '{}' is already installed ({note})",
+            installed.directory
+        )),
+        InstallOutcome::Renamed => out::success(&format!(
+            "✓ Installed skill as '{}' to avoid a directory conflict ({note})",
+            installed.directory
+        )),
+        InstallOutcome::Installed => out::success(&format!(
+            "✓ Installed skill '{}' ({note})",
+            installed.directory
+        )),
+    }
+    if options.no_enable && outcome != InstallOutcome::AlreadyInstalled {
+        out::info(&format!(
+            "  Activate with `cc-switch skills enable {} --app {}`",
             installed.directory,
             app_type.as_str()
-        ))
-    );
+        ));
+    }
+    Ok(())
+}
+
+/// 安装结果后缀：已为哪个应用启用，或 `--no-enable` 时仅暂存
+fn enablement_note(app_type: &AppType, options: InstallOptions) -> String {
+    if options.no_enable {
+        "staged in SSOT, not enabled for any app".to_string()
+    } else {
+        format!("enabled for {}", app_type.as_str())
+    }
+}
+
+fn install_skills_from_file(
+    app_type: &AppType,
+    path: &Path,
+    options: InstallOptions,
+    timeout: Option<u64>,
+    parallel: usize,
+    progress: ProgressMode,
+) -> Result<(), AppError> {
+    let specs = SkillService::read_spec_list(path)?;
+    if specs.is_empty() {
+        if progress == ProgressMode::Json {
+            print_json_line(&serde_json::json!({ "event": "summary", "total": 0, "failed": 0 }));
+        } else if json_output() {
+            emit(&serde_json::json!([]))?;
+        } else {
+            out::info(&format!("No skill specs found in {}", path.display()));
+        }
+        return Ok(());
+    }
+
+    let mut service = SkillService::with_timeout(timeout)?;
+    if progress == ProgressMode::Json {
+        service = service.with_progress(Arc::new(|event: SkillProgress| {
+            print_json_line(&serde_json::to_value(&event).unwrap_or_default());
+        }));
+    } else if !json_output() {
+        out::info(&format!(
+            "Installing {} skill(s) for {} ({} at a time)...",
+            specs.len(),
+            app_type.as_str(),
+            parallel.max(1)
+        ));
+    }
+    let results = run_async(async {
+        Ok(service
+            .install_many(specs, app_type, options, parallel)
+            .await)
+    })?;
+    let failed = results.iter().filter(|r| r.error.is_some()).count();
+    let failure = (failed > 0).then(|| {
+        AppError::localized(
+            "skills.bulk_install_failed",
+            format!("{failed}/{} 个技能安装失败", results.len()),
+            format!("{failed} of {} skill(s) failed to install", results.len()),
+        )
+    });
+
+    if progress == ProgressMode::Json {
+        // stdout 只含 JSON 行：summary 之后不再输出任何文本，失败仅体现在退出码与 stderr
+        print_json_line(&serde_json::json!({
+            "event": "summary",
+            "total": results.len(),
+            "failed": failed,
+        }));
+        return failure.map_or(Ok(()), Err);
+    }
+    if json_output() {
+        emit(&results)?;
+        return failure.map_or(Ok(()), Err);
+    }
+
+    let mut table = create_table();
+    table.set_header(vec!["Spec", "Result", "Directory / Error"]);
+    for r in &results {
+        let (status, detail) = match (&r.outcome, &r.error) {
+            (_, Some(error)) => ("✗ failed", error.clone()),
+            (Some(InstallOutcome::AlreadyInstalled), _) => ("already installed", String::new()),
+            (Some(InstallOutcome::Renamed), _) => ("✓ renamed", String::new()),
+            _ => ("✓ installed", String::new()),
+        };
+        let detail = if detail.is_empty() {
+            r.directory.clone().unwrap_or_default()
+        } else {
+            detail
+        };
+        table.add_row(vec![r.spec.clone(), status.to_string(), detail]);
+    }
+    println!("{table}");
+
+    if let Some(failure) = failure {
+        return Err(failure);
+    }
+    out::success(&format!("✓ Installed {} skill(s)", results.len()));
+    Ok(())
+}
+
+/// One compact JSON object per line, flushed so wrappers see it immediately.
+fn print_json_line(value: &serde_json::Value) {
+    use std::io::Write;
+    let mut stdout = std::io::stdout().lock();
+    let _ = writeln!(stdout, "{value}");
+    let _ = stdout.flush();
+}
+
+fn install_skill_from_url(
+    app_type: &AppType,
+    url: &str,
+    directory: &str,
+    options: InstallOptions,
+    timeout: Option<u64>,
+    sha256: Option<&str>,
+) -> Result<(), AppError> {
+    let service = checked_service(timeout, sha256)?;
+    let installed = run_async(service.install_from_url(url, directory, app_type, options))?;
+    out::success(&format!(
+        "✓ Installed skill '{}' from {} ({})",
+        installed.directory,
+        url,
+        enablement_note(app_type, options)
+    ));
     Ok(())
 }
 
-fn uninstall_skill(spec: &str) -> Result<(), AppError> {
-    SkillService::uninstall(spec)?;
-    println!("{}", success(&format!("✓ Uninstalled skill '{spec}'")));
+fn uninstall_skill(spec: &str, keep_ssot: bool) -> Result<(), AppError> {
+    SkillService::uninstall_with_options(spec, keep_ssot)?;
+    out::success(&format!("✓ Uninstalled skill '{spec}'"));
+    if keep_ssot {
+        out::info(
+            "  Files kept in the SSOT dir; see `skills scan-unmanaged`, re-adopt with `skills import-from-apps <dir>`",
+        );
+    }
     Ok(())
 }
 
 fn toggle_skill(app_type: &AppType, spec: &str, enabled: bool) -> Result<(), AppError> {
     SkillService::toggle_app(spec, app_type, enabled)?;
-    println!(
-        "{}",
-        success(&format!(
-            "✓ {} '{}' for {}",
-            if enabled { "Enabled" } else { "Disabled" },
-            spec,
+    out::success(&format!(
+        "✓ {} '{}' for {}",
+        if enabled { "Enabled" } else { "Disabled" },
+        spec,
+        app_type.as_str()
+    ));
+    Ok(())
+}
+
+fn toggle_all_skills(app_type: &AppType, enabled: bool, yes: bool) -> Result<(), AppError> {
+    let skills = SkillService::list_installed()?;
+    if skills.is_empty() {
+        out::info("No installed skills.");
+        return Ok(());
+    }
+
+    let action = if enabled { "Enable" } else { "Disable" };
+    if !yes {
+        ensure_interactive("the bulk toggle confirmation", Some("--yes"))?;
+        let confirm = inquire::Confirm::new(&format!(
+            "{action} all {} skill(s) for {}?",
+            skills.len(),
             app_type.as_str()
         ))
+        .with_default(false)
+        .prompt()
+        .map_err(|e| AppError::Message(format!("Prompt failed: {}", e)))?;
+        if !confirm {
+            out::info("Cancelled.");
+            return Ok(());
+        }
+    }
+
+    let mut table = create_table();
+    table.set_header(vec!["Directory", "Result"]);
+    let mut failed = 0;
+    for skill in &skills {
+        let was_enabled = skill.apps.is_enabled_for(app_type);
+        let result = match SkillService::toggle_app(&skill.directory, app_type, enabled) {
+            Ok(()) if was_enabled == enabled => "unchanged".to_string(),
+            Ok(()) if enabled => "enabled".to_string(),
+            Ok(()) => "disabled".to_string(),
+            Err(e) => {
+                failed += 1;
+                format!("failed: {e}")
+            }
+        };
+        table.add_row(vec![skill.directory.clone(), result]);
+    }
+    println!("{}", table);
+
+    let done = skills.len() - failed;
+    let summary = format!(
+        "{action}d {done}/{} skill(s) for {}",
+        skills.len(),
+        app_type.as_str()
     );
+    if failed == 0 {
+        out::success(&format!("✓ {summary}"));
+        Ok(())
+    } else {
+        Err(AppError::Message(format!("{summary}; {failed} failed")))
+    }
+}
+
+fn sync_skills(app: Option<&AppType>, method: Option<SyncMethod>) -> Result<(), AppError> {
+    SkillService::sync_all_enabled_with_method(app, method)?;
+    out::success("✓ Skills synced successfully");
     Ok(())
 }
 
-fn sync_skills(app: Option<&AppType>) -> Result<(), AppError> {
-    SkillService::sync_all_enabled(app)?;
-    println!("{}", success("✓ Skills synced successfully"));
+enum WatchMsg {
+    Fs(notify::Result<notify::Event>),
+    Stop,
+}
+
+/// 变化事件合并窗口：编辑器保存通常触发多个事件
+const WATCH_DEBOUNCE: Duration = Duration::from_millis(500);
+
+fn watch_skills(app: Option<&AppType>) -> Result<(), AppError> {
+    let ssot_dir = SkillService::get_ssot_dir()?;
+    let (tx, rx) = mpsc::channel();
+
+    let fs_tx = tx.clone();
+    let mut watcher = notify::recommended_watcher(move |res| {
+        let _ = fs_tx.send(WatchMsg::Fs(res));
+    })
+    .map_err(|e| AppError::Message(format!("Failed to start file watcher: {e}")))?;
+    watcher
+        .watch(&ssot_dir, RecursiveMode::Recursive)
+        .map_err(|e| AppError::Message(format!("Failed to watch {}: {e}", ssot_dir.display())))?;
+    ctrlc::set_handler(move || {
+        let _ = tx.send(WatchMsg::Stop);
+    })
+    .map_err(|e| AppError::Message(format!("Failed to install Ctrl-C handler: {e}")))?;
+
+    out::info(&format!(
+        "Watching {} (Ctrl-C to stop)...",
+        ssot_dir.display()
+    ));
+
+    loop {
+        let mut changed = BTreeSet::new();
+        let mut stop = false;
+        // 阻塞等待第一个事件，随后在去抖窗口内继续收集
+        let mut next = rx.recv().ok();
+        while let Some(msg) = next {
+            match msg {
+                WatchMsg::Stop => {
+                    stop = true;
+                    break;
+                }
+                WatchMsg::Fs(Ok(event)) => {
+                    for path in &event.paths {
+                        if let Some(dir) = SkillService::ssot_skill_of(path)? {
+                            changed.insert(dir);
+                        }
+                    }
+                }
+                WatchMsg::Fs(Err(e)) => out::warning(&format!("Watch error: {e}")),
+            }
+            next = rx.recv_timeout(WATCH_DEBOUNCE).ok();
+        }
+
+        for dir in &changed {
+            match SkillService::resync_skill(dir, app) {
+                Ok(results) if results.is_empty() => {}
+                Ok(results) => {
+                    let apps = results
+                        .iter()
+                        .map(|(app, synced)| {
+                            let status = if *synced { "synced" } else { "link ok" };
+                            format!("{} ({status})", app.as_str())
+                        })
+                        .collect::<Vec<_>>()
+                        .join(", ");
+                    out::success(&format!("✓ {dir}: {apps}"));
+                }
+                Err(e) => out::warning(&format!("{dir}: {e}")),
+            }
+        }
+
+        if stop {
+            out::info("Stopped watching.");
+            return Ok(());
+        }
+    }
+}
+
+fn normalize_skills(dry_run: bool) -> Result<(), AppError> {
+    let fixes = SkillService::normalize_casing(dry_run)?;
+    if json_output() {
+        return emit(&fixes);
+    }
+    if fixes.is_empty() {
+        out::info("No skill directories differ only by case.");
+        return Ok(());
+    }
+
+    let mut table = create_table();
+    table.set_header(vec!["Canonical", "Merged", "Records Merged", "Re-synced"]);
+    for fix in &fixes {
+        let resynced = fix
+            .resynced
+            .iter()
+            .map(|app| app.as_str())
+            .collect::<Vec<_>>()
+            .join(", ");
+        table.add_row(vec![
+            fix.canonical.clone(),
+            fix.merged.join(", "),
+            fix.records_merged.to_string(),
+            resynced,
+        ]);
+    }
+    println!("{}", table);
+
+    for fix in fixes.iter().filter(|fix| !fix.differing.is_empty()) {
+        out::warning(&format!(
+            "{}: {} differ in content from the kept copy",
+            fix.canonical,
+            fix.differing.join(", ")
+        ));
+    }
+    if dry_run {
+        out::info(&format!(
+            "Dry run: {} skill(s) would be consolidated; run without --dry-run to apply.",
+            fixes.len()
+        ));
+    } else {
+        out::success(&format!("✓ Consolidated {} skill(s)", fixes.len()));
+        if let Some(dir) = fixes.iter().find_map(|fix| fix.backup_dir.as_ref()) {
+            out::info(&format!("Removed directories moved to {}", dir.display()));
+        }
+    }
+    Ok(())
+}
+
+fn prune_skills(dry_run: bool, yes: bool) -> Result<(), AppError> {
+    let orphans = SkillService::find_orphaned_ssot_dirs()?;
+    if orphans.is_empty() {
+        out::info("No orphaned skill directories found.");
+        return Ok(());
+    }
+
+    let mut table = create_table();
+    table.set_header(vec!["Directory", "Size", "Linked From"]);
+    for orphan in &orphans {
+        let linked = orphan
+            .referenced_by
+            .iter()
+            .map(|app| app.as_str())
+            .collect::<Vec<_>>()
+            .join(", ");
+        table.add_row(vec![
+            orphan.directory.clone(),
+            format_bytes(orphan.size_bytes),
+            linked,
+        ]);
+    }
+    println!("{}", table);
+
+    let (removable, linked): (Vec<_>, Vec<_>) = orphans
+        .iter()
+        .partition(|orphan| orphan.referenced_by.is_empty());
+    if !linked.is_empty() {
+        out::warning(&format!(
+            "{} dir(s) are still symlinked from app skills dirs and will be kept.",
+            linked.len()
+        ));
+    }
+    let total: u64 = removable.iter().map(|orphan| orphan.size_bytes).sum();
+
+    if removable.is_empty() {
+        return Ok(());
+    }
+    if dry_run {
+        out::info(&format!(
+            "Dry run: {} dir(s) would be removed, reclaiming {}.",
+            removable.len(),
+            format_bytes(total)
+        ));
+        return Ok(());
+    }
+
+    if !yes {
+        ensure_interactive("the prune confirmation", Some("--yes"))?;
+        let confirm = inquire::Confirm::new(&format!(
+            "Delete {} orphaned skill dir(s)?",
+            removable.len()
+        ))
+        .with_default(false)
+        .prompt()
+        .map_err(|e| AppError::Message(format!("Prompt failed: {}", e)))?;
+        if !confirm {
+            out::info("Cancelled.");
+            return Ok(());
+        }
+    }
+
+    let mut reclaimed = 0;
+    let mut removed = 0;
+    for orphan in removable {
+        match SkillService::remove_orphaned_ssot_dir(orphan) {
+            Ok(()) => {
+                reclaimed += orphan.size_bytes;
+                removed += 1;
+            }
+            Err(e) => out::warning(&format!("{}: {}", orphan.directory, e)),
+        }
+    }
+
+    out::success(&format!(
+        "✓ Removed {} orphaned dir(s), reclaimed {}",
+        removed,
+        format_bytes(reclaimed)
+    ));
     Ok(())
 }
 
 fn scan_unmanaged() -> Result<(), AppError> {
     let skills = SkillService::scan_unmanaged()?;
     if skills.is_empty() {
-        println!("{}", info("No unmanaged skills found."));
+        out::info("No unmanaged skills found.");
         return Ok(());
     }
 
@@ -222,10 +951,35 @@ fn import_from_apps(directories: Vec<String>) -> Result<(), AppError> {
     }
 
     let imported = SkillService::import_from_apps(directories)?;
+    out::success(&format!("✓ Imported {} skill(s) into SSOT", imported.len()));
+    Ok(())
+}
+
+fn show_skill_stats() -> Result<(), AppError> {
+    let stats = SkillService::stats()?;
+
+    if json_output() {
+        return emit(&stats);
+    }
+
+    out::highlight("Skills");
+    println!("Installed: {}", stats.installed);
+    let enabled: Vec<String> = stats
+        .enabled_by_app
+        .iter()
+        .map(|(app, count)| format!("{app}={count}"))
+        .collect();
+    println!("Enabled:   {}", enabled.join(" "));
+    println!(
+        "Sources:   repo={} archive={} local={}",
+        stats.from_repo, stats.from_archive, stats.local
+    );
+    println!("SSOT size: {}", format_bytes(stats.ssot_bytes));
     println!(
-        "{}",
-        success(&format!("✓ Imported {} skill(s) into SSOT", imported.len()))
+        "Repos:     {} configured, {} enabled",
+        stats.repos_configured, stats.repos_enabled
     );
+
     Ok(())
 }
 
@@ -238,7 +992,7 @@ fn show_skill_info(spec: &str) -> Result<(), AppError> {
         .find(|s| s.directory.eq_ignore_ascii_case(spec) || s.id.eq_ignore_ascii_case(spec))
         .ok_or_else(|| AppError::Message(format!("Skill not found: {spec}")))?;
 
-    println!("{}", highlight("Skill"));
+    out::highlight("Skill");
     println!("Directory: {}", record.directory);
     println!("Name:      {}", record.name);
     if let Some(desc) = record
@@ -248,6 +1002,9 @@ fn show_skill_info(spec: &str) -> Result<(), AppError> {
     {
         println!("Desc:      {}", desc);
     }
+    if let Some(source_url) = record.source_url.as_deref() {
+        println!("Source:    {}", source_url);
+    }
     println!(
         "Enabled:   claude={} codex={} gemini={}",
         record.apps.claude, record.apps.codex, record.apps.gemini
@@ -256,11 +1013,92 @@ fn show_skill_info(spec: &str) -> Result<(), AppError> {
     Ok(())
 }
 
+fn show_skill_readme(spec: &str, raw: bool, timeout: Option<u64>) -> Result<(), AppError> {
+    let service = SkillService::with_timeout(timeout)?;
+    let readme = run_async(service.read_readme(spec))?;
+
+    if json_output() {
+        emit(&readme)?;
+        return Ok(());
+    }
+
+    if raw {
+        print!("{}", readme.content);
+        if !readme.content.ends_with('\n') {
+            println!();
+        }
+        return Ok(());
+    }
+
+    let origin = match readme.source {
+        SkillReadmeSource::Installed => "installed",
+        SkillReadmeSource::Cache => "repo cache",
+        SkillReadmeSource::Remote => "remote",
+    };
+    out::highlight(&format!("{} ({}, {origin})", readme.key, readme.file_name));
+    if let Some(url) = readme.readme_url.as_deref() {
+        out::info(url);
+    }
+    println!();
+    for line in render_markdown(&readme.content) {
+        println!("{line}");
+    }
+    Ok(())
+}
+
+/// 轻量终端渲染：去掉 front matter，标题高亮，代码块着色，列表符号替换为圆点
+fn render_markdown(content: &str) -> Vec<String> {
+    let body = content
+        .strip_prefix("---")
+        .and_then(|rest| rest.split_once("\n---"))
+        .map(|(_, after)| after.split_once('\n').map_or("", |(_, body)| body))
+        .unwrap_or(content);
+
+    let mut in_code = false;
+    let mut lines = Vec::new();
+    for line in body.trim_start_matches('\n').lines() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") {
+            in_code = !in_code;
+            continue;
+        }
+        if in_code {
+            lines.push(colors::info(&format!("    {line}")));
+        } else if trimmed.starts_with('#') {
+            lines.push(colors::highlight(trimmed.trim_start_matches('#').trim()));
+        } else if let Some(item) = trimmed
+            .strip_prefix("- ")
+            .or_else(|| trimmed.strip_prefix("* "))
+        {
+            let indent = &line[..line.len() - trimmed.len()];
+            lines.push(format!("{indent}• {item}"));
+        } else {
+            lines.push(line.to_string());
+        }
+    }
+    lines
+}
+
 fn execute_repos(cmd: SkillReposCommand) -> Result<(), AppError> {
     match cmd {
         SkillReposCommand::List => list_repos(),
-        SkillReposCommand::Add { url } => add_repo(&url),
+        SkillReposCommand::Add {
+            url,
+            branch,
+            private,
+        } => add_repo(&url, branch.as_deref(), private),
         SkillReposCommand::Remove { url } => remove_repo(&url),
+        SkillReposCommand::Enable { url } => set_repo_enabled(&url, true),
+        SkillReposCommand::Disable { url } => set_repo_enabled(&url, false),
+        SkillReposCommand::Import { file } => import_repos(&file),
+        SkillReposCommand::Export { file } => export_repos(&file),
+        SkillReposCommand::Test {
+            url,
+            branch,
+            refresh,
+            timeout,
+        } => test_repo(&url, branch.as_deref(), refresh, timeout),
+        SkillReposCommand::Refresh { url } => refresh_repos(url.as_deref()),
     }
 }
 
@@ -268,7 +1106,7 @@ fn list_repos() -> Result<(), AppError> {
     let repos = SkillService::list_repos()?;
 
     if repos.is_empty() {
-        println!("{}", info("No skill repos configured."));
+        out::info("No skill repos configured.");
         return Ok(());
     }
 
@@ -277,7 +1115,11 @@ fn list_repos() -> Result<(), AppError> {
     for repo in repos {
         table.add_row(vec![
             if repo.enabled { "✓" } else { " " }.to_string(),
-            format!("{}/{}", repo.owner, repo.name),
+            if repo.token.is_some() {
+                format!("{}/{} (private)", repo.owner, repo.name)
+            } else {
+                format!("{}/{}", repo.owner, repo.name)
+            },
             repo.branch,
         ]);
     }
@@ -285,17 +1127,194 @@ fn list_repos() -> Result<(), AppError> {
     Ok(())
 }
 
-fn add_repo(_url: &str) -> Result<(), AppError> {
-    let repo = parse_repo_spec(_url)?;
-    SkillService::upsert_repo(repo)?;
-    println!("{}", success("✓ Repository added."));
+fn add_repo(url: &str, branch: Option<&str>, private: bool) -> Result<(), AppError> {
+    let mut repo = parse_repo_spec_for_probe(url, branch)?;
+    if private {
+        let token = read_secret("GitHub token")?;
+        repo.token = Some(SkillService::store_repo_token(
+            &repo.owner,
+            &repo.name,
+            &token,
+        )?);
+    }
+    let previous = SkillService::tracked_branch(&repo.owner, &repo.name)?;
+    let service = SkillService::new()?;
+    let repo = run_async(service.add_repo(repo))?;
+    if let Some(previous) = previous.filter(|b| *b != repo.branch) {
+        out::warning(&format!(
+            "Tracked branch of {}/{} changed: {previous} -> {}",
+            repo.owner, repo.name, repo.branch
+        ));
+    }
+    let private_note = if repo.token.is_some() {
+        ", private"
+    } else {
+        ""
+    };
+    out::success(&format!(
+        "✓ Repository {}/{} added (branch: {}{private_note}).",
+        repo.owner, repo.name, repo.branch
+    ));
+    Ok(())
+}
+
+fn import_repos(file: &Path) -> Result<(), AppError> {
+    let repos = SkillService::read_repo_manifest(file)?;
+    let report = SkillService::import_repos(repos)?;
+
+    if json_output() {
+        emit(&report)?;
+        return Ok(());
+    }
+
+    out::success(&format!(
+        "✓ Imported repositories from {}: {} added, {} merged, {} unchanged",
+        file.display(),
+        report.added.len(),
+        report.merged.len(),
+        report.unchanged.len()
+    ));
+    for repo in &report.added {
+        println!("  + {repo}");
+    }
+    for repo in &report.merged {
+        println!("  ~ {repo}");
+    }
+    if !report.duplicates.is_empty() {
+        out::warning(&format!(
+            "Duplicate entries merged (last one wins): {}",
+            report.duplicates.join(", ")
+        ));
+    }
+    Ok(())
+}
+
+fn export_repos(file: &Path) -> Result<(), AppError> {
+    let count = SkillService::export_repo_manifest(file)?;
+    out::success(&format!(
+        "✓ Exported {count} repositories to {}",
+        file.display()
+    ));
+    Ok(())
+}
+
+fn test_repo(
+    url: &str,
+    branch: Option<&str>,
+    refresh: bool,
+    timeout: Option<u64>,
+) -> Result<(), AppError> {
+    let repo = parse_repo_spec_for_probe(url, branch)?;
+    let service = SkillService::with_timeout(timeout)?;
+    let report = run_async(service.test_repo(repo, refresh))?;
+
+    if json_output() {
+        emit(&report)?;
+    } else if !report.skills.is_empty() {
+        let source = if report.from_cache {
+            "cache"
+        } else {
+            "download"
+        };
+        out::success(&format!(
+            "✓ {}/{}@{}: {} skill(s) found ({source})",
+            report.owner,
+            report.name,
+            report.branch,
+            report.skills.len()
+        ));
+        let mut table = create_table();
+        table.set_header(vec!["Directory", "Name"]);
+        for skill in &report.skills {
+            table.add_row(vec![skill.directory.clone(), skill.name.clone()]);
+        }
+        println!("{}", table);
+    }
+
+    if report.skills.is_empty() {
+        return Err(AppError::localized(
+            "skills.repo_empty",
+            format!(
+                "仓库 {}/{}@{} 中没有找到包含 SKILL.md 的目录",
+                report.owner, report.name, report.branch
+            ),
+            format!(
+                "No directories with a SKILL.md found in {}/{}@{}",
+                report.owner, report.name, report.branch
+            ),
+        ));
+    }
+    Ok(())
+}
+
+fn refresh_repos(url: Option<&str>) -> Result<(), AppError> {
+    let repos = SkillService::list_repos()?;
+    let targets: Vec<SkillRepo> = match url {
+        Some(url) => {
+            let wanted = parse_repo_spec(url)?;
+            let repo = repos
+                .into_iter()
+                .find(|r| {
+                    r.owner.eq_ignore_ascii_case(&wanted.owner)
+                        && r.name.eq_ignore_ascii_case(&wanted.name)
+                })
+                .ok_or_else(|| {
+                    AppError::InvalidInput(format!(
+                        "Repository {}/{} is not configured",
+                        wanted.owner, wanted.name
+                    ))
+                })?;
+            vec![repo]
+        }
+        None => repos.into_iter().filter(|r| r.enabled).collect(),
+    };
+    if targets.is_empty() {
+        out::info("No enabled skill repos to refresh.");
+        return Ok(());
+    }
+
+    let service = SkillService::new()?;
+    let mut failed = 0;
+    for repo in &targets {
+        match run_async(service.refresh_repo_cache(repo)) {
+            Ok(count) => out::success(&format!(
+                "✓ {}/{}@{}: {count} skill(s) cached",
+                repo.owner, repo.name, repo.branch
+            )),
+            Err(e) => {
+                failed += 1;
+                out::warning(&format!(
+                    "✗ {}/{}@{}: {e}",
+                    repo.owner, repo.name, repo.branch
+                ));
+            }
+        }
+    }
+
+    if failed > 0 {
+        return Err(AppError::Message(format!(
+            "Failed to refresh {failed} of {} repo(s)",
+            targets.len()
+        )));
+    }
     Ok(())
 }
 
 fn remove_repo(_url: &str) -> Result<(), AppError> {
     let repo = parse_repo_spec(_url)?;
     SkillService::remove_repo(&repo.owner, &repo.name)?;
-    println!("{}", success("✓ Repository removed."));
+    out::success("✓ Repository removed.");
+    Ok(())
+}
+
+fn set_repo_enabled(url: &str, enabled: bool) -> Result<(), AppError> {
+    let repo = parse_repo_spec(url)?;
+    let repo = SkillService::set_repo_enabled(&repo.owner, &repo.name, enabled)?;
+    let state = if enabled { "enabled" } else { "disabled" };
+    out::success(&format!(
+        "✓ Repository {}/{} {state}.",
+        repo.owner, repo.name
+    ));
     Ok(())
 }
 
@@ -303,14 +1322,11 @@ fn sync_method(method: Option<SyncMethod>) -> Result<(), AppError> {
     match method {
         Some(method) => {
             SkillService::set_sync_method(method)?;
-            println!(
-                "{}",
-                success(&format!("✓ Skill sync method set to {method:?}"))
-            );
+            out::success(&format!("✓ Skill sync method set to {method:?}"));
         }
         None => {
             let method = SkillService::get_sync_method()?;
-            println!("{}", highlight("Skill Sync Method"));
+            out::highlight("Skill Sync Method");
             println!("{method:?}");
         }
     }
@@ -350,5 +1366,17 @@ fn parse_repo_spec(raw: &str) -> Result<SkillRepo, AppError> {
         name: name.to_string(),
         branch: branch.unwrap_or("main").to_string(),
         enabled: true,
+        token: None,
     })
 }
+
+/// 同 `parse_repo_spec`，但 spec 与 `--branch` 都未指定分支时留空，交由 SkillService 探测默认分支
+fn parse_repo_spec_for_probe(raw: &str, branch: Option<&str>) -> Result<SkillRepo, AppError> {
+    let mut repo = parse_repo_spec(raw)?;
+    match branch.map(str::trim).filter(|b| !b.is_empty()) {
+        Some(branch) => repo.branch = branch.to_string(),
+        None if !raw.contains('@') => repo.branch.clear(),
+        None => {}
+    }
+    Ok(repo)
+}
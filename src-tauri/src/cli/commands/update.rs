@@ -0,0 +1,711 @@
+use chrono::Utc;
+use clap::{Args, Subcommand};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::cli::runtime::run_async;
+use crate::cli::ui::out;
+use crate::config::{get_app_config_dir, read_json_file, write_json_file};
+use crate::error::AppError;
+use crate::settings::UpdateChannel;
+use crate::util::archive::{self, ArchiveKind, ExtractLimits};
+
+const RELEASE_REPO: &str = "saladday/cc-switch-cli";
+const CURRENT_VERSION: &str = env!("CARGO_PKG_VERSION");
+const BACKUP_MANIFEST_FILE: &str = "update-backups.json";
+/// 校验和清单资产：每行 `<sha256>  <asset>`
+const CHECKSUMS_ASSET: &str = "SHA256SUMS";
+const LAST_UPDATE_FILE: &str = "last-update.json";
+/// 发布包解压上限（发布包只含一个二进制和文档）
+const RELEASE_ARCHIVE_LIMITS: ExtractLimits = ExtractLimits {
+    max_entries: 1_000,
+    max_bytes: 512 * 1024 * 1024,
+};
+
+/// `update --check` 发现新版本时的退出码
+pub const UPDATE_AVAILABLE_EXIT_CODE: i32 = 10;
+
+/// 命令执行结果；由 `main` 映射为进程退出码
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UpdateOutcome {
+    Done,
+    /// `--check` 发现了更新的版本
+    UpdateAvailable,
+}
+
+impl UpdateOutcome {
+    pub fn exit_code(self) -> i32 {
+        match self {
+            Self::Done => 0,
+            Self::UpdateAvailable => UPDATE_AVAILABLE_EXIT_CODE,
+        }
+    }
+}
+
+#[derive(Args)]
+#[command(args_conflicts_with_subcommands = true)]
+pub struct UpdateCommand {
+    #[command(subcommand)]
+    pub action: Option<UpdateAction>,
+    /// Only report whether an update is available (exit code 10 if so, 0 if up to date)
+    #[arg(long)]
+    pub check: bool,
+    /// Target release tag (defaults to the latest release on the channel)
+    #[arg(long)]
+    pub tag: Option<String>,
+    /// Release channel to track (defaults to the updateChannel setting)
+    #[arg(long, value_enum, conflicts_with = "tag")]
+    pub channel: Option<UpdateChannel>,
+    /// GitHub Enterprise host or internal release mirror (defaults to the releaseHost
+    /// setting, then github.com)
+    #[arg(long, value_name = "URL")]
+    pub release_host: Option<String>,
+    /// Allow targeting an explicit --tag older than the current version
+    #[arg(long, requires = "tag")]
+    pub allow_downgrade: bool,
+    /// Keep the replaced binary as cc-switch.<version>.bak for `update rollback`
+    #[arg(long)]
+    pub keep_backup: bool,
+}
+
+#[derive(Subcommand)]
+pub enum UpdateAction {
+    /// Restore the most recently saved previous binary
+    Rollback,
+}
+
+/// 查询与下载发布版本的地址
+#[derive(Debug, PartialEq, Eq)]
+struct ReleaseHost {
+    /// REST API 根地址，如 `https://api.github.com` 或 `https://ghe.example.com/api/v3`
+    api_base: String,
+    /// 下载发布资产的 Web 根地址，如 `https://github.com`
+    web_base: String,
+}
+
+impl ReleaseHost {
+    /// `host` 为已校验的根地址；github.com（或未设置）使用公共端点
+    fn from_base(host: Option<&str>) -> Self {
+        match host {
+            Some(base) if !is_github_com(base) => Self {
+                api_base: format!("{base}/api/v3"),
+                web_base: base.to_string(),
+            },
+            _ => Self {
+                api_base: "https://api.github.com".to_string(),
+                web_base: "https://github.com".to_string(),
+            },
+        }
+    }
+
+    fn releases_url(&self, path: &str) -> String {
+        format!("{}/repos/{RELEASE_REPO}/releases{path}", self.api_base)
+    }
+
+    fn asset_url(&self, tag: &str, asset: &str) -> String {
+        format!(
+            "{}/{RELEASE_REPO}/releases/download/{tag}/{asset}",
+            self.web_base
+        )
+    }
+}
+
+fn is_github_com(base: &str) -> bool {
+    url::Url::parse(base)
+        .ok()
+        .and_then(|url| url.host_str().map(|h| h.eq_ignore_ascii_case("github.com")))
+        .unwrap_or(false)
+}
+
+/// 校验发布地址：必须是带主机名、无查询串和片段的 https 根地址，返回时去掉末尾斜杠。
+/// 下载的二进制会替换当前程序，因此拒绝明文 http。
+pub(crate) fn parse_release_host(raw: &str) -> Result<String, AppError> {
+    let raw = raw.trim();
+    let invalid = |reason: String| {
+        AppError::localized(
+            "update.invalid_release_host",
+            format!("无效的发布站点地址 '{raw}': {reason}"),
+            format!("Invalid release host '{raw}': {reason}"),
+        )
+    };
+    let url = url::Url::parse(raw).map_err(|e| invalid(e.to_string()))?;
+    if url.scheme() != "https" {
+        return Err(invalid(format!(
+            "unsupported scheme '{}' (expected https)",
+            url.scheme()
+        )));
+    }
+    if url.host().is_none() {
+        return Err(invalid("missing host".to_string()));
+    }
+    if url.query().is_some() || url.fragment().is_some() {
+        return Err(invalid(
+            "query strings and fragments are not allowed".to_string(),
+        ));
+    }
+    Ok(url.as_str().trim_end_matches('/').to_string())
+}
+
+/// 目标版本与当前版本的关系
+#[derive(Debug, PartialEq, Eq)]
+enum UpdateDecision {
+    Newer,
+    UpToDate,
+    Older,
+}
+
+#[derive(Deserialize)]
+struct GithubRelease {
+    tag_name: String,
+    #[serde(default)]
+    draft: bool,
+}
+
+/// `update --keep-backup` 保留的旧版本二进制（最新的在最后）
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BackupManifest {
+    #[serde(default)]
+    backups: Vec<BinaryBackup>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BinaryBackup {
+    version: String,
+    path: PathBuf,
+    saved_at: i64,
+}
+
+/// 最近一次成功的 `cc-switch update`（由 `cc-switch version` 展示）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SelfUpdateRecord {
+    pub from: String,
+    pub to: String,
+    pub updated_at: i64,
+}
+
+pub fn execute(cmd: UpdateCommand) -> Result<UpdateOutcome, AppError> {
+    if let Some(UpdateAction::Rollback) = cmd.action {
+        return rollback().map(|()| UpdateOutcome::Done);
+    }
+
+    let channel = cmd
+        .channel
+        .unwrap_or_else(crate::settings::get_update_channel);
+    let release_host = match cmd.release_host.as_deref() {
+        Some(raw) => Some(parse_release_host(raw)?),
+        None => crate::settings::get_release_host(),
+    };
+    let host = ReleaseHost::from_base(release_host.as_deref());
+    let client = http_client(10)?;
+    let tag = run_async(resolve_target_tag(
+        &client,
+        &host,
+        cmd.tag.as_deref(),
+        channel,
+    ))?;
+    let current = parse_version(CURRENT_VERSION)?;
+    let target = parse_version(&tag)?;
+    let decision = compare_versions(&current, &target);
+
+    if cmd.check {
+        if decision == UpdateDecision::Newer {
+            println!("Update available: {current} -> {target}");
+            return Ok(UpdateOutcome::UpdateAvailable);
+        }
+        println!("Up to date: {current}");
+        return Ok(UpdateOutcome::Done);
+    }
+
+    out::highlight("cc-switch update");
+    println!("Current version: {current}");
+    println!("Target version:  {target}");
+    match decision {
+        UpdateDecision::Newer => {}
+        UpdateDecision::Older if cmd.allow_downgrade => {
+            out::warning(&format!("Downgrading {current} -> {target}"));
+        }
+        UpdateDecision::Older => {
+            out::info(&format!(
+                "\nTarget {target} is older than the current version; pass --allow-downgrade to use it anyway."
+            ));
+            return Ok(UpdateOutcome::Done);
+        }
+        UpdateDecision::UpToDate => {
+            out::success("\n✓ Already up to date");
+            return Ok(UpdateOutcome::Done);
+        }
+    }
+
+    let asset = release_asset_name()?;
+    let url = host.asset_url(&tag, asset);
+    // 预演模式不下载也不替换二进制
+    if crate::dry_run::skip(format_args!(
+        "update cc-switch {current} -> {target} from {url}"
+    )) {
+        return Ok(UpdateOutcome::Done);
+    }
+    out::info(&format!("Downloading {url}"));
+
+    let download_client = http_client(300)?;
+    let bytes = run_async(download_asset(&download_client, &url))?;
+    let sums_url = host.asset_url(&tag, CHECKSUMS_ASSET);
+    let sums = run_async(download_asset(&download_client, &sums_url))?;
+    verify_asset_checksum(&String::from_utf8_lossy(&sums), asset, &bytes)?;
+    out::info(&format!("Verified SHA-256 against {CHECKSUMS_ASSET}"));
+
+    let temp_dir = tempfile::tempdir().map_err(|e| AppError::IoContext {
+        context: "Failed to create temp dir".to_string(),
+        source: e,
+    })?;
+    let archive_path = temp_dir.path().join(asset);
+    fs::write(&archive_path, &bytes).map_err(|e| AppError::io(&archive_path, e))?;
+    let new_binary = extract_binary(&archive_path, temp_dir.path())?;
+
+    replace_current_binary(&new_binary, &current.to_string(), cmd.keep_backup)?;
+    let record = SelfUpdateRecord {
+        from: current.to_string(),
+        to: target.to_string(),
+        updated_at: Utc::now().timestamp(),
+    };
+    if let Err(e) = write_json_file(&get_app_config_dir().join(LAST_UPDATE_FILE), &record) {
+        log::warn!("记录自更新信息失败: {e}");
+    }
+
+    out::success(&format!("✓ Updated cc-switch {current} -> {target}"));
+    if cmd.keep_backup {
+        out::info("Previous binary saved; run `cc-switch update rollback` to restore it.");
+    }
+    Ok(UpdateOutcome::Done)
+}
+
+fn http_client(timeout_secs: u64) -> Result<reqwest::Client, AppError> {
+    crate::http_client::ensure_online("update")?;
+    crate::http_client::client_builder()?
+        .timeout(std::time::Duration::from_secs(timeout_secs))
+        .build()
+        .map_err(|e| AppError::Message(format!("Failed to create HTTP client: {e}")))
+}
+
+/// 确定要比较的发布标签：显式指定的标签，否则取 `channel` 上最新的版本
+async fn resolve_target_tag(
+    client: &reqwest::Client,
+    host: &ReleaseHost,
+    requested: Option<&str>,
+    channel: UpdateChannel,
+) -> Result<String, AppError> {
+    if let Some(tag) = requested.map(str::trim).filter(|s| !s.is_empty()) {
+        return Ok(if tag.starts_with('v') {
+            tag.to_string()
+        } else {
+            format!("v{tag}")
+        });
+    }
+
+    if channel == UpdateChannel::Prerelease {
+        let releases: Vec<GithubRelease> =
+            fetch_releases(client, &host.releases_url("?per_page=100")).await?;
+        return highest_release_tag(&releases).ok_or_else(|| {
+            AppError::Message("No published releases found (prerelease channel)".to_string())
+        });
+    }
+
+    let release: GithubRelease = fetch_releases(client, &host.releases_url("/latest")).await?;
+    Ok(release.tag_name)
+}
+
+/// 请求 GitHub releases 接口并解析 JSON
+async fn fetch_releases<T: serde::de::DeserializeOwned>(
+    client: &reqwest::Client,
+    url: &str,
+) -> Result<T, AppError> {
+    let response = client
+        .get(url)
+        .header("Accept", "application/vnd.github+json")
+        .send()
+        .await
+        .map_err(|e| {
+            AppError::Message(format!(
+                "Failed to query latest release: {}",
+                crate::http_client::describe_error(&e)
+            ))
+        })?;
+
+    if !response.status().is_success() {
+        return Err(AppError::Message(format!(
+            "Failed to query latest release: HTTP {}",
+            response.status().as_u16()
+        )));
+    }
+
+    response
+        .json()
+        .await
+        .map_err(|e| AppError::Message(format!("Invalid release response: {e}")))
+}
+
+/// 已发布版本中 semver 最高者（含预发布版本），无法解析的标签跳过
+fn highest_release_tag(releases: &[GithubRelease]) -> Option<String> {
+    releases
+        .iter()
+        .filter(|release| !release.draft)
+        .filter_map(|release| {
+            parse_version(&release.tag_name)
+                .ok()
+                .map(|version| (version, &release.tag_name))
+        })
+        .max_by(|(a, _), (b, _)| a.cmp(b))
+        .map(|(_, tag)| tag.clone())
+}
+
+async fn download_asset(client: &reqwest::Client, url: &str) -> Result<Vec<u8>, AppError> {
+    let response = client.get(url).send().await.map_err(|e| {
+        AppError::Message(format!(
+            "Download failed: {}",
+            crate::http_client::describe_error(&e)
+        ))
+    })?;
+    if !response.status().is_success() {
+        return Err(AppError::Message(format!(
+            "Download failed: HTTP {} ({url})",
+            response.status().as_u16()
+        )));
+    }
+    let bytes = response
+        .bytes()
+        .await
+        .map_err(|e| AppError::Message(format!("Download failed: {e}")))?;
+    Ok(bytes.to_vec())
+}
+
+/// 按 `sha256sum` 格式的清单（`<hex>  <name>`，二进制模式为 `*<name>`）校验 `asset`；
+/// 清单中缺少该资产时报错
+fn verify_asset_checksum(sums: &str, asset: &str, bytes: &[u8]) -> Result<(), AppError> {
+    let expected = sums
+        .lines()
+        .filter_map(|line| line.split_once(char::is_whitespace))
+        .find(|(_, name)| name.trim().trim_start_matches('*') == asset)
+        .map(|(hash, _)| crate::util::hash::normalize_sha256(hash))
+        .transpose()?
+        .ok_or_else(|| {
+            AppError::localized(
+                "update.checksum_missing",
+                format!("{CHECKSUMS_ASSET} 中没有 {asset} 的校验值，已取消更新"),
+                format!("{CHECKSUMS_ASSET} has no entry for {asset}; update aborted"),
+            )
+        })?;
+    let actual = crate::util::hash::compute_sha256_hex(bytes);
+    if actual != expected {
+        return Err(AppError::localized(
+            "update.checksum_mismatch",
+            format!("{asset} 的 SHA-256 不匹配（期望 {expected}，实际 {actual}），已取消更新"),
+            format!(
+                "SHA-256 mismatch for {asset} (expected {expected}, got {actual}); update aborted"
+            ),
+        ));
+    }
+    Ok(())
+}
+
+/// 当前平台对应的发布资产（见 README 安装章节）
+fn release_asset_name() -> Result<&'static str, AppError> {
+    match (std::env::consts::OS, std::env::consts::ARCH) {
+        ("macos", _) => Ok("cc-switch-cli-darwin-universal.tar.gz"),
+        ("linux", "x86_64") => Ok("cc-switch-cli-linux-x64-musl.tar.gz"),
+        ("linux", "aarch64") => Ok("cc-switch-cli-linux-arm64-musl.tar.gz"),
+        ("windows", "x86_64") => Ok("cc-switch-cli-windows-x64.zip"),
+        (os, arch) => Err(AppError::Message(format!(
+            "No prebuilt release for {os}/{arch}; please build from source."
+        ))),
+    }
+}
+
+fn binary_file_name() -> &'static str {
+    if cfg!(windows) {
+        "cc-switch.exe"
+    } else {
+        "cc-switch"
+    }
+}
+
+/// 将发布包解压到 `dest`，返回其中 cc-switch 二进制的路径
+fn extract_binary(archive: &Path, dest: &Path) -> Result<PathBuf, AppError> {
+    let out_dir = dest.join("extracted");
+    fs::create_dir_all(&out_dir).map_err(|e| AppError::io(&out_dir, e))?;
+
+    let kind = ArchiveKind::detect(archive)?;
+    archive::extract_file(archive, kind, &out_dir, RELEASE_ARCHIVE_LIMITS)?;
+
+    let binary = out_dir.join(binary_file_name());
+    if !binary.is_file() {
+        return Err(AppError::Message(format!(
+            "Release archive does not contain {}",
+            binary_file_name()
+        )));
+    }
+    Ok(binary)
+}
+
+/// 用 `new_binary` 替换正在运行的程序。
+///
+/// 旧二进制先移到 `<name>.old`，复制失败时可还原；`keep_backup` 时保留为
+/// `cc-switch.<version>.bak` 并记入备份清单，否则删除。
+fn replace_current_binary(
+    new_binary: &Path,
+    current_version: &str,
+    keep_backup: bool,
+) -> Result<(), AppError> {
+    let current_exe = std::env::current_exe().map_err(|e| AppError::IoContext {
+        context: "Failed to locate current executable".to_string(),
+        source: e,
+    })?;
+    let old = swap_binary(&current_exe, new_binary)?;
+
+    if keep_backup {
+        let backup = current_exe.with_file_name(format!("cc-switch.{current_version}.bak"));
+        crate::dry_run::rename(&old, &backup).map_err(|e| AppError::io(&backup, e))?;
+
+        let mut manifest = load_backup_manifest()?;
+        manifest.backups.retain(|b| b.path != backup);
+        manifest.backups.push(BinaryBackup {
+            version: current_version.to_string(),
+            path: backup,
+            saved_at: Utc::now().timestamp(),
+        });
+        save_backup_manifest(&manifest)?;
+    } else if let Err(e) = crate::dry_run::remove_file(&old) {
+        // Windows 无法删除运行中的程序，留待下次运行清理
+        log::warn!("删除旧版本二进制失败 {}: {e}", old.display());
+    }
+    Ok(())
+}
+
+/// 将 `target` 移到 `<name>.old`，再把 `replacement` 复制到原位置
+fn swap_binary(target: &Path, replacement: &Path) -> Result<PathBuf, AppError> {
+    let file_name = target
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| binary_file_name().to_string());
+    let old = target.with_file_name(format!("{file_name}.old"));
+    if old.exists() {
+        crate::dry_run::remove_file(&old).map_err(|e| AppError::io(&old, e))?;
+    }
+
+    crate::dry_run::rename(target, &old).map_err(|e| AppError::io(target, e))?;
+    if let Err(e) = crate::dry_run::copy(replacement, target) {
+        let _ = crate::dry_run::rename(&old, target);
+        return Err(AppError::io(target, e));
+    }
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        if !crate::dry_run::skip(format_args!("set mode 755 on {}", target.display())) {
+            fs::set_permissions(target, fs::Permissions::from_mode(0o755))
+                .map_err(|e| AppError::io(target, e))?;
+        }
+    }
+
+    Ok(old)
+}
+
+fn backup_manifest_path() -> PathBuf {
+    get_app_config_dir().join(BACKUP_MANIFEST_FILE)
+}
+
+fn load_backup_manifest() -> Result<BackupManifest, AppError> {
+    let path = backup_manifest_path();
+    if !path.exists() {
+        return Ok(BackupManifest::default());
+    }
+    read_json_file(&path)
+}
+
+fn save_backup_manifest(manifest: &BackupManifest) -> Result<(), AppError> {
+    write_json_file(&backup_manifest_path(), manifest)
+}
+
+/// 最近一次自更新记录（仅当它产生的正是当前运行的二进制时返回）
+pub fn last_self_update() -> Option<SelfUpdateRecord> {
+    let path = get_app_config_dir().join(LAST_UPDATE_FILE);
+    if !path.exists() {
+        return None;
+    }
+    let record: SelfUpdateRecord = read_json_file(&path).ok()?;
+    let current = parse_version(CURRENT_VERSION).ok()?;
+    (parse_version(&record.to).ok()? == current).then_some(record)
+}
+
+fn rollback() -> Result<(), AppError> {
+    let mut manifest = load_backup_manifest()?;
+    // 丢弃备份文件已被手动删除的条目
+    manifest.backups.retain(|b| b.path.is_file());
+
+    let Some(backup) = manifest.backups.pop() else {
+        save_backup_manifest(&manifest)?;
+        return Err(AppError::Message(
+            "No saved previous version found. Use `update --keep-backup` next time.".to_string(),
+        ));
+    };
+
+    if crate::dry_run::skip(format_args!(
+        "roll back cc-switch {CURRENT_VERSION} -> {}",
+        backup.version
+    )) {
+        return Ok(());
+    }
+    let current_exe = std::env::current_exe().map_err(|e| AppError::IoContext {
+        context: "Failed to locate current executable".to_string(),
+        source: e,
+    })?;
+    let old = swap_binary(&current_exe, &backup.path)?;
+    if let Err(e) = crate::dry_run::remove_file(&old) {
+        log::warn!("删除旧版本二进制失败 {}: {e}", old.display());
+    }
+    if let Err(e) = crate::dry_run::remove_file(&backup.path) {
+        log::warn!("删除备份文件失败 {}: {e}", backup.path.display());
+    }
+    save_backup_manifest(&manifest)?;
+
+    out::success(&format!(
+        "✓ Rolled back cc-switch {CURRENT_VERSION} -> {}",
+        backup.version
+    ));
+    Ok(())
+}
+
+/// 将发布标签（`v4.6.3`、`4.6.3-rc1`）解析为语义化版本
+fn parse_version(tag: &str) -> Result<semver::Version, AppError> {
+    let raw = tag.trim();
+    let raw = raw.strip_prefix('v').unwrap_or(raw);
+    semver::Version::parse(raw)
+        .map_err(|e| AppError::InvalidInput(format!("Invalid version '{tag}': {e}")))
+}
+
+/// 按 semver 优先级比较（预发布版本排在正式版之前）
+fn compare_versions(current: &semver::Version, target: &semver::Version) -> UpdateDecision {
+    match target.cmp(current) {
+        std::cmp::Ordering::Greater => UpdateDecision::Newer,
+        std::cmp::Ordering::Equal => UpdateDecision::UpToDate,
+        std::cmp::Ordering::Less => UpdateDecision::Older,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn update_outcome_maps_to_exit_code() {
+        assert_eq!(UpdateOutcome::Done.exit_code(), 0);
+        assert_eq!(
+            UpdateOutcome::UpdateAvailable.exit_code(),
+            UPDATE_AVAILABLE_EXIT_CODE
+        );
+    }
+
+    fn decide(current: &str, target: &str) -> UpdateDecision {
+        compare_versions(
+            &parse_version(current).expect("current"),
+            &parse_version(target).expect("target"),
+        )
+    }
+
+    #[test]
+    fn compare_versions_uses_semver_precedence() {
+        assert_eq!(decide("4.6.2", "v4.6.3"), UpdateDecision::Newer);
+        assert_eq!(decide("4.6.2", "v4.6.2"), UpdateDecision::UpToDate);
+        assert_eq!(decide("4.6.10", "4.6.9"), UpdateDecision::Older);
+        assert_eq!(decide("4.6.2", "v4.6.3-rc1"), UpdateDecision::Newer);
+        assert_eq!(decide("4.6.3", "v4.6.3-rc1"), UpdateDecision::Older);
+        assert_eq!(decide("4.6.3-rc1", "v4.6.3-rc2"), UpdateDecision::Newer);
+    }
+
+    #[test]
+    fn highest_release_tag_includes_prereleases_and_skips_drafts() {
+        let release = |tag: &str, draft: bool| GithubRelease {
+            tag_name: tag.to_string(),
+            draft,
+        };
+        let releases = vec![
+            release("v4.6.2", false),
+            release("v4.7.0-rc1", false),
+            release("v4.7.0-rc2", true),
+            release("nightly", false),
+            release("v4.6.10", false),
+        ];
+        assert_eq!(
+            highest_release_tag(&releases).as_deref(),
+            Some("v4.7.0-rc1")
+        );
+        assert_eq!(highest_release_tag(&[]), None);
+    }
+
+    #[test]
+    fn release_host_defaults_to_github_and_maps_enterprise_api() {
+        let default = ReleaseHost::from_base(None);
+        assert_eq!(
+            default.releases_url("/latest"),
+            format!("https://api.github.com/repos/{RELEASE_REPO}/releases/latest")
+        );
+        assert_eq!(ReleaseHost::from_base(Some("https://github.com")), default);
+
+        let base = parse_release_host("https://ghe.example.com/mirror/").expect("valid host");
+        assert_eq!(base, "https://ghe.example.com/mirror");
+        let enterprise = ReleaseHost::from_base(Some(&base));
+        assert_eq!(
+            enterprise.releases_url("/latest"),
+            format!("https://ghe.example.com/mirror/api/v3/repos/{RELEASE_REPO}/releases/latest")
+        );
+        assert_eq!(
+            enterprise.asset_url("v1.0.0", "a.tar.gz"),
+            format!(
+                "https://ghe.example.com/mirror/{RELEASE_REPO}/releases/download/v1.0.0/a.tar.gz"
+            )
+        );
+
+        assert!(parse_release_host("ftp://mirror.local").is_err());
+        assert!(parse_release_host("http://mirror.local").is_err());
+        assert!(parse_release_host("https://mirror.local/?x=1").is_err());
+    }
+
+    #[test]
+    fn verify_asset_checksum_requires_matching_entry() {
+        let asset = "cc-switch-cli-linux-x64-musl.tar.gz";
+        let hash = crate::util::hash::compute_sha256_hex(b"binary");
+        let sums = format!("{}  other.zip\n{hash} *{asset}\n", "0".repeat(64));
+
+        verify_asset_checksum(&sums, asset, b"binary").expect("matching checksum");
+        let err = verify_asset_checksum(&sums, asset, b"tampered").unwrap_err();
+        assert_eq!(err.code(), "UPDATE_CHECKSUM_MISMATCH");
+        let err = verify_asset_checksum(&sums, "missing.zip", b"binary").unwrap_err();
+        assert_eq!(err.code(), "UPDATE_CHECKSUM_MISSING");
+    }
+
+    #[test]
+    fn swap_binary_restores_target_when_copy_fails() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let target = dir.path().join("cc-switch");
+        fs::write(&target, b"current").expect("write target");
+
+        let missing = dir.path().join("does-not-exist");
+        swap_binary(&target, &missing).expect_err("copy of a missing file fails");
+        assert_eq!(fs::read(&target).expect("target restored"), b"current");
+        assert!(!dir.path().join("cc-switch.old").exists());
+
+        let replacement = dir.path().join("new");
+        fs::write(&replacement, b"new").expect("write replacement");
+        let old = swap_binary(&target, &replacement).expect("swap");
+        assert_eq!(fs::read(&target).expect("target"), b"new");
+        assert_eq!(fs::read(&old).expect("old kept aside"), b"current");
+    }
+
+    #[test]
+    fn parse_version_rejects_odd_tags() {
+        assert!(parse_version("latest").is_err());
+        assert!(parse_version("v4.6").is_err());
+    }
+}
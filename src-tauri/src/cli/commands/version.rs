@@ -0,0 +1,53 @@
+use serde::Serialize;
+
+use crate::cli::commands::update::{last_self_update, SelfUpdateRecord};
+use crate::cli::ui::{emit, json_output};
+use crate::error::AppError;
+
+/// 构建期由 build.rs 注入的元数据
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BuildInfo {
+    pub version: &'static str,
+    pub git_commit: &'static str,
+    pub build_date: &'static str,
+    pub rustc_version: &'static str,
+    pub target: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub self_update: Option<SelfUpdateRecord>,
+}
+
+impl BuildInfo {
+    pub fn current() -> Self {
+        Self {
+            version: env!("CARGO_PKG_VERSION"),
+            git_commit: env!("CC_SWITCH_GIT_HASH"),
+            build_date: env!("CC_SWITCH_BUILD_DATE"),
+            rustc_version: env!("CC_SWITCH_RUSTC_VERSION"),
+            target: env!("CC_SWITCH_TARGET"),
+            self_update: last_self_update(),
+        }
+    }
+}
+
+pub fn execute() -> Result<(), AppError> {
+    let info = BuildInfo::current();
+
+    if json_output() {
+        emit(&info)?;
+        return Ok(());
+    }
+
+    println!("cc-switch {}", info.version);
+    println!("Commit:      {}", info.git_commit);
+    println!("Build date:  {}", info.build_date);
+    println!("Rustc:       {}", info.rustc_version);
+    println!("Target:      {}", info.target);
+    if let Some(update) = &info.self_update {
+        let when = chrono::DateTime::from_timestamp(update.updated_at, 0)
+            .map(|t| t.format("%Y-%m-%d %H:%M UTC").to_string())
+            .unwrap_or_else(|| update.updated_at.to_string());
+        println!("Self-update: {} -> {} ({when})", update.from, update.to);
+    }
+    Ok(())
+}
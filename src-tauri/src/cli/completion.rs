@@ -0,0 +1,167 @@
+//! 动态补全：隐藏命令 `complete-dynamic <kind>` 输出候选项，生成的补全脚本在
+//! 特定参数位置调用它（bash / zsh / fish；其他 shell 只有静态补全）
+
+use clap_complete::Shell;
+
+use crate::error::AppError;
+use crate::services::SkillService;
+
+/// `complete-dynamic` 支持的候选类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum CompletionKind {
+    /// 已安装 Skill 的目录名
+    Skills,
+    /// 已配置的 Skills 仓库（owner/name）
+    SkillRepos,
+}
+
+impl CompletionKind {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::Skills => "skills",
+            Self::SkillRepos => "skill-repos",
+        }
+    }
+}
+
+/// 需要动态补全的命令路径（最后一个词之后的第一个位置参数）
+const TARGETS: &[(&[&str], CompletionKind)] = &[
+    (&["skills", "uninstall"], CompletionKind::Skills),
+    (&["skills", "enable"], CompletionKind::Skills),
+    (&["skills", "disable"], CompletionKind::Skills),
+    (&["skills", "info"], CompletionKind::Skills),
+    (&["skills", "repos", "remove"], CompletionKind::SkillRepos),
+    (&["skills", "repos", "enable"], CompletionKind::SkillRepos),
+    (&["skills", "repos", "disable"], CompletionKind::SkillRepos),
+    (&["skills", "repo", "remove"], CompletionKind::SkillRepos),
+    (&["skills", "repo", "enable"], CompletionKind::SkillRepos),
+    (&["skills", "repo", "disable"], CompletionKind::SkillRepos),
+];
+
+pub fn candidates(kind: CompletionKind) -> Result<Vec<String>, AppError> {
+    Ok(match kind {
+        CompletionKind::Skills => SkillService::list_installed()?
+            .into_iter()
+            .map(|skill| skill.directory)
+            .collect(),
+        CompletionKind::SkillRepos => SkillService::list_repos()?
+            .into_iter()
+            .map(|repo| format!("{}/{}", repo.owner, repo.name))
+            .collect(),
+    })
+}
+
+/// `cc-switch complete-dynamic <kind>`：每行一个候选项
+pub fn print_candidates(kind: CompletionKind) -> Result<(), AppError> {
+    for candidate in candidates(kind)? {
+        println!("{candidate}");
+    }
+    Ok(())
+}
+
+/// 追加在 clap 生成的静态脚本之后的动态补全片段
+pub fn dynamic_script(shell: Shell, bin: &str) -> Option<String> {
+    match shell {
+        Shell::Bash => Some(bash_script(bin)),
+        Shell::Zsh => Some(zsh_script(bin)),
+        Shell::Fish => Some(fish_script(bin)),
+        _ => None,
+    }
+}
+
+/// bash / zsh 共用的 case 分支：匹配光标前的词以命令路径结尾
+fn case_arms() -> String {
+    TARGETS
+        .iter()
+        .map(|(path, kind)| {
+            format!(
+                "        *\" {} \") kind=\"{}\" ;;\n",
+                path.join(" "),
+                kind.as_str()
+            )
+        })
+        .collect()
+}
+
+fn bash_script(bin: &str) -> String {
+    format!(
+        r#"
+__cc_switch_dynamic() {{
+    local cur="${{COMP_WORDS[COMP_CWORD]}}" kind=""
+    case " ${{COMP_WORDS[*]:1:COMP_CWORD-1}} " in
+{arms}    esac
+    if [[ -n "$kind" && "$cur" != -* ]]; then
+        COMPREPLY=( $(compgen -W "$({bin} complete-dynamic "$kind" 2>/dev/null)" -- "$cur") )
+        return 0
+    fi
+    _{bin} "$@"
+}}
+complete -F __cc_switch_dynamic -o bashdefault -o default {bin}
+"#,
+        arms = case_arms(),
+    )
+}
+
+fn zsh_script(bin: &str) -> String {
+    format!(
+        r#"
+__cc_switch_dynamic() {{
+    local kind=""
+    case " ${{words[2,CURRENT-1]}} " in
+{arms}    esac
+    if [[ -n "$kind" && "$PREFIX" != -* ]]; then
+        local -a items
+        items=(${{(f)"$({bin} complete-dynamic "$kind" 2>/dev/null)"}})
+        compadd -a items
+        return
+    fi
+    _{bin} "$@"
+}}
+compdef __cc_switch_dynamic {bin}
+"#,
+        arms = case_arms(),
+    )
+}
+
+fn fish_script(bin: &str) -> String {
+    let mut script = String::from("\n");
+    for (path, kind) in TARGETS {
+        let mut condition: Vec<String> = path
+            .iter()
+            .map(|word| format!("__fish_seen_subcommand_from {word}"))
+            .collect();
+        if path.len() == 2 {
+            // `skills enable` 与 `skills repos enable` 同名，排除后者
+            condition.push("not __fish_seen_subcommand_from repos repo".to_string());
+        }
+        script.push_str(&format!(
+            "complete -c {bin} -n '{}' -f -a '({bin} complete-dynamic {} 2>/dev/null)'\n",
+            condition.join("; and "),
+            kind.as_str()
+        ));
+    }
+    script
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bash_script_routes_skill_positions_to_complete_command() {
+        let script = dynamic_script(Shell::Bash, "cc-switch").expect("bash script");
+        assert!(script.contains(r#"*" skills uninstall ") kind="skills" ;;"#));
+        assert!(script.contains(r#"*" skills repo remove ") kind="skill-repos" ;;"#));
+        assert!(script.contains("_cc-switch \"$@\""));
+        assert!(dynamic_script(Shell::PowerShell, "cc-switch").is_none());
+    }
+
+    #[test]
+    fn fish_script_excludes_repo_subcommands_for_skill_targets() {
+        let script = dynamic_script(Shell::Fish, "cc-switch").expect("fish script");
+        assert!(script.contains(
+            "__fish_seen_subcommand_from skills; and __fish_seen_subcommand_from enable; and not __fish_seen_subcommand_from repos repo"
+        ));
+        assert!(script.contains("(cc-switch complete-dynamic skill-repos 2>/dev/null)"));
+    }
+}
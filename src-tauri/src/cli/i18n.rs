@@ -543,9 +543,9 @@ pub mod texts {
 
     pub fn tui_help_text() -> &'static str {
         if is_chinese() {
-            "[ ]  切换应用\n←→  切换菜单/内容焦点\n↑↓  移动\n/   过滤\nEsc  返回\n?   显示/关闭帮助\n\n页面快捷键（在页面内容区顶部显示）：\n- Providers: Enter 详情，s 切换，a 添加，e 编辑，d 删除，t 测速\n- Provider Detail: s 切换，e 编辑，t 测速\n- MCP: x 启用/禁用(当前应用)，m 选择应用，a 添加，e 编辑，i 导入，v 校验命令，d 删除\n- Prompts: Enter 查看，a 激活，x 取消激活(当前)，e 编辑，d 删除\n- Skills: Enter 详情，x 启用/禁用(当前应用)，a 安装，d 卸载，f 发现，u 未管理，r 仓库，s 同步，m 同步方式\n- Config: Enter 打开/执行，e 编辑片段\n- Settings: Enter 应用"
+            "[ ]  切换应用\n←→  切换菜单/内容焦点\n↑↓  移动\n/   过滤\nCtrl+F  全局搜索（供应商/MCP/技能/提示词）\nEsc  返回\n?   显示/关闭帮助\n\n页面快捷键（在页面内容区顶部显示）：\n- Providers: Enter 详情，s 切换，a 添加，e 编辑，d 删除，t 测速\n- Provider Detail: s 切换，e 编辑，t 测速\n- MCP: x 启用/禁用(当前应用)，m 选择应用，a 添加，e 编辑，i 导入，v 校验命令，d 删除\n- Prompts: Enter 查看，a 激活，x 取消激活(当前)，e 编辑，d 删除\n- Skills: Enter 详情，x 启用/禁用(当前应用)，a 安装，d 卸载，f 发现，u 未管理，r 仓库，s 同步，m 同步方式\n- Config: Enter 打开/执行，e 编辑片段\n- Settings: Enter 应用"
         } else {
-            "[ ]  switch app\n←→  focus menu/content\n↑↓  move\n/   filter\nEsc  back\n?   toggle help\n\nPage keys (shown at the top of each page):\n- Providers: Enter details, s switch, a add, e edit, d delete, t speedtest\n- Provider Detail: s switch, e edit, t speedtest\n- MCP: x toggle current, m select apps, a add, e edit, i import, v validate, d delete\n- Prompts: Enter view, a activate, x deactivate active, e edit, d delete\n- Skills: Enter details, x toggle current, a install, d uninstall, f discover, u unmanaged, r repos, s sync, m sync method\n- Config: Enter open/run, e edit snippet\n- Settings: Enter apply"
+            "[ ]  switch app\n←→  focus menu/content\n↑↓  move\n/   filter\nCtrl+F  global search (providers/MCP/skills/prompts)\nEsc  back\n?   toggle help\n\nPage keys (shown at the top of each page):\n- Providers: Enter details, s switch, a add, e edit, d delete, t speedtest\n- Provider Detail: s switch, e edit, t speedtest\n- MCP: x toggle current, m select apps, a add, e edit, i import, v validate, d delete\n- Prompts: Enter view, a activate, x deactivate active, e edit, d delete\n- Skills: Enter details, x toggle current, a install, d uninstall, f discover, u unmanaged, r repos, s sync, m sync method\n- Config: Enter open/run, e edit snippet\n- Settings: Enter apply"
         }
     }
 
@@ -1766,6 +1766,14 @@ pub mod texts {
         }
     }
 
+    pub fn tui_search_title() -> &'static str {
+        if is_chinese() {
+            "全局搜索（Enter 切换/启用）"
+        } else {
+            "Global Search (Enter to switch/toggle)"
+        }
+    }
+
     pub fn tui_backup_picker_title() -> &'static str {
         if is_chinese() {
             "选择备份（Enter 恢复）"
@@ -2737,6 +2745,31 @@ pub mod texts {
         }
     }
 
+    // Legacy interactive menu item (not used in ratatui TUI navigation).
+    pub fn menu_search_all() -> &'static str {
+        if is_chinese() {
+            "🔍 全局搜索"
+        } else {
+            "🔍 Search Everything"
+        }
+    }
+
+    pub fn search_all_prompt() -> &'static str {
+        if is_chinese() {
+            "搜索供应商 / MCP / 技能 / 提示词（输入以过滤）:"
+        } else {
+            "Search providers / MCP / skills / prompts (type to filter):"
+        }
+    }
+
+    pub fn search_all_empty() -> &'static str {
+        if is_chinese() {
+            "没有可搜索的资源。"
+        } else {
+            "Nothing to search yet."
+        }
+    }
+
     // Legacy interactive menu item (not used in ratatui TUI navigation).
     pub fn menu_view_config() -> &'static str {
         if is_chinese() {
@@ -3144,6 +3177,22 @@ pub mod texts {
         }
     }
 
+    pub fn current_marker() -> &'static str {
+        if is_chinese() {
+            "当前"
+        } else {
+            "current"
+        }
+    }
+
+    pub fn already_current_provider(id: &str) -> String {
+        if is_chinese() {
+            format!("'{}' 已是当前供应商。", id)
+        } else {
+            format!("'{}' is already the current provider.", id)
+        }
+    }
+
     pub fn switched_to_provider(id: &str) -> String {
         if is_chinese() {
             format!("✓ 已切换到供应商 '{}'", id)
@@ -3232,6 +3281,14 @@ pub mod texts {
         }
     }
 
+    pub fn no_backups_tagged(tag: &str) -> String {
+        if is_chinese() {
+            format!("没有带标签 '{tag}' 的备份。")
+        } else {
+            format!("No backups tagged '{tag}'.")
+        }
+    }
+
     pub fn create_backup_first_hint() -> &'static str {
         if is_chinese() {
             "请先创建备份：cc-switch config backup"
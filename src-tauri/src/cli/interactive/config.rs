@@ -395,10 +395,7 @@ fn restore_config_interactive() -> Result<(), AppError> {
     println!("\n{}", texts::found_backups(backups.len()));
     println!();
 
-    let choices: Vec<String> = backups
-        .iter()
-        .map(|b| format!("{} - {}", b.display_name, b.id))
-        .collect();
+    let choices: Vec<String> = backups.iter().map(|b| b.label()).collect();
 
     let Some(selection) = prompt_select(texts::select_backup_to_restore(), choices)? else {
         return Ok(());
@@ -500,7 +497,7 @@ fn reset_config_interactive() -> Result<(), AppError> {
     let backup_id = ConfigService::create_backup(&db_path, None)?;
 
     if db_path.exists() {
-        std::fs::remove_file(&db_path).map_err(|e| AppError::io(&db_path, e))?;
+        crate::dry_run::remove_file(&db_path).map_err(|e| AppError::io(&db_path, e))?;
     }
 
     let _ = crate::Database::init()?;
@@ -10,7 +10,7 @@ use super::utils::{
     app_switch_direction_from_key, clear_screen, cycle_app_type, pause, prompt_select,
     prompt_text_with_default,
 };
-use super::{config, mcp, prompts, provider, settings, skills};
+use super::{config, mcp, prompts, provider, search, settings, skills};
 
 pub fn run(app: Option<AppType>) -> Result<(), AppError> {
     if !std::io::stdin().is_terminal() || !std::io::stdout().is_terminal() {
@@ -57,6 +57,12 @@ pub fn run(app: Option<AppType>) -> Result<(), AppError> {
                     pause();
                 }
             }
+            MainMenuChoice::Search => {
+                if let Err(e) = search::search_menu(&app_type) {
+                    println!("\n{}", error(&format!("{}: {}", texts::error_prefix(), e)));
+                    pause();
+                }
+            }
             MainMenuChoice::ViewCurrentConfig => {
                 if let Err(e) = view_current_config(&app_type) {
                     println!("\n{}", error(&format!("{}: {}", texts::error_prefix(), e)));
@@ -93,6 +99,7 @@ enum MainMenuChoice {
     ManagePrompts,
     ManageConfig,
     ManageSkills,
+    Search,
     ViewCurrentConfig,
     SwitchApp,
     Settings,
@@ -107,6 +114,7 @@ impl std::fmt::Display for MainMenuChoice {
             Self::ManagePrompts => write!(f, "{}", texts::menu_manage_prompts()),
             Self::ManageConfig => write!(f, "{}", texts::menu_manage_config()),
             Self::ManageSkills => write!(f, "{}", texts::menu_manage_skills()),
+            Self::Search => write!(f, "{}", texts::menu_search_all()),
             Self::ViewCurrentConfig => write!(f, "{}", texts::menu_view_config()),
             Self::SwitchApp => write!(f, "{}", texts::menu_switch_app()),
             Self::Settings => write!(f, "{}", texts::menu_settings()),
@@ -136,6 +144,7 @@ fn show_main_menu(app_type: &mut AppType) -> Result<MainMenuChoice, AppError> {
         MainMenuChoice::ManagePrompts,
         MainMenuChoice::ManageConfig,
         MainMenuChoice::ManageSkills,
+        MainMenuChoice::Search,
         MainMenuChoice::ViewCurrentConfig,
         MainMenuChoice::SwitchApp,
         MainMenuChoice::Settings,
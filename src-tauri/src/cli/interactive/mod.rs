@@ -2,6 +2,7 @@ mod config;
 mod mcp;
 mod prompts;
 mod provider;
+pub(crate) mod search;
 mod settings;
 mod skills;
 mod utils;
@@ -1,8 +1,9 @@
 use indexmap::IndexMap;
 
 use crate::app_config::AppType;
+use crate::cli::commands::provider::{print_switch_hook_output, sort_providers};
 use crate::cli::i18n::texts;
-use crate::cli::ui::{create_table, error, highlight, info, success, warning};
+use crate::cli::ui::{create_table, display_secret, error, highlight, info, success, warning};
 use crate::error::AppError;
 use crate::services::{ProviderService, SpeedtestService};
 use crate::store::AppState;
@@ -26,12 +27,7 @@ pub fn manage_providers_menu(app_type: &AppType) -> Result<(), AppError> {
             table.set_header(vec!["", texts::header_name(), "API URL"]);
 
             let mut provider_list: Vec<_> = providers.iter().collect();
-            provider_list.sort_by(|(_, a), (_, b)| match (a.sort_index, b.sort_index) {
-                (Some(idx_a), Some(idx_b)) => idx_a.cmp(&idx_b),
-                (Some(_), None) => std::cmp::Ordering::Less,
-                (None, Some(_)) => std::cmp::Ordering::Greater,
-                (None, None) => a.created_at.cmp(&b.created_at),
-            });
+            sort_providers(&mut provider_list, None);
 
             for (id, provider) in &provider_list {
                 let marker = if *id == &current_id {
@@ -282,14 +278,13 @@ fn switch_provider_interactive(
         return Ok(());
     }
 
-    let mut provider_choices: Vec<_> = providers
-        .iter()
-        .filter(|(id, _)| *id != current_id)
-        .map(|(id, p)| format!("{} ({})", p.name, id))
-        .collect();
-    provider_choices.sort();
+    let history = state
+        .db
+        .get_provider_switch_history(app_type.as_str())
+        .unwrap_or_default();
+    let provider_choices = switch_choices(providers, current_id, &history);
 
-    if provider_choices.is_empty() {
+    if provider_choices.iter().all(|choice| choice.current) {
         println!("\n{}", info(texts::no_other_providers()));
         pause();
         return Ok(());
@@ -298,15 +293,15 @@ fn switch_provider_interactive(
     let Some(choice) = prompt_select(texts::select_provider_to_switch(), provider_choices)? else {
         return Ok(());
     };
-
-    let id = choice
-        .split('(')
-        .nth(1)
-        .and_then(|s| s.strip_suffix(')'))
-        .ok_or_else(|| AppError::Message(texts::invalid_selection_format().to_string()))?;
+    if choice.current {
+        println!("\n{}", info(&texts::already_current_provider(&choice.id)));
+        pause();
+        return Ok(());
+    }
+    let id = choice.id.as_str();
 
     let skip_live_sync = !crate::sync_policy::should_sync_live(app_type);
-    ProviderService::switch(state, app_type.clone(), id)?;
+    let hook_output = ProviderService::switch_with_hook(state, app_type.clone(), id, true)?;
 
     println!("\n{}", success(&texts::switched_to_provider(id)));
     if skip_live_sync {
@@ -317,6 +312,9 @@ fn switch_provider_interactive(
             ))
         );
     }
+    if let Some(hook_output) = hook_output {
+        print_switch_hook_output(&hook_output);
+    }
     println!("{}", info(texts::restart_note()));
     pause();
 
@@ -372,7 +370,12 @@ fn delete_provider_interactive(
 fn add_provider_interactive(app_type: &AppType) -> Result<(), AppError> {
     // 调用命令层的实现
     crate::cli::commands::provider::execute(
-        crate::cli::commands::provider::ProviderCommand::Add,
+        crate::cli::commands::provider::ProviderCommand::Add {
+            name: None,
+            endpoint: None,
+            api_key: None,
+            model: None,
+        },
         Some(app_type.clone()),
     )?;
 
@@ -380,6 +383,55 @@ fn add_provider_interactive(app_type: &AppType) -> Result<(), AppError> {
     Ok(())
 }
 
+/// Provider entry in the switch picker
+#[derive(Debug, Clone)]
+struct SwitchChoice {
+    id: String,
+    name: String,
+    current: bool,
+}
+
+impl std::fmt::Display for SwitchChoice {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} ({})", self.name, self.id)?;
+        if self.current {
+            write!(f, " ✓ {}", texts::current_marker())?;
+        }
+        Ok(())
+    }
+}
+
+/// Most recently used first (so Enter switches back to the previous provider), then
+/// alphabetical for providers without history; the current provider is listed last.
+fn switch_choices(
+    providers: &IndexMap<String, crate::provider::Provider>,
+    current_id: &str,
+    history: &[String],
+) -> Vec<SwitchChoice> {
+    let rank = |id: &str| {
+        history
+            .iter()
+            .position(|used| used == id)
+            .unwrap_or(usize::MAX)
+    };
+    let mut choices: Vec<SwitchChoice> = providers
+        .iter()
+        .map(|(id, p)| SwitchChoice {
+            id: id.clone(),
+            name: p.name.clone(),
+            current: id == current_id,
+        })
+        .collect();
+    choices.sort_by(|a, b| {
+        a.current
+            .cmp(&b.current)
+            .then_with(|| rank(&a.id).cmp(&rank(&b.id)))
+            .then_with(|| a.name.to_lowercase().cmp(&b.name.to_lowercase()))
+            .then_with(|| a.id.cmp(&b.id))
+    });
+    choices
+}
+
 /// Edit mode choices for provider editing
 #[derive(Debug, Clone)]
 enum EditMode {
@@ -426,12 +478,7 @@ fn edit_provider_interactive(
 
     // 1. 显示供应商列表让用户选择
     let mut provider_list: Vec<_> = providers.iter().collect();
-    provider_list.sort_by(|(_, a), (_, b)| match (a.sort_index, b.sort_index) {
-        (Some(idx_a), Some(idx_b)) => idx_a.cmp(&idx_b),
-        (Some(_), None) => std::cmp::Ordering::Less,
-        (None, Some(_)) => std::cmp::Ordering::Greater,
-        (None, None) => a.created_at.cmp(&b.created_at),
-    });
+    sort_providers(&mut provider_list, None);
 
     // 2. 使用 ID 列表配对，避免字符串匹配的潜在 bug
     let choices: Vec<String> = provider_list
@@ -659,9 +706,12 @@ fn edit_provider_with_json_editor(
 
         // 8. Immediately sync to live config files
         println!("\n{}", info("Syncing to live config files..."));
-        ProviderService::switch(&state, app_type.clone(), id)?;
+        let hook_output = ProviderService::switch_with_hook(&state, app_type.clone(), id, true)?;
 
         println!("{}", success("✓ Changes synced to live config files"));
+        if let Some(hook_output) = hook_output {
+            print_switch_hook_output(&hook_output);
+        }
         println!("{}", info(texts::restart_note()));
 
         break;
@@ -725,7 +775,7 @@ fn extract_claude_config(settings_config: &serde_json::Value) -> ClaudeConfig {
                 .get("ANTHROPIC_AUTH_TOKEN")
                 .or_else(|| env.get("ANTHROPIC_API_KEY"))
                 .and_then(|v| v.as_str())
-                .map(|s| mask_api_key(s)),
+                .map(display_secret),
             base_url: env
                 .get("ANTHROPIC_BASE_URL")
                 .and_then(|v| v.as_str())
@@ -752,15 +802,6 @@ fn extract_claude_config(settings_config: &serde_json::Value) -> ClaudeConfig {
     }
 }
 
-/// 将 API Key 脱敏显示（显示前8位 + ...）
-fn mask_api_key(key: &str) -> String {
-    if key.len() > 8 {
-        format!("{}...", &key[..8])
-    } else {
-        key.to_string()
-    }
-}
-
 /// 简化模型名称（去掉日期后缀）
 /// 例如：claude-3-5-sonnet-20241022 -> claude-3-5-sonnet
 fn simplify_model_name(name: &str) -> String {
@@ -773,3 +814,54 @@ fn simplify_model_name(name: &str) -> String {
     }
     name.to_string()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::provider::Provider;
+    use serde_json::json;
+
+    fn providers(names: &[(&str, &str)]) -> IndexMap<String, Provider> {
+        names
+            .iter()
+            .map(|(id, name)| {
+                (
+                    id.to_string(),
+                    Provider::with_id(id.to_string(), name.to_string(), json!({}), None),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn switch_choices_put_recent_first_and_current_last() {
+        let all = providers(&[
+            ("c", "Gamma"),
+            ("a", "Alpha"),
+            ("b", "Beta"),
+            ("d", "Delta"),
+        ]);
+        let history = vec!["b".to_string(), "d".to_string()];
+
+        let ids: Vec<_> = switch_choices(&all, "b", &history)
+            .into_iter()
+            .map(|c| (c.id, c.current))
+            .collect();
+        assert_eq!(
+            ids,
+            vec![
+                ("d".to_string(), false),
+                ("a".to_string(), false),
+                ("c".to_string(), false),
+                ("b".to_string(), true),
+            ]
+        );
+
+        // 无历史时按名称排序
+        let ids: Vec<_> = switch_choices(&all, "b", &[])
+            .into_iter()
+            .map(|c| c.id)
+            .collect();
+        assert_eq!(ids, vec!["a", "d", "c", "b"]);
+    }
+}
@@ -0,0 +1,183 @@
+use std::fmt;
+
+use crate::app_config::AppType;
+use crate::cli::i18n::texts;
+use crate::cli::ui::{info, success};
+use crate::error::AppError;
+use crate::services::{McpService, PromptService, ProviderService, SkillService};
+use crate::store::AppState;
+
+use super::utils::{get_state, pause, prompt_select};
+use crate::cli::commands::provider::print_switch_hook_output;
+
+/// 全局搜索条目的类型（决定选中后执行的动作）
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum SearchKind {
+    /// 切换到该供应商
+    Provider(AppType),
+    /// 切换当前应用的 MCP 启用状态
+    Mcp,
+    /// 切换当前应用的 Skill 启用状态
+    Skill,
+    /// 启用 / 禁用当前应用的提示词
+    Prompt,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct SearchItem {
+    pub kind: SearchKind,
+    pub id: String,
+    pub label: String,
+    /// 当前是否处于激活/启用状态
+    pub active: bool,
+}
+
+impl fmt::Display for SearchItem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let tag = match &self.kind {
+            SearchKind::Provider(app) => format!("provider:{}", app.as_str()),
+            SearchKind::Mcp => "mcp".to_string(),
+            SearchKind::Skill => "skill".to_string(),
+            SearchKind::Prompt => "prompt".to_string(),
+        };
+        let marker = if self.active { "✓" } else { " " };
+        if self.label == self.id {
+            write!(f, "[{marker}] [{tag}] {}", self.label)
+        } else {
+            write!(f, "[{marker}] [{tag}] {} — {}", self.label, self.id)
+        }
+    }
+}
+
+/// 收集所有应用的供应商，以及当前应用下的 MCP / Skills / 提示词
+pub(crate) fn build_searchable_index(
+    state: &AppState,
+    app_type: &AppType,
+) -> Result<Vec<SearchItem>, AppError> {
+    let mut items = Vec::new();
+
+    for app in [AppType::Claude, AppType::Codex, AppType::Gemini] {
+        let current = ProviderService::current(state, app.clone())?;
+        for (id, provider) in ProviderService::list(state, app.clone())? {
+            items.push(SearchItem {
+                kind: SearchKind::Provider(app.clone()),
+                active: id == current,
+                label: provider.name.clone(),
+                id,
+            });
+        }
+    }
+
+    let mut servers: Vec<_> = McpService::get_all_servers(state)?.into_values().collect();
+    servers.sort_by(|a, b| a.id.cmp(&b.id));
+    for server in servers {
+        items.push(SearchItem {
+            kind: SearchKind::Mcp,
+            active: server.apps.is_enabled_for(app_type),
+            label: server.name,
+            id: server.id,
+        });
+    }
+
+    for skill in SkillService::list_installed()? {
+        items.push(SearchItem {
+            kind: SearchKind::Skill,
+            active: skill.apps.is_enabled_for(app_type),
+            label: skill.name,
+            id: skill.directory,
+        });
+    }
+
+    let mut prompts: Vec<_> = PromptService::get_prompts(state, app_type.clone())?
+        .into_values()
+        .collect();
+    prompts.sort_by(|a, b| a.id.cmp(&b.id));
+    for prompt in prompts {
+        items.push(SearchItem {
+            kind: SearchKind::Prompt,
+            active: prompt.enabled,
+            label: prompt.name,
+            id: prompt.id,
+        });
+    }
+
+    Ok(items)
+}
+
+pub fn search_menu(app_type: &AppType) -> Result<(), AppError> {
+    let state = get_state()?;
+    let items = build_searchable_index(&state, app_type)?;
+    if items.is_empty() {
+        println!("\n{}", info(texts::search_all_empty()));
+        pause();
+        return Ok(());
+    }
+
+    let Some(item) = prompt_select(texts::search_all_prompt(), items)? else {
+        return Ok(());
+    };
+
+    let mut hook_output = None;
+    let message = match &item.kind {
+        SearchKind::Provider(app) => {
+            hook_output = ProviderService::switch_with_hook(&state, app.clone(), &item.id, true)?;
+            format!(
+                "{} ({})",
+                texts::switched_to_provider(&item.id),
+                app.as_str()
+            )
+        }
+        SearchKind::Mcp => {
+            McpService::toggle_app(&state, &item.id, app_type.clone(), !item.active)?;
+            toggled_message(&item, app_type)
+        }
+        SearchKind::Skill => {
+            SkillService::toggle_app(&item.id, app_type, !item.active)?;
+            toggled_message(&item, app_type)
+        }
+        SearchKind::Prompt => {
+            if item.active {
+                PromptService::disable_prompt(&state, app_type.clone(), &item.id)?;
+            } else {
+                PromptService::enable_prompt(&state, app_type.clone(), &item.id)?;
+            }
+            toggled_message(&item, app_type)
+        }
+    };
+
+    println!("\n{}", success(&message));
+    if let Some(hook_output) = hook_output {
+        print_switch_hook_output(&hook_output);
+    }
+    pause();
+    Ok(())
+}
+
+fn toggled_message(item: &SearchItem, app_type: &AppType) -> String {
+    let state = if item.active { "off" } else { "on" };
+    format!("{} ({}): {state}", item.label, app_type.as_str())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn search_item_display_includes_kind_and_id() {
+        let item = SearchItem {
+            kind: SearchKind::Provider(AppType::Codex),
+            id: "p1".to_string(),
+            label: "Provider One".to_string(),
+            active: true,
+        };
+        assert_eq!(item.to_string(), "[✓] [provider:codex] Provider One — p1");
+
+        let item = SearchItem {
+            kind: SearchKind::Mcp,
+            id: "fetch".to_string(),
+            label: "fetch".to_string(),
+            active: false,
+        };
+        assert_eq!(item.to_string(), "[ ] [mcp] fetch");
+    }
+}
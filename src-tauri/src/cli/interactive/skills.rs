@@ -1,8 +1,8 @@
 use std::fmt;
-use std::future::Future;
 
 use crate::app_config::AppType;
 use crate::cli::i18n::texts;
+use crate::cli::runtime::run_async;
 use crate::cli::ui::{create_table, error, highlight, info, success};
 use crate::error::AppError;
 use crate::services::skill::{SkillRepo, SkillService as SkillServiceType, SyncMethod};
@@ -12,14 +12,6 @@ use super::utils::{
     clear_screen, pause, prompt_confirm, prompt_multiselect, prompt_select, prompt_text,
 };
 
-fn run_async<T>(fut: impl Future<Output = Result<T, AppError>>) -> Result<T, AppError> {
-    tokio::runtime::Builder::new_current_thread()
-        .enable_all()
-        .build()
-        .map_err(|e| AppError::Message(format!("Failed to create runtime: {e}")))?
-        .block_on(fut)
-}
-
 #[derive(Clone)]
 struct DiscoverChoice {
     key: String,
@@ -577,9 +569,17 @@ fn add_repo() -> Result<(), AppError> {
     let Some(raw) = prompt_text(texts::skills_repo_enter_spec())? else {
         return Ok(());
     };
-    let repo = parse_repo_spec(&raw)?;
-    SkillServiceType::upsert_repo(repo)?;
-    println!("{}", success("✓ Repo added."));
+    let mut repo = parse_repo_spec(&raw)?;
+    if !raw.contains('@') {
+        // 未指定分支：留空交由 add_repo 探测默认分支
+        repo.branch.clear();
+    }
+    let service = SkillServiceType::new()?;
+    let repo = run_async(service.add_repo(repo))?;
+    println!(
+        "{}",
+        success(&format!("✓ Repo added (branch: {}).", repo.branch))
+    );
     pause();
     Ok(())
 }
@@ -653,5 +653,6 @@ fn parse_repo_spec(raw: &str) -> Result<SkillRepo, AppError> {
         name: name.to_string(),
         branch: branch.unwrap_or("main").to_string(),
         enabled: true,
+        token: None,
     })
 }
@@ -2,13 +2,16 @@ use clap::{Parser, Subcommand};
 use clap_complete::Shell;
 
 pub mod commands;
+pub mod completion;
 pub mod i18n;
 pub mod interactive;
+pub mod runtime;
 pub mod terminal;
 pub mod tui;
 pub mod ui;
 
 use crate::app_config::AppType;
+use crate::error::AppError;
 
 #[derive(Parser)]
 #[command(
@@ -18,18 +21,66 @@ use crate::app_config::AppType;
     long_about = "Unified management for Claude Code, Codex & Gemini CLI provider configurations, MCP servers, Skills extensions, and system prompts.\n\nRun without arguments to enter interactive mode."
 )]
 pub struct Cli {
-    /// Specify the application type
+    /// Specify the application type (`all` is accepted by `provider switch`)
     #[arg(short, long, global = true, value_enum)]
-    pub app: Option<AppType>,
+    pub app: Option<AppSelector>,
 
     /// Enable verbose output
     #[arg(short, long, global = true)]
     pub verbose: bool,
 
-    /// Output in JSON format
+    /// Output in JSON format (same as --format json)
     #[arg(short, long, global = true)]
     pub json: bool,
 
+    /// Output format for read commands: table, json or yaml (--json takes precedence)
+    #[arg(long, global = true, value_enum, value_name = "FORMAT")]
+    pub format: Option<crate::cli::ui::OutputFormat>,
+
+    /// Suppress informational output; errors still go to stderr
+    #[arg(short, long, global = true)]
+    pub quiet: bool,
+
+    /// Disable colored output (also honors the NO_COLOR env var)
+    #[arg(long, global = true)]
+    pub no_color: bool,
+
+    /// Show API keys and tokens in full instead of masking them
+    #[arg(long, global = true)]
+    pub reveal_secrets: bool,
+
+    /// Use an alternate config directory instead of ~/.cc-switch
+    /// (takes precedence over the CC_SWITCH_CONFIG_DIR env var)
+    #[arg(long, global = true, value_name = "PATH")]
+    pub config_dir: Option<std::path::PathBuf>,
+
+    /// Wait for another running cc-switch instance to finish instead of failing
+    #[arg(long, global = true)]
+    pub wait: bool,
+
+    /// Route outbound HTTP (skills, update) through this proxy (http, https or socks5 URL)
+    #[arg(long, global = true, value_name = "URL", conflicts_with = "no_proxy")]
+    pub proxy: Option<String>,
+
+    /// Connect directly, ignoring the proxy setting and proxy env vars
+    #[arg(long, global = true)]
+    pub no_proxy: bool,
+
+    /// Never touch the network: use cached skill repos, fail fast elsewhere
+    /// (also enabled by CC_SWITCH_OFFLINE=1)
+    #[arg(long, global = true)]
+    pub offline: bool,
+
+    /// Fail instead of prompting (selections, confirmations, text input);
+    /// the error names the flags to pass instead
+    #[arg(long, global = true)]
+    pub non_interactive: bool,
+
+    /// Preview every file, database and keychain write without performing it
+    /// (each is logged to stderr as "[dry-run] would ...")
+    #[arg(long, global = true)]
+    pub dry_run_all: bool,
+
     #[command(subcommand)]
     pub command: Option<Commands>,
 }
@@ -56,10 +107,28 @@ pub enum Commands {
     #[command(subcommand)]
     Config(commands::config::ConfigCommand),
 
+    /// Manage per-app settings (config directory overrides)
+    #[command(subcommand)]
+    App(commands::app::AppCommand),
+
+    /// View and edit stored settings (list, get, set)
+    #[command(subcommand)]
+    Settings(commands::settings::SettingsCommand),
+
     /// Manage environment variables
     #[command(subcommand)]
     Env(commands::env::EnvCommand),
 
+    /// Handle ccswitch:// deep links
+    #[command(subcommand)]
+    Deeplink(commands::deeplink::DeeplinkCommand),
+
+    /// Check for a newer cc-switch release
+    Update(commands::update::UpdateCommand),
+
+    /// Show version and build metadata (commit, build date, rustc, target)
+    Version,
+
     /// Enter interactive mode
     #[command(alias = "ui")]
     Interactive,
@@ -70,6 +139,140 @@ pub enum Commands {
         #[arg(value_enum)]
         shell: Shell,
     },
+
+    /// Print dynamic completion candidates (used by the generated completion scripts)
+    #[command(name = "complete-dynamic", hide = true)]
+    Complete {
+        #[arg(value_enum)]
+        kind: completion::CompletionKind,
+    },
+}
+
+/// `--app` 的取值：单个应用，或仅 `provider switch` 支持的 `all`
+#[derive(Debug, Clone, PartialEq, Eq, clap::ValueEnum)]
+pub enum AppSelector {
+    Claude,
+    Codex,
+    Gemini,
+    All,
+}
+
+impl AppSelector {
+    /// 单个应用；`all` 返回 None
+    pub fn app_type(&self) -> Option<AppType> {
+        match self {
+            Self::Claude => Some(AppType::Claude),
+            Self::Codex => Some(AppType::Codex),
+            Self::Gemini => Some(AppType::Gemini),
+            Self::All => None,
+        }
+    }
+}
+
+/// 不支持 `--app all` 的命令返回的错误
+pub fn app_all_unsupported() -> AppError {
+    AppError::localized(
+        "cli.app_all_unsupported",
+        "--app all 仅支持 `provider switch <名称>`",
+        "--app all is only supported by `provider switch <name>`",
+    )
+}
+
+impl Commands {
+    /// 不修改数据库 / live 配置的命令，无需获取跨进程实例锁
+    pub fn is_read_only(&self) -> bool {
+        use commands::app::{AppCommand, AppHookCommand, AppOverrideCommand, AppThemeCommand};
+        use commands::config::{CommonConfigCommand, ConfigCommand};
+        use commands::env::EnvCommand;
+        use commands::mcp::McpCommand;
+        use commands::prompts::PromptsCommand;
+        use commands::provider::{ProviderCommand, ProviderEndpointsCommand};
+        use commands::settings::SettingsCommand;
+        use commands::skills::{SkillReposCommand, SkillsCommand};
+
+        match self {
+            Commands::Provider(cmd) => matches!(
+                cmd,
+                ProviderCommand::List { .. }
+                    | ProviderCommand::Current
+                    | ProviderCommand::Show { .. }
+                    | ProviderCommand::Endpoints {
+                        action: None | Some(ProviderEndpointsCommand::List),
+                        ..
+                    }
+                    | ProviderCommand::Switch { dry_run: true, .. }
+                    | ProviderCommand::Validate
+                    | ProviderCommand::History { .. }
+            ),
+            Commands::Mcp(cmd) => matches!(
+                cmd,
+                McpCommand::List
+                    | McpCommand::Test { .. }
+                    | McpCommand::Validate { .. }
+                    | McpCommand::Export { .. }
+            ),
+            Commands::Prompts(cmd) => matches!(
+                cmd,
+                PromptsCommand::List
+                    | PromptsCommand::Current
+                    | PromptsCommand::Show { .. }
+                    | PromptsCommand::Export { .. }
+            ),
+            Commands::Skills(cmd) => matches!(
+                cmd,
+                SkillsCommand::List { .. }
+                    | SkillsCommand::Stats
+                    | SkillsCommand::Discover { .. }
+                    | SkillsCommand::Info { .. }
+                    | SkillsCommand::ScanUnmanaged
+                    | SkillsCommand::Prune { dry_run: true, .. }
+                    | SkillsCommand::Normalize { dry_run: true }
+                    | SkillsCommand::Repos(
+                        SkillReposCommand::List
+                            | SkillReposCommand::Export { .. }
+                            | SkillReposCommand::Test { .. }
+                    )
+            ),
+            Commands::Config(cmd) => matches!(
+                cmd,
+                ConfigCommand::Show { .. }
+                    | ConfigCommand::Path
+                    | ConfigCommand::Export { .. }
+                    | ConfigCommand::Backup { .. }
+                    | ConfigCommand::Upgrade { check: true }
+                    | ConfigCommand::Validate
+                    | ConfigCommand::Import { dry_run: true, .. }
+                    | ConfigCommand::Restore { dry_run: true, .. }
+                    | ConfigCommand::Common(CommonConfigCommand::Show)
+            ),
+            Commands::App(cmd) => matches!(
+                cmd,
+                AppCommand::List
+                    | AppCommand::Override(AppOverrideCommand::Show)
+                    | AppCommand::Theme(AppThemeCommand::Show)
+                    | AppCommand::Hook(AppHookCommand::Show)
+                    | AppCommand::Backup { list: true }
+            ),
+            Commands::Settings(cmd) => {
+                matches!(cmd, SettingsCommand::List | SettingsCommand::Get { .. })
+            }
+            Commands::Env(cmd) => {
+                matches!(
+                    cmd,
+                    EnvCommand::Check { fix: false, .. }
+                        | EnvCommand::List
+                        | EnvCommand::Diff { .. }
+                        | EnvCommand::Set { write: None, .. }
+                        | EnvCommand::Unset { write: None, .. }
+                )
+            }
+            Commands::Update(_)
+            | Commands::Version
+            | Commands::Completions { .. }
+            | Commands::Complete { .. } => true,
+            Commands::Deeplink(_) | Commands::Interactive => false,
+        }
+    }
 }
 
 /// Generate shell completions
@@ -77,5 +280,85 @@ pub fn generate_completions(shell: Shell) {
     use clap::CommandFactory;
     let mut cmd = Cli::command();
     let name = cmd.get_name().to_string();
-    clap_complete::generate(shell, &mut cmd, name, &mut std::io::stdout());
+    clap_complete::generate(shell, &mut cmd, name.clone(), &mut std::io::stdout());
+    // 已安装 Skill / 仓库等动态候选项由隐藏命令 `complete-dynamic` 提供
+    if let Some(script) = completion::dynamic_script(shell, &name) {
+        print!("{script}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn read_only(args: &[&str]) -> bool {
+        let cli = Cli::try_parse_from(std::iter::once("cc-switch").chain(args.iter().copied()))
+            .expect("parse args");
+        cli.command.expect("command").is_read_only()
+    }
+
+    #[test]
+    fn completions_render_for_every_shell() {
+        use clap::CommandFactory;
+        Cli::command().debug_assert();
+        for shell in [
+            Shell::Bash,
+            Shell::Zsh,
+            Shell::Fish,
+            Shell::PowerShell,
+            Shell::Elvish,
+        ] {
+            let mut script = Vec::new();
+            clap_complete::generate(shell, &mut Cli::command(), "cc-switch", &mut script);
+            assert!(!script.is_empty(), "{shell:?}");
+        }
+    }
+
+    #[test]
+    fn app_all_is_parsed_as_a_selector() {
+        let cli = Cli::try_parse_from(["cc-switch", "--app", "all", "provider", "switch", "Gw"])
+            .expect("parse args");
+        assert_eq!(cli.app, Some(AppSelector::All));
+        assert_eq!(AppSelector::All.app_type(), None);
+        assert_eq!(AppSelector::Codex.app_type(), Some(AppType::Codex));
+    }
+
+    #[test]
+    fn destructive_confirmations_accept_yes() {
+        for args in [
+            &["config", "import", "x.sql", "--yes"][..],
+            &["config", "restore", "--backup", "b1", "-y"],
+            &["config", "reset", "--yes"],
+            &["provider", "delete", "p1", "-y"],
+            &["mcp", "delete", "fs", "--yes"],
+            &["prompts", "delete", "p1", "--yes"],
+        ] {
+            let argv = ["cc-switch", "--non-interactive"].iter().chain(args);
+            assert!(Cli::try_parse_from(argv).is_ok(), "{args:?}");
+        }
+    }
+
+    #[test]
+    fn only_mutating_commands_need_the_instance_lock() {
+        assert!(read_only(&["provider", "list"]));
+        assert!(read_only(&["provider", "switch", "p1", "--dry-run"]));
+        assert!(read_only(&["config", "import", "x.sql", "--dry-run"]));
+        assert!(!read_only(&["provider", "switch", "p1"]));
+        assert!(!read_only(&["config", "import", "x.sql"]));
+        assert!(!read_only(&["--wait", "skills", "sync"]));
+        assert!(read_only(&["skills", "stats"]));
+        assert!(read_only(&["skills", "discover", "lint", "--json"]));
+        assert!(read_only(&["complete-dynamic", "skill-repos"]));
+        assert!(read_only(&["skills", "repo", "test", "owner/name"]));
+        assert!(read_only(&["skills", "repo", "export", "repos.json"]));
+        assert!(!read_only(&["skills", "repo", "import", "repos.json"]));
+        assert!(read_only(&["provider", "endpoints", "p1"]));
+        assert!(!read_only(&[
+            "provider",
+            "endpoints",
+            "p1",
+            "auto-select",
+            "on"
+        ]));
+    }
 }
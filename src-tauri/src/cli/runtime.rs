@@ -0,0 +1,39 @@
+//! CLI 中网络操作的异步运行时
+//!
+//! Ctrl-C（SIGINT）时丢弃进行中的 future：下载用的临时目录随 `TempDir` 一起清理，
+//! 命令以 "cancelled by user" 错误非零退出。
+
+use std::future::Future;
+
+use crate::error::AppError;
+
+pub fn cancelled_by_user() -> AppError {
+    AppError::localized("operation.cancelled", "已被用户取消", "cancelled by user")
+}
+
+/// 在单线程运行时上执行 `fut`，收到 Ctrl-C 时取消
+pub fn run_async<T>(fut: impl Future<Output = Result<T, AppError>>) -> Result<T, AppError> {
+    tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .map_err(|e| AppError::Message(format!("Failed to create runtime: {e}")))?
+        .block_on(async {
+            tokio::select! {
+                result = fut => result,
+                // 无法注册信号处理时该分支失效，不会误判为取消
+                Ok(()) = tokio::signal::ctrl_c() => Err(cancelled_by_user()),
+            }
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn run_async_returns_the_future_result() {
+        assert_eq!(run_async(async { Ok(7) }).expect("result"), 7);
+        let err = run_async::<()>(async { Err(cancelled_by_user()) }).expect_err("error");
+        assert_eq!(err.to_string(), cancelled_by_user().to_string());
+    }
+}
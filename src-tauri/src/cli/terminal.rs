@@ -1,8 +1,75 @@
 use std::io::{self, IsTerminal, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Once;
 
+use crate::error::AppError;
+
 static DISABLE_BRACKETED_PASTE: Once = Once::new();
 
+static NON_INTERACTIVE: AtomicBool = AtomicBool::new(false);
+
+/// 全局 `--non-interactive`：任何需要输入的提示（选择、确认、文本）都直接报错
+pub fn enable_non_interactive() {
+    NON_INTERACTIVE.store(true, Ordering::Relaxed);
+}
+
+pub fn is_non_interactive() -> bool {
+    NON_INTERACTIVE.load(Ordering::Relaxed)
+}
+
+/// 弹出提示前调用；`alternative` 为可改用的参数（如 "--backup <id>"），没有则为 None
+pub fn ensure_interactive(prompt: &str, alternative: Option<&str>) -> Result<(), AppError> {
+    if !is_non_interactive() {
+        return Ok(());
+    }
+    Err(match alternative {
+        Some(flags) => AppError::localized(
+            "cli.non_interactive",
+            format!("--non-interactive：{prompt} 需要交互输入，请改用 {flags}"),
+            format!("--non-interactive: {prompt} needs interactive input; pass {flags} instead"),
+        ),
+        None => AppError::localized(
+            "cli.non_interactive",
+            format!("--non-interactive：{prompt} 需要交互输入，且没有非交互方式"),
+            format!(
+                "--non-interactive: {prompt} needs interactive input and has no non-interactive form"
+            ),
+        ),
+    })
+}
+
+/// 读取密钥类输入：交互终端下掩码输入，管道输入时读取 stdin 第一行；`label` 如 "API key"
+pub fn read_secret(label: &str) -> Result<String, AppError> {
+    use std::io::BufRead;
+
+    let secret = if io::stdin().is_terminal() {
+        ensure_interactive(
+            &format!("the {label} prompt"),
+            Some(&format!("the {label} on stdin")),
+        )?;
+        inquire::Password::new(&format!("{label}:"))
+            .with_display_mode(inquire::PasswordDisplayMode::Masked)
+            .without_confirmation()
+            .prompt()
+            .map_err(|e| AppError::Message(format!("Failed to read {label}: {e}")))?
+    } else {
+        let mut line = String::new();
+        io::stdin()
+            .lock()
+            .read_line(&mut line)
+            .map_err(|e| AppError::IoContext {
+                context: format!("Failed to read {label} from stdin"),
+                source: e,
+            })?;
+        line
+    };
+    let secret = secret.trim().to_string();
+    if secret.is_empty() {
+        return Err(AppError::InvalidInput(format!("{label} must not be empty")));
+    }
+    Ok(secret)
+}
+
 /// Disables bracketed paste mode to work around inquire dropping paste events.
 ///
 /// When bracketed paste mode is enabled (common in zsh/fish, tmux/zellij, and some terminals),
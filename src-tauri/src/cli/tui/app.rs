@@ -5,6 +5,7 @@ use std::collections::HashSet;
 use crate::app_config::AppType;
 use crate::cli::i18n::texts;
 use crate::cli::i18n::Language;
+use crate::cli::interactive::search::{SearchItem, SearchKind};
 use crate::services::skill::SyncMethod;
 
 use super::data::UiData;
@@ -115,6 +116,31 @@ pub struct TextViewState {
     pub scroll: usize,
 }
 
+#[derive(Debug, Clone)]
+pub struct SearchPickerState {
+    pub query: String,
+    pub selected: usize,
+    pub items: Vec<SearchItem>,
+}
+
+impl SearchPickerState {
+    pub fn new(items: Vec<SearchItem>) -> Self {
+        Self {
+            query: String::new(),
+            selected: 0,
+            items,
+        }
+    }
+
+    pub fn visible(&self) -> Vec<&SearchItem> {
+        let query = self.query.trim().to_lowercase();
+        self.items
+            .iter()
+            .filter(|item| query.is_empty() || item.to_string().to_lowercase().contains(&query))
+            .collect()
+    }
+}
+
 #[derive(Debug, Clone)]
 pub enum Overlay {
     None,
@@ -135,6 +161,7 @@ pub enum Overlay {
     SkillsSyncMethodPicker {
         selected: usize,
     },
+    SearchPicker(SearchPickerState),
     Loading {
         title: String,
         message: String,
@@ -353,6 +380,7 @@ pub enum Action {
     Quit,
     SetAppType(AppType),
     LocalEnvRefresh,
+    SearchOpen,
 
     SkillsToggle {
         directory: String,
@@ -393,6 +421,11 @@ pub enum Action {
     ProviderSwitch {
         id: String,
     },
+    /// 全局搜索中选中了其它应用的供应商：先切换到该应用再切换供应商
+    ProviderSwitchForApp {
+        app_type: AppType,
+        id: String,
+    },
     ProviderDelete {
         id: String,
     },
@@ -658,6 +691,10 @@ impl App {
             return self.on_filter_key(key);
         }
 
+        if key.modifiers.contains(KeyModifiers::CONTROL) && matches!(key.code, KeyCode::Char('f')) {
+            return Action::SearchOpen;
+        }
+
         // Global actions.
         match key.code {
             KeyCode::Char('?') => {
@@ -1614,6 +1651,47 @@ impl App {
                 }
                 _ => Action::None,
             },
+            Overlay::SearchPicker(search) => match key.code {
+                KeyCode::Esc => {
+                    self.overlay = Overlay::None;
+                    Action::None
+                }
+                KeyCode::Up => {
+                    search.selected = search.selected.saturating_sub(1);
+                    Action::None
+                }
+                KeyCode::Down => {
+                    let len = search.visible().len();
+                    if len > 0 {
+                        search.selected = (search.selected + 1).min(len - 1);
+                    }
+                    Action::None
+                }
+                KeyCode::Backspace => {
+                    search.query.pop();
+                    search.selected = 0;
+                    Action::None
+                }
+                KeyCode::Char(c) => {
+                    if !c.is_control() {
+                        search.query.push(c);
+                        search.selected = 0;
+                    }
+                    Action::None
+                }
+                KeyCode::Enter => {
+                    let Some(item) = search
+                        .visible()
+                        .get(search.selected)
+                        .map(|item| (*item).clone())
+                    else {
+                        return Action::None;
+                    };
+                    self.overlay = Overlay::None;
+                    self.search_item_action(item)
+                }
+                _ => Action::None,
+            },
             Overlay::Loading { .. } => match key.code {
                 KeyCode::Esc => {
                     self.overlay = Overlay::None;
@@ -1689,6 +1767,28 @@ impl App {
         }
     }
 
+    fn search_item_action(&self, item: SearchItem) -> Action {
+        match item.kind {
+            SearchKind::Provider(app_type) if app_type == self.app_type => {
+                Action::ProviderSwitch { id: item.id }
+            }
+            SearchKind::Provider(app_type) => Action::ProviderSwitchForApp {
+                app_type,
+                id: item.id,
+            },
+            SearchKind::Mcp => Action::McpToggle {
+                id: item.id,
+                enabled: !item.active,
+            },
+            SearchKind::Skill => Action::SkillsToggle {
+                directory: item.id,
+                enabled: !item.active,
+            },
+            SearchKind::Prompt if item.active => Action::PromptDeactivate { id: item.id },
+            SearchKind::Prompt => Action::PromptActivate { id: item.id },
+        }
+    }
+
     pub fn open_editor(
         &mut self,
         title: impl Into<String>,
@@ -2867,6 +2967,48 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn ctrl_f_opens_global_search_and_enter_dispatches_item_action() {
+        let mut app = App::new(Some(AppType::Claude));
+        let data = data();
+        assert!(matches!(
+            app.on_key(ctrl(KeyCode::Char('f')), &data),
+            Action::SearchOpen
+        ));
+
+        let item = |kind, id: &str, active| SearchItem {
+            kind,
+            id: id.to_string(),
+            label: id.to_string(),
+            active,
+        };
+        app.overlay = Overlay::SearchPicker(SearchPickerState::new(vec![
+            item(SearchKind::Provider(AppType::Codex), "codex-p", false),
+            item(SearchKind::Mcp, "fetch", true),
+        ]));
+
+        for c in "codex".chars() {
+            app.on_key(key(KeyCode::Char(c)), &data);
+        }
+        let action = app.on_key(key(KeyCode::Enter), &data);
+        assert!(matches!(
+            action,
+            Action::ProviderSwitchForApp { app_type: AppType::Codex, id } if id == "codex-p"
+        ));
+        assert!(matches!(app.overlay, Overlay::None));
+
+        app.overlay = Overlay::SearchPicker(SearchPickerState::new(vec![item(
+            SearchKind::Mcp,
+            "fetch",
+            true,
+        )]));
+        let action = app.on_key(key(KeyCode::Enter), &data);
+        assert!(matches!(
+            action,
+            Action::McpToggle { id, enabled: false } if id == "fetch"
+        ));
+    }
+
     #[test]
     fn providers_s_key_triggers_switch_action() {
         let mut app = App::new(Some(AppType::Claude));
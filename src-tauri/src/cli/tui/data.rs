@@ -98,7 +98,11 @@ impl UiData {
 fn load_providers(state: &AppState, app_type: &AppType) -> Result<ProvidersSnapshot, AppError> {
     let current_id = ProviderService::current(state, app_type.clone())?;
     let providers = ProviderService::list(state, app_type.clone())?;
-    let sorted = sort_providers(&providers);
+    let history = state
+        .db
+        .get_provider_switch_history(app_type.as_str())
+        .unwrap_or_default();
+    let sorted = sort_providers(&providers, &current_id, &history);
 
     let rows = sorted
         .into_iter()
@@ -113,7 +117,12 @@ fn load_providers(state: &AppState, app_type: &AppType) -> Result<ProvidersSnaps
     Ok(ProvidersSnapshot { current_id, rows })
 }
 
-fn sort_providers(providers: &IndexMap<String, Provider>) -> Vec<(String, Provider)> {
+/// 与交互式切换列表一致：最近使用的在前、当前供应商在最后，其余按 sort_index / created_at
+fn sort_providers(
+    providers: &IndexMap<String, Provider>,
+    current_id: &str,
+    history: &[String],
+) -> Vec<(String, Provider)> {
     let mut items = providers
         .iter()
         .map(|(id, p)| (id.clone(), p.clone()))
@@ -126,6 +135,18 @@ fn sort_providers(providers: &IndexMap<String, Provider>) -> Vec<(String, Provid
         (None, None) => a.created_at.cmp(&b.created_at),
     });
 
+    let rank = |id: &str| {
+        history
+            .iter()
+            .position(|used| used == id)
+            .unwrap_or(usize::MAX)
+    };
+    items.sort_by(|(id_a, _), (id_b, _)| {
+        (id_a == current_id)
+            .cmp(&(id_b == current_id))
+            .then_with(|| rank(id_a).cmp(&rank(id_b)))
+    });
+
     items
 }
 
@@ -227,6 +248,27 @@ mod tests {
     use super::*;
     use serde_json::json;
 
+    #[test]
+    fn sort_providers_puts_recent_first_and_current_last() {
+        let providers: IndexMap<String, Provider> = ["a", "b", "c", "d"]
+            .into_iter()
+            .enumerate()
+            .map(|(idx, id)| {
+                let mut provider =
+                    Provider::with_id(id.to_string(), id.to_uppercase(), json!({}), None);
+                provider.sort_index = Some(idx);
+                (id.to_string(), provider)
+            })
+            .collect();
+        let history = vec!["b".to_string(), "c".to_string()];
+
+        let ids = sort_providers(&providers, "b", &history)
+            .into_iter()
+            .map(|(id, _)| id)
+            .collect::<Vec<_>>();
+        assert_eq!(ids, vec!["c", "a", "d", "b"]);
+    }
+
     #[test]
     fn extract_api_url_gemini_prefers_google_env_key() {
         let settings = json!({
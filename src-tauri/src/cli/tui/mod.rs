@@ -15,6 +15,7 @@ use serde_json::Value;
 
 use crate::app_config::AppType;
 use crate::cli::i18n::{set_language, texts};
+use crate::cli::interactive::search::build_searchable_index;
 use crate::error::AppError;
 use crate::provider::Provider;
 use crate::services::{
@@ -22,7 +23,7 @@ use crate::services::{
     SkillService,
 };
 
-use app::{Action, App, EditorSubmit, Overlay, TextViewState, ToastKind};
+use app::{Action, App, EditorSubmit, Overlay, SearchPickerState, TextViewState, ToastKind};
 use data::{load_state, UiData};
 use terminal::{PanicRestoreHookGuard, TuiTerminal};
 
@@ -331,6 +332,16 @@ fn handle_action(
             *data = next_data;
             Ok(())
         }
+        Action::SearchOpen => {
+            let state = load_state()?;
+            let items = build_searchable_index(&state, &app.app_type)?;
+            if items.is_empty() {
+                app.push_toast(texts::search_all_empty(), ToastKind::Info);
+            } else {
+                app.overlay = Overlay::SearchPicker(SearchPickerState::new(items));
+            }
+            Ok(())
+        }
         Action::LocalEnvRefresh => {
             let Some(tx) = local_env_req_tx else {
                 app.local_env_loading = false;
@@ -726,20 +737,11 @@ fn handle_action(
             }
         },
 
-        Action::ProviderSwitch { id } => {
-            let state = load_state()?;
-            ProviderService::switch(&state, app.app_type.clone(), &id)?;
-            if !crate::sync_policy::should_sync_live(&app.app_type) {
-                let mut message =
-                    texts::tui_toast_live_sync_skipped_uninitialized(app.app_type.as_str());
-                message.push(' ');
-                message.push_str(texts::restart_note());
-                app.push_toast(message, ToastKind::Warning);
-            } else {
-                app.push_toast(texts::restart_note(), ToastKind::Success);
-            }
-            *data = UiData::load(&app.app_type)?;
-            Ok(())
+        Action::ProviderSwitch { id } => switch_provider(app, data, &id),
+        Action::ProviderSwitchForApp { app_type, id } => {
+            *data = UiData::load(&app_type)?;
+            app.app_type = app_type;
+            switch_provider(app, data, &id)
         }
         Action::ProviderDelete { id } => {
             let state = load_state()?;
@@ -899,7 +901,7 @@ fn handle_action(
         Action::ConfigExport { path } => {
             let target = PathBuf::from(path);
             if let Some(parent) = target.parent() {
-                std::fs::create_dir_all(parent).map_err(|e| AppError::io(parent, e))?;
+                crate::dry_run::create_dir_all(parent).map_err(|e| AppError::io(parent, e))?;
             }
             ConfigService::export_config_to_path(&target)?;
             app.push_toast(
@@ -1036,7 +1038,7 @@ fn handle_action(
             let backup_id = ConfigService::create_backup(&db_path, None)?;
 
             if db_path.exists() {
-                std::fs::remove_file(&db_path).map_err(|e| AppError::io(&db_path, e))?;
+                crate::dry_run::remove_file(&db_path).map_err(|e| AppError::io(&db_path, e))?;
             }
             let _ = crate::Database::init()?;
             if backup_id.is_empty() {
@@ -1062,6 +1064,26 @@ fn handle_action(
     }
 }
 
+fn switch_provider(app: &mut App, data: &mut UiData, id: &str) -> Result<(), AppError> {
+    let state = load_state()?;
+    let hook_output = ProviderService::switch_with_hook(&state, app.app_type.clone(), id, true)?;
+    if let Some(hook) = hook_output.filter(|hook| !hook.success) {
+        app.push_toast(
+            format!("Post-switch hook failed: {}", hook.command),
+            ToastKind::Warning,
+        );
+    } else if !crate::sync_policy::should_sync_live(&app.app_type) {
+        let mut message = texts::tui_toast_live_sync_skipped_uninitialized(app.app_type.as_str());
+        message.push(' ');
+        message.push_str(texts::restart_note());
+        app.push_toast(message, ToastKind::Warning);
+    } else {
+        app.push_toast(texts::restart_note(), ToastKind::Success);
+    }
+    *data = UiData::load(&app.app_type)?;
+    Ok(())
+}
+
 fn refresh_common_snippet_overlay(app: &mut App, data: &UiData) {
     let Overlay::CommonSnippetView(view) = &mut app.overlay else {
         return;
@@ -1301,6 +1323,7 @@ fn parse_repo_spec(raw: &str) -> Result<SkillRepo, AppError> {
         name: name.to_string(),
         branch: branch.unwrap_or("main").to_string(),
         enabled: true,
+        token: None,
     })
 }
 
@@ -13,7 +13,7 @@ pub struct Theme {
 }
 
 pub fn no_color() -> bool {
-    std::env::var("NO_COLOR").is_ok()
+    crate::cli::ui::colors_disabled()
 }
 
 pub fn theme_for(app: &AppType) -> Theme {
@@ -21,10 +21,13 @@ pub fn theme_for(app: &AppType) -> Theme {
     let accent = if no_color {
         Color::Reset
     } else {
-        match app {
-            AppType::Codex => Color::LightGreen,
-            AppType::Claude => Color::LightCyan,
-            AppType::Gemini => Color::LightMagenta,
+        match crate::cli::ui::custom_theme_color(app) {
+            Some(color) => tui_color(color),
+            None => match app {
+                AppType::Codex => Color::LightGreen,
+                AppType::Claude => Color::LightCyan,
+                AppType::Gemini => Color::LightMagenta,
+            },
         }
     };
 
@@ -45,3 +48,26 @@ pub fn theme_for(app: &AppType) -> Theme {
         no_color,
     }
 }
+
+fn tui_color(color: colored::Color) -> Color {
+    use colored::Color as C;
+    match color {
+        C::Black => Color::Black,
+        C::Red => Color::Red,
+        C::Green => Color::Green,
+        C::Yellow => Color::Yellow,
+        C::Blue => Color::Blue,
+        C::Magenta => Color::Magenta,
+        C::Cyan => Color::Cyan,
+        C::White => Color::Gray,
+        C::BrightBlack => Color::DarkGray,
+        C::BrightRed => Color::LightRed,
+        C::BrightGreen => Color::LightGreen,
+        C::BrightYellow => Color::LightYellow,
+        C::BrightBlue => Color::LightBlue,
+        C::BrightMagenta => Color::LightMagenta,
+        C::BrightCyan => Color::LightCyan,
+        C::BrightWhite => Color::White,
+        C::TrueColor { r, g, b } => Color::Rgb(r, g, b),
+    }
+}
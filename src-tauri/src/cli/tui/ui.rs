@@ -3134,6 +3134,55 @@ fn render_overlay(frame: &mut Frame<'_>, app: &App, data: &UiData, theme: &super
             state.select(Some(*selected));
             frame.render_stateful_widget(list, chunks[1], &mut state);
         }
+        Overlay::SearchPicker(search) => {
+            let area = centered_rect(80, 80, frame.area());
+            frame.render_widget(Clear, area);
+
+            let outer = Block::default()
+                .borders(Borders::ALL)
+                .border_type(BorderType::Plain)
+                .border_style(Style::default().fg(theme.dim))
+                .title(texts::tui_search_title());
+            frame.render_widget(outer.clone(), area);
+            let inner = outer.inner(area);
+
+            let chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([
+                    Constraint::Length(1),
+                    Constraint::Length(1),
+                    Constraint::Min(0),
+                ])
+                .split(inner);
+
+            render_key_bar(
+                frame,
+                chunks[0],
+                theme,
+                &[
+                    ("Enter", texts::tui_key_select()),
+                    ("Esc", texts::tui_key_cancel()),
+                ],
+            );
+
+            frame.render_widget(
+                Paragraph::new(Line::raw(format!("> {}", search.query))),
+                chunks[1],
+            );
+
+            let items = search
+                .visible()
+                .into_iter()
+                .map(|item| ListItem::new(Line::from(Span::raw(item.to_string()))));
+
+            let list = List::new(items)
+                .highlight_style(selection_style(theme))
+                .highlight_symbol(highlight_symbol(theme));
+
+            let mut state = ListState::default();
+            state.select(Some(search.selected));
+            frame.render_stateful_widget(list, chunks[2], &mut state);
+        }
         Overlay::Loading { title, message } => {
             let area = centered_rect(60, 30, frame.area());
             frame.render_widget(Clear, area);
@@ -3394,6 +3443,7 @@ mod tests {
             description: Some("Demo".to_string()),
             directory: directory.to_string(),
             readme_url: None,
+            source_url: None,
             repo_owner: None,
             repo_name: None,
             repo_branch: None,
@@ -3612,6 +3662,7 @@ mod tests {
             name: "skills".to_string(),
             branch: "main".to_string(),
             enabled: true,
+            token: None,
         }];
 
         let buf = render(&app, &data);
@@ -3715,6 +3766,8 @@ mod tests {
             path: std::path::PathBuf::from("/tmp/b1.json"),
             timestamp: "20260131_000000".to_string(),
             display_name: "backup".to_string(),
+            compressed: false,
+            meta: Default::default(),
         }];
 
         let buf = render(&app, &data);
@@ -1,5 +1,6 @@
 use colored::Color;
 use colored::Colorize;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{OnceLock, RwLock};
 
 use crate::app_config::AppType;
@@ -8,6 +9,19 @@ use inquire::set_global_render_config;
 use inquire::ui::{Color as InquireColor, RenderConfig, StyleSheet, Styled};
 
 static TUI_THEME_APP: OnceLock<RwLock<Option<AppType>>> = OnceLock::new();
+static COLORS_DISABLED: AtomicBool = AtomicBool::new(false);
+
+/// 全局关闭颜色输出（`--no-color` / `--json`）
+pub fn disable_colors() {
+    COLORS_DISABLED.store(true, Ordering::Relaxed);
+    colored::control::set_override(false);
+    apply_inquire_theme();
+}
+
+/// 是否关闭颜色：`--no-color`、`--json` 或设置了 `NO_COLOR` 环境变量
+pub fn colors_disabled() -> bool {
+    COLORS_DISABLED.load(Ordering::Relaxed) || std::env::var("NO_COLOR").is_ok()
+}
 
 fn tui_theme_app_cell() -> &'static RwLock<Option<AppType>> {
     TUI_THEME_APP.get_or_init(|| RwLock::new(None))
@@ -28,16 +42,60 @@ fn get_tui_theme_app() -> Option<AppType> {
         .clone()
 }
 
+/// 解析主题色：颜色名（如 `magenta`、`bright-cyan`）或 `#rrggbb`
+pub fn parse_theme_color(raw: &str) -> Option<Color> {
+    let raw = raw.trim();
+    if let Some(hex) = raw.strip_prefix('#') {
+        if hex.len() != 6 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+            return None;
+        }
+        let rgb = u32::from_str_radix(hex, 16).ok()?;
+        return Some(Color::TrueColor {
+            r: (rgb >> 16) as u8,
+            g: (rgb >> 8) as u8,
+            b: rgb as u8,
+        });
+    }
+    raw.replace(['-', '_'], " ").parse::<Color>().ok()
+}
+
+/// 用户在设置中为该应用配置的主题色（无效值视为未设置）
+pub fn custom_theme_color(app_type: &AppType) -> Option<Color> {
+    crate::settings::get_theme_color(app_type).and_then(|raw| parse_theme_color(&raw))
+}
+
 fn inquire_color_for_app(app_type: &AppType) -> InquireColor {
-    match app_type {
-        AppType::Codex => InquireColor::LightGreen,
-        AppType::Claude => InquireColor::LightCyan,
-        AppType::Gemini => InquireColor::LightMagenta,
+    let Some(color) = custom_theme_color(app_type) else {
+        return match app_type {
+            AppType::Codex => InquireColor::LightGreen,
+            AppType::Claude => InquireColor::LightCyan,
+            AppType::Gemini => InquireColor::LightMagenta,
+        };
+    };
+
+    match color {
+        Color::Black => InquireColor::Black,
+        Color::Red => InquireColor::DarkRed,
+        Color::Green => InquireColor::DarkGreen,
+        Color::Yellow => InquireColor::DarkYellow,
+        Color::Blue => InquireColor::DarkBlue,
+        Color::Magenta => InquireColor::DarkMagenta,
+        Color::Cyan => InquireColor::DarkCyan,
+        Color::White => InquireColor::Grey,
+        Color::BrightBlack => InquireColor::DarkGrey,
+        Color::BrightRed => InquireColor::LightRed,
+        Color::BrightGreen => InquireColor::LightGreen,
+        Color::BrightYellow => InquireColor::LightYellow,
+        Color::BrightBlue => InquireColor::LightBlue,
+        Color::BrightMagenta => InquireColor::LightMagenta,
+        Color::BrightCyan => InquireColor::LightCyan,
+        Color::BrightWhite => InquireColor::White,
+        Color::TrueColor { r, g, b } => InquireColor::Rgb { r, g, b },
     }
 }
 
 fn apply_inquire_theme() {
-    if std::env::var("NO_COLOR").is_ok() {
+    if colors_disabled() {
         set_global_render_config(RenderConfig::empty());
         return;
     }
@@ -77,7 +135,8 @@ pub fn info(text: &str) -> String {
     text.cyan().to_string()
 }
 
-fn highlight_color_for_app(app_type: &AppType) -> Color {
+/// 内置默认主题色
+pub fn default_theme_color(app_type: &AppType) -> Color {
     match app_type {
         AppType::Codex => Color::BrightGreen,
         AppType::Claude => Color::BrightCyan,
@@ -85,6 +144,10 @@ fn highlight_color_for_app(app_type: &AppType) -> Color {
     }
 }
 
+fn highlight_color_for_app(app_type: &AppType) -> Color {
+    custom_theme_color(app_type).unwrap_or_else(|| default_theme_color(app_type))
+}
+
 pub fn highlight(text: &str) -> String {
     let Some(app_type) = get_tui_theme_app() else {
         return text.bright_blue().bold().to_string();
@@ -139,4 +202,21 @@ mod tests {
             "x".color(Color::BrightMagenta).bold().to_string()
         );
     }
+
+    #[test]
+    fn parse_theme_color_accepts_names_and_hex() {
+        assert_eq!(parse_theme_color("magenta"), Some(Color::Magenta));
+        assert_eq!(parse_theme_color("Bright-Cyan"), Some(Color::BrightCyan));
+        assert_eq!(parse_theme_color("bright_green"), Some(Color::BrightGreen));
+        assert_eq!(
+            parse_theme_color("#ff8000"),
+            Some(Color::TrueColor {
+                r: 255,
+                g: 128,
+                b: 0
+            })
+        );
+        assert_eq!(parse_theme_color("#ff80"), None);
+        assert_eq!(parse_theme_color("chartreuse"), None);
+    }
 }
@@ -1,9 +1,149 @@
 use serde::Serialize;
+use std::sync::atomic::{AtomicBool, AtomicU8, Ordering};
+
+use crate::error::AppError;
+
+/// 全局 `--format`：表格（默认）或结构化输出
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum OutputFormat {
+    Table,
+    Json,
+    Yaml,
+}
+
+static OUTPUT_FORMAT: AtomicU8 = AtomicU8::new(OutputFormat::Table as u8);
+static REVEAL_SECRETS: AtomicBool = AtomicBool::new(false);
+
+/// 全局 `--json`：等同于 `--format json`
+pub fn enable_json_output() {
+    set_output_format(OutputFormat::Json);
+}
+
+pub fn set_output_format(format: OutputFormat) {
+    OUTPUT_FORMAT.store(format as u8, Ordering::Relaxed);
+}
+
+pub fn output_format() -> OutputFormat {
+    match OUTPUT_FORMAT.load(Ordering::Relaxed) {
+        x if x == OutputFormat::Json as u8 => OutputFormat::Json,
+        x if x == OutputFormat::Yaml as u8 => OutputFormat::Yaml,
+        _ => OutputFormat::Table,
+    }
+}
+
+/// 是否输出结构化数据（JSON 或 YAML）而非表格；具体格式由 [`emit`] 决定
+pub fn json_output() -> bool {
+    output_format() != OutputFormat::Table
+}
+
+/// 全局 `--reveal-secrets`：关闭 API Key / Token 的脱敏显示
+pub fn enable_reveal_secrets() {
+    REVEAL_SECRETS.store(true, Ordering::Relaxed);
+}
+
+pub fn reveal_secrets() -> bool {
+    REVEAL_SECRETS.load(Ordering::Relaxed)
+}
+
+/// 是否为敏感字段名：按 `_` / `-` / `.` / 驼峰拆词后，任一词为 TOKEN / SECRET / PASSWORD /
+/// KEY / AUTHORIZATION 等（不区分大小写；`maxTokens`、`keyring` 这类不算）
+pub fn is_sensitive_key(name: &str) -> bool {
+    const MARKERS: [&str; 8] = [
+        "TOKEN",
+        "SECRET",
+        "PASSWORD",
+        "PASSWD",
+        "KEY",
+        "APIKEY",
+        "AUTHORIZATION",
+        "CREDENTIAL",
+    ];
+    let mut words = Vec::new();
+    let mut word = String::new();
+    let mut prev_lower = false;
+    for c in name.chars() {
+        if !c.is_ascii_alphanumeric() {
+            words.push(std::mem::take(&mut word));
+            prev_lower = false;
+            continue;
+        }
+        if c.is_ascii_uppercase() && prev_lower {
+            words.push(std::mem::take(&mut word));
+        }
+        prev_lower = c.is_ascii_lowercase() || c.is_ascii_digit();
+        word.push(c.to_ascii_uppercase());
+    }
+    words.push(word);
+    words.iter().any(|w| MARKERS.contains(&w.as_str()))
+}
+
+/// 脱敏：仅保留末 4 位；过短的值整体隐藏。`keyring:<名称>` 引用本身不是密钥，原样显示
+pub fn redact_secret(value: &str) -> String {
+    if crate::keyring_store::parse_reference(value).is_some() {
+        return value.to_string();
+    }
+    let chars: Vec<char> = value.chars().collect();
+    if chars.len() <= 8 {
+        return "****".to_string();
+    }
+    let tail: String = chars[chars.len() - 4..].iter().collect();
+    format!("****{tail}")
+}
+
+/// 用于展示的密钥：除非指定 `--reveal-secrets`，否则脱敏
+pub fn display_secret(value: &str) -> String {
+    if reveal_secrets() {
+        value.to_string()
+    } else {
+        redact_secret(value)
+    }
+}
+
+/// 递归脱敏 JSON 中的敏感字段（仅影响展示副本）
+pub fn redact_secrets_in_json(value: &mut serde_json::Value) {
+    match value {
+        serde_json::Value::Object(map) => {
+            for (key, child) in map.iter_mut() {
+                match child {
+                    serde_json::Value::String(s) if is_sensitive_key(key) && !s.is_empty() => {
+                        *s = redact_secret(s);
+                    }
+                    _ => redact_secrets_in_json(child),
+                }
+            }
+        }
+        serde_json::Value::Array(items) => items.iter_mut().for_each(redact_secrets_in_json),
+        _ => {}
+    }
+}
 
 pub fn to_json<T: Serialize>(value: &T) -> Result<String, serde_json::Error> {
     serde_json::to_string_pretty(value)
 }
 
+/// 多行字符串输出为块标量，含特殊字符的字符串自动加引号
+pub fn to_yaml<T: Serialize>(value: &T) -> Result<String, serde_yaml::Error> {
+    serde_yaml::to_string(value)
+}
+
+/// 按当前 `--format` 打印结构化数据（表格模式下按 JSON 输出）
+pub fn emit<T: Serialize>(value: &T) -> Result<(), AppError> {
+    let output = match output_format() {
+        OutputFormat::Yaml => to_yaml(value).map_err(|e| {
+            AppError::localized(
+                "output.yaml_serialize",
+                format!("YAML 序列化失败: {e}"),
+                format!("Failed to serialize YAML: {e}"),
+            )
+        })?,
+        OutputFormat::Json | OutputFormat::Table => {
+            to_json(value).map_err(|e| AppError::JsonSerialize { source: e })?
+        }
+    };
+    println!("{}", output.trim_end());
+    Ok(())
+}
+
 pub fn format_bool(value: bool) -> &'static str {
     if value {
         "✓"
@@ -11,3 +151,111 @@ pub fn format_bool(value: bool) -> &'static str {
         "✗"
     }
 }
+
+pub fn format_bytes(bytes: u64) -> String {
+    const KIB: f64 = 1024.0;
+    let value = bytes as f64;
+    if value >= KIB * KIB {
+        format!("{:.1} MiB", value / (KIB * KIB))
+    } else if value >= KIB {
+        format!("{:.1} KiB", value / KIB)
+    } else {
+        format!("{} B", bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn redact_secrets_in_json_masks_known_key_fields_only() {
+        let mut value = serde_json::json!({
+            "env": {
+                "ANTHROPIC_AUTH_TOKEN": "sk-abcdef1234",
+                "ANTHROPIC_BASE_URL": "https://api.example.com"
+            },
+            "auth": { "OPENAI_API_KEY": "short" },
+            "meta": { "usageScript": { "apiKey": "usage-key-9876" } }
+        });
+        redact_secrets_in_json(&mut value);
+
+        assert_eq!(value["env"]["ANTHROPIC_AUTH_TOKEN"], "****1234");
+        assert_eq!(
+            value["env"]["ANTHROPIC_BASE_URL"],
+            "https://api.example.com"
+        );
+        assert_eq!(value["auth"]["OPENAI_API_KEY"], "****");
+        assert_eq!(value["meta"]["usageScript"]["apiKey"], "****9876");
+        assert_eq!(redact_secret("keyring:claude-prod"), "keyring:claude-prod");
+    }
+
+    #[test]
+    fn redact_secrets_keeps_last_four_chars_of_keys_only() {
+        let mut value = serde_json::json!({
+            "settingsConfig": {
+                "env": {
+                    "ANTHROPIC_AUTH_TOKEN": "sk-abcdef1234",
+                    "ANTHROPIC_BASE_URL": "https://api.example.com"
+                }
+            },
+            "meta": { "usageScript": { "apiKey": "abc" } }
+        });
+        redact_secrets_in_json(&mut value);
+
+        assert_eq!(
+            value["settingsConfig"]["env"]["ANTHROPIC_AUTH_TOKEN"],
+            "****1234"
+        );
+        assert_eq!(
+            value["settingsConfig"]["env"]["ANTHROPIC_BASE_URL"],
+            "https://api.example.com"
+        );
+        assert_eq!(value["meta"]["usageScript"]["apiKey"], "****");
+    }
+
+    #[test]
+    fn sensitive_key_matches_common_secret_names() {
+        for name in [
+            "ANTHROPIC_AUTH_TOKEN",
+            "OPENAI_API_KEY",
+            "apiKey",
+            "access_token",
+            "refresh_token",
+            "id_token",
+            "client_secret",
+            "DB_PASSWORD",
+            "Authorization",
+            "x-api-key",
+            "GITHUB_TOKEN",
+            "privateKey",
+        ] {
+            assert!(is_sensitive_key(name), "{name} should be sensitive");
+        }
+        for name in [
+            "ANTHROPIC_BASE_URL",
+            "CLAUDE_CODE_MAX_OUTPUT_TOKENS",
+            "maxTokens",
+            "keyring",
+            "model",
+        ] {
+            assert!(!is_sensitive_key(name), "{name} should not be sensitive");
+        }
+    }
+
+    #[test]
+    fn yaml_output_keeps_multiline_and_special_strings_and_redaction() {
+        let mut value = serde_json::json!({
+            "env": { "ANTHROPIC_AUTH_TOKEN": "sk-abcdef1234" },
+            "notes": "line one\nline two: with colon",
+            "name": "- starts with dash # and hash",
+            "flag": "yes"
+        });
+        redact_secrets_in_json(&mut value);
+        let yaml = to_yaml(&value).expect("yaml");
+
+        assert!(!yaml.contains("sk-abcdef1234"));
+        let parsed: serde_json::Value = serde_yaml::from_str(&yaml).expect("parse back");
+        assert_eq!(parsed, value);
+    }
+}
@@ -1,5 +1,6 @@
 pub mod colors;
 pub mod formatters;
+pub mod out;
 pub mod table;
 
 pub use colors::*;
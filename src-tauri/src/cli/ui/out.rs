@@ -0,0 +1,40 @@
+//! 受 `--quiet` 控制的输出：提示类信息统一经由这里打印，错误仍由 main 输出到 stderr
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use super::colors;
+
+static QUIET: AtomicBool = AtomicBool::new(false);
+
+/// 全局 `--quiet`：屏蔽 info / success / highlight / warning 等提示输出
+pub fn enable_quiet() {
+    QUIET.store(true, Ordering::Relaxed);
+}
+
+pub fn quiet() -> bool {
+    QUIET.load(Ordering::Relaxed)
+}
+
+pub fn success(msg: &str) {
+    if !quiet() {
+        println!("{}", colors::success(msg));
+    }
+}
+
+pub fn info(msg: &str) {
+    if !quiet() {
+        println!("{}", colors::info(msg));
+    }
+}
+
+pub fn highlight(msg: &str) {
+    if !quiet() {
+        println!("{}", colors::highlight(msg));
+    }
+}
+
+pub fn warning(msg: &str) {
+    if !quiet() {
+        println!("{}", colors::warning(msg));
+    }
+}
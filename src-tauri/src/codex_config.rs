@@ -65,7 +65,7 @@ pub fn write_codex_live_atomic(
     let config_path = get_codex_config_path();
 
     if let Some(parent) = auth_path.parent() {
-        std::fs::create_dir_all(parent).map_err(|e| AppError::io(parent, e))?;
+        crate::dry_run::create_dir_all(parent).map_err(|e| AppError::io(parent, e))?;
     }
 
     // 读取旧内容用于回滚
@@ -2,6 +2,7 @@ use serde::{Deserialize, Serialize};
 use std::fs;
 use std::io::Write;
 use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
 
 use crate::error::AppError;
 
@@ -62,18 +63,39 @@ pub fn get_claude_settings_path() -> PathBuf {
     settings
 }
 
+/// 环境变量：覆盖应用配置目录（优先级低于 `--config-dir`）
+pub const CONFIG_DIR_ENV: &str = "CC_SWITCH_CONFIG_DIR";
+
+static APP_CONFIG_DIR_OVERRIDE: OnceLock<PathBuf> = OnceLock::new();
+
+/// 设置进程级配置目录覆盖（`--config-dir`），需在执行任何命令前调用；只有首次设置生效
+pub fn set_app_config_dir_override(dir: PathBuf) -> Result<(), AppError> {
+    APP_CONFIG_DIR_OVERRIDE
+        .set(dir)
+        .map_err(|dir| AppError::Config(format!("配置目录已被设置，无法覆盖为 {}", dir.display())))
+}
+
 /// 获取应用配置目录路径 (~/.cc-switch)
+///
+/// 优先级：`--config-dir` > `CC_SWITCH_CONFIG_DIR` > `~/.cc-switch`
 pub fn get_app_config_dir() -> PathBuf {
-    // CLI mode: no app store override, always use default
-    // if let Some(custom) = crate::app_store::get_app_config_dir_override() {
-    //     return custom;
-    // }
+    if let Some(dir) = APP_CONFIG_DIR_OVERRIDE.get() {
+        return dir.clone();
+    }
+    if let Some(dir) = std::env::var_os(CONFIG_DIR_ENV).filter(|v| !v.is_empty()) {
+        return PathBuf::from(dir);
+    }
 
     dirs::home_dir()
         .expect("无法获取用户主目录")
         .join(".cc-switch")
 }
 
+/// 可随时删除的缓存目录（~/.cc-switch/cache），如 Skills 仓库解压缓存
+pub fn get_app_cache_dir() -> PathBuf {
+    get_app_config_dir().join("cache")
+}
+
 /// 获取应用配置文件路径
 pub fn get_app_config_path() -> PathBuf {
     get_app_config_dir().join("config.json")
@@ -112,11 +134,6 @@ pub fn read_json_file<T: for<'a> Deserialize<'a>>(path: &Path) -> Result<T, AppE
 
 /// 写入 JSON 配置文件
 pub fn write_json_file<T: Serialize>(path: &Path, data: &T) -> Result<(), AppError> {
-    // 确保目录存在
-    if let Some(parent) = path.parent() {
-        fs::create_dir_all(parent).map_err(|e| AppError::io(parent, e))?;
-    }
-
     let json =
         serde_json::to_string_pretty(data).map_err(|e| AppError::JsonSerialize { source: e })?;
 
@@ -125,14 +142,18 @@ pub fn write_json_file<T: Serialize>(path: &Path, data: &T) -> Result<(), AppErr
 
 /// 原子写入文本文件（用于 TOML/纯文本）
 pub fn write_text_file(path: &Path, data: &str) -> Result<(), AppError> {
-    if let Some(parent) = path.parent() {
-        fs::create_dir_all(parent).map_err(|e| AppError::io(parent, e))?;
-    }
     atomic_write(path, data.as_bytes())
 }
 
-/// 原子写入：写入临时文件后 rename 替换，避免半写状态
+/// 原子写入：写入临时文件后 rename 替换，避免半写状态（会创建缺失的父目录）
 pub fn atomic_write(path: &Path, data: &[u8]) -> Result<(), AppError> {
+    if crate::dry_run::skip(format_args!(
+        "write {} ({} bytes)",
+        path.display(),
+        data.len()
+    )) {
+        return Ok(());
+    }
     if let Some(parent) = path.parent() {
         fs::create_dir_all(parent).map_err(|e| AppError::io(parent, e))?;
     }
@@ -226,7 +247,7 @@ mod tests {
 
 /// 复制文件
 pub fn copy_file(from: &Path, to: &Path) -> Result<(), AppError> {
-    fs::copy(from, to).map_err(|e| AppError::IoContext {
+    crate::dry_run::copy(from, to).map_err(|e| AppError::IoContext {
         context: format!("复制文件失败 ({} -> {})", from.display(), to.display()),
         source: e,
     })?;
@@ -236,7 +257,7 @@ pub fn copy_file(from: &Path, to: &Path) -> Result<(), AppError> {
 /// 删除文件
 pub fn delete_file(path: &Path) -> Result<(), AppError> {
     if path.exists() {
-        fs::remove_file(path).map_err(|e| AppError::io(path, e))?;
+        crate::dry_run::remove_file(path).map_err(|e| AppError::io(path, e))?;
     }
     Ok(())
 }
@@ -11,6 +11,7 @@ use rusqlite::types::ValueRef;
 use rusqlite::Connection;
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::sync::Mutex;
 use tempfile::NamedTempFile;
 
 const CC_SWITCH_SQL_EXPORT_HEADER: &str = "-- CC Switch SQLite 导出";
@@ -18,16 +19,21 @@ const CC_SWITCH_SQL_EXPORT_HEADER: &str = "-- CC Switch SQLite 导出";
 impl Database {
     /// 导出为 SQLite 兼容的 SQL 文本
     pub fn export_sql(&self, target_path: &Path) -> Result<(), AppError> {
-        let snapshot = self.snapshot_to_memory()?;
-        let dump = Self::dump_sql(&snapshot)?;
+        let dump = self.export_sql_string()?;
 
         if let Some(parent) = target_path.parent() {
-            fs::create_dir_all(parent).map_err(|e| AppError::io(parent, e))?;
+            crate::dry_run::create_dir_all(parent).map_err(|e| AppError::io(parent, e))?;
         }
 
         crate::config::atomic_write(target_path, dump.as_bytes())
     }
 
+    /// 导出 SQL 文本（不落盘，供加密导出等场景使用）
+    pub fn export_sql_string(&self) -> Result<String, AppError> {
+        let snapshot = self.snapshot_to_memory()?;
+        Self::dump_sql(&snapshot)
+    }
+
     /// 从 SQL 文件导入，返回生成的备份 ID（若无备份则为空字符串）
     pub fn import_sql(&self, source_path: &Path) -> Result<String, AppError> {
         if !source_path.exists() {
@@ -38,6 +44,11 @@ impl Database {
         }
 
         let sql_raw = fs::read_to_string(source_path).map_err(|e| AppError::io(source_path, e))?;
+        self.import_sql_str(&sql_raw)
+    }
+
+    /// 从 SQL 文本导入，返回生成的备份 ID（若无备份则为空字符串）
+    pub fn import_sql_str(&self, sql_raw: &str) -> Result<String, AppError> {
         let sql_content = sql_raw.trim_start_matches('\u{feff}');
         Self::validate_cc_switch_sql_export(sql_content)?;
 
@@ -53,14 +64,7 @@ impl Database {
         let temp_conn =
             Connection::open(&temp_path).map_err(|e| AppError::Database(e.to_string()))?;
 
-        temp_conn
-            .execute_batch(sql_content)
-            .map_err(|e| AppError::Database(format!("执行 SQL 导入失败: {e}")))?;
-
-        // 补齐缺失表/索引并进行基础校验
-        Self::create_tables_on_conn(&temp_conn)?;
-        Self::apply_schema_migrations_on_conn(&temp_conn)?;
-        Self::validate_basic_state(&temp_conn)?;
+        Self::load_sql_into(&temp_conn, sql_content)?;
 
         // 使用 Backup 将临时库原子写回主库
         {
@@ -79,6 +83,28 @@ impl Database {
         Ok(backup_id)
     }
 
+    /// 将 SQL 导出加载到独立的内存数据库，不触碰主库（供 `--dry-run` 预览）
+    pub fn load_sql_preview(sql_raw: &str) -> Result<Self, AppError> {
+        let sql_content = sql_raw.trim_start_matches('\u{feff}');
+        Self::validate_cc_switch_sql_export(sql_content)?;
+
+        let conn = Connection::open_in_memory().map_err(|e| AppError::Database(e.to_string()))?;
+        Self::load_sql_into(&conn, sql_content)?;
+        Ok(Self {
+            conn: Mutex::new(conn),
+        })
+    }
+
+    /// 执行 SQL，补齐缺失表/索引并进行基础校验
+    fn load_sql_into(conn: &Connection, sql_content: &str) -> Result<(), AppError> {
+        conn.execute_batch(sql_content)
+            .map_err(|e| AppError::Database(format!("执行 SQL 导入失败: {e}")))?;
+
+        Self::create_tables_on_conn(conn)?;
+        Self::apply_schema_migrations_on_conn(conn)?;
+        Self::validate_basic_state(conn)
+    }
+
     /// 创建内存快照以避免长时间持有数据库锁
     pub(crate) fn snapshot_to_memory(&self) -> Result<Connection, AppError> {
         let conn = lock_conn!(self.conn);
@@ -115,6 +141,13 @@ impl Database {
         if !db_path.exists() {
             return Ok(None);
         }
+        if crate::dry_run::skip(format_args!(
+            "back up {} to {}",
+            db_path.display(),
+            db_path.with_file_name("backups").display()
+        )) {
+            return Ok(None);
+        }
 
         let backup_dir = db_path
             .parent()
@@ -16,3 +16,4 @@ pub mod skills;
 // 所有 DAO 方法都通过 Database impl 提供，无需单独导出
 // 导出 FailoverQueueItem 供外部使用
 pub use failover::FailoverQueueItem;
+pub use settings::ProviderSwitchRecord;
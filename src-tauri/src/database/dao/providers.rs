@@ -17,7 +17,7 @@ impl Database {
     ) -> Result<IndexMap<String, Provider>, AppError> {
         let conn = lock_conn!(self.conn);
         let mut stmt = conn.prepare(
-            "SELECT id, name, settings_config, website_url, category, created_at, sort_index, notes, icon, icon_color, meta, in_failover_queue
+            "SELECT id, name, settings_config, website_url, category, created_at, sort_index, notes, icon, icon_color, meta, in_failover_queue, added_at, updated_at, extra
              FROM providers WHERE app_type = ?1
              ORDER BY COALESCE(sort_index, 999999), created_at ASC, id ASC"
         ).map_err(|e| AppError::Database(e.to_string()))?;
@@ -36,10 +36,14 @@ impl Database {
                 let icon_color: Option<String> = row.get(9)?;
                 let meta_str: String = row.get(10)?;
                 let in_failover_queue: bool = row.get(11)?;
+                let added_at: Option<i64> = row.get(12)?;
+                let updated_at: Option<i64> = row.get(13)?;
+                let extra_str: String = row.get(14)?;
 
                 let settings_config =
                     serde_json::from_str(&settings_config_str).unwrap_or(serde_json::Value::Null);
                 let meta: ProviderMeta = serde_json::from_str(&meta_str).unwrap_or_default();
+                let extra = serde_json::from_str(&extra_str).unwrap_or_default();
 
                 Ok((
                     id,
@@ -50,12 +54,15 @@ impl Database {
                         website_url,
                         category,
                         created_at,
+                        added_at,
+                        updated_at,
                         sort_index,
                         notes,
                         meta: Some(meta),
                         icon,
                         icon_color,
                         in_failover_queue,
+                        extra,
                     },
                 ))
             })
@@ -131,7 +138,7 @@ impl Database {
     ) -> Result<Option<Provider>, AppError> {
         let conn = lock_conn!(self.conn);
         let result = conn.query_row(
-            "SELECT name, settings_config, website_url, category, created_at, sort_index, notes, icon, icon_color, meta, in_failover_queue
+            "SELECT name, settings_config, website_url, category, created_at, sort_index, notes, icon, icon_color, meta, in_failover_queue, added_at, updated_at, extra
              FROM providers WHERE id = ?1 AND app_type = ?2",
             params![id, app_type],
             |row| {
@@ -146,9 +153,13 @@ impl Database {
                 let icon_color: Option<String> = row.get(8)?;
                 let meta_str: String = row.get(9)?;
                 let in_failover_queue: bool = row.get(10)?;
+                let added_at: Option<i64> = row.get(11)?;
+                let updated_at: Option<i64> = row.get(12)?;
+                let extra_str: String = row.get(13)?;
 
                 let settings_config = serde_json::from_str(&settings_config_str).unwrap_or(serde_json::Value::Null);
                 let meta: ProviderMeta = serde_json::from_str(&meta_str).unwrap_or_default();
+                let extra = serde_json::from_str(&extra_str).unwrap_or_default();
 
                 Ok(Provider {
                     id: id.to_string(),
@@ -157,12 +168,15 @@ impl Database {
                     website_url,
                     category,
                     created_at,
+                    added_at,
+                    updated_at,
                     sort_index,
                     notes,
                     meta: Some(meta),
                     icon,
                     icon_color,
                     in_failover_queue,
+                    extra,
                 })
             },
         );
@@ -198,6 +212,10 @@ impl Database {
             .ok();
 
         let is_update = existing.is_some();
+        let extra_json = serde_json::to_string(&provider.extra)
+            .map_err(|e| AppError::Database(format!("Failed to serialize extra: {e}")))?;
+        // added_at 仅在首次保存时写入，updated_at 每次保存都刷新
+        let now = chrono::Utc::now().timestamp();
         let (is_current, in_failover_queue) =
             existing.unwrap_or((false, provider.in_failover_queue));
 
@@ -216,8 +234,11 @@ impl Database {
                     icon_color = ?9,
                     meta = ?10,
                     is_current = ?11,
-                    in_failover_queue = ?12
-                WHERE id = ?13 AND app_type = ?14",
+                    in_failover_queue = ?12,
+                    added_at = COALESCE(added_at, ?13),
+                    updated_at = ?14,
+                    extra = ?15
+                WHERE id = ?16 AND app_type = ?17",
                 params![
                     provider.name,
                     serde_json::to_string(&provider.settings_config).map_err(|e| {
@@ -235,6 +256,9 @@ impl Database {
                     )))?,
                     is_current,
                     in_failover_queue,
+                    provider.added_at.unwrap_or(now),
+                    now,
+                    extra_json,
                     provider.id,
                     app_type,
                 ],
@@ -245,8 +269,9 @@ impl Database {
             tx.execute(
                 "INSERT INTO providers (
                     id, app_type, name, settings_config, website_url, category,
-                    created_at, sort_index, notes, icon, icon_color, meta, is_current, in_failover_queue
-                ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14)",
+                    created_at, sort_index, notes, icon, icon_color, meta, is_current, in_failover_queue,
+                    added_at, updated_at, extra
+                ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17)",
                 params![
                     provider.id,
                     app_type,
@@ -264,6 +289,9 @@ impl Database {
                         .map_err(|e| AppError::Database(format!("Failed to serialize meta: {e}")))?,
                     is_current,
                     in_failover_queue,
+                    provider.added_at.unwrap_or(now),
+                    now,
+                    extra_json,
                 ],
             )
             .map_err(|e| AppError::Database(e.to_string()))?;
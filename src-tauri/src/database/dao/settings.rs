@@ -5,6 +5,16 @@
 use crate::database::{lock_conn, Database};
 use crate::error::AppError;
 use rusqlite::params;
+use serde::{Deserialize, Serialize};
+
+/// 一次带时间戳的供应商切换记录（`provider history` 使用）
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProviderSwitchRecord {
+    pub provider_id: String,
+    /// 切换时间（Unix 毫秒）
+    pub switched_at: i64,
+}
 
 impl Database {
     /// 获取设置值
@@ -63,6 +73,67 @@ impl Database {
         }
     }
 
+    // --- 供应商切换历史 ---
+
+    /// 每个应用保留的切换历史条数
+    const PROVIDER_SWITCH_HISTORY_LIMIT: usize = 20;
+
+    /// 每个应用保留的带时间戳切换记录条数
+    const PROVIDER_SWITCH_LOG_LIMIT: usize = 500;
+
+    /// 获取指定应用的供应商切换历史（最近使用的在前）
+    pub fn get_provider_switch_history(&self, app_type: &str) -> Result<Vec<String>, AppError> {
+        Ok(self
+            .get_setting(&format!("provider_switch_history_{app_type}"))?
+            .and_then(|raw| serde_json::from_str(&raw).ok())
+            .unwrap_or_default())
+    }
+
+    /// 获取指定应用带时间戳的切换记录（最新的在前，不去重）
+    pub fn get_provider_switch_log(
+        &self,
+        app_type: &str,
+    ) -> Result<Vec<ProviderSwitchRecord>, AppError> {
+        Ok(self
+            .get_setting(&format!("provider_switch_log_{app_type}"))?
+            .and_then(|raw| serde_json::from_str(&raw).ok())
+            .unwrap_or_default())
+    }
+
+    /// 记录一次切换：将供应商移到历史最前面，并追加一条带时间戳的记录
+    pub fn record_provider_switch(&self, app_type: &str, id: &str) -> Result<(), AppError> {
+        self.record_provider_switch_at(app_type, id, chrono::Utc::now().timestamp_millis())
+    }
+
+    /// 同 [`Self::record_provider_switch`]，使用指定的切换时间（Unix 毫秒）
+    pub fn record_provider_switch_at(
+        &self,
+        app_type: &str,
+        id: &str,
+        switched_at: i64,
+    ) -> Result<(), AppError> {
+        let mut history = self.get_provider_switch_history(app_type)?;
+        history.retain(|existing| existing != id);
+        history.insert(0, id.to_string());
+        history.truncate(Self::PROVIDER_SWITCH_HISTORY_LIMIT);
+        let raw = serde_json::to_string(&history)
+            .map_err(|e| AppError::Database(format!("Failed to serialize history: {e}")))?;
+        self.set_setting(&format!("provider_switch_history_{app_type}"), &raw)?;
+
+        let mut log = self.get_provider_switch_log(app_type)?;
+        log.insert(
+            0,
+            ProviderSwitchRecord {
+                provider_id: id.to_string(),
+                switched_at,
+            },
+        );
+        log.truncate(Self::PROVIDER_SWITCH_LOG_LIMIT);
+        let raw = serde_json::to_string(&log)
+            .map_err(|e| AppError::Database(format!("Failed to serialize history: {e}")))?;
+        self.set_setting(&format!("provider_switch_log_{app_type}"), &raw)
+    }
+
     // --- 全局出站代理 ---
 
     /// 全局代理 URL 的存储键名
@@ -0,0 +1,57 @@
+//! 数据库维护
+//!
+//! 提供 VACUUM / PRAGMA optimize 压缩以及完整性检查。
+
+use super::{lock_conn, Database};
+use crate::error::AppError;
+use rusqlite::Connection;
+
+impl Database {
+    /// 当前数据库大小（字节，page_count × page_size）
+    pub fn database_size(&self) -> Result<u64, AppError> {
+        let conn = lock_conn!(self.conn);
+        Self::database_size_on_conn(&conn)
+    }
+
+    /// 执行 `VACUUM` 与 `PRAGMA optimize`，返回 (压缩前, 压缩后) 的字节数
+    pub fn vacuum(&self) -> Result<(u64, u64), AppError> {
+        let conn = lock_conn!(self.conn);
+        let before = Self::database_size_on_conn(&conn)?;
+
+        conn.execute_batch("VACUUM; PRAGMA optimize;")
+            .map_err(|e| AppError::Database(format!("VACUUM failed: {e}")))?;
+
+        let after = Self::database_size_on_conn(&conn)?;
+        Ok((before, after))
+    }
+
+    /// 执行 `PRAGMA integrity_check`，返回发现的问题（为空表示数据库完好）
+    pub fn integrity_check(&self) -> Result<Vec<String>, AppError> {
+        let conn = lock_conn!(self.conn);
+        let mut stmt = conn
+            .prepare("PRAGMA integrity_check")
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        let rows = stmt
+            .query_map([], |row| row.get::<_, String>(0))
+            .map_err(|e| AppError::Database(e.to_string()))?;
+
+        let mut problems = Vec::new();
+        for row in rows {
+            let line = row.map_err(|e| AppError::Database(e.to_string()))?;
+            if line != "ok" {
+                problems.push(line);
+            }
+        }
+        Ok(problems)
+    }
+
+    fn database_size_on_conn(conn: &Connection) -> Result<u64, AppError> {
+        let page_count: i64 = conn
+            .query_row("PRAGMA page_count", [], |row| row.get(0))
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        let page_size: i64 = conn
+            .query_row("PRAGMA page_size", [], |row| row.get(0))
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        Ok((page_count.max(0) as u64) * (page_size.max(0) as u64))
+    }
+}
@@ -14,6 +14,7 @@
 //! ├── mod.rs        - Database 结构体 + 初始化
 //! ├── schema.rs     - 表结构定义 + Schema 迁移
 //! ├── backup.rs     - SQL 导入导出 + 快照备份
+//! ├── maintenance.rs - VACUUM 压缩 + 完整性检查
 //! ├── migration.rs  - JSON → SQLite 数据迁移
 //! └── dao/          - 数据访问对象
 //!     ├── providers.rs
@@ -25,6 +26,7 @@
 
 mod backup;
 mod dao;
+mod maintenance;
 mod migration;
 mod schema;
 
@@ -32,12 +34,14 @@ mod schema;
 mod tests;
 
 // DAO 类型导出供外部使用
-pub use dao::FailoverQueueItem;
+pub use dao::{FailoverQueueItem, ProviderSwitchRecord};
 
 use crate::config::get_app_config_dir;
 use crate::error::AppError;
-use rusqlite::Connection;
+use rusqlite::hooks::Action;
+use rusqlite::{Connection, DatabaseName};
 use serde::Serialize;
+use std::path::{Path, PathBuf};
 use std::sync::Mutex;
 
 // DAO 方法通过 impl Database 提供，无需额外导出
@@ -47,7 +51,7 @@ const DB_BACKUP_RETAIN: usize = 10;
 
 /// 当前 Schema 版本号
 /// 每次修改表结构时递增，并在 schema.rs 中添加相应的迁移逻辑
-pub(crate) const SCHEMA_VERSION: i32 = 5;
+pub(crate) const SCHEMA_VERSION: i32 = 9;
 
 /// 安全地序列化 JSON，避免 unwrap panic
 pub(crate) fn to_json_string<T: Serialize>(value: &T) -> Result<String, AppError> {
@@ -80,14 +84,30 @@ impl Database {
     ///
     /// 数据库文件位于 `~/.cc-switch/cc-switch.db`
     pub fn init() -> Result<Self, AppError> {
-        let db_path = get_app_config_dir().join("cc-switch.db");
+        Self::init_with_upgrade_backup().map(|(db, _)| db)
+    }
 
-        // 确保父目录存在
-        if let Some(parent) = db_path.parent() {
-            std::fs::create_dir_all(parent).map_err(|e| AppError::io(parent, e))?;
-        }
+    /// 同 [`Self::init`]；已有数据库的 Schema 版本较旧时，先把数据库文件复制到
+    /// `backups/db_pre_upgrade_v<版本>_<时间>.db` 再迁移，并返回备份路径
+    pub(crate) fn init_with_upgrade_backup() -> Result<(Self, Option<PathBuf>), AppError> {
+        let db_path = get_app_config_dir().join("cc-switch.db");
 
-        let conn = Connection::open(&db_path).map_err(|e| AppError::Database(e.to_string()))?;
+        let mut upgrade_backup = None;
+        let conn = if crate::dry_run::is_dry_run() {
+            Self::open_dry_run_copy(&db_path)?
+        } else {
+            // 确保父目录存在
+            if let Some(parent) = db_path.parent() {
+                std::fs::create_dir_all(parent).map_err(|e| AppError::io(parent, e))?;
+            }
+            if db_path.exists() {
+                let version = Self::stored_schema_version(&db_path)?;
+                if version < SCHEMA_VERSION {
+                    upgrade_backup = Some(Self::backup_before_upgrade(&db_path, version)?);
+                }
+            }
+            Connection::open(&db_path).map_err(|e| AppError::Database(e.to_string()))?
+        };
 
         // 启用外键约束
         conn.execute("PRAGMA foreign_keys = ON;", [])
@@ -100,7 +120,51 @@ impl Database {
         db.apply_schema_migrations()?;
         db.ensure_model_pricing_seeded()?;
 
-        Ok(db)
+        Ok((db, upgrade_backup))
+    }
+
+    /// 隐式迁移前的数据库文件备份（迁移失败或需回退旧版本时可手动恢复）
+    fn backup_before_upgrade(db_path: &Path, version: i32) -> Result<PathBuf, AppError> {
+        let backup_dir = get_app_config_dir().join("backups");
+        std::fs::create_dir_all(&backup_dir).map_err(|e| AppError::io(&backup_dir, e))?;
+        let backup_path = backup_dir.join(format!(
+            "db_pre_upgrade_v{version}_{}.db",
+            chrono::Utc::now().format("%Y%m%d_%H%M%S")
+        ));
+        std::fs::copy(db_path, &backup_path).map_err(|e| AppError::io(&backup_path, e))?;
+        log::info!(
+            "数据库 Schema v{version} -> v{SCHEMA_VERSION}，迁移前已备份到 {}",
+            backup_path.display()
+        );
+        Ok(backup_path)
+    }
+
+    /// 预演模式：把磁盘数据库复制到内存，写入只记录日志，进程结束即丢弃
+    fn open_dry_run_copy(db_path: &Path) -> Result<Connection, AppError> {
+        let mut conn =
+            Connection::open_in_memory().map_err(|e| AppError::Database(e.to_string()))?;
+        if db_path.exists() {
+            conn.restore(
+                DatabaseName::Main,
+                db_path,
+                None::<fn(rusqlite::backup::Progress)>,
+            )
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        } else {
+            crate::dry_run::skip(format_args!("create database {}", db_path.display()));
+        }
+        conn.update_hook(Some(
+            |action: Action, _db: &str, table: &str, rowid: i64| {
+                let verb = match action {
+                    Action::SQLITE_INSERT => "insert into",
+                    Action::SQLITE_UPDATE => "update",
+                    Action::SQLITE_DELETE => "delete from",
+                    _ => "modify",
+                };
+                crate::dry_run::skip(format_args!("{verb} database table {table} (row {rowid})"));
+            },
+        ));
+        Ok(conn)
     }
 
     /// 创建内存数据库（用于测试）
@@ -4,7 +4,8 @@
 
 use super::{lock_conn, Database, SCHEMA_VERSION};
 use crate::error::AppError;
-use rusqlite::Connection;
+use rusqlite::{Connection, OpenFlags};
+use std::path::Path;
 
 impl Database {
     /// 创建所有数据库表
@@ -32,6 +33,9 @@ impl Database {
                 meta TEXT NOT NULL DEFAULT '{}',
                 is_current BOOLEAN NOT NULL DEFAULT 0,
                 in_failover_queue BOOLEAN NOT NULL DEFAULT 0,
+                added_at INTEGER,
+                updated_at INTEGER,
+                extra TEXT NOT NULL DEFAULT '{}',
                 PRIMARY KEY (id, app_type)
             )",
             [],
@@ -86,7 +90,8 @@ impl Database {
             enabled_codex BOOLEAN NOT NULL DEFAULT 0,
             enabled_gemini BOOLEAN NOT NULL DEFAULT 0,
             enabled_opencode BOOLEAN NOT NULL DEFAULT 0,
-            installed_at INTEGER NOT NULL DEFAULT 0
+            installed_at INTEGER NOT NULL DEFAULT 0,
+            source_url TEXT
         )",
             [],
         )
@@ -96,7 +101,7 @@ impl Database {
         conn.execute(
             "CREATE TABLE IF NOT EXISTS skill_repos (
             owner TEXT NOT NULL, name TEXT NOT NULL, branch TEXT NOT NULL DEFAULT 'main',
-            enabled BOOLEAN NOT NULL DEFAULT 1, PRIMARY KEY (owner, name)
+            enabled BOOLEAN NOT NULL DEFAULT 1, token TEXT, PRIMARY KEY (owner, name)
         )",
             [],
         )
@@ -309,6 +314,13 @@ impl Database {
         Ok(())
     }
 
+    /// 只读打开数据库文件并读取 user_version，不创建表也不执行迁移
+    pub(crate) fn stored_schema_version(db_path: &Path) -> Result<i32, AppError> {
+        let conn = Connection::open_with_flags(db_path, OpenFlags::SQLITE_OPEN_READ_ONLY)
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        Self::get_user_version(&conn)
+    }
+
     /// 应用 Schema 迁移
     pub(crate) fn apply_schema_migrations(&self) -> Result<(), AppError> {
         let conn = lock_conn!(self.conn);
@@ -360,6 +372,26 @@ impl Database {
                         Self::migrate_v4_to_v5(conn)?;
                         Self::set_user_version(conn, 5)?;
                     }
+                    5 => {
+                        log::info!("迁移数据库从 v5 到 v6（Skill 压缩包来源）");
+                        Self::migrate_v5_to_v6(conn)?;
+                        Self::set_user_version(conn, 6)?;
+                    }
+                    6 => {
+                        log::info!("迁移数据库从 v6 到 v7（供应商时间戳）");
+                        Self::migrate_v6_to_v7(conn)?;
+                        Self::set_user_version(conn, 7)?;
+                    }
+                    7 => {
+                        log::info!("迁移数据库从 v7 到 v8（供应商未识别字段）");
+                        Self::migrate_v7_to_v8(conn)?;
+                        Self::set_user_version(conn, 8)?;
+                    }
+                    8 => {
+                        log::info!("迁移数据库从 v8 到 v9（私有 Skill 仓库令牌）");
+                        Self::migrate_v8_to_v9(conn)?;
+                        Self::set_user_version(conn, 9)?;
+                    }
                     _ => {
                         return Err(AppError::Database(format!(
                             "未知的数据库版本 {version}，无法迁移到 {SCHEMA_VERSION}"
@@ -914,6 +946,58 @@ impl Database {
         Ok(())
     }
 
+    /// v5 -> v6 迁移：skills 表新增 source_url（从 release 压缩包安装的来源地址）
+    fn migrate_v5_to_v6(conn: &Connection) -> Result<(), AppError> {
+        if Self::table_exists(conn, "skills")? {
+            Self::add_column_if_missing(conn, "skills", "source_url", "TEXT")?;
+        }
+
+        log::info!("v5 -> v6 迁移完成：已添加 Skill 来源地址字段");
+        Ok(())
+    }
+
+    /// v6 -> v7 迁移：providers 新增 added_at / updated_at（Unix 秒）
+    ///
+    /// 已有记录的 added_at 由 created_at 回填（毫秒值换算为秒）。
+    fn migrate_v6_to_v7(conn: &Connection) -> Result<(), AppError> {
+        if Self::table_exists(conn, "providers")? {
+            Self::add_column_if_missing(conn, "providers", "added_at", "INTEGER")?;
+            Self::add_column_if_missing(conn, "providers", "updated_at", "INTEGER")?;
+            conn.execute(
+                "UPDATE providers SET added_at = CASE
+                    WHEN created_at > 100000000000 THEN created_at / 1000
+                    ELSE created_at
+                 END
+                 WHERE added_at IS NULL AND created_at IS NOT NULL",
+                [],
+            )
+            .map_err(|e| AppError::Database(format!("回填 providers.added_at 失败: {e}")))?;
+        }
+
+        log::info!("v6 -> v7 迁移完成：已添加供应商时间戳字段");
+        Ok(())
+    }
+
+    /// v7 -> v8 迁移：providers 表新增 extra（保留未识别的供应商字段，JSON 对象）
+    fn migrate_v7_to_v8(conn: &Connection) -> Result<(), AppError> {
+        if Self::table_exists(conn, "providers")? {
+            Self::add_column_if_missing(conn, "providers", "extra", "TEXT NOT NULL DEFAULT '{}'")?;
+        }
+
+        log::info!("v7 -> v8 迁移完成：已添加供应商 extra 字段");
+        Ok(())
+    }
+
+    /// v8 -> v9 迁移：skill_repos 表新增 token（私有仓库令牌的钥匙串引用）
+    fn migrate_v8_to_v9(conn: &Connection) -> Result<(), AppError> {
+        if Self::table_exists(conn, "skill_repos")? {
+            Self::add_column_if_missing(conn, "skill_repos", "token", "TEXT")?;
+        }
+
+        log::info!("v8 -> v9 迁移完成：已添加 Skill 仓库令牌字段");
+        Ok(())
+    }
+
     /// 插入默认模型定价数据
     /// 格式: (model_id, display_name, input, output, cache_read, cache_creation)
     /// 注意: model_id 使用短横线格式（如 claude-haiku-4-5），与 API 返回的模型名称标准化后一致
@@ -327,6 +327,155 @@ fn schema_migration_v4_adds_pricing_model_columns() {
     );
 }
 
+#[test]
+fn schema_migration_v5_adds_skill_source_url() {
+    let conn = Connection::open_in_memory().expect("open memory db");
+    conn.execute_batch(
+        r#"
+        CREATE TABLE skills (id TEXT PRIMARY KEY, name TEXT NOT NULL, directory TEXT NOT NULL);
+        "#,
+    )
+    .expect("seed v5 schema");
+
+    Database::set_user_version(&conn, 5).expect("set user_version=5");
+    Database::apply_schema_migrations_on_conn(&conn).expect("apply migrations");
+
+    let source_url = get_column_info(&conn, "skills", "source_url");
+    assert_eq!(source_url.r#type, "TEXT");
+    assert_eq!(source_url.notnull, 0);
+    assert_eq!(
+        Database::get_user_version(&conn).expect("version after migration"),
+        SCHEMA_VERSION
+    );
+}
+
+#[test]
+fn schema_migration_v8_adds_skill_repo_token() {
+    let conn = Connection::open_in_memory().expect("open memory db");
+    conn.execute_batch(
+        r#"
+        CREATE TABLE skill_repos (
+            owner TEXT NOT NULL, name TEXT NOT NULL, branch TEXT NOT NULL DEFAULT 'main',
+            enabled BOOLEAN NOT NULL DEFAULT 1, PRIMARY KEY (owner, name)
+        );
+        INSERT INTO skill_repos (owner, name) VALUES ('anthropics', 'skills');
+        "#,
+    )
+    .expect("seed v8 schema");
+
+    Database::set_user_version(&conn, 8).expect("set user_version=8");
+    Database::apply_schema_migrations_on_conn(&conn).expect("apply migrations");
+
+    let token = get_column_info(&conn, "skill_repos", "token");
+    assert_eq!(token.r#type, "TEXT");
+    assert_eq!(token.notnull, 0);
+    let stored: Option<String> = conn
+        .query_row("SELECT token FROM skill_repos", [], |row| row.get(0))
+        .expect("read token");
+    assert_eq!(stored, None);
+    assert_eq!(
+        Database::get_user_version(&conn).expect("version after migration"),
+        SCHEMA_VERSION
+    );
+}
+
+#[test]
+fn schema_migration_v6_adds_provider_timestamps() {
+    let conn = Connection::open_in_memory().expect("open memory db");
+    conn.execute_batch(
+        r#"
+        CREATE TABLE providers (
+            id TEXT NOT NULL, app_type TEXT NOT NULL, name TEXT NOT NULL,
+            settings_config TEXT NOT NULL, created_at INTEGER,
+            PRIMARY KEY (id, app_type)
+        );
+        INSERT INTO providers VALUES ('ms', 'claude', 'Millis', '{}', 1700000000000);
+        INSERT INTO providers VALUES ('s', 'claude', 'Seconds', '{}', 1700000000);
+        INSERT INTO providers VALUES ('none', 'claude', 'None', '{}', NULL);
+        "#,
+    )
+    .expect("seed v6 schema");
+
+    Database::set_user_version(&conn, 6).expect("set user_version=6");
+    Database::apply_schema_migrations_on_conn(&conn).expect("apply migrations");
+
+    assert!(Database::has_column(&conn, "providers", "updated_at").expect("check column"));
+    let added_at = |id: &str| -> Option<i64> {
+        conn.query_row("SELECT added_at FROM providers WHERE id = ?1", [id], |r| {
+            r.get(0)
+        })
+        .expect("read added_at")
+    };
+    assert_eq!(added_at("ms"), Some(1_700_000_000));
+    assert_eq!(added_at("s"), Some(1_700_000_000));
+    assert_eq!(added_at("none"), None);
+}
+
+#[test]
+fn provider_unknown_fields_survive_db_round_trip() {
+    let provider: Provider = serde_json::from_value(json!({
+        "id": "p1",
+        "name": "P1",
+        "settingsConfig": {},
+        "futureField": { "nested": [1, 2] },
+        "someFlag": true
+    }))
+    .expect("deserialize provider with unknown fields");
+    assert_eq!(provider.extra.len(), 2);
+
+    let db = Database::memory().expect("create memory db");
+    db.save_provider("claude", &provider)
+        .expect("insert provider");
+    db.save_provider("claude", &provider)
+        .expect("update provider");
+
+    let loaded = db
+        .get_all_providers("claude")
+        .expect("load providers")
+        .swap_remove("p1")
+        .expect("provider exists");
+    assert_eq!(
+        loaded.extra.get("futureField"),
+        Some(&json!({ "nested": [1, 2] }))
+    );
+    assert_eq!(loaded.extra.get("someFlag"), Some(&json!(true)));
+
+    let value = serde_json::to_value(&loaded).expect("serialize provider");
+    assert_eq!(value["futureField"], json!({ "nested": [1, 2] }));
+}
+
+#[test]
+fn save_provider_sets_added_at_once_and_bumps_updated_at() {
+    let db = Database::memory().expect("create memory db");
+    let mut provider = Provider::with_id("p1".to_string(), "P1".to_string(), json!({}), None);
+    db.save_provider("claude", &provider)
+        .expect("insert provider");
+
+    let first = db
+        .get_provider_by_id("p1", "claude")
+        .expect("load provider")
+        .expect("provider exists");
+    let added_at = first.added_at.expect("added_at set on insert");
+    assert!(first.updated_at.is_some_and(|t| t >= added_at));
+
+    // 旧记录再次保存时（即使传入的 added_at 为空）也不得覆盖首次时间
+    {
+        let conn = db.conn.lock().expect("lock conn");
+        conn.execute("UPDATE providers SET added_at = 1, updated_at = 1", [])
+            .expect("backdate timestamps");
+    }
+    provider.name = "Renamed".to_string();
+    db.save_provider("claude", &provider)
+        .expect("update provider");
+
+    let second = db
+        .get_provider_by_id("p1", "claude")
+        .expect("load provider")
+        .expect("provider exists");
+    assert_eq!(second.added_at, Some(1));
+    assert!(second.updated_at.is_some_and(|t| t > 1));
+}
+
 #[test]
 fn schema_create_tables_repairs_legacy_proxy_config_singleton_to_per_app() {
     let conn = Connection::open_in_memory().expect("open memory db");
@@ -531,12 +680,15 @@ fn dry_run_validates_schema_compatibility() {
             website_url: None,
             category: None,
             created_at: Some(1234567890),
+            added_at: None,
+            updated_at: None,
             sort_index: None,
             notes: None,
             meta: None,
             icon: None,
             icon_color: None,
             in_failover_queue: false,
+            extra: Default::default(),
         },
     );
 
@@ -624,3 +776,60 @@ fn schema_model_pricing_is_seeded_on_init() {
         gemini_count
     );
 }
+
+#[test]
+fn vacuum_and_integrity_check_on_healthy_database() {
+    let db = Database::memory().expect("create memory db");
+
+    let problems = db.integrity_check().expect("run integrity check");
+    assert!(problems.is_empty(), "健康数据库不应报告问题: {problems:?}");
+
+    let (before, after) = db.vacuum().expect("vacuum database");
+    assert!(before > 0, "数据库大小应大于 0");
+    assert!(
+        after <= before,
+        "VACUUM 后大小不应增加: {before} -> {after}"
+    );
+    assert_eq!(db.database_size().expect("size"), after);
+}
+
+#[test]
+fn provider_switch_history_is_most_recent_first_and_deduplicated() {
+    let db = Database::memory().expect("memory db");
+    assert!(db
+        .get_provider_switch_history("claude")
+        .expect("empty history")
+        .is_empty());
+
+    for id in ["a", "b", "a", "c"] {
+        db.record_provider_switch("claude", id).expect("record");
+    }
+    db.record_provider_switch("codex", "x")
+        .expect("record codex");
+
+    assert_eq!(
+        db.get_provider_switch_history("claude").expect("history"),
+        vec!["c".to_string(), "a".to_string(), "b".to_string()]
+    );
+    assert_eq!(
+        db.get_provider_switch_history("codex").expect("history"),
+        vec!["x".to_string()]
+    );
+}
+
+#[test]
+fn provider_switch_log_keeps_every_switch_with_timestamps() {
+    let db = Database::memory().expect("memory db");
+    for (id, at) in [("a", 1_000), ("b", 2_000), ("a", 3_000)] {
+        db.record_provider_switch_at("claude", id, at)
+            .expect("record");
+    }
+
+    let log = db.get_provider_switch_log("claude").expect("log");
+    let entries: Vec<_> = log
+        .iter()
+        .map(|r| (r.provider_id.as_str(), r.switched_at))
+        .collect();
+    assert_eq!(entries, vec![("a", 3_000), ("b", 2_000), ("a", 1_000)]);
+    assert!(db.get_provider_switch_log("codex").expect("log").is_empty());
+}
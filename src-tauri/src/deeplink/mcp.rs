@@ -0,0 +1,167 @@
+//! MCP server import from deep link.
+
+use super::utils::decode_base64_param;
+use super::DeepLinkImportRequest;
+use crate::app_config::{McpApps, McpServer};
+use crate::error::AppError;
+use crate::mcp::validate_server_spec;
+use crate::services::McpService;
+use crate::store::AppState;
+use crate::AppType;
+use serde_json::{json, Map, Value};
+use std::str::FromStr;
+
+/// Import an MCP server from a deep link request.
+///
+/// Returns the id of the imported server.
+pub fn import_mcp_from_deeplink(
+    state: &AppState,
+    request: DeepLinkImportRequest,
+) -> Result<String, AppError> {
+    if request.resource != "mcp" {
+        return Err(AppError::InvalidInput(format!(
+            "Expected mcp resource, got '{}'",
+            request.resource
+        )));
+    }
+
+    let name = request
+        .name
+        .clone()
+        .filter(|n| !n.trim().is_empty())
+        .ok_or_else(|| AppError::InvalidInput("Missing 'name' field for mcp".to_string()))?;
+
+    let server_id = name
+        .trim()
+        .chars()
+        .filter(|c| c.is_alphanumeric() || *c == '-' || *c == '_')
+        .collect::<String>()
+        .to_lowercase();
+    if server_id.is_empty() {
+        return Err(AppError::InvalidInput(format!(
+            "Cannot derive MCP server id from name '{name}'"
+        )));
+    }
+    let exists = {
+        let cfg = state.config.read()?;
+        cfg.mcp
+            .servers
+            .as_ref()
+            .is_some_and(|servers| servers.contains_key(&server_id))
+    };
+    if exists {
+        return Err(AppError::InvalidInput(format!(
+            "MCP server '{server_id}' already exists"
+        )));
+    }
+
+    let spec = build_server_spec(&request)?;
+    validate_server_spec(&spec)?;
+
+    let server = McpServer {
+        id: server_id.clone(),
+        name: name.trim().to_string(),
+        server: spec,
+        apps: parse_apps(request.apps.as_deref(), request.enabled)?,
+        description: request.description.clone(),
+        homepage: request.homepage.clone().filter(|h| !h.is_empty()),
+        docs: None,
+        tags: Vec::new(),
+    };
+
+    McpService::upsert_server(state, server)?;
+
+    Ok(server_id)
+}
+
+/// Build the server spec: start from the decoded `config` object (if any),
+/// then apply `command` / `args` / `env` on top.
+pub(crate) fn build_server_spec(request: &DeepLinkImportRequest) -> Result<Value, AppError> {
+    let mut spec = match &request.config {
+        Some(raw) => {
+            let decoded = decode_base64_param("config", raw)?;
+            let value: Value = serde_json::from_slice(&decoded)
+                .map_err(|e| AppError::InvalidInput(format!("Invalid MCP config JSON: {e}")))?;
+            if !value.is_object() {
+                return Err(AppError::InvalidInput(
+                    "MCP config must be a JSON object".to_string(),
+                ));
+            }
+            value
+        }
+        None => json!({ "type": "stdio" }),
+    };
+    let obj = spec
+        .as_object_mut()
+        .expect("spec is guaranteed to be an object");
+
+    if let Some(command) = &request.command {
+        obj.insert("command".to_string(), json!(command));
+    }
+    if let Some(args) = &request.args {
+        obj.insert("args".to_string(), json!(parse_args(args)?));
+    }
+    if let Some(env) = &request.env {
+        obj.insert("env".to_string(), Value::Object(parse_env(env)?));
+    }
+
+    Ok(spec)
+}
+
+/// `args` accepts either a JSON array or a comma-separated list.
+fn parse_args(raw: &str) -> Result<Vec<String>, AppError> {
+    let trimmed = raw.trim();
+    if trimmed.starts_with('[') {
+        return serde_json::from_str::<Vec<String>>(trimmed).map_err(|e| {
+            AppError::InvalidInput(format!("Invalid 'args': expected JSON string array: {e}"))
+        });
+    }
+    Ok(trimmed
+        .split(',')
+        .map(|a| a.trim().to_string())
+        .filter(|a| !a.is_empty())
+        .collect())
+}
+
+/// `env` is a comma-separated list of `KEY=VALUE` pairs.
+fn parse_env(raw: &str) -> Result<Map<String, Value>, AppError> {
+    let mut env = Map::new();
+    for pair in raw.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+        let (key, value) = pair.split_once('=').ok_or_else(|| {
+            AppError::InvalidInput(format!("Invalid 'env' entry '{pair}': expected KEY=VALUE"))
+        })?;
+        let key = key.trim();
+        if key.is_empty() {
+            return Err(AppError::InvalidInput(format!(
+                "Invalid 'env' entry '{pair}': key cannot be empty"
+            )));
+        }
+        env.insert(key.to_string(), json!(value));
+    }
+    Ok(env)
+}
+
+/// Resolve enabled apps; defaults to all apps. `enabled=false` imports without enabling.
+fn parse_apps(raw: Option<&str>, enabled: Option<bool>) -> Result<McpApps, AppError> {
+    let mut apps = McpApps::default();
+    if enabled == Some(false) {
+        return Ok(apps);
+    }
+
+    match raw.map(str::trim).filter(|r| !r.is_empty()) {
+        Some(list) => {
+            for name in list.split(',').map(str::trim).filter(|a| !a.is_empty()) {
+                let app = AppType::from_str(name)
+                    .map_err(|_| AppError::InvalidInput(format!("Invalid app type: {name}")))?;
+                apps.set_enabled_for(&app, true);
+            }
+        }
+        None => {
+            for app in [AppType::Claude, AppType::Codex, AppType::Gemini] {
+                apps.set_enabled_for(&app, true);
+            }
+        }
+    }
+
+    Ok(apps)
+}
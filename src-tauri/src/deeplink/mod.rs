@@ -1,21 +1,31 @@
 //! Deep link import functionality for CC Switch (CLI edition).
 //!
 //! Implements the `ccswitch://v1/import?...` protocol for importing resources.
-//! Currently supports importing provider configurations for Claude/Codex/Gemini.
+//! Currently supports importing provider configurations for Claude/Codex/Gemini,
+//! MCP server definitions and skills.
 
+mod mcp;
 mod parser;
 mod provider;
+mod register;
+mod skill;
 mod utils;
 
 use serde::{Deserialize, Serialize};
 
+pub(crate) use mcp::build_server_spec;
+pub use mcp::import_mcp_from_deeplink;
 pub use parser::parse_deeplink_url;
-pub use provider::import_provider_from_deeplink;
+pub(crate) use provider::build_settings_config;
+pub use provider::{import_provider_from_deeplink, parse_and_merge_config};
+pub use register::{register_url_scheme, unregister_url_scheme};
+pub use skill::import_skill_from_deeplink;
+pub(crate) use utils::{infer_homepage_from_endpoint, validate_url};
 
 /// Deep link import request model.
 ///
 /// This mirrors the upstream request model to keep URL parsing compatible.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct DeepLinkImportRequest {
     pub version: String,
@@ -62,6 +72,13 @@ pub struct DeepLinkImportRequest {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub branch: Option<String>,
 
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub command: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub args: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub env: Option<String>,
+
     #[serde(skip_serializing_if = "Option::is_none")]
     pub config: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -2,16 +2,23 @@
 //!
 //! Parses `ccswitch://` URLs into `DeepLinkImportRequest` structures.
 
-use super::utils::validate_url;
+use super::utils::{decode_base64_param, validate_https_url, validate_url};
 use super::DeepLinkImportRequest;
 use crate::error::AppError;
 use std::collections::HashMap;
 use url::Url;
 
+/// Maximum length of the (Base64 encoded) `config` parameter.
+const MAX_CONFIG_PARAM_LEN: usize = 64 * 1024;
+/// Maximum length of the `apiKey` parameter.
+const MAX_API_KEY_LEN: usize = 1024;
+
 /// Parse a `ccswitch://` URL into a `DeepLinkImportRequest`.
 ///
 /// Expected format:
 /// `ccswitch://v1/import?resource=provider&...`
+/// `ccswitch://v1/import?resource=mcp&...`
+/// `ccswitch://v1/import?resource=skill&...`
 pub fn parse_deeplink_url(url_str: &str) -> Result<DeepLinkImportRequest, AppError> {
     let url = Url::parse(url_str)
         .map_err(|e| AppError::InvalidInput(format!("Invalid deep link URL: {e}")))?;
@@ -48,6 +55,8 @@ pub fn parse_deeplink_url(url_str: &str) -> Result<DeepLinkImportRequest, AppErr
 
     match resource.as_str() {
         "provider" => parse_provider_deeplink(&params, version, resource),
+        "mcp" => parse_mcp_deeplink(&params, version, resource),
+        "skill" => parse_skill_deeplink(&params, version, resource),
         _ => Err(AppError::InvalidInput(format!(
             "Unsupported resource type: {resource}"
         ))),
@@ -94,6 +103,21 @@ fn parse_provider_deeplink(
         }
     }
 
+    if let Some(key) = api_key.as_deref().filter(|k| !k.is_empty()) {
+        validate_api_key(key)?;
+    }
+
+    let config = params.get("config").cloned();
+    let config_format = params.get("configFormat").cloned();
+    let config_url = params.get("configUrl").cloned();
+
+    if let Some(ref raw) = config {
+        validate_config_param(raw, config_format.as_deref())?;
+    }
+    if let Some(ref cu) = config_url {
+        validate_https_url(cu.trim(), "configUrl")?;
+    }
+
     Ok(DeepLinkImportRequest {
         version,
         resource,
@@ -118,9 +142,12 @@ fn parse_provider_deeplink(
         repo: None,
         directory: None,
         branch: None,
-        config: params.get("config").cloned(),
-        config_format: params.get("configFormat").cloned(),
-        config_url: params.get("configUrl").cloned(),
+        command: None,
+        args: None,
+        env: None,
+        config,
+        config_format,
+        config_url,
         usage_enabled: params
             .get("usageEnabled")
             .and_then(|v| v.parse::<bool>().ok()),
@@ -134,3 +161,203 @@ fn parse_provider_deeplink(
             .and_then(|v| v.parse::<u64>().ok()),
     })
 }
+
+fn parse_mcp_deeplink(
+    params: &HashMap<String, String>,
+    version: String,
+    resource: String,
+) -> Result<DeepLinkImportRequest, AppError> {
+    let name = params
+        .get("name")
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+        .ok_or_else(|| AppError::InvalidInput("Missing 'name' parameter".to_string()))?;
+
+    let command = params
+        .get("command")
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty());
+    let config = params.get("config").cloned();
+    if command.is_none() && config.is_none() {
+        return Err(AppError::InvalidInput(
+            "MCP deep link requires either 'command' or 'config' parameter".to_string(),
+        ));
+    }
+    if let Some(ref raw) = config {
+        validate_config_param(raw, Some("json"))?;
+    }
+
+    let apps = params.get("apps").cloned();
+    if let Some(ref raw) = apps {
+        validate_apps_param(raw)?;
+    }
+
+    let homepage = params.get("homepage").cloned();
+    if let Some(ref hp) = homepage {
+        if !hp.is_empty() {
+            validate_url(hp, "homepage")?;
+        }
+    }
+
+    Ok(DeepLinkImportRequest {
+        version,
+        resource,
+        app: None,
+        name: Some(name),
+        enabled: params.get("enabled").and_then(|v| v.parse::<bool>().ok()),
+        homepage,
+        endpoint: None,
+        api_key: None,
+        icon: None,
+        model: None,
+        notes: None,
+        haiku_model: None,
+        sonnet_model: None,
+        opus_model: None,
+        content: None,
+        description: params.get("description").cloned(),
+        apps,
+        repo: None,
+        directory: None,
+        branch: None,
+        command,
+        args: params.get("args").cloned(),
+        env: params.get("env").cloned(),
+        config,
+        config_format: None,
+        config_url: None,
+        usage_enabled: None,
+        usage_script: None,
+        usage_api_key: None,
+        usage_base_url: None,
+        usage_access_token: None,
+        usage_user_id: None,
+        usage_auto_interval: None,
+    })
+}
+
+fn parse_skill_deeplink(
+    params: &HashMap<String, String>,
+    version: String,
+    resource: String,
+) -> Result<DeepLinkImportRequest, AppError> {
+    let repo = params
+        .get("repo")
+        .map(|v| v.trim().trim_end_matches('/').to_string())
+        .filter(|v| !v.is_empty())
+        .ok_or_else(|| AppError::InvalidInput("Missing 'repo' parameter".to_string()))?;
+    let valid_repo = repo
+        .split_once('/')
+        .is_some_and(|(owner, name)| !owner.is_empty() && !name.is_empty() && !name.contains('/'));
+    if !valid_repo {
+        return Err(AppError::InvalidInput(format!(
+            "Invalid 'repo': expected 'owner/name', got '{repo}'"
+        )));
+    }
+
+    let directory = params
+        .get("directory")
+        .map(|v| v.trim().trim_matches('/').to_string())
+        .filter(|v| !v.is_empty())
+        .ok_or_else(|| AppError::InvalidInput("Missing 'directory' parameter".to_string()))?;
+    if directory.split('/').any(|seg| seg == "..") {
+        return Err(AppError::InvalidInput(format!(
+            "Invalid 'directory': must not contain '..', got '{directory}'"
+        )));
+    }
+
+    let apps = params.get("apps").cloned();
+    if let Some(ref raw) = apps {
+        validate_apps_param(raw)?;
+    }
+
+    Ok(DeepLinkImportRequest {
+        version,
+        resource,
+        app: None,
+        name: params.get("name").cloned(),
+        enabled: None,
+        homepage: None,
+        endpoint: None,
+        api_key: None,
+        icon: None,
+        model: None,
+        notes: None,
+        haiku_model: None,
+        sonnet_model: None,
+        opus_model: None,
+        content: None,
+        description: None,
+        apps,
+        repo: Some(repo),
+        directory: Some(directory),
+        branch: params
+            .get("branch")
+            .map(|v| v.trim().to_string())
+            .filter(|v| !v.is_empty()),
+        command: None,
+        args: None,
+        env: None,
+        config: None,
+        config_format: None,
+        config_url: None,
+        usage_enabled: None,
+        usage_script: None,
+        usage_api_key: None,
+        usage_base_url: None,
+        usage_access_token: None,
+        usage_user_id: None,
+        usage_auto_interval: None,
+    })
+}
+
+/// Validate a comma-separated `apps` list against the supported app ids.
+fn validate_apps_param(raw: &str) -> Result<(), AppError> {
+    for app in raw.split(',').map(str::trim).filter(|a| !a.is_empty()) {
+        if app != "claude" && app != "codex" && app != "gemini" {
+            return Err(AppError::InvalidInput(format!(
+                "Invalid app in 'apps': must be 'claude', 'codex', or 'gemini', got '{app}'"
+            )));
+        }
+    }
+    Ok(())
+}
+
+fn validate_api_key(key: &str) -> Result<(), AppError> {
+    if key.len() > MAX_API_KEY_LEN {
+        return Err(AppError::InvalidInput(format!(
+            "Invalid 'apiKey': exceeds {MAX_API_KEY_LEN} characters"
+        )));
+    }
+    if key.chars().any(|c| c.is_whitespace() || c.is_control()) {
+        return Err(AppError::InvalidInput(
+            "Invalid 'apiKey': must not contain whitespace or control characters".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Ensure `config` is bounded in size and decodes to valid JSON/TOML per `configFormat`.
+fn validate_config_param(raw: &str, format: Option<&str>) -> Result<(), AppError> {
+    if raw.len() > MAX_CONFIG_PARAM_LEN {
+        return Err(AppError::InvalidInput(format!(
+            "Invalid 'config': exceeds {MAX_CONFIG_PARAM_LEN} bytes"
+        )));
+    }
+
+    let decoded = decode_base64_param("config", raw)?;
+    let content = String::from_utf8(decoded)
+        .map_err(|e| AppError::InvalidInput(format!("Invalid 'config': not UTF-8: {e}")))?;
+
+    match format.unwrap_or("json") {
+        "json" => serde_json::from_str::<serde_json::Value>(&content)
+            .map(|_| ())
+            .map_err(|e| AppError::InvalidInput(format!("Invalid 'config': not valid JSON: {e}"))),
+        "toml" => toml::from_str::<toml::Value>(&content)
+            .map(|_| ())
+            .map_err(|e| AppError::InvalidInput(format!("Invalid 'config': not valid TOML: {e}"))),
+        other => Err(AppError::InvalidInput(format!(
+            "Invalid 'configFormat': must be 'json' or 'toml', got '{other}'"
+        ))),
+    }
+}
@@ -148,15 +148,39 @@ fn build_provider_from_request(
         website_url: request.homepage.clone(),
         category: None,
         created_at: Some(chrono::Utc::now().timestamp_millis()),
+        added_at: None,
+        updated_at: None,
         sort_index: None,
         notes: request.notes.clone(),
         meta,
         icon: request.icon.clone(),
         icon_color: None,
         in_failover_queue: false,
+        extra: Default::default(),
     })
 }
 
+/// Build an app's `settings_config` from endpoint / API key / model, the same way a
+/// deep link import does (used by `provider add` with flags).
+pub(crate) fn build_settings_config(
+    app_type: &AppType,
+    endpoint: &str,
+    api_key: &str,
+    model: Option<&str>,
+) -> serde_json::Value {
+    let request = DeepLinkImportRequest {
+        endpoint: Some(endpoint.to_string()),
+        api_key: Some(api_key.to_string()),
+        model: model.map(str::to_string),
+        ..Default::default()
+    };
+    match app_type {
+        AppType::Claude => build_claude_settings(&request),
+        AppType::Codex => build_codex_settings(&request),
+        AppType::Gemini => build_gemini_settings(&request),
+    }
+}
+
 fn get_primary_endpoint(request: &DeepLinkImportRequest) -> String {
     request
         .endpoint
@@ -0,0 +1,274 @@
+//! OS-level `ccswitch://` URL scheme registration.
+//!
+//! - Linux: `.desktop` entry with `x-scheme-handler/ccswitch` (+ `xdg-mime default`)
+//! - macOS: AppleScript applet bundle declaring `CFBundleURLTypes`, registered via `lsregister`
+//! - Windows: `HKCU\Software\Classes\ccswitch` registry keys
+//!
+//! Each function returns human-readable descriptions of what was changed.
+
+use crate::error::AppError;
+use std::path::Path;
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+use std::path::PathBuf;
+#[cfg(any(target_os = "linux", target_os = "macos", target_os = "windows"))]
+use std::process::Command;
+
+pub const URL_SCHEME: &str = "ccswitch";
+
+/// Register `exe` as the handler for `ccswitch://` URLs.
+pub fn register_url_scheme(exe: &Path) -> Result<Vec<String>, AppError> {
+    platform::register(exe)
+}
+
+/// Remove the `ccswitch://` association created by [`register_url_scheme`].
+pub fn unregister_url_scheme() -> Result<Vec<String>, AppError> {
+    platform::unregister()
+}
+
+/// Run a helper command; returns an error if it cannot be spawned or exits non-zero.
+#[cfg(any(target_os = "linux", target_os = "macos", target_os = "windows"))]
+fn run(program: &str, args: &[&str]) -> Result<(), AppError> {
+    if crate::dry_run::skip(format_args!("run {program} {}", args.join(" "))) {
+        return Ok(());
+    }
+    let output = Command::new(program)
+        .args(args)
+        .output()
+        .map_err(|e| AppError::Message(format!("Failed to run {program}: {e}")))?;
+    if !output.status.success() {
+        return Err(AppError::Message(format!(
+            "{program} {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+fn home_dir() -> Result<PathBuf, AppError> {
+    dirs::home_dir().ok_or_else(|| AppError::Config("无法获取用户主目录".to_string()))
+}
+
+#[cfg(target_os = "linux")]
+mod platform {
+    use super::*;
+
+    const DESKTOP_FILE: &str = "cc-switch-deeplink.desktop";
+
+    fn applications_dir() -> Result<PathBuf, AppError> {
+        Ok(std::env::var_os("XDG_DATA_HOME")
+            .filter(|v| !v.is_empty())
+            .map(PathBuf::from)
+            .unwrap_or(home_dir()?.join(".local").join("share"))
+            .join("applications"))
+    }
+
+    /// Quote an Exec argument per the Desktop Entry spec (string escaping applies first,
+    /// hence the doubled backslashes).
+    fn quote_exec_arg(arg: &str) -> String {
+        let escaped = arg
+            .replace('\\', "\\\\\\\\")
+            .replace('"', "\\\\\"")
+            .replace('`', "\\\\`")
+            .replace('$', "\\\\$")
+            .replace('%', "%%");
+        format!("\"{escaped}\"")
+    }
+
+    pub fn register(exe: &Path) -> Result<Vec<String>, AppError> {
+        let dir = applications_dir()?;
+        crate::dry_run::create_dir_all(&dir).map_err(|e| AppError::io(&dir, e))?;
+        let path = dir.join(DESKTOP_FILE);
+
+        let content = format!(
+            "[Desktop Entry]\n\
+             Type=Application\n\
+             Name=CC Switch Deep Link Handler\n\
+             Exec={} deeplink handle %u\n\
+             Terminal=true\n\
+             NoDisplay=true\n\
+             MimeType=x-scheme-handler/{URL_SCHEME};\n",
+            quote_exec_arg(&exe.to_string_lossy())
+        );
+        crate::dry_run::write(&path, content).map_err(|e| AppError::io(&path, e))?;
+
+        let mut changes = vec![format!("Wrote {}", path.display())];
+
+        let mime = format!("x-scheme-handler/{URL_SCHEME}");
+        match run("xdg-mime", &["default", DESKTOP_FILE, &mime]) {
+            Ok(()) => changes.push(format!("Set {DESKTOP_FILE} as default handler for {mime}")),
+            Err(e) => log::warn!("xdg-mime 设置默认处理程序失败: {e}"),
+        }
+        if run("update-desktop-database", &[&dir.to_string_lossy()]).is_ok() {
+            changes.push(format!("Refreshed desktop database in {}", dir.display()));
+        }
+
+        Ok(changes)
+    }
+
+    pub fn unregister() -> Result<Vec<String>, AppError> {
+        let dir = applications_dir()?;
+        let path = dir.join(DESKTOP_FILE);
+        let mut changes = Vec::new();
+
+        if path.exists() {
+            crate::dry_run::remove_file(&path).map_err(|e| AppError::io(&path, e))?;
+            changes.push(format!("Removed {}", path.display()));
+            if run("update-desktop-database", &[&dir.to_string_lossy()]).is_ok() {
+                changes.push(format!("Refreshed desktop database in {}", dir.display()));
+            }
+        }
+
+        Ok(changes)
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod platform {
+    use super::*;
+
+    const BUNDLE_NAME: &str = "CC Switch Deeplink.app";
+    const BUNDLE_ID: &str = "com.ccswitch.cli.deeplink";
+    const LSREGISTER: &str = "/System/Library/Frameworks/CoreServices.framework/Frameworks/LaunchServices.framework/Support/lsregister";
+
+    fn bundle_path() -> Result<PathBuf, AppError> {
+        Ok(home_dir()?.join("Applications").join(BUNDLE_NAME))
+    }
+
+    fn applescript_string(value: &str) -> String {
+        format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+    }
+
+    pub fn register(exe: &Path) -> Result<Vec<String>, AppError> {
+        let bundle = bundle_path()?;
+        if let Some(parent) = bundle.parent() {
+            crate::dry_run::create_dir_all(parent).map_err(|e| AppError::io(parent, e))?;
+        }
+        if bundle.exists() {
+            crate::dry_run::remove_dir_all(&bundle).map_err(|e| AppError::io(&bundle, e))?;
+        }
+
+        // URLs arrive as Apple Events, so a tiny applet forwards them to the CLI in Terminal.
+        let script = format!(
+            "on open location theURL\n\
+             \ttell application \"Terminal\"\n\
+             \t\tactivate\n\
+             \t\tdo script (quoted form of {}) & \" deeplink handle \" & (quoted form of theURL)\n\
+             \tend tell\n\
+             end open location\n",
+            applescript_string(&exe.to_string_lossy())
+        );
+        let bundle_str = bundle.to_string_lossy().to_string();
+        run("osacompile", &["-o", &bundle_str, "-e", &script])?;
+
+        let plist = bundle.join("Contents").join("Info.plist");
+        let plist_str = plist.to_string_lossy().to_string();
+        let url_types = format!(
+            r#"[{{"CFBundleURLName":"{BUNDLE_ID}","CFBundleURLSchemes":["{URL_SCHEME}"]}}]"#
+        );
+        run(
+            "plutil",
+            &[
+                "-replace",
+                "CFBundleIdentifier",
+                "-string",
+                BUNDLE_ID,
+                &plist_str,
+            ],
+        )?;
+        run(
+            "plutil",
+            &[
+                "-replace",
+                "CFBundleURLTypes",
+                "-json",
+                &url_types,
+                &plist_str,
+            ],
+        )?;
+        run(LSREGISTER, &["-f", &bundle_str])?;
+
+        Ok(vec![
+            format!("Created {}", bundle.display()),
+            format!("Registered {BUNDLE_ID} for {URL_SCHEME}:// with LaunchServices"),
+        ])
+    }
+
+    pub fn unregister() -> Result<Vec<String>, AppError> {
+        let bundle = bundle_path()?;
+        let mut changes = Vec::new();
+
+        if bundle.exists() {
+            let bundle_str = bundle.to_string_lossy().to_string();
+            if let Err(e) = run(LSREGISTER, &["-u", &bundle_str]) {
+                log::warn!("lsregister 注销失败: {e}");
+            } else {
+                changes.push(format!("Unregistered {BUNDLE_ID} from LaunchServices"));
+            }
+            crate::dry_run::remove_dir_all(&bundle).map_err(|e| AppError::io(&bundle, e))?;
+            changes.push(format!("Removed {}", bundle.display()));
+        }
+
+        Ok(changes)
+    }
+}
+
+#[cfg(target_os = "windows")]
+mod platform {
+    use super::*;
+
+    const CLASS_KEY: &str = r"HKCU\Software\Classes\ccswitch";
+
+    pub fn register(exe: &Path) -> Result<Vec<String>, AppError> {
+        let command_key = format!(r"{CLASS_KEY}\shell\open\command");
+        let command = format!("\"{}\" deeplink handle \"%1\"", exe.display());
+
+        run(
+            "reg",
+            &[
+                "add",
+                CLASS_KEY,
+                "/ve",
+                "/d",
+                "URL:CC Switch Protocol",
+                "/f",
+            ],
+        )?;
+        run(
+            "reg",
+            &["add", CLASS_KEY, "/v", "URL Protocol", "/d", "", "/f"],
+        )?;
+        run("reg", &["add", &command_key, "/ve", "/d", &command, "/f"])?;
+
+        Ok(vec![
+            format!("Created registry key {CLASS_KEY} ({URL_SCHEME}:// URL Protocol)"),
+            format!("Set {command_key} to {command}"),
+        ])
+    }
+
+    pub fn unregister() -> Result<Vec<String>, AppError> {
+        match run("reg", &["delete", CLASS_KEY, "/f"]) {
+            Ok(()) => Ok(vec![format!("Deleted registry key {CLASS_KEY}")]),
+            // Key did not exist: nothing to undo.
+            Err(_) => Ok(Vec::new()),
+        }
+    }
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
+mod platform {
+    use super::*;
+
+    pub fn register(_exe: &Path) -> Result<Vec<String>, AppError> {
+        Err(AppError::Message(
+            "URL scheme registration is not supported on this platform".to_string(),
+        ))
+    }
+
+    pub fn unregister() -> Result<Vec<String>, AppError> {
+        Err(AppError::Message(
+            "URL scheme registration is not supported on this platform".to_string(),
+        ))
+    }
+}
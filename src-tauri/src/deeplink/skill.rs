@@ -0,0 +1,100 @@
+//! Skill install from deep link.
+
+use super::DeepLinkImportRequest;
+use crate::app_config::InstalledSkill;
+use crate::error::AppError;
+use crate::services::skill::SkillRepo;
+use crate::services::SkillService;
+use crate::AppType;
+use std::str::FromStr;
+
+/// Install a skill from a deep link request.
+///
+/// The source repo is registered first, then the skill is installed for the first listed
+/// app and enabled for the remaining ones. A link whose branch differs from the one an
+/// already-tracked repo uses is rejected instead of switching the repo's branch.
+pub async fn import_skill_from_deeplink(
+    request: DeepLinkImportRequest,
+) -> Result<InstalledSkill, AppError> {
+    if request.resource != "skill" {
+        return Err(AppError::InvalidInput(format!(
+            "Expected skill resource, got '{}'",
+            request.resource
+        )));
+    }
+
+    let repo = request
+        .repo
+        .as_deref()
+        .ok_or_else(|| AppError::InvalidInput("Missing 'repo' field for skill".to_string()))?;
+    let (owner, name) = repo
+        .split_once('/')
+        .ok_or_else(|| AppError::InvalidInput(format!("Invalid repo: {repo}")))?;
+    let directory = request
+        .directory
+        .as_deref()
+        .ok_or_else(|| AppError::InvalidInput("Missing 'directory' field for skill".to_string()))?;
+
+    let apps = parse_apps(request.apps.as_deref())?;
+
+    // 深链接不得静默改写已跟踪仓库的分支，切换分支须显式执行 `skills repos add --branch`
+    let branch = match (
+        SkillService::tracked_branch(owner, name)?,
+        request.branch.clone(),
+    ) {
+        (Some(tracked), Some(wanted)) if tracked != wanted => {
+            return Err(AppError::localized(
+                "deeplink.skill_branch_mismatch",
+                format!(
+                    "仓库 {owner}/{name} 已跟踪分支 {tracked}，链接要求 {wanted}；如需切换请运行 `cc-switch skills repos add {owner}/{name} --branch {wanted}`"
+                ),
+                format!(
+                    "Repository {owner}/{name} already tracks branch {tracked}, but the link asks for {wanted}; run `cc-switch skills repos add {owner}/{name} --branch {wanted}` to switch"
+                ),
+            ));
+        }
+        (tracked, wanted) => wanted.or(tracked).unwrap_or_else(|| "main".to_string()),
+    };
+    SkillService::upsert_repo(SkillRepo {
+        owner: owner.to_string(),
+        name: name.to_string(),
+        branch,
+        enabled: true,
+        token: None,
+    })?;
+
+    let service = SkillService::new()?;
+    let key = format!("{owner}/{name}:{directory}");
+    let (first, rest) = apps
+        .split_first()
+        .expect("parse_apps always yields at least one app");
+    let mut installed = service.install(&key, first).await?;
+
+    for app in rest {
+        SkillService::toggle_app(&installed.directory, app, true)?;
+        installed.apps.set_enabled_for(app, true);
+    }
+
+    Ok(installed)
+}
+
+/// Resolve the apps to install for; defaults to Claude.
+fn parse_apps(raw: Option<&str>) -> Result<Vec<AppType>, AppError> {
+    let mut apps = Vec::new();
+    for name in raw
+        .unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|a| !a.is_empty())
+    {
+        let app = AppType::from_str(name)
+            .map_err(|_| AppError::InvalidInput(format!("Invalid app type: {name}")))?;
+        if !apps.contains(&app) {
+            apps.push(app);
+        }
+    }
+    if apps.is_empty() {
+        apps.push(AppType::Claude);
+    }
+    Ok(apps)
+}
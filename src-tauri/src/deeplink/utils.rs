@@ -19,6 +19,22 @@ pub fn validate_url(url_str: &str, field_name: &str) -> Result<(), AppError> {
     Ok(())
 }
 
+/// Validate that a string is a valid HTTPS URL (used for remote resources we fetch).
+pub fn validate_https_url(url_str: &str, field_name: &str) -> Result<(), AppError> {
+    validate_url(url_str, field_name)?;
+
+    let url = Url::parse(url_str)
+        .map_err(|e| AppError::InvalidInput(format!("Invalid URL for '{field_name}': {e}")))?;
+    if url.scheme() != "https" {
+        return Err(AppError::InvalidInput(format!(
+            "Invalid URL scheme for '{field_name}': must be https, got '{}'",
+            url.scheme()
+        )));
+    }
+
+    Ok(())
+}
+
 /// Decode a Base64 parameter from deep link URL.
 ///
 /// Handles common Base64-in-URL issues:
@@ -0,0 +1,101 @@
+//! 全局预演模式（`--dry-run-all`）：拦截文件系统与数据库写入，只输出将要执行的操作
+//!
+//! 文件写入经本模块的 `std::fs` 同名包装函数（或 [`skip`]）短路；数据库在预演模式下
+//! 打开磁盘数据库的内存副本，写入由 update hook 记录后随进程丢弃。
+//! 日志写到 stderr（`[dry-run] would ...`），不影响 `--json` 输出。
+//!
+//! 临时目录（下载、解压）中的操作不拦截：它们不改变用户状态，且后续步骤依赖其结果。
+
+use std::fmt;
+use std::io;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static DRY_RUN: AtomicBool = AtomicBool::new(false);
+
+/// 全局 `--dry-run-all`，须在创建 `AppState` 之前设置
+pub fn enable_dry_run() {
+    DRY_RUN.store(true, Ordering::Relaxed);
+}
+
+pub fn is_dry_run() -> bool {
+    DRY_RUN.load(Ordering::Relaxed)
+}
+
+/// 预演模式下输出 `action` 并返回 true，调用方据此跳过实际写入
+pub fn skip(action: fmt::Arguments<'_>) -> bool {
+    if !is_dry_run() {
+        return false;
+    }
+    eprintln!("[dry-run] would {action}");
+    true
+}
+
+pub fn write<P: AsRef<Path>, C: AsRef<[u8]>>(path: P, contents: C) -> io::Result<()> {
+    let path = path.as_ref();
+    if skip(format_args!(
+        "write {} ({} bytes)",
+        path.display(),
+        contents.as_ref().len()
+    )) {
+        return Ok(());
+    }
+    std::fs::write(path, contents)
+}
+
+pub fn create_dir_all<P: AsRef<Path>>(path: P) -> io::Result<()> {
+    let path = path.as_ref();
+    // 已存在的目录不算写入，避免刷屏
+    if path.is_dir() || skip(format_args!("create directory {}", path.display())) {
+        return Ok(());
+    }
+    std::fs::create_dir_all(path)
+}
+
+pub fn remove_file<P: AsRef<Path>>(path: P) -> io::Result<()> {
+    let path = path.as_ref();
+    if skip(format_args!("remove {}", path.display())) {
+        return Ok(());
+    }
+    std::fs::remove_file(path)
+}
+
+pub fn remove_dir_all<P: AsRef<Path>>(path: P) -> io::Result<()> {
+    let path = path.as_ref();
+    if skip(format_args!("remove directory tree {}", path.display())) {
+        return Ok(());
+    }
+    std::fs::remove_dir_all(path)
+}
+
+pub fn rename<P: AsRef<Path>, Q: AsRef<Path>>(from: P, to: Q) -> io::Result<()> {
+    let (from, to) = (from.as_ref(), to.as_ref());
+    if skip(format_args!("move {} -> {}", from.display(), to.display())) {
+        return Ok(());
+    }
+    std::fs::rename(from, to)
+}
+
+pub fn copy<P: AsRef<Path>, Q: AsRef<Path>>(from: P, to: Q) -> io::Result<u64> {
+    let (from, to) = (from.as_ref(), to.as_ref());
+    if skip(format_args!("copy {} -> {}", from.display(), to.display())) {
+        return Ok(0);
+    }
+    std::fs::copy(from, to)
+}
+
+/// 创建目录符号链接（Windows 上为 `symlink_dir`）
+pub fn symlink_dir<P: AsRef<Path>, Q: AsRef<Path>>(src: P, dest: Q) -> io::Result<()> {
+    let (src, dest) = (src.as_ref(), dest.as_ref());
+    if skip(format_args!("link {} -> {}", dest.display(), src.display())) {
+        return Ok(());
+    }
+    #[cfg(unix)]
+    {
+        std::os::unix::fs::symlink(src, dest)
+    }
+    #[cfg(windows)]
+    {
+        std::os::windows::fs::symlink_dir(src, dest)
+    }
+}
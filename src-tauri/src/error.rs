@@ -1,8 +1,41 @@
 use std::path::Path;
 use std::sync::PoisonError;
 
+use serde::Serialize;
+use serde_json::{json, Value};
 use thiserror::Error;
 
+/// 稳定的错误分类码（`--json` 模式下输出，供脚本按码分支处理）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ErrorCode {
+    ConfigError,
+    DatabaseError,
+    InvalidInput,
+    IoError,
+    JsonError,
+    TomlError,
+    LockError,
+    McpValidation,
+    GeneralError,
+}
+
+impl ErrorCode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::ConfigError => "CONFIG_ERROR",
+            Self::DatabaseError => "DATABASE_ERROR",
+            Self::InvalidInput => "INVALID_INPUT",
+            Self::IoError => "IO_ERROR",
+            Self::JsonError => "JSON_ERROR",
+            Self::TomlError => "TOML_ERROR",
+            Self::LockError => "LOCK_ERROR",
+            Self::McpValidation => "MCP_VALIDATION",
+            Self::GeneralError => "GENERAL_ERROR",
+        }
+    }
+}
+
 #[derive(Debug, Error)]
 pub enum AppError {
     #[error("配置错误: {0}")]
@@ -83,6 +116,63 @@ impl AppError {
             en: en.into(),
         }
     }
+
+    /// 按变体划分的错误类别
+    pub fn error_code(&self) -> ErrorCode {
+        match self {
+            Self::Config(_) => ErrorCode::ConfigError,
+            Self::Database(_) => ErrorCode::DatabaseError,
+            Self::InvalidInput(_) => ErrorCode::InvalidInput,
+            Self::Io { .. } | Self::IoContext { .. } => ErrorCode::IoError,
+            Self::Json { .. } | Self::JsonSerialize { .. } => ErrorCode::JsonError,
+            Self::Toml { .. } => ErrorCode::TomlError,
+            Self::Lock(_) => ErrorCode::LockError,
+            Self::McpValidation(_) => ErrorCode::McpValidation,
+            Self::Message(_) | Self::Localized { .. } => ErrorCode::GeneralError,
+        }
+    }
+
+    /// 机器可读的错误码：优先使用 `format_skill_error` 内嵌的 code，
+    /// 其次是本地化错误的 key（`skill.repo_not_found` -> `SKILL_REPO_NOT_FOUND`），
+    /// 最后回退到变体类别。
+    pub fn code(&self) -> String {
+        match self {
+            Self::Message(msg) => structured_skill_error(msg)
+                .and_then(|v| v.get("code")?.as_str().map(str::to_string))
+                .unwrap_or_else(|| self.error_code().as_str().to_string()),
+            Self::Localized { key, .. } => key.replace(['.', '-'], "_").to_ascii_uppercase(),
+            _ => self.error_code().as_str().to_string(),
+        }
+    }
+
+    /// `--json` 模式下的错误输出：`{"error":{"code":..,"message":..}}`
+    pub fn to_json(&self) -> Value {
+        let mut error = json!({
+            "code": self.code(),
+            "category": self.error_code(),
+            "message": self.to_string(),
+        });
+        if let Self::Message(msg) = self {
+            if let Some(structured) = structured_skill_error(msg) {
+                for field in ["context", "suggestion"] {
+                    if let Some(value) = structured.get(field).filter(|v| !v.is_null()) {
+                        error[field] = value.clone();
+                    }
+                }
+            }
+        }
+        json!({ "error": error })
+    }
+}
+
+/// 解析 `format_skill_error` 生成的 JSON 消息
+fn structured_skill_error(msg: &str) -> Option<Value> {
+    if !msg.trim_start().starts_with('{') {
+        return None;
+    }
+    let value: Value = serde_json::from_str(msg).ok()?;
+    value.get("code")?.as_str()?;
+    Some(value)
 }
 
 impl<T> From<PoisonError<T>> for AppError {
@@ -109,8 +199,6 @@ pub fn format_skill_error(
     context: &[(&str, &str)],
     suggestion: Option<&str>,
 ) -> String {
-    use serde_json::json;
-
     let mut ctx_map = serde_json::Map::new();
     for (key, value) in context {
         ctx_map.insert(key.to_string(), json!(value));
@@ -127,3 +215,37 @@ pub fn format_skill_error(
         format!("ERROR:{code}")
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn codes_cover_variants_localized_keys_and_skill_errors() {
+        assert_eq!(
+            AppError::InvalidInput("x".to_string()).code(),
+            "INVALID_INPUT"
+        );
+        assert_eq!(
+            AppError::localized("skill.repo_not_found", "zh", "en").code(),
+            "SKILL_REPO_NOT_FOUND"
+        );
+
+        let err = AppError::Message(format_skill_error(
+            "SKILL_DIRECTORY_CONFLICT",
+            &[("directory", "foo")],
+            Some("uninstallFirst"),
+        ));
+        assert_eq!(err.code(), "SKILL_DIRECTORY_CONFLICT");
+        let value = err.to_json();
+        assert_eq!(value["error"]["code"], "SKILL_DIRECTORY_CONFLICT");
+        assert_eq!(value["error"]["category"], "GENERAL_ERROR");
+        assert_eq!(value["error"]["context"]["directory"], "foo");
+        assert_eq!(value["error"]["suggestion"], "uninstallFirst");
+
+        assert_eq!(
+            AppError::Message("{not json".to_string()).code(),
+            "GENERAL_ERROR"
+        );
+    }
+}
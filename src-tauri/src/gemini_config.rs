@@ -160,7 +160,7 @@ pub fn write_gemini_env_atomic(map: &HashMap<String, String>) -> Result<(), AppE
 
     // 确保目录存在
     if let Some(parent) = path.parent() {
-        fs::create_dir_all(parent).map_err(|e| AppError::io(parent, e))?;
+        crate::dry_run::create_dir_all(parent).map_err(|e| AppError::io(parent, e))?;
 
         // 设置目录权限为 700（仅所有者可读写执行）
         #[cfg(unix)]
@@ -298,7 +298,7 @@ fn update_selected_type(selected_type: &str) -> Result<(), AppError> {
 
     // 确保目录存在
     if let Some(parent) = settings_path.parent() {
-        fs::create_dir_all(parent).map_err(|e| AppError::io(parent, e))?;
+        crate::dry_run::create_dir_all(parent).map_err(|e| AppError::io(parent, e))?;
     }
 
     // 读取现有的 settings.json（如果存在）
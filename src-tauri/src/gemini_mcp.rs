@@ -31,7 +31,7 @@ fn read_json_value(path: &Path) -> Result<Value, AppError> {
 
 fn write_json_value(path: &Path, value: &Value) -> Result<(), AppError> {
     if let Some(parent) = path.parent() {
-        fs::create_dir_all(parent).map_err(|e| AppError::io(parent, e))?;
+        crate::dry_run::create_dir_all(parent).map_err(|e| AppError::io(parent, e))?;
     }
     let json =
         serde_json::to_string_pretty(value).map_err(|e| AppError::JsonSerialize { source: e })?;
@@ -0,0 +1,219 @@
+//! 出站 HTTP 客户端的代理配置（Skills 下载、自更新、测速、用量脚本）
+//!
+//! 优先级：`--no-proxy` / `--proxy <url>` > 设置项 `proxy` > 环境变量。
+//! 环境变量中 `HTTP(S)_PROXY` 由 reqwest 自动读取；reqwest 不识别的 `ALL_PROXY`
+//! 在这里显式补上（同样遵循 `NO_PROXY`）。代理地址支持 IPv6 字面量，如 `http://[::1]:8080`。
+//!
+//! 离线模式（`--offline` 或 `CC_SWITCH_OFFLINE=1`）下，联网操作在发出请求前经
+//! [`ensure_online`] 直接失败，能用缓存的地方（如 Skills 仓库）改用缓存。
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::OnceLock;
+
+use crate::error::AppError;
+
+/// 命令行对代理的覆盖
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProxyOverride {
+    /// `--proxy <url>`
+    Url(String),
+    /// `--no-proxy`：忽略设置与环境变量，直连
+    Disabled,
+}
+
+static OVERRIDE: OnceLock<ProxyOverride> = OnceLock::new();
+
+static OFFLINE: AtomicBool = AtomicBool::new(false);
+
+/// 开启离线模式的环境变量
+pub const OFFLINE_ENV: &str = "CC_SWITCH_OFFLINE";
+
+/// 设置项 `proxy` 中表示“禁用代理”的取值
+pub const PROXY_OFF: &str = "off";
+
+const ENV_PROXY_VARS: [&str; 6] = [
+    "HTTPS_PROXY",
+    "https_proxy",
+    "HTTP_PROXY",
+    "http_proxy",
+    "ALL_PROXY",
+    "all_proxy",
+];
+
+/// 由 CLI 入口调用，须在创建任何客户端之前设置
+pub fn set_proxy_override(value: ProxyOverride) -> Result<(), AppError> {
+    if let ProxyOverride::Url(url) = &value {
+        parse_proxy_url(url)?;
+    }
+    OVERRIDE
+        .set(value)
+        .map_err(|_| AppError::Config("代理覆盖已被设置".to_string()))
+}
+
+/// 全局 `--offline`
+pub fn enable_offline() {
+    OFFLINE.store(true, Ordering::Relaxed);
+}
+
+/// 是否处于离线模式：`--offline` 或 `CC_SWITCH_OFFLINE` 为 1 / true / yes
+pub fn is_offline() -> bool {
+    OFFLINE.load(Ordering::Relaxed)
+        || std::env::var(OFFLINE_ENV)
+            .is_ok_and(|v| matches!(v.trim().to_ascii_lowercase().as_str(), "1" | "true" | "yes"))
+}
+
+/// 离线模式下拒绝联网操作；`action` 为英文的操作描述，如 "skills discover"
+pub fn ensure_online(action: &str) -> Result<(), AppError> {
+    if !is_offline() {
+        return Ok(());
+    }
+    Err(AppError::localized(
+        "network.offline",
+        format!("离线模式：{action} 需要访问网络（由 --offline 或 {OFFLINE_ENV} 开启）"),
+        format!("Offline mode: {action} requires network access (enabled by --offline or {OFFLINE_ENV})"),
+    ))
+}
+
+/// 生效的代理来源
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum ProxySource {
+    /// 显式代理（`--proxy` 或设置项），附带来源说明
+    Explicit {
+        url: String,
+        origin: &'static str,
+    },
+    /// 显式禁用
+    Disabled {
+        origin: &'static str,
+    },
+    /// 来自环境变量
+    Env {
+        var: &'static str,
+        url: String,
+    },
+    None,
+}
+
+fn effective_proxy() -> ProxySource {
+    match OVERRIDE.get() {
+        Some(ProxyOverride::Url(url)) => {
+            return ProxySource::Explicit {
+                url: url.clone(),
+                origin: "--proxy",
+            }
+        }
+        Some(ProxyOverride::Disabled) => {
+            return ProxySource::Disabled {
+                origin: "--no-proxy",
+            }
+        }
+        None => {}
+    }
+    match crate::settings::get_proxy() {
+        Some(value) if value.eq_ignore_ascii_case(PROXY_OFF) => {
+            return ProxySource::Disabled {
+                origin: "proxy setting",
+            }
+        }
+        Some(url) => {
+            return ProxySource::Explicit {
+                url,
+                origin: "proxy setting",
+            }
+        }
+        None => {}
+    }
+    ENV_PROXY_VARS
+        .into_iter()
+        .find_map(|var| {
+            std::env::var(var)
+                .ok()
+                .filter(|v| !v.trim().is_empty())
+                .map(|url| ProxySource::Env { var, url })
+        })
+        .unwrap_or(ProxySource::None)
+}
+
+/// 校验代理地址：http / https / socks5 / socks5h，且必须包含主机
+pub fn parse_proxy_url(raw: &str) -> Result<reqwest::Proxy, AppError> {
+    let raw = raw.trim();
+    let invalid = |reason: String| {
+        AppError::localized(
+            "proxy.invalid_url",
+            format!("无效的代理地址 '{raw}': {reason}"),
+            format!("Invalid proxy URL '{raw}': {reason}"),
+        )
+    };
+    let url = url::Url::parse(raw).map_err(|e| invalid(e.to_string()))?;
+    if !matches!(url.scheme(), "http" | "https" | "socks5" | "socks5h") {
+        return Err(invalid(format!(
+            "unsupported scheme '{}' (expected http, https, socks5 or socks5h)",
+            url.scheme()
+        )));
+    }
+    if url.host().is_none() {
+        return Err(invalid("missing host".to_string()));
+    }
+    reqwest::Proxy::all(url.as_str()).map_err(|e| invalid(e.to_string()))
+}
+
+/// 按生效的代理配置调整 builder
+pub fn apply_proxy(builder: reqwest::ClientBuilder) -> Result<reqwest::ClientBuilder, AppError> {
+    Ok(match effective_proxy() {
+        ProxySource::Explicit { url, .. } => builder.proxy(parse_proxy_url(&url)?),
+        ProxySource::Disabled { .. } => builder.no_proxy(),
+        // reqwest 自行处理 HTTP(S)_PROXY；仅 ALL_PROXY 需要显式设置
+        ProxySource::Env { var, url } if var.eq_ignore_ascii_case("ALL_PROXY") => {
+            let proxy = parse_proxy_url(&url)?.no_proxy(reqwest::NoProxy::from_env());
+            builder.proxy(proxy)
+        }
+        ProxySource::Env { .. } | ProxySource::None => builder,
+    })
+}
+
+/// 带代理配置与默认 User-Agent 的 builder
+pub fn client_builder() -> Result<reqwest::ClientBuilder, AppError> {
+    apply_proxy(reqwest::Client::builder().user_agent("cc-switch"))
+}
+
+/// 当前代理配置的简短描述（用于错误提示）
+pub fn describe_proxy() -> String {
+    match effective_proxy() {
+        ProxySource::Explicit { url, origin } => format!("using proxy {url} (from {origin})"),
+        ProxySource::Disabled { origin } => format!("proxy disabled by {origin}"),
+        ProxySource::Env { var, url } => format!("using proxy {url} (from {var})"),
+        ProxySource::None => "no proxy configured".to_string(),
+    }
+}
+
+/// 连接失败 / 超时时附加的代理提示；其他错误返回 None
+pub fn connection_error_hint(err: &reqwest::Error) -> Option<String> {
+    (err.is_connect() || err.is_timeout()).then(|| {
+        format!(
+            "{}; adjust with --proxy <url>, --no-proxy or `cc-switch settings set proxy <url|off>`",
+            describe_proxy()
+        )
+    })
+}
+
+/// 将 reqwest 错误格式化为字符串，连接类错误附带代理提示
+pub fn describe_error(err: &reqwest::Error) -> String {
+    match connection_error_hint(err) {
+        Some(hint) => format!("{err} ({hint})"),
+        None => err.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_proxy_url_accepts_supported_schemes_and_ipv6() {
+        assert!(parse_proxy_url("http://proxy.local:3128").is_ok());
+        assert!(parse_proxy_url("socks5h://127.0.0.1:1080").is_ok());
+        assert!(parse_proxy_url("http://[::1]:8080").is_ok());
+        assert!(parse_proxy_url("ftp://proxy.local").is_err());
+        assert!(parse_proxy_url("not a url").is_err());
+    }
+}
@@ -15,7 +15,7 @@ pub async fn export_config_to_file(file_path: String) -> Result<Value, String> {
         );
     };
     if !parent.as_os_str().is_empty() {
-        std::fs::create_dir_all(parent).map_err(|e| AppError::io(parent, e).to_string())?;
+        crate::dry_run::create_dir_all(parent).map_err(|e| AppError::io(parent, e).to_string())?;
     }
 
     let db = Database::init().map_err(|e| e.to_string())?;
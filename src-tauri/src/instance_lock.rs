@@ -0,0 +1,77 @@
+//! 跨进程实例锁：避免多个 cc-switch 进程同时写数据库与 live 配置
+//!
+//! 锁文件为配置目录下的 `cc-switch.lock`（advisory lock，进程退出时由系统释放），
+//! 内容为持有者 PID，仅用于错误提示。默认不加锁，由 CLI 入口按命令是否修改数据启用。
+
+use std::fs::{self, File, OpenOptions};
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::sync::{Mutex, OnceLock};
+
+use fs2::FileExt;
+
+use crate::error::AppError;
+
+/// 加锁策略
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LockMode {
+    /// 不加锁（只读命令、库调用与测试）
+    #[default]
+    Off,
+    /// 独占锁，被占用时立即报错
+    Exclusive,
+    /// 独占锁，被占用时阻塞等待（`--wait`）
+    Wait,
+}
+
+static MODE: Mutex<LockMode> = Mutex::new(LockMode::Off);
+/// 持有的锁文件，进程结束前不释放
+static HELD: OnceLock<File> = OnceLock::new();
+
+pub fn set_lock_mode(mode: LockMode) {
+    if let Ok(mut guard) = MODE.lock() {
+        *guard = mode;
+    }
+}
+
+/// 按当前策略获取实例锁；同一进程内重复调用为空操作
+pub fn acquire_instance_lock() -> Result<(), AppError> {
+    let mode = MODE.lock().map(|m| *m).unwrap_or_default();
+    if mode == LockMode::Off || HELD.get().is_some() {
+        return Ok(());
+    }
+
+    let dir = crate::config::get_app_config_dir();
+    fs::create_dir_all(&dir).map_err(|e| AppError::io(&dir, e))?;
+    let path = dir.join("cc-switch.lock");
+    let mut file = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(&path)
+        .map_err(|e| AppError::io(&path, e))?;
+
+    if mode == LockMode::Wait {
+        file.lock_exclusive().map_err(|e| AppError::io(&path, e))?;
+    } else if file.try_lock_exclusive().is_err() {
+        let mut holder = String::new();
+        let _ = file.read_to_string(&mut holder);
+        let pid = holder.trim();
+        let pid = if pid.is_empty() { "?" } else { pid };
+        return Err(AppError::localized(
+            "instance.locked",
+            format!("另一个 cc-switch 实例正在运行（PID {pid}），可使用 --wait 等待其结束"),
+            format!("Another cc-switch instance is running (PID {pid}); use --wait to wait for it"),
+        ));
+    }
+
+    // 记录持有者 PID（失败不影响加锁）
+    let _ = file
+        .set_len(0)
+        .and_then(|_| file.seek(SeekFrom::Start(0)))
+        .and_then(|_| write!(file, "{}", std::process::id()))
+        .and_then(|_| file.flush());
+
+    let _ = HELD.set(file);
+    Ok(())
+}
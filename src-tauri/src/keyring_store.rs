@@ -0,0 +1,162 @@
+//! 系统钥匙串中的密钥引用
+//!
+//! 供应商配置中的字符串值可写成 `keyring:<名称>`，真实密钥保存在系统钥匙串
+//! （service 为 `cc-switch`，account 为 `<名称>`）。写入 live 配置前解析为真实值；
+//! 从 live 回填时把引用放回原处，数据库中只保存引用。
+
+use serde_json::Value;
+
+use crate::error::AppError;
+use crate::provider::Provider;
+
+/// 引用前缀
+pub const KEYRING_PREFIX: &str = "keyring:";
+
+const KEYRING_SERVICE: &str = "cc-switch";
+
+/// 解析 `keyring:<名称>`，返回名称；普通值返回 None
+pub fn parse_reference(value: &str) -> Option<&str> {
+    value
+        .strip_prefix(KEYRING_PREFIX)
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+}
+
+/// 构造引用字符串
+pub fn reference(name: &str) -> String {
+    format!("{KEYRING_PREFIX}{name}")
+}
+
+fn entry(name: &str) -> Result<keyring::Entry, AppError> {
+    keyring::Entry::new(KEYRING_SERVICE, name).map_err(|e| access_failed(name, e))
+}
+
+fn access_failed(name: &str, err: keyring::Error) -> AppError {
+    AppError::localized(
+        "keyring.access_failed",
+        format!("访问系统钥匙串条目 '{name}' 失败: {err}"),
+        format!("Failed to access keychain entry '{name}': {err}"),
+    )
+}
+
+pub fn store_secret(name: &str, secret: &str) -> Result<(), AppError> {
+    if crate::dry_run::skip(format_args!("store keychain entry '{name}'")) {
+        return Ok(());
+    }
+    entry(name)?
+        .set_password(secret)
+        .map_err(|e| access_failed(name, e))
+}
+
+pub fn load_secret(name: &str) -> Result<String, AppError> {
+    entry(name)?.get_password().map_err(|e| match e {
+        keyring::Error::NoEntry => AppError::localized(
+            "keyring.not_found",
+            format!("系统钥匙串中没有条目 '{name}'，可用 `cc-switch provider set-key <id> --keyring` 写入"),
+            format!("No keychain entry '{name}'; store one with `cc-switch provider set-key <id> --keyring`"),
+        ),
+        e => access_failed(name, e),
+    })
+}
+
+/// 删除条目；条目不存在视为成功
+pub fn delete_secret(name: &str) -> Result<(), AppError> {
+    if crate::dry_run::skip(format_args!("delete keychain entry '{name}'")) {
+        return Ok(());
+    }
+    match entry(name)?.delete_credential() {
+        Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+        Err(e) => Err(access_failed(name, e)),
+    }
+}
+
+/// 引用则从钥匙串读取，普通值原样返回
+pub fn resolve_secret(value: &str) -> Result<String, AppError> {
+    match parse_reference(value) {
+        Some(name) => load_secret(name),
+        None => Ok(value.to_string()),
+    }
+}
+
+/// 递归替换 JSON 中的所有引用
+pub fn resolve_references(value: &mut Value) -> Result<(), AppError> {
+    match value {
+        Value::String(s) => {
+            if let Some(name) = parse_reference(s) {
+                *s = load_secret(name)?;
+            }
+        }
+        Value::Object(map) => {
+            for child in map.values_mut() {
+                resolve_references(child)?;
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                resolve_references(item)?;
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// 写 live 配置前使用：返回引用已解析的副本
+pub fn resolve_provider(provider: &Provider) -> Result<Provider, AppError> {
+    let mut resolved = provider.clone();
+    resolve_references(&mut resolved.settings_config)?;
+    Ok(resolved)
+}
+
+/// 回填时把 `stored` 中的引用放回 `live` 的相同位置（live 中对应值仍为字符串时）
+pub fn restore_references(live: &mut Value, stored: &Value) {
+    match (live, stored) {
+        (Value::Object(live_map), Value::Object(stored_map)) => {
+            for (key, stored_child) in stored_map {
+                if let Some(live_child) = live_map.get_mut(key) {
+                    restore_references(live_child, stored_child);
+                }
+            }
+        }
+        (live, Value::String(s)) if live.is_string() && parse_reference(s).is_some() => {
+            *live = Value::String(s.clone());
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn parse_reference_requires_prefix_and_name() {
+        assert_eq!(parse_reference("keyring:claude-prod"), Some("claude-prod"));
+        assert_eq!(parse_reference("keyring:"), None);
+        assert_eq!(parse_reference("sk-keyring:x"), None);
+        assert_eq!(reference("claude-prod"), "keyring:claude-prod");
+    }
+
+    #[test]
+    fn restore_references_puts_refs_back_after_backfill() {
+        let stored = json!({
+            "env": {
+                "ANTHROPIC_AUTH_TOKEN": "keyring:claude-prod",
+                "ANTHROPIC_BASE_URL": "https://old.example.com"
+            }
+        });
+        let mut live = json!({
+            "env": {
+                "ANTHROPIC_AUTH_TOKEN": "sk-real-token",
+                "ANTHROPIC_BASE_URL": "https://new.example.com"
+            },
+            "model": "opus"
+        });
+        restore_references(&mut live, &stored);
+
+        assert_eq!(live["env"]["ANTHROPIC_AUTH_TOKEN"], "keyring:claude-prod");
+        assert_eq!(live["env"]["ANTHROPIC_BASE_URL"], "https://new.example.com");
+        assert_eq!(live["model"], "opus");
+    }
+}
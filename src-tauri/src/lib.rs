@@ -6,11 +6,15 @@ mod codex_config;
 mod config;
 mod database;
 mod deeplink;
+mod dry_run;
 mod error;
 mod gemini_config;
 mod gemini_mcp;
+mod http_client;
 mod import_export;
 mod init_status;
+mod instance_lock;
+mod keyring_store;
 mod mcp;
 mod prompt;
 mod prompt_files;
@@ -22,6 +26,7 @@ mod settings;
 mod store;
 mod sync_policy;
 mod usage_script;
+mod util;
 
 // CLI module
 pub mod cli;
@@ -29,11 +34,20 @@ pub mod cli;
 // Public exports
 pub use app_config::{AppType, McpApps, McpServer, MultiAppConfig};
 pub use codex_config::{get_codex_auth_path, get_codex_config_path, write_codex_live_atomic};
-pub use config::{get_claude_mcp_path, get_claude_settings_path, read_json_file};
-pub use database::{Database, FailoverQueueItem};
-pub use deeplink::{import_provider_from_deeplink, parse_deeplink_url, DeepLinkImportRequest};
-pub use error::AppError;
+pub use config::{
+    get_app_config_dir, get_claude_mcp_path, get_claude_settings_path, read_json_file,
+    set_app_config_dir_override, CONFIG_DIR_ENV,
+};
+pub use database::{Database, FailoverQueueItem, ProviderSwitchRecord};
+pub use deeplink::{
+    import_mcp_from_deeplink, import_provider_from_deeplink, import_skill_from_deeplink,
+    parse_deeplink_url, DeepLinkImportRequest,
+};
+pub use dry_run::{enable_dry_run, is_dry_run};
+pub use error::{AppError, ErrorCode};
+pub use http_client::{enable_offline, set_proxy_override, ProxyOverride};
 pub use import_export::export_config_to_file;
+pub use instance_lock::{acquire_instance_lock, set_lock_mode, LockMode};
 pub use mcp::{
     import_from_claude, import_from_codex, import_from_gemini, remove_server_from_claude,
     remove_server_from_codex, remove_server_from_gemini, sync_enabled_to_claude,
@@ -42,8 +56,14 @@ pub use mcp::{
 };
 pub use provider::{Provider, ProviderMeta};
 pub use services::{
-    ConfigService, EndpointLatency, McpService, PromptService, ProviderService, SkillService,
-    SpeedtestService,
+    live_backup, BackupInfo, BackupMeta, BulkInstallResult, ConfigSectionDiff, ConfigService,
+    ConflictStrategy, EndpointLatency, InstallOptions, InstallOutcome, InstallPlan,
+    LiveConfigPreview, McpImportOutcome, McpService, PromptImportReport, PromptService,
+    ProviderModels, ProviderService, SkillCasingFix, SkillProgress, SkillReadme, SkillReadmeSource,
+    SkillRepo, SkillService, SkillStats, SpeedtestService, SyncMethod, TemporarySwitch,
+};
+pub use settings::{
+    get_setting_value, set_app_override_dir, set_setting_value, update_settings, AppSettings,
+    SettingKey,
 };
-pub use settings::{update_settings, AppSettings};
 pub use store::AppState;
@@ -1,12 +1,64 @@
-use cc_switch_lib::cli::{Cli, Commands};
+use cc_switch_lib::cli::{AppSelector, Cli, Commands};
 use cc_switch_lib::AppError;
 use clap::Parser;
+use std::io::Write;
 use std::process;
 
 fn main() {
     // 解析命令行参数
     let cli = Cli::parse();
 
+    if cli.json {
+        cc_switch_lib::cli::ui::enable_json_output();
+    } else if let Some(format) = cli.format {
+        cc_switch_lib::cli::ui::set_output_format(format);
+    }
+    // 关闭颜色：--no-color、NO_COLOR 环境变量；结构化输出（JSON / YAML）始终不带颜色
+    if cli.no_color
+        || cc_switch_lib::cli::ui::json_output()
+        || std::env::var_os("NO_COLOR").is_some()
+    {
+        cc_switch_lib::cli::ui::disable_colors();
+    }
+    if cli.quiet {
+        cc_switch_lib::cli::ui::out::enable_quiet();
+    }
+    if cli.reveal_secrets {
+        cc_switch_lib::cli::ui::enable_reveal_secrets();
+    }
+    if cli.non_interactive {
+        cc_switch_lib::cli::terminal::enable_non_interactive();
+    }
+
+    // 配置目录覆盖必须在任何命令访问路径之前设置
+    if let Some(dir) = cli.config_dir.clone() {
+        if let Err(e) = cc_switch_lib::set_app_config_dir_override(dir) {
+            eprintln!("Error: {}", e);
+            process::exit(1);
+        }
+    }
+
+    let proxy = match (cli.proxy.clone(), cli.no_proxy) {
+        (Some(url), _) => Some(cc_switch_lib::ProxyOverride::Url(url)),
+        (None, true) => Some(cc_switch_lib::ProxyOverride::Disabled),
+        (None, false) => None,
+    };
+    if let Some(proxy) = proxy {
+        if let Err(e) = cc_switch_lib::set_proxy_override(proxy) {
+            eprintln!("Error: {}", e);
+            process::exit(1);
+        }
+    }
+
+    if cli.offline {
+        cc_switch_lib::enable_offline();
+    }
+
+    // 必须在任何命令打开数据库之前开启
+    if cli.dry_run_all {
+        cc_switch_lib::enable_dry_run();
+    }
+
     // 初始化日志（交互模式和命令行模式都避免干扰输出）
     let log_level = if cli.verbose {
         "debug"
@@ -15,30 +67,76 @@ fn main() {
     };
     env_logger::Builder::from_env(env_logger::Env::default().default_filter_or(log_level)).init();
 
+    // 修改类命令（含交互模式）需要跨进程实例锁，避免并发写坏数据库
+    let read_only = cli.command.as_ref().is_some_and(|cmd| cmd.is_read_only());
+    if !read_only {
+        cc_switch_lib::set_lock_mode(if cli.wait {
+            cc_switch_lib::LockMode::Wait
+        } else {
+            cc_switch_lib::LockMode::Exclusive
+        });
+    }
+
     // 执行命令
-    if let Err(e) = run(cli) {
-        eprintln!("Error: {}", e);
-        process::exit(1);
+    let json =
+        cc_switch_lib::cli::ui::output_format() == cc_switch_lib::cli::ui::OutputFormat::Json;
+    match run(cli) {
+        Ok(0) => {}
+        Ok(code) => {
+            let _ = std::io::stdout().flush();
+            process::exit(code);
+        }
+        Err(e) => {
+            if json {
+                // 结构化错误：{"error":{"code":..,"message":..}}，便于脚本按错误码处理
+                eprintln!("{}", e.to_json());
+            } else {
+                eprintln!("Error: {}", e);
+            }
+            process::exit(1);
+        }
     }
 }
 
-fn run(cli: Cli) -> Result<(), AppError> {
+/// 返回进程退出码（目前仅 `update --check` 发现新版本时非零）
+fn run(cli: Cli) -> Result<i32, AppError> {
+    // 在分发前加锁：Skills 等服务会绕过 AppState 直接打开数据库并写文件
+    cc_switch_lib::acquire_instance_lock()?;
+
+    let all_apps = cli.app == Some(AppSelector::All);
+    let app = cli.app.as_ref().and_then(AppSelector::app_type);
     match cli.command {
+        Some(Commands::Provider(cmd)) if all_apps => {
+            cc_switch_lib::cli::commands::provider::execute_all_apps(cmd)
+        }
+        _ if all_apps => Err(cc_switch_lib::cli::app_all_unsupported()),
         // Default to interactive mode if no command is provided
-        None | Some(Commands::Interactive) => cc_switch_lib::cli::interactive::run(cli.app),
-        Some(Commands::Provider(cmd)) => {
-            cc_switch_lib::cli::commands::provider::execute(cmd, cli.app)
+        None | Some(Commands::Interactive) => {
+            cc_switch_lib::cli::terminal::ensure_interactive(
+                "interactive mode",
+                Some("a subcommand (see --help)"),
+            )?;
+            cc_switch_lib::cli::interactive::run(app)
         }
-        Some(Commands::Mcp(cmd)) => cc_switch_lib::cli::commands::mcp::execute(cmd, cli.app),
-        Some(Commands::Prompts(cmd)) => {
-            cc_switch_lib::cli::commands::prompts::execute(cmd, cli.app)
+        Some(Commands::Provider(cmd)) => cc_switch_lib::cli::commands::provider::execute(cmd, app),
+        Some(Commands::Mcp(cmd)) => cc_switch_lib::cli::commands::mcp::execute(cmd, app),
+        Some(Commands::Prompts(cmd)) => cc_switch_lib::cli::commands::prompts::execute(cmd, app),
+        Some(Commands::Skills(cmd)) => cc_switch_lib::cli::commands::skills::execute(cmd, app),
+        Some(Commands::Config(cmd)) => cc_switch_lib::cli::commands::config::execute(cmd, app),
+        Some(Commands::App(cmd)) => cc_switch_lib::cli::commands::app::execute(cmd, app),
+        Some(Commands::Settings(cmd)) => cc_switch_lib::cli::commands::settings::execute(cmd),
+        Some(Commands::Env(cmd)) => cc_switch_lib::cli::commands::env::execute(cmd, app),
+        Some(Commands::Deeplink(cmd)) => cc_switch_lib::cli::commands::deeplink::execute(cmd),
+        Some(Commands::Update(cmd)) => {
+            return cc_switch_lib::cli::commands::update::execute(cmd)
+                .map(|outcome| outcome.exit_code());
         }
-        Some(Commands::Skills(cmd)) => cc_switch_lib::cli::commands::skills::execute(cmd, cli.app),
-        Some(Commands::Config(cmd)) => cc_switch_lib::cli::commands::config::execute(cmd, cli.app),
-        Some(Commands::Env(cmd)) => cc_switch_lib::cli::commands::env::execute(cmd, cli.app),
+        Some(Commands::Version) => cc_switch_lib::cli::commands::version::execute(),
         Some(Commands::Completions { shell }) => {
             cc_switch_lib::cli::generate_completions(shell);
             Ok(())
         }
-    }
+        Some(Commands::Complete { kind }) => cc_switch_lib::cli::completion::print_candidates(kind),
+    }?;
+    Ok(0)
 }
@@ -5,7 +5,7 @@ use crate::app_config::{AppType, McpConfig, MultiAppConfig};
 use crate::error::AppError;
 
 /// 基础校验：允许 stdio/http/sse；或省略 type（视为 stdio）。对应必填字段存在
-fn validate_server_spec(spec: &Value) -> Result<(), AppError> {
+pub(crate) fn validate_server_spec(spec: &Value) -> Result<(), AppError> {
     if !spec.is_object() {
         return Err(AppError::McpValidation(
             "MCP 服务器连接定义必须为 JSON 对象".into(),
@@ -1068,8 +1068,8 @@ pub fn sync_single_server_to_codex(
     // 使用唯一正确的格式：[mcp_servers]
     doc["mcp_servers"][id] = Item::Table(toml_table);
 
-    // 写回文件
-    std::fs::write(&config_path, doc.to_string()).map_err(|e| AppError::io(&config_path, e))?;
+    // 写回文件（~/.codex 可能尚未创建）
+    crate::config::write_text_file(&config_path, &doc.to_string())?;
 
     Ok(())
 }
@@ -1105,7 +1105,8 @@ pub fn remove_server_from_codex(id: &str) -> Result<(), AppError> {
     }
 
     // 写回文件
-    std::fs::write(&config_path, doc.to_string()).map_err(|e| AppError::io(&config_path, e))?;
+    crate::dry_run::write(&config_path, doc.to_string())
+        .map_err(|e| AppError::io(&config_path, e))?;
 
     Ok(())
 }
@@ -1,6 +1,6 @@
 use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
-use serde_json::Value;
+use serde_json::{Map, Value};
 use std::collections::HashMap;
 
 // SSOT 模式：不再写供应商副本文件
@@ -20,6 +20,14 @@ pub struct Provider {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(rename = "createdAt")]
     pub created_at: Option<i64>,
+    /// 首次保存时间（Unix 秒），由数据库层维护
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "addedAt")]
+    pub added_at: Option<i64>,
+    /// 最近一次修改时间（Unix 秒），每次保存时更新
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "updatedAt")]
+    pub updated_at: Option<i64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(rename = "sortIndex")]
     pub sort_index: Option<usize>,
@@ -40,6 +48,9 @@ pub struct Provider {
     #[serde(default)]
     #[serde(rename = "inFailoverQueue")]
     pub in_failover_queue: bool,
+    /// 未识别的字段（来自导入文件或更新版本），原样保留以免 load→save 往返时丢失
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
 }
 
 impl Provider {
@@ -57,12 +68,15 @@ impl Provider {
             website_url,
             category: None,
             created_at: None,
+            added_at: None,
+            updated_at: None,
             sort_index: None,
             notes: None,
             meta: None,
             icon: None,
             icon_color: None,
             in_failover_queue: false,
+            extra: Map::new(),
         }
     }
 }
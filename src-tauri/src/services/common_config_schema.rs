@@ -0,0 +1,229 @@
+//! 通用配置片段的内置最小 schema 校验
+//!
+//! 只约束已知字段的类型与结构（未知字段放行），在 `config common set` 时给出
+//! 带路径的错误，避免片段在切换供应商时才静默破坏 live 配置。
+
+use serde_json::Value;
+
+use crate::app_config::AppType;
+use crate::error::AppError;
+
+#[derive(Debug, Clone, Copy)]
+enum Schema {
+    Any,
+    String,
+    Bool,
+    Number,
+    Array(&'static Schema),
+    /// 已知字段按各自 schema 校验，其余字段不限制
+    Object(&'static [(&'static str, Schema)]),
+    /// 任意键的对象，值按给定 schema 校验
+    Map(&'static Schema),
+    /// 环境变量表：键须为合法变量名，值为 string / number / bool
+    EnvMap,
+    /// 同 `EnvMap`，但值只能是 string（Gemini `.env` 会丢弃非字符串值）
+    StringEnvMap,
+}
+
+const STRING_LIST: Schema = Schema::Array(&Schema::String);
+const OBJECT: Schema = Schema::Map(&Schema::Any);
+
+const CLAUDE_SCHEMA: Schema = Schema::Object(&[
+    ("env", Schema::EnvMap),
+    (
+        "permissions",
+        Schema::Object(&[
+            ("allow", STRING_LIST),
+            ("deny", STRING_LIST),
+            ("ask", STRING_LIST),
+            ("additionalDirectories", STRING_LIST),
+            ("defaultMode", Schema::String),
+        ]),
+    ),
+    ("model", Schema::String),
+    ("apiKeyHelper", Schema::String),
+    ("includeCoAuthoredBy", Schema::Bool),
+    ("alwaysThinkingEnabled", Schema::Bool),
+    ("cleanupPeriodDays", Schema::Number),
+    ("hooks", OBJECT),
+    ("statusLine", OBJECT),
+]);
+
+const CODEX_SCHEMA: Schema = Schema::Object(&[
+    ("model", Schema::String),
+    ("model_provider", Schema::String),
+    ("model_reasoning_effort", Schema::String),
+    ("disable_response_storage", Schema::Bool),
+    ("model_providers", Schema::Map(&OBJECT)),
+    ("mcp_servers", Schema::Map(&OBJECT)),
+]);
+
+const GEMINI_SCHEMA: Schema = Schema::Object(&[("env", Schema::StringEnvMap), ("config", OBJECT)]);
+
+fn schema_for(app: &AppType) -> &'static Schema {
+    match app {
+        AppType::Claude => &CLAUDE_SCHEMA,
+        AppType::Codex => &CODEX_SCHEMA,
+        AppType::Gemini => &GEMINI_SCHEMA,
+    }
+}
+
+fn is_env_var_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    matches!(chars.next(), Some(c) if c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+fn check(schema: &Schema, value: &Value, path: &str, errors: &mut Vec<String>) {
+    let mismatch = |expected: &str, errors: &mut Vec<String>| {
+        errors.push(format!(
+            "{path}: expected {expected}, got {}",
+            type_name(value)
+        ));
+    };
+
+    match schema {
+        Schema::Any => {}
+        Schema::String if !value.is_string() => mismatch("string", errors),
+        Schema::Bool if !value.is_boolean() => mismatch("boolean", errors),
+        Schema::Number if !value.is_number() => mismatch("number", errors),
+        Schema::String | Schema::Bool | Schema::Number => {}
+        Schema::Array(item) => match value.as_array() {
+            Some(items) => {
+                for (i, child) in items.iter().enumerate() {
+                    check(item, child, &format!("{path}[{i}]"), errors);
+                }
+            }
+            None => mismatch("array", errors),
+        },
+        Schema::Object(fields) => match value.as_object() {
+            Some(map) => {
+                for (key, child_schema) in fields.iter() {
+                    if let Some(child) = map.get(*key) {
+                        check(child_schema, child, &format!("{path}.{key}"), errors);
+                    }
+                }
+            }
+            None => mismatch("object", errors),
+        },
+        Schema::Map(child_schema) => match value.as_object() {
+            Some(map) => {
+                for (key, child) in map {
+                    check(child_schema, child, &format!("{path}.{key}"), errors);
+                }
+            }
+            None => mismatch("object", errors),
+        },
+        Schema::EnvMap | Schema::StringEnvMap => {
+            let Some(map) = value.as_object() else {
+                return mismatch("object", errors);
+            };
+            let strings_only = matches!(schema, Schema::StringEnvMap);
+            for (key, child) in map {
+                let child_path = format!("{path}.{key}");
+                if !is_env_var_name(key) {
+                    errors.push(format!("{child_path}: invalid environment variable name"));
+                }
+                let ok = child.is_string()
+                    || (!strings_only && (child.is_number() || child.is_boolean()));
+                if !ok {
+                    let expected = if strings_only {
+                        "string"
+                    } else {
+                        "string, number or boolean"
+                    };
+                    errors.push(format!(
+                        "{child_path}: expected {expected}, got {}",
+                        type_name(child)
+                    ));
+                }
+            }
+        }
+    }
+}
+
+/// 按应用内置 schema 校验通用配置片段；返回所有不匹配项（`$.env.FOO: ...`）组成的错误
+pub fn validate_common_config_snippet(app: &AppType, value: &Value) -> Result<(), AppError> {
+    let mut errors = Vec::new();
+    check(schema_for(app), value, "$", &mut errors);
+    if errors.is_empty() {
+        return Ok(());
+    }
+
+    let details = errors.join("\n  ");
+    Err(AppError::localized(
+        "common_config.schema_invalid",
+        format!(
+            "{} 通用配置片段不符合预期结构（可用 --no-validate 跳过）:\n  {details}",
+            app.as_str()
+        ),
+        format!(
+            "{} common config snippet does not match the expected shape (use --no-validate to skip):\n  {details}",
+            app.as_str()
+        ),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn issues(app: AppType, value: Value) -> Vec<String> {
+        let mut errors = Vec::new();
+        check(schema_for(&app), &value, "$", &mut errors);
+        errors
+    }
+
+    #[test]
+    fn accepts_known_and_unknown_fields_with_valid_shapes() {
+        let value = json!({
+            "env": { "CLAUDE_CODE_DISABLE_NONESSENTIAL_TRAFFIC": 1, "DEBUG": true },
+            "permissions": { "allow": ["Bash(ls)"] },
+            "includeCoAuthoredBy": false,
+            "somethingNew": { "nested": [1, 2] }
+        });
+        assert!(validate_common_config_snippet(&AppType::Claude, &value).is_ok());
+    }
+
+    #[test]
+    fn reports_path_based_errors() {
+        let errors = issues(
+            AppType::Claude,
+            json!({
+                "env": { "BAD-NAME": "x", "OK": { "nested": 1 } },
+                "permissions": { "allow": ["a", 3] },
+                "includeCoAuthoredBy": "no"
+            }),
+        );
+        assert_eq!(
+            errors,
+            vec![
+                "$.env.BAD-NAME: invalid environment variable name".to_string(),
+                "$.env.OK: expected string, number or boolean, got object".to_string(),
+                "$.permissions.allow[1]: expected string, got number".to_string(),
+                "$.includeCoAuthoredBy: expected boolean, got string".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn gemini_env_values_must_be_strings() {
+        let errors = issues(AppType::Gemini, json!({ "env": { "GEMINI_MODEL": 1 } }));
+        assert_eq!(
+            errors,
+            vec!["$.env.GEMINI_MODEL: expected string, got number".to_string()]
+        );
+    }
+}
@@ -1,3 +1,4 @@
+use super::config_crypto;
 use super::provider::ProviderService;
 use crate::app_config::{AppType, MultiAppConfig};
 use crate::database::Database;
@@ -5,11 +6,49 @@ use crate::error::AppError;
 use crate::provider::Provider;
 use crate::store::AppState;
 use chrono::Utc;
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
 use std::fs;
 use std::path::{Path, PathBuf};
 
 const MAX_BACKUPS: usize = 10;
+/// gzip 魔数
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+/// 备份文件名对应的 ID：去掉 `.sql.gz` 或 `.sql` 后缀，其余文件返回 None
+fn backup_id_of(path: &Path) -> Option<String> {
+    let name = path.file_name()?.to_str()?;
+    name.strip_suffix(".sql.gz")
+        .or_else(|| name.strip_suffix(".sql"))
+        .map(str::to_string)
+}
+
+/// 备份的附加说明，保存在备份旁的 `<id>.meta.json`（旧备份没有该文件）
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackupMeta {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+}
+
+impl BackupMeta {
+    pub fn is_empty(&self) -> bool {
+        self.description.is_none() && self.tags.is_empty()
+    }
+
+    /// 是否带有指定标签（忽略大小写）
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.iter().any(|t| t.eq_ignore_ascii_case(tag.trim()))
+    }
+}
+
+fn backup_meta_path(backup_dir: &Path, backup_id: &str) -> PathBuf {
+    backup_dir.join(format!("{backup_id}.meta.json"))
+}
 
 /// 备份信息
 #[derive(Debug, Clone)]
@@ -22,6 +61,323 @@ pub struct BackupInfo {
     pub timestamp: String,
     /// 显示名称（用于 UI）
     pub display_name: String,
+    /// 是否为 gzip 压缩备份（`.sql.gz`）
+    pub compressed: bool,
+    /// 描述与标签（无 sidecar 时为空）
+    pub meta: BackupMeta,
+}
+
+impl BackupInfo {
+    /// 选择列表中的一行：`名称 - ID [标签] 描述`
+    pub fn label(&self) -> String {
+        let mut label = format!("{} - {}", self.display_name, self.id);
+        if !self.meta.tags.is_empty() {
+            label.push_str(&format!(" [{}]", self.meta.tags.join(", ")));
+        }
+        if let Some(description) = &self.meta.description {
+            label.push_str(&format!(" {description}"));
+        }
+        label
+    }
+}
+
+/// 旧版配置合并时的冲突策略
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
+pub enum ConflictStrategy {
+    /// 保留数据库中已有的条目
+    #[default]
+    Skip,
+    /// 使用旧配置中的条目覆盖
+    Overwrite,
+}
+
+/// 单个配置分区（如 `providers:claude`、`mcp`）的差异，按 id 列出
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfigSectionDiff {
+    pub section: String,
+    pub current: usize,
+    pub incoming: usize,
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+    pub changed: Vec<String>,
+}
+
+impl ConfigSectionDiff {
+    pub fn is_unchanged(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+/// 由数据库维护的字段（时间戳、排序）：同一条目导出再导入后必然不同，比较时忽略
+const VOLATILE_KEYS: [&str; 5] = [
+    "createdAt",
+    "addedAt",
+    "updatedAt",
+    "installedAt",
+    "sortIndex",
+];
+
+/// 序列化为用于比较的 JSON：去掉 [`VOLATILE_KEYS`]，以及经数据库往返后才补上的
+/// 空值（`null`、空的 `meta` / `extra` 等）
+fn comparable_value<V: Serialize>(value: &V) -> Value {
+    let mut value = serde_json::to_value(value).unwrap_or(Value::Null);
+    if let Some(map) = value.as_object_mut() {
+        for key in VOLATILE_KEYS {
+            map.remove(key);
+        }
+        map.retain(|_, v| match v {
+            Value::Null => false,
+            Value::Object(o) => !o.is_empty(),
+            Value::Array(a) => !a.is_empty(),
+            _ => true,
+        });
+    }
+    value
+}
+
+fn value_map<'a, V: Serialize + 'a>(
+    items: impl IntoIterator<Item = (&'a String, &'a V)>,
+) -> BTreeMap<&'a str, Value> {
+    items
+        .into_iter()
+        .map(|(id, v)| (id.as_str(), comparable_value(v)))
+        .collect()
+}
+
+fn diff_section<'a, V: Serialize + 'a>(
+    section: String,
+    current: impl IntoIterator<Item = (&'a String, &'a V)>,
+    incoming: impl IntoIterator<Item = (&'a String, &'a V)>,
+) -> ConfigSectionDiff {
+    let current = value_map(current);
+    let incoming = value_map(incoming);
+
+    let mut diff = ConfigSectionDiff {
+        section,
+        current: current.len(),
+        incoming: incoming.len(),
+        ..Default::default()
+    };
+    for (id, value) in &incoming {
+        match current.get(id) {
+            None => diff.added.push(id.to_string()),
+            Some(old) if old != value => diff.changed.push(id.to_string()),
+            Some(_) => {}
+        }
+    }
+    diff.removed = current
+        .keys()
+        .filter(|id| !incoming.contains_key(*id))
+        .map(|id| id.to_string())
+        .collect();
+    diff
+}
+
+/// 按分区比较两份配置：各应用的供应商 / 提示词 / 通用配置片段，以及 MCP 服务器
+pub fn diff_configs(current: &MultiAppConfig, incoming: &MultiAppConfig) -> Vec<ConfigSectionDiff> {
+    let empty_providers = IndexMap::new();
+    let empty_mcp = HashMap::new();
+    let mut sections = Vec::new();
+
+    for app in [AppType::Claude, AppType::Codex, AppType::Gemini] {
+        let cur = current.get_manager(&app);
+        let inc = incoming.get_manager(&app);
+        let mut diff = diff_section(
+            format!("providers:{}", app.as_str()),
+            cur.map_or(&empty_providers, |m| &m.providers),
+            inc.map_or(&empty_providers, |m| &m.providers),
+        );
+        let cur_id = cur.map(|m| m.current.as_str()).unwrap_or_default();
+        let inc_id = inc.map(|m| m.current.as_str()).unwrap_or_default();
+        if cur_id != inc_id {
+            diff.changed
+                .push(format!("(current: {cur_id} -> {inc_id})"));
+        }
+        sections.push(diff);
+    }
+
+    sections.push(diff_section(
+        "mcp".to_string(),
+        current.mcp.servers.as_ref().unwrap_or(&empty_mcp),
+        incoming.mcp.servers.as_ref().unwrap_or(&empty_mcp),
+    ));
+
+    for app in [AppType::Claude, AppType::Codex, AppType::Gemini] {
+        let prompts = |cfg: &MultiAppConfig| match app {
+            AppType::Claude => cfg.prompts.claude.prompts.clone(),
+            AppType::Codex => cfg.prompts.codex.prompts.clone(),
+            AppType::Gemini => cfg.prompts.gemini.prompts.clone(),
+        };
+        let (cur, inc) = (prompts(current), prompts(incoming));
+        sections.push(diff_section(
+            format!("prompts:{}", app.as_str()),
+            &cur,
+            &inc,
+        ));
+
+        let key = "snippet".to_string();
+        let snippet = |cfg: &MultiAppConfig| {
+            cfg.common_config_snippets
+                .get(&app)
+                .map(|s| (key.clone(), s.clone()))
+                .into_iter()
+                .collect::<BTreeMap<_, _>>()
+        };
+        let (cur, inc) = (snippet(current), snippet(incoming));
+        sections.push(diff_section(format!("common:{}", app.as_str()), &cur, &inc));
+    }
+
+    sections
+}
+
+/// `config show --diff-default`：当前配置与全新默认配置的差异
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DefaultDeviations {
+    /// 有变化的分区，按 id 列出（与导入预览相同）
+    pub sections: Vec<ConfigSectionDiff>,
+    /// 配置中与默认值不同的键；`null` 表示默认存在、当前已删除
+    pub config: Value,
+    /// Skills 索引中与默认值不同的键
+    pub skills: Value,
+}
+
+impl DefaultDeviations {
+    pub fn is_empty(&self) -> bool {
+        self.sections.is_empty() && is_empty_object(&self.config) && is_empty_object(&self.skills)
+    }
+}
+
+fn is_empty_object(value: &Value) -> bool {
+    value.as_object().is_some_and(|map| map.is_empty())
+}
+
+/// 只保留 `current` 中与 `default` 不同的部分：对象逐键递归比较，数组与标量整体比较；
+/// 完全相同时返回 None
+pub fn prune_defaults(current: &Value, default: &Value) -> Option<Value> {
+    match (current, default) {
+        (Value::Object(cur), Value::Object(def)) => {
+            let mut changed = serde_json::Map::new();
+            for (key, value) in cur {
+                let pruned = match def.get(key) {
+                    Some(default_value) => prune_defaults(value, default_value),
+                    None => Some(value.clone()),
+                };
+                if let Some(pruned) = pruned {
+                    changed.insert(key.clone(), pruned);
+                }
+            }
+            for key in def.keys().filter(|key| !cur.contains_key(*key)) {
+                changed.insert(key.clone(), Value::Null);
+            }
+            (!changed.is_empty()).then_some(Value::Object(changed))
+        }
+        _ if current == default => None,
+        _ => Some(current.clone()),
+    }
+}
+
+fn prune_serialized<T: Serialize>(current: &T, default: &T) -> Result<Value, AppError> {
+    let to_value =
+        |v: &T| serde_json::to_value(v).map_err(|e| AppError::JsonSerialize { source: e });
+    Ok(prune_defaults(&to_value(current)?, &to_value(default)?)
+        .unwrap_or_else(|| Value::Object(Default::default())))
+}
+
+/// `config import --merge` 中单个分区的合并结果，按 id 列出
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportMergeSection {
+    pub section: String,
+    pub added: Vec<String>,
+    pub updated: Vec<String>,
+    /// 与现有条目冲突且按 `skip` 策略保留原值
+    pub skipped: Vec<String>,
+    /// 与现有条目完全相同
+    pub unchanged: usize,
+    /// 导入文件中有记录、但本机缺少对应文件而未导入（如 Skill 的 SSOT 目录）
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub missing: Vec<String>,
+}
+
+/// `config import --merge` 的结果
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportMergeReport {
+    pub sections: Vec<ImportMergeSection>,
+    /// 合并前创建的备份 ID（dry-run 或无变化时为 None）
+    pub backup_id: Option<String>,
+}
+
+/// 规划单个分区的合并：返回结果与需要写入的条目（新增 + 覆盖）
+fn plan_merge_section<'a, 'b, V: Serialize + 'a + 'b>(
+    section: String,
+    current: impl IntoIterator<Item = (&'a String, &'a V)>,
+    incoming: impl IntoIterator<Item = (&'b String, &'b V)>,
+    strategy: ConflictStrategy,
+) -> (ImportMergeSection, Vec<&'b V>) {
+    let current = value_map(current);
+    let mut result = ImportMergeSection {
+        section,
+        ..Default::default()
+    };
+    let mut writes = Vec::new();
+    for (id, value) in incoming {
+        match current.get(id.as_str()) {
+            None => {
+                result.added.push(id.clone());
+                writes.push(value);
+            }
+            Some(old) if *old == comparable_value(value) => {
+                result.unchanged += 1;
+            }
+            Some(_) if strategy == ConflictStrategy::Overwrite => {
+                result.updated.push(id.clone());
+                writes.push(value);
+            }
+            Some(_) => result.skipped.push(id.clone()),
+        }
+    }
+    (result, writes)
+}
+
+/// `config migrate` 的合并结果
+#[derive(Debug, Clone, Default)]
+pub struct LegacyMigrationReport {
+    pub providers: usize,
+    pub prompts: usize,
+    pub mcp_servers: usize,
+    /// 因冲突被跳过的条目数
+    pub skipped: usize,
+    /// 源文件归档后的路径
+    pub archived_to: Option<PathBuf>,
+}
+
+/// `config upgrade` 检测到的存储版本及执行结果
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SchemaUpgradeReport {
+    pub database_path: PathBuf,
+    /// 数据库的 `user_version`；数据库尚未创建时为 None
+    pub database_version: Option<i32>,
+    /// 当前程序支持的数据库版本
+    pub target_version: i32,
+    /// 待导入的旧版 skills.json 的 index 版本（只在数据库尚未创建时导入）
+    pub skills_index_version: Option<u32>,
+    /// 待导入的旧版 config.json（只在数据库尚未创建时导入）
+    pub legacy_config: bool,
+    /// 升级前数据库文件的备份
+    pub backup_path: Option<PathBuf>,
+    pub applied: bool,
+}
+
+impl SchemaUpgradeReport {
+    pub fn is_pending(&self) -> bool {
+        self.database_version != Some(self.target_version)
+            || self.skills_index_version.is_some()
+            || self.legacy_config
+    }
 }
 
 /// 配置导入导出相关业务逻辑
@@ -40,6 +396,25 @@ impl ConfigService {
     pub fn create_backup(
         config_path: &Path,
         custom_name: Option<String>,
+    ) -> Result<String, AppError> {
+        Self::create_backup_with_options(config_path, custom_name, false)
+    }
+
+    /// 同 [`Self::create_backup`]；`compress` 为 true 时写出 gzip 压缩的 `{id}.sql.gz`
+    pub fn create_backup_with_options(
+        config_path: &Path,
+        custom_name: Option<String>,
+        compress: bool,
+    ) -> Result<String, AppError> {
+        Self::create_backup_with_meta(config_path, custom_name, compress, &BackupMeta::default())
+    }
+
+    /// 同 [`Self::create_backup_with_options`]；`meta` 非空时写入 `<id>.meta.json`
+    pub fn create_backup_with_meta(
+        config_path: &Path,
+        custom_name: Option<String>,
+        compress: bool,
+        meta: &BackupMeta,
     ) -> Result<String, AppError> {
         let db_path = crate::config::get_app_config_dir().join("cc-switch.db");
         if !db_path.exists() {
@@ -59,11 +434,33 @@ impl ConfigService {
             .ok_or_else(|| AppError::Config("Invalid config path".into()))?
             .join("backups");
 
-        fs::create_dir_all(&backup_dir).map_err(|e| AppError::io(&backup_dir, e))?;
+        crate::dry_run::create_dir_all(&backup_dir).map_err(|e| AppError::io(&backup_dir, e))?;
 
-        let backup_path = backup_dir.join(format!("{backup_id}.sql"));
         let db = Database::init()?;
-        db.export_sql(&backup_path)?;
+        if compress {
+            use flate2::{write::GzEncoder, Compression};
+            use std::io::Write;
+
+            let backup_path = backup_dir.join(format!("{backup_id}.sql.gz"));
+            let sql = db.export_sql_string()?;
+            let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+            encoder
+                .write_all(sql.as_bytes())
+                .map_err(|e| AppError::io(&backup_path, e))?;
+            let data = encoder
+                .finish()
+                .map_err(|e| AppError::io(&backup_path, e))?;
+            crate::config::atomic_write(&backup_path, &data)?;
+        } else {
+            db.export_sql(&backup_dir.join(format!("{backup_id}.sql")))?;
+        }
+        let meta_path = backup_meta_path(&backup_dir, &backup_id);
+        if meta.is_empty() {
+            // 同名备份被覆盖时不保留旧的元数据
+            let _ = crate::dry_run::remove_file(&meta_path);
+        } else {
+            crate::config::write_json_file(&meta_path, meta)?;
+        }
 
         Self::cleanup_old_backups(&backup_dir, MAX_BACKUPS)?;
 
@@ -85,16 +482,9 @@ impl ConfigService {
 
         let mut backups: Vec<BackupInfo> = entries
             .filter_map(|entry| entry.ok())
-            .filter(|entry| {
-                entry
-                    .path()
-                    .extension()
-                    .map(|ext| ext == "sql")
-                    .unwrap_or(false)
-            })
             .filter_map(|entry| {
                 let path = entry.path();
-                let filename = path.file_stem()?.to_str()?.to_string();
+                let filename = backup_id_of(&path)?;
 
                 // 提取时间戳（假设格式为 xxx_YYYYMMDD_HHMMSS）
                 let timestamp = Self::extract_timestamp(&filename)?;
@@ -102,11 +492,21 @@ impl ConfigService {
                 // 生成显示名称
                 let display_name = Self::format_display_name(&filename, &timestamp);
 
+                // sidecar 缺失或损坏时按无元数据处理
+                let meta_path = backup_meta_path(&backup_dir, &filename);
+                let meta = if meta_path.exists() {
+                    crate::config::read_json_file(&meta_path).unwrap_or_default()
+                } else {
+                    BackupMeta::default()
+                };
+
                 Some(BackupInfo {
                     id: filename.clone(),
                     path: path.clone(),
                     timestamp,
                     display_name,
+                    compressed: path.extension().is_some_and(|ext| ext == "gz"),
+                    meta,
                 })
             })
             .collect();
@@ -119,19 +519,23 @@ impl ConfigService {
 
     /// 根据备份 ID 恢复配置
     pub fn restore_from_backup_id(backup_id: &str, state: &AppState) -> Result<String, AppError> {
+        let backup_path = Self::backup_file_path(backup_id)?;
+        Self::import_config_from_path(&backup_path, state)
+    }
+
+    /// 备份 ID 对应的 SQL 文件路径（`.sql` 或 `.sql.gz`，文件不存在时报错）
+    pub fn backup_file_path(backup_id: &str) -> Result<PathBuf, AppError> {
         let config_path = crate::config::get_app_config_path();
         let backup_dir = config_path
             .parent()
             .ok_or_else(|| AppError::Config("Invalid config path".into()))?
             .join("backups");
 
-        let backup_path = backup_dir.join(format!("{}.sql", backup_id));
-
-        if !backup_path.exists() {
-            return Err(AppError::Message(format!("备份文件不存在: {}", backup_id)));
-        }
-
-        Self::import_config_from_path(&backup_path, state)
+        ["sql", "sql.gz"]
+            .iter()
+            .map(|ext| backup_dir.join(format!("{backup_id}.{ext}")))
+            .find(|path| path.exists())
+            .ok_or_else(|| AppError::Message(format!("备份文件不存在: {}", backup_id)))
     }
 
     /// 从文件名提取时间戳字符串
@@ -187,13 +591,7 @@ impl ConfigService {
         let entries = match fs::read_dir(backup_dir) {
             Ok(iter) => iter
                 .filter_map(|entry| entry.ok())
-                .filter(|entry| {
-                    entry
-                        .path()
-                        .extension()
-                        .map(|ext| ext == "sql")
-                        .unwrap_or(false)
-                })
+                .filter(|entry| backup_id_of(&entry.path()).is_some())
                 .collect::<Vec<_>>(),
             Err(_) => return Ok(()),
         };
@@ -212,12 +610,16 @@ impl ConfigService {
         });
 
         for entry in sorted.into_iter().take(remove_count) {
-            if let Err(err) = fs::remove_file(entry.path()) {
+            if let Err(err) = crate::dry_run::remove_file(entry.path()) {
                 log::warn!(
                     "Failed to remove old backup {}: {}",
                     entry.path().display(),
                     err
                 );
+                continue;
+            }
+            if let Some(id) = backup_id_of(&entry.path()) {
+                let _ = crate::dry_run::remove_file(backup_meta_path(backup_dir, &id));
             }
         }
 
@@ -230,21 +632,444 @@ impl ConfigService {
         db.export_sql(target_path)
     }
 
+    /// 导出为口令加密的文件（见 [`config_crypto`] 的格式说明）
+    pub fn export_config_encrypted_to_path(
+        target_path: &Path,
+        passphrase: &str,
+    ) -> Result<(), AppError> {
+        let db = Database::init()?;
+        let dump = db.export_sql_string()?;
+        let data = config_crypto::encrypt(dump.as_bytes(), passphrase)?;
+
+        if let Some(parent) = target_path.parent() {
+            crate::dry_run::create_dir_all(parent).map_err(|e| AppError::io(parent, e))?;
+        }
+        crate::config::atomic_write(target_path, &data)
+    }
+
+    /// 文件是否为加密导出（按魔数判断）
+    pub fn is_encrypted_export(path: &Path) -> Result<bool, AppError> {
+        use std::io::Read;
+
+        let mut head = [0u8; 8];
+        let mut file = fs::File::open(path).map_err(|e| AppError::io(path, e))?;
+        let n = file.read(&mut head).map_err(|e| AppError::io(path, e))?;
+        Ok(config_crypto::is_encrypted(&head[..n]))
+    }
+
     pub fn import_config_from_path(file_path: &Path, state: &AppState) -> Result<String, AppError> {
+        Self::import_config_from_path_with_passphrase(file_path, None, state)
+    }
+
+    /// 导入 SQL 导出文件；加密文件需提供口令，明文文件忽略口令
+    pub fn import_config_from_path_with_passphrase(
+        file_path: &Path,
+        passphrase: Option<&str>,
+        state: &AppState,
+    ) -> Result<String, AppError> {
         let db_path = crate::config::get_app_config_dir().join("cc-switch.db");
         if !db_path.exists() {
             return Err(AppError::Config("数据库不存在，无法导入".to_string()));
         }
 
+        // 先读取（并解密、校验口令），避免口令错误时也生成一份导入前备份
+        let sql = Self::read_import_sql(file_path, passphrase)?;
+
         // Pre-import backup (SQL).
         let backup_id = Self::create_backup(&db_path, None)?;
 
         // Import SQL into DB (also performs an internal binary snapshot backup).
-        state.db.import_sql(file_path)?;
+        state.db.import_sql_str(&sql)?;
 
         Ok(backup_id)
     }
 
+    /// 读取导入文件的 SQL 文本；gzip 文件（`.gz` 扩展名或魔数）先解压，加密文件使用口令解密
+    fn read_import_sql(file_path: &Path, passphrase: Option<&str>) -> Result<String, AppError> {
+        if !file_path.exists() {
+            return Err(AppError::InvalidInput(format!(
+                "SQL 文件不存在: {}",
+                file_path.display()
+            )));
+        }
+        let gzipped = file_path.extension().is_some_and(|ext| ext == "gz");
+        let data = fs::read(file_path).map_err(|e| AppError::io(file_path, e))?;
+        if gzipped || data.starts_with(&GZIP_MAGIC) {
+            use std::io::Read;
+
+            let mut sql = String::new();
+            flate2::read::GzDecoder::new(data.as_slice())
+                .read_to_string(&mut sql)
+                .map_err(|e| {
+                    AppError::Message(format!("解压备份文件失败 {}: {e}", file_path.display()))
+                })?;
+            return Ok(sql);
+        }
+        if !config_crypto::is_encrypted(&data) {
+            return String::from_utf8(data)
+                .map_err(|e| AppError::Message(format!("SQL 文件不是有效的 UTF-8: {e}")));
+        }
+
+        let passphrase = passphrase.ok_or_else(|| {
+            AppError::localized(
+                "config.passphrase_required",
+                format!("{} 是加密导出文件，需要提供口令", file_path.display()),
+                format!(
+                    "{} is an encrypted export; a passphrase is required",
+                    file_path.display()
+                ),
+            )
+        })?;
+        let plain = config_crypto::decrypt(&data, passphrase)?;
+        String::from_utf8(plain)
+            .map_err(|e| AppError::Message(format!("解密后的内容不是有效的 UTF-8: {e}")))
+    }
+
+    /// 预览导入：在内存中加载导入文件并与当前配置比较，不修改数据库
+    pub fn preview_import_from_path(
+        file_path: &Path,
+        passphrase: Option<&str>,
+        state: &AppState,
+    ) -> Result<Vec<ConfigSectionDiff>, AppError> {
+        let sql = Self::read_import_sql(file_path, passphrase)?;
+        let preview_db = Database::load_sql_preview(&sql)?;
+        let incoming = crate::store::export_db_to_multi_app_config(&preview_db)?;
+        let current = state.config.read().map_err(AppError::from)?;
+        Ok(diff_configs(&current, &incoming))
+    }
+
+    /// 当前配置与 Skills 索引相对全新默认值的偏离，用于排查问题时只贴出改动过的部分
+    pub fn diff_from_default(config: &MultiAppConfig) -> Result<DefaultDeviations, AppError> {
+        let default_config = MultiAppConfig::default();
+        let mut skills = super::skill::SkillService::load_index()?;
+        let mut default_skills = super::skill::SkillsIndex::default();
+        // 数据库按 owner/name 排序返回仓库；内部迁移标记不算用户改动
+        for index in [&mut skills, &mut default_skills] {
+            index
+                .repos
+                .sort_by(|a, b| (&a.owner, &a.name).cmp(&(&b.owner, &b.name)));
+        }
+        skills.ssot_migration_pending = default_skills.ssot_migration_pending;
+        Ok(DefaultDeviations {
+            sections: diff_configs(&default_config, config)
+                .into_iter()
+                .filter(|section| !section.is_unchanged())
+                .collect(),
+            config: prune_serialized(config, &default_config)?,
+            skills: prune_serialized(&skills, &default_skills)?,
+        })
+    }
+
+    /// 将导入文件中的条目合并进当前数据库（新增或按策略覆盖），不删除导入文件中没有的条目。
+    ///
+    /// 覆盖范围：各应用的供应商 / 提示词 / 通用配置片段、MCP 服务器、已安装 Skills 与 Skill 仓库。
+    /// 各应用的当前供应商保持不变；`dry_run` 时只返回结果，不写入也不备份。
+    /// 合并的 MCP 服务器会同步到启用的应用；本机没有 SSOT 目录的 Skill 记录不导入，列在 `missing` 中。
+    pub fn merge_import_from_path(
+        file_path: &Path,
+        passphrase: Option<&str>,
+        strategy: ConflictStrategy,
+        dry_run: bool,
+        state: &AppState,
+    ) -> Result<ImportMergeReport, AppError> {
+        let sql = Self::read_import_sql(file_path, passphrase)?;
+        let preview_db = Database::load_sql_preview(&sql)?;
+        let incoming = crate::store::export_db_to_multi_app_config(&preview_db)?;
+        let ssot_dir = super::skill::SkillService::get_ssot_dir()?;
+        let (incoming_skills, missing_skills): (IndexMap<_, _>, IndexMap<_, _>) = preview_db
+            .get_all_installed_skills()?
+            .into_iter()
+            .partition(|(_, skill)| ssot_dir.join(&skill.directory).is_dir());
+        let current_skills = state.db.get_all_installed_skills()?;
+        let repo_map = |repos: Vec<super::skill::SkillRepo>| {
+            repos
+                .into_iter()
+                .map(|r| (format!("{}/{}", r.owner, r.name), r))
+                .collect::<BTreeMap<_, _>>()
+        };
+        let incoming_repos = repo_map(preview_db.get_skill_repos()?);
+        let current_repos = repo_map(state.db.get_skill_repos()?);
+
+        let empty_providers = IndexMap::new();
+        let empty_mcp = HashMap::new();
+        let snippet_map = |cfg: &MultiAppConfig, app: &AppType| {
+            cfg.common_config_snippets
+                .get(app)
+                .map(|s| ("snippet".to_string(), s.clone()))
+                .into_iter()
+                .collect::<BTreeMap<_, _>>()
+        };
+        let prompts_of = |cfg: &MultiAppConfig, app: &AppType| match app {
+            AppType::Claude => cfg.prompts.claude.prompts.clone(),
+            AppType::Codex => cfg.prompts.codex.prompts.clone(),
+            AppType::Gemini => cfg.prompts.gemini.prompts.clone(),
+        };
+        let incoming_snippets: Vec<_> = [AppType::Claude, AppType::Codex, AppType::Gemini]
+            .iter()
+            .map(|app| snippet_map(&incoming, app))
+            .collect();
+        let incoming_prompts: Vec<_> = [AppType::Claude, AppType::Codex, AppType::Gemini]
+            .iter()
+            .map(|app| prompts_of(&incoming, app))
+            .collect();
+
+        let mut sections = Vec::new();
+        let mut provider_writes = Vec::new();
+        let mut prompt_writes = Vec::new();
+        let mut snippet_writes = Vec::new();
+        let mcp_writes;
+        {
+            let guard = state.config.read().map_err(AppError::from)?;
+            let current: &MultiAppConfig = &guard;
+            for (i, app) in [AppType::Claude, AppType::Codex, AppType::Gemini]
+                .into_iter()
+                .enumerate()
+            {
+                let (section, writes) = plan_merge_section(
+                    format!("providers:{}", app.as_str()),
+                    current
+                        .get_manager(&app)
+                        .map_or(&empty_providers, |m| &m.providers),
+                    incoming
+                        .get_manager(&app)
+                        .map_or(&empty_providers, |m| &m.providers),
+                    strategy,
+                );
+                sections.push(section);
+                provider_writes.push((app.clone(), writes));
+
+                let current_prompts = prompts_of(current, &app);
+                let (section, writes) = plan_merge_section(
+                    format!("prompts:{}", app.as_str()),
+                    &current_prompts,
+                    &incoming_prompts[i],
+                    strategy,
+                );
+                sections.push(section);
+                prompt_writes.push((app.clone(), writes));
+
+                let current_snippet = snippet_map(current, &app);
+                let (section, writes) = plan_merge_section(
+                    format!("common:{}", app.as_str()),
+                    &current_snippet,
+                    &incoming_snippets[i],
+                    strategy,
+                );
+                sections.push(section);
+                snippet_writes.push((app, writes));
+            }
+
+            let (section, writes) = plan_merge_section(
+                "mcp".to_string(),
+                current.mcp.servers.as_ref().unwrap_or(&empty_mcp),
+                incoming.mcp.servers.as_ref().unwrap_or(&empty_mcp),
+                strategy,
+            );
+            sections.push(section);
+            mcp_writes = writes;
+        }
+        let (mut section, skill_writes) = plan_merge_section(
+            "skills".to_string(),
+            &current_skills,
+            &incoming_skills,
+            strategy,
+        );
+        section.missing = missing_skills.into_keys().collect();
+        sections.push(section);
+        let (section, repo_writes) = plan_merge_section(
+            "skill-repos".to_string(),
+            &current_repos,
+            &incoming_repos,
+            strategy,
+        );
+        sections.push(section);
+
+        let mut report = ImportMergeReport {
+            sections,
+            backup_id: None,
+        };
+        let has_writes = report
+            .sections
+            .iter()
+            .any(|s| !s.added.is_empty() || !s.updated.is_empty());
+        if dry_run || !has_writes {
+            return Ok(report);
+        }
+
+        let db_path = crate::config::get_app_config_dir().join("cc-switch.db");
+        let backup_id = Self::create_backup(&db_path, None)?;
+        report.backup_id = (!backup_id.is_empty()).then_some(backup_id);
+
+        for (app, providers) in provider_writes {
+            for provider in providers {
+                state.db.save_provider(app.as_str(), provider)?;
+            }
+        }
+        for (app, prompts) in prompt_writes {
+            for prompt in prompts {
+                state.db.save_prompt(app.as_str(), prompt)?;
+            }
+        }
+        for (app, snippets) in snippet_writes {
+            for snippet in snippets {
+                state
+                    .db
+                    .set_config_snippet(app.as_str(), Some(snippet.clone()))?;
+            }
+        }
+        for skill in skill_writes {
+            state.db.save_skill(skill)?;
+        }
+        for repo in repo_writes {
+            state.db.save_skill_repo(repo)?;
+        }
+        state.reload()?;
+        // 与 `mcp add` 相同：写入后同步到启用的应用，并清理不再启用的应用
+        for server in mcp_writes {
+            super::McpService::upsert_server(state, server.clone())?;
+        }
+
+        Ok(report)
+    }
+
+    /// 检测数据库与旧版 JSON 文件的版本；`apply` 时先备份数据库文件，再执行待处理的迁移
+    ///
+    /// 迁移本身与 [`AppState::try_new`] 走同一条路径，这里只是让它可以被显式执行和核对。
+    pub fn upgrade(apply: bool) -> Result<SchemaUpgradeReport, AppError> {
+        let config_dir = crate::config::get_app_config_dir();
+        let database_path = config_dir.join("cc-switch.db");
+        let target_version = crate::database::SCHEMA_VERSION;
+
+        let database_version = if database_path.exists() {
+            Some(Database::stored_schema_version(&database_path)?)
+        } else {
+            None
+        };
+        if let Some(version) = database_version.filter(|v| *v > target_version) {
+            return Err(AppError::localized(
+                "config.schema_too_new",
+                format!(
+                    "数据库版本过新（{version}），当前程序仅支持 {target_version}，请先运行 cc-switch update"
+                ),
+                format!(
+                    "Database schema {version} is newer than this build supports ({target_version}); run `cc-switch update` first"
+                ),
+            ));
+        }
+
+        // 旧版 JSON 文件只在首次创建数据库时导入，之后即使存在也会被忽略
+        let skills_path = config_dir.join("skills.json");
+        let skills_index_version = if database_version.is_none() && skills_path.exists() {
+            Some(crate::store::legacy_skills_index_version(&skills_path)?)
+        } else {
+            None
+        };
+        let legacy_config =
+            database_version.is_none() && crate::config::get_app_config_path().exists();
+
+        let mut report = SchemaUpgradeReport {
+            database_path,
+            database_version,
+            target_version,
+            skills_index_version,
+            legacy_config,
+            backup_path: None,
+            applied: false,
+        };
+        if !apply || !report.is_pending() {
+            return Ok(report);
+        }
+
+        // 打开数据库时旧版本会先备份再迁移（与隐式升级相同），这里只取回备份路径
+        if database_version.is_some() {
+            report.backup_path = Database::init_with_upgrade_backup()?.1;
+        }
+        AppState::try_new()?;
+        report.applied = true;
+        Ok(report)
+    }
+
+    /// 将旧版 config.json 合并进当前数据库，完成后归档源文件（`<file>.migrated`）。
+    pub fn migrate_legacy_config(
+        file_path: &Path,
+        strategy: ConflictStrategy,
+        state: &AppState,
+    ) -> Result<LegacyMigrationReport, AppError> {
+        let mut legacy = MultiAppConfig::load_legacy_file(file_path)?;
+        let mut report = LegacyMigrationReport::default();
+
+        {
+            let current = state.config.read().map_err(AppError::from)?;
+            let skip = strategy == ConflictStrategy::Skip;
+
+            for app in [AppType::Claude, AppType::Codex, AppType::Gemini] {
+                let existing = current.get_manager(&app);
+                if let Some(manager) = legacy.get_manager_mut(&app) {
+                    if skip {
+                        let before = manager.providers.len();
+                        manager
+                            .providers
+                            .retain(|id, _| existing.is_none_or(|m| !m.providers.contains_key(id)));
+                        report.skipped += before - manager.providers.len();
+                    }
+                    // 已有当前供应商时不改变它，避免出现多个 is_current
+                    if existing.is_some_and(|m| !m.current.trim().is_empty())
+                        || !manager.providers.contains_key(&manager.current)
+                    {
+                        manager.current.clear();
+                    }
+                    report.providers += manager.providers.len();
+                }
+
+                let (legacy_prompts, existing_prompts) = match app {
+                    AppType::Claude => (
+                        &mut legacy.prompts.claude.prompts,
+                        &current.prompts.claude.prompts,
+                    ),
+                    AppType::Codex => (
+                        &mut legacy.prompts.codex.prompts,
+                        &current.prompts.codex.prompts,
+                    ),
+                    AppType::Gemini => (
+                        &mut legacy.prompts.gemini.prompts,
+                        &current.prompts.gemini.prompts,
+                    ),
+                };
+                if skip {
+                    let before = legacy_prompts.len();
+                    legacy_prompts.retain(|id, _| !existing_prompts.contains_key(id));
+                    report.skipped += before - legacy_prompts.len();
+                }
+                report.prompts += legacy_prompts.len();
+
+                if skip && current.common_config_snippets.get(&app).is_some() {
+                    legacy.common_config_snippets.set(&app, None);
+                }
+            }
+
+            if let Some(servers) = legacy.mcp.servers.as_mut() {
+                if skip {
+                    let before = servers.len();
+                    servers.retain(|id, _| {
+                        current
+                            .mcp
+                            .servers
+                            .as_ref()
+                            .is_none_or(|existing| !existing.contains_key(id))
+                    });
+                    report.skipped += before - servers.len();
+                }
+                report.mcp_servers = servers.len();
+            }
+        }
+
+        state.db.migrate_from_json(&legacy)?;
+        state.reload()?;
+        report.archived_to = crate::store::archive_legacy_file(file_path, "migrated")?;
+
+        Ok(report)
+    }
+
     /// 同步当前供应商到对应的 live 配置。
     pub fn sync_current_providers_to_live(config: &mut MultiAppConfig) -> Result<(), AppError> {
         Self::sync_current_provider_for_app(config, &AppType::Claude)?;
@@ -306,8 +1131,10 @@ impl ConfigService {
             )));
         }
         let cfg_text = settings.get("config").and_then(Value::as_str);
+        let mut auth = auth.clone();
+        crate::keyring_store::resolve_references(&mut auth)?;
 
-        crate::codex_config::write_codex_live_atomic(auth, cfg_text)?;
+        crate::codex_config::write_codex_live_atomic(&auth, cfg_text)?;
         crate::mcp::sync_enabled_to_codex(config)?;
 
         let cfg_text_after = crate::codex_config::read_and_validate_codex_config_text()?;
@@ -334,12 +1161,14 @@ impl ConfigService {
 
         let settings_path = crate::config::get_claude_settings_path();
         if let Some(parent) = settings_path.parent() {
-            fs::create_dir_all(parent).map_err(|e| AppError::io(parent, e))?;
+            crate::dry_run::create_dir_all(parent).map_err(|e| AppError::io(parent, e))?;
         }
 
-        write_json_file(&settings_path, &provider.settings_config)?;
+        let resolved = crate::keyring_store::resolve_provider(provider)?;
+        write_json_file(&settings_path, &resolved.settings_config)?;
 
-        let live_after = read_json_file::<serde_json::Value>(&settings_path)?;
+        let mut live_after = read_json_file::<serde_json::Value>(&settings_path)?;
+        crate::keyring_store::restore_references(&mut live_after, &provider.settings_config);
         if let Some(manager) = config.get_manager_mut(&AppType::Claude) {
             if let Some(target) = manager.providers.get_mut(provider_id) {
                 target.settings_config = live_after;
@@ -371,6 +1200,7 @@ impl ConfigService {
         if let Some(obj) = live_after.as_object_mut() {
             obj.insert("config".to_string(), live_after_config);
         }
+        crate::keyring_store::restore_references(&mut live_after, &provider.settings_config);
 
         if let Some(manager) = config.get_manager_mut(&AppType::Gemini) {
             if let Some(target) = manager.providers.get_mut(provider_id) {
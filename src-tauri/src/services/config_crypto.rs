@@ -0,0 +1,130 @@
+//! 加密导出格式
+//!
+//! 布局：`MAGIC (8) | 版本 (1) | salt (16) | nonce (12) | AES-256-GCM 密文`。
+//! 密钥由口令经 PBKDF2-HMAC-SHA256 派生；头部作为 AAD 参与认证，篡改版本或 salt 会导致解密失败。
+
+use aes_gcm::aead::rand_core::RngCore;
+use aes_gcm::aead::{Aead, AeadCore, OsRng, Payload};
+use aes_gcm::{Aes256Gcm, Key, KeyInit, Nonce};
+use sha2::Sha256;
+
+use crate::error::AppError;
+
+const MAGIC: &[u8; 8] = b"CCSWENC\0";
+const FORMAT_VERSION: u8 = 1;
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 12;
+const HEADER_LEN: usize = MAGIC.len() + 1 + SALT_LEN + NONCE_LEN;
+// 单元测试在 debug 构建下运行，降低轮数避免拖慢测试
+const PBKDF2_ROUNDS: u32 = if cfg!(test) { 1_000 } else { 600_000 };
+
+/// 是否为加密导出文件（仅检查魔数）
+pub fn is_encrypted(data: &[u8]) -> bool {
+    data.starts_with(MAGIC)
+}
+
+fn derive_key(passphrase: &str, salt: &[u8]) -> Key<Aes256Gcm> {
+    let mut key = [0u8; 32];
+    pbkdf2::pbkdf2_hmac::<Sha256>(passphrase.as_bytes(), salt, PBKDF2_ROUNDS, &mut key);
+    key.into()
+}
+
+pub fn encrypt(plaintext: &[u8], passphrase: &str) -> Result<Vec<u8>, AppError> {
+    if passphrase.is_empty() {
+        return Err(passphrase_empty());
+    }
+
+    let mut salt = [0u8; SALT_LEN];
+    OsRng.fill_bytes(&mut salt);
+    let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+
+    let mut out = Vec::with_capacity(HEADER_LEN + plaintext.len() + 16);
+    out.extend_from_slice(MAGIC);
+    out.push(FORMAT_VERSION);
+    out.extend_from_slice(&salt);
+    out.extend_from_slice(&nonce);
+
+    let cipher = Aes256Gcm::new(&derive_key(passphrase, &salt));
+    let ciphertext = cipher
+        .encrypt(
+            &nonce,
+            Payload {
+                msg: plaintext,
+                aad: &out,
+            },
+        )
+        .map_err(|_| AppError::Message("加密导出数据失败".to_string()))?;
+    out.extend_from_slice(&ciphertext);
+    Ok(out)
+}
+
+pub fn decrypt(data: &[u8], passphrase: &str) -> Result<Vec<u8>, AppError> {
+    if !is_encrypted(data) || data.len() < HEADER_LEN {
+        return Err(AppError::localized(
+            "config.encrypted_invalid",
+            "不是有效的 cc-switch 加密导出文件",
+            "Not a valid cc-switch encrypted export",
+        ));
+    }
+    let version = data[MAGIC.len()];
+    if version != FORMAT_VERSION {
+        return Err(AppError::localized(
+            "config.encrypted_version",
+            format!("不支持的加密导出格式版本: {version}"),
+            format!("Unsupported encrypted export format version: {version}"),
+        ));
+    }
+
+    let (header, ciphertext) = data.split_at(HEADER_LEN);
+    let salt = &header[MAGIC.len() + 1..MAGIC.len() + 1 + SALT_LEN];
+    let nonce = Nonce::from_slice(&header[HEADER_LEN - NONCE_LEN..]);
+
+    let cipher = Aes256Gcm::new(&derive_key(passphrase, salt));
+    cipher
+        .decrypt(
+            nonce,
+            Payload {
+                msg: ciphertext,
+                aad: header,
+            },
+        )
+        .map_err(|_| {
+            AppError::localized(
+                "config.passphrase_wrong",
+                "解密失败：口令错误或文件已损坏",
+                "Decryption failed: wrong passphrase or corrupted file",
+            )
+        })
+}
+
+fn passphrase_empty() -> AppError {
+    AppError::localized(
+        "config.passphrase_empty",
+        "口令不能为空",
+        "Passphrase must not be empty",
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip_and_rejects_wrong_passphrase() {
+        let data = encrypt(b"-- CC Switch SQLite export", "secret").expect("encrypt");
+        assert!(is_encrypted(&data));
+        assert_eq!(data[MAGIC.len()], FORMAT_VERSION);
+
+        let plain = decrypt(&data, "secret").expect("decrypt");
+        assert_eq!(plain, b"-- CC Switch SQLite export");
+        assert!(decrypt(&data, "wrong").is_err());
+    }
+
+    #[test]
+    fn header_tampering_is_detected() {
+        let mut data = encrypt(b"payload", "secret").expect("encrypt");
+        data[MAGIC.len() + 1] ^= 0xff; // flip a salt byte
+        assert!(decrypt(&data, "secret").is_err());
+        assert!(!is_encrypted(b"-- CC Switch SQLite"));
+    }
+}
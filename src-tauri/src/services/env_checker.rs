@@ -98,57 +98,107 @@ fn check_system_env(keywords: &[&str]) -> Result<Vec<EnvConflict>, String> {
     Ok(conflicts)
 }
 
-/// Check shell configuration files for environment variable exports (Unix only)
+/// Shell startup files that may export variables: per-user rc/profile files (including
+/// `$ZDOTDIR` for zsh) and the system-wide ones.
 #[cfg(not(target_os = "windows"))]
-fn check_shell_configs(keywords: &[&str]) -> Result<Vec<EnvConflict>, String> {
-    let mut conflicts = Vec::new();
-
+fn shell_config_files() -> Vec<String> {
     let home = std::env::var("HOME").unwrap_or_else(|_| "/tmp".to_string());
-    let config_files = vec![
-        format!("{}/.bashrc", home),
-        format!("{}/.bash_profile", home),
-        format!("{}/.zshrc", home),
-        format!("{}/.zprofile", home),
-        format!("{}/.profile", home),
-        "/etc/profile".to_string(),
-        "/etc/bashrc".to_string(),
-    ];
-
-    for file_path in config_files {
-        if let Ok(content) = fs::read_to_string(&file_path) {
-            // Parse lines for export statements
-            for (line_num, line) in content.lines().enumerate() {
-                let trimmed = line.trim();
-
-                // Match patterns like: export VAR=value or VAR=value
-                if trimmed.starts_with("export ")
-                    || (!trimmed.starts_with('#') && trimmed.contains('='))
-                {
-                    let export_line = trimmed.strip_prefix("export ").unwrap_or(trimmed);
-
-                    if let Some(eq_pos) = export_line.find('=') {
-                        let var_name = export_line[..eq_pos].trim();
-                        let var_value = export_line[eq_pos + 1..].trim();
-
-                        // Check if variable name contains any keyword
-                        if keywords.iter().any(|k| var_name.to_uppercase().contains(k)) {
-                            conflicts.push(EnvConflict {
-                                var_name: var_name.to_string(),
-                                var_value: var_value
-                                    .trim_matches('"')
-                                    .trim_matches('\'')
-                                    .to_string(),
-                                source_type: "file".to_string(),
-                                source_path: format!("{}:{}", file_path, line_num + 1),
-                            });
-                        }
-                    }
-                }
-            }
+    let mut files: Vec<String> = [
+        ".bashrc",
+        ".bash_profile",
+        ".bash_login",
+        ".profile",
+        ".zshenv",
+        ".zshrc",
+        ".zprofile",
+        ".zlogin",
+    ]
+    .iter()
+    .map(|name| format!("{home}/{name}"))
+    .collect();
+    if let Some(zdotdir) = std::env::var("ZDOTDIR")
+        .ok()
+        .filter(|dir| !dir.trim().is_empty() && *dir != home)
+    {
+        for name in [".zshenv", ".zshrc", ".zprofile", ".zlogin"] {
+            files.push(format!("{zdotdir}/{name}"));
         }
     }
+    files.extend(
+        [
+            "/etc/profile",
+            "/etc/bashrc",
+            "/etc/bash.bashrc",
+            "/etc/zshenv",
+            "/etc/zshrc",
+            "/etc/zsh/zshenv",
+            "/etc/zsh/zshrc",
+            "/etc/environment",
+        ]
+        .iter()
+        .map(|path| path.to_string()),
+    );
+    files
+}
 
-    Ok(conflicts)
+/// Parse a shell line that assigns a variable: `VAR=value`, `export VAR=value`,
+/// `declare -x VAR=value` or `typeset -x VAR=value`. Comments and lines whose left side
+/// is not a plain identifier (tests, aliases, ...) return None.
+pub fn parse_env_assignment(line: &str) -> Option<(&str, &str)> {
+    let trimmed = line.trim();
+    if trimmed.starts_with('#') {
+        return None;
+    }
+    let assignment = ["export ", "declare -x ", "typeset -x "]
+        .iter()
+        .find_map(|prefix| trimmed.strip_prefix(prefix))
+        .unwrap_or(trimmed)
+        .trim_start();
+
+    let (name, value) = assignment.split_once('=')?;
+    let valid_name = !name.is_empty()
+        && !name.starts_with(|c: char| c.is_ascii_digit())
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if !valid_name {
+        return None;
+    }
+    Some((name, value.trim().trim_matches('"').trim_matches('\'')))
+}
+
+/// Scan one shell file for assignments of matching variables, reported as `path:line`
+#[cfg(not(target_os = "windows"))]
+fn scan_shell_file(file_path: &str, keywords: &[&str]) -> Vec<EnvConflict> {
+    let Ok(content) = fs::read_to_string(file_path) else {
+        return Vec::new();
+    };
+    content
+        .lines()
+        .enumerate()
+        .filter_map(|(line_num, line)| {
+            let (var_name, var_value) = parse_env_assignment(line)?;
+            keywords
+                .iter()
+                .any(|k| var_name.to_uppercase().contains(k))
+                .then(|| EnvConflict {
+                    var_name: var_name.to_string(),
+                    var_value: var_value.to_string(),
+                    source_type: "file".to_string(),
+                    source_path: format!("{}:{}", file_path, line_num + 1),
+                })
+        })
+        .collect()
+}
+
+/// Check shell configuration files for environment variable exports (Unix only).
+///
+/// Files are scanned even when the current process did not load them, so a variable that
+/// only a fresh login shell would pick up is still reported with its file and line.
+#[cfg(not(target_os = "windows"))]
+fn check_shell_configs(keywords: &[&str]) -> Result<Vec<EnvConflict>, String> {
+    Ok(shell_config_files()
+        .iter()
+        .flat_map(|file_path| scan_shell_file(file_path, keywords))
+        .collect())
 }
 
 #[cfg(test)]
@@ -165,4 +215,45 @@ mod tests {
         );
         assert_eq!(get_keywords_for_app("unknown"), Vec::<&str>::new());
     }
+
+    #[test]
+    fn parse_env_assignment_accepts_shell_forms_only() {
+        assert_eq!(
+            parse_env_assignment("export ANTHROPIC_API_KEY=\"sk-1\""),
+            Some(("ANTHROPIC_API_KEY", "sk-1"))
+        );
+        assert_eq!(
+            parse_env_assignment("  declare -x OPENAI_BASE_URL='https://x'"),
+            Some(("OPENAI_BASE_URL", "https://x"))
+        );
+        assert_eq!(
+            parse_env_assignment("GEMINI_API_KEY=abc"),
+            Some(("GEMINI_API_KEY", "abc"))
+        );
+        assert_eq!(parse_env_assignment("# export ANTHROPIC_API_KEY=x"), None);
+        assert_eq!(
+            parse_env_assignment("alias ant='ANTHROPIC_MODEL=x claude'"),
+            None
+        );
+        assert_eq!(parse_env_assignment("[ \"$ANTHROPIC_API_KEY\" = x ]"), None);
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    #[test]
+    fn scan_shell_file_reports_file_and_line() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let rc = dir.path().join(".zshrc");
+        fs::write(
+            &rc,
+            "# comment\nexport PATH=$PATH:/bin\nexport ANTHROPIC_BASE_URL=https://relay\n",
+        )
+        .expect("write rc");
+
+        let rc = rc.to_string_lossy().to_string();
+        let conflicts = scan_shell_file(&rc, &["ANTHROPIC"]);
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].var_name, "ANTHROPIC_BASE_URL");
+        assert_eq!(conflicts[0].var_value, "https://relay");
+        assert_eq!(conflicts[0].source_path, format!("{rc}:3"));
+    }
 }
@@ -2,14 +2,14 @@ use super::env_checker::EnvConflict;
 use chrono::Utc;
 use serde::{Deserialize, Serialize};
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 #[cfg(target_os = "windows")]
 use winreg::enums::*;
 #[cfg(target_os = "windows")]
 use winreg::RegKey;
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct BackupInfo {
     pub backup_path: String,
@@ -18,32 +18,36 @@ pub struct BackupInfo {
 }
 
 /// Delete environment variables with automatic backup
-pub fn delete_env_vars(conflicts: Vec<EnvConflict>) -> Result<BackupInfo, String> {
+///
+/// 返回备份信息与实际删除的条目数（Unix 下仅存在于进程环境中的变量无法删除，不计入）
+pub fn delete_env_vars(conflicts: Vec<EnvConflict>) -> Result<(BackupInfo, usize), String> {
     // Step 1: Create backup
     let backup_info = create_backup(&conflicts)?;
 
-    // Step 2: Delete variables
-    for conflict in &conflicts {
-        match delete_single_env(conflict) {
-            Ok(_) => {}
-            Err(e) => {
-                // If deletion fails, we keep the backup but return error
-                return Err(format!(
-                    "删除环境变量失败: {}. 备份已保存到: {}",
-                    e, backup_info.backup_path
-                ));
-            }
-        }
-    }
+    // Step 2: Delete variables; if deletion fails, we keep the backup but return error
+    let removed = delete_conflicts(&conflicts).map_err(|e| {
+        format!(
+            "删除环境变量失败: {}. 备份已保存到: {}",
+            e, backup_info.backup_path
+        )
+    })?;
 
-    Ok(backup_info)
+    Ok((backup_info, removed))
+}
+
+fn delete_conflicts(conflicts: &[EnvConflict]) -> Result<usize, String> {
+    let mut removed = 0;
+    for conflict in conflicts {
+        removed += delete_single_env(conflict)?;
+    }
+    Ok(removed)
 }
 
 /// Create backup file before deletion
 fn create_backup(conflicts: &[EnvConflict]) -> Result<BackupInfo, String> {
     // Get backup directory
     let backup_dir = get_backup_dir()?;
-    fs::create_dir_all(&backup_dir).map_err(|e| format!("创建备份目录失败: {e}"))?;
+    crate::dry_run::create_dir_all(&backup_dir).map_err(|e| format!("创建备份目录失败: {e}"))?;
 
     // Generate backup file name with timestamp
     let timestamp = Utc::now().format("%Y%m%d_%H%M%S").to_string();
@@ -60,22 +64,175 @@ fn create_backup(conflicts: &[EnvConflict]) -> Result<BackupInfo, String> {
     let json = serde_json::to_string_pretty(&backup_info)
         .map_err(|e| format!("序列化备份数据失败: {e}"))?;
 
-    fs::write(&backup_file, json).map_err(|e| format!("写入备份文件失败: {e}"))?;
+    crate::dry_run::write(&backup_file, json).map_err(|e| format!("写入备份文件失败: {e}"))?;
 
     Ok(backup_info)
 }
 
+/// List env backups (newest first)
+pub fn list_backups() -> Result<Vec<BackupInfo>, String> {
+    list_backups_in(&get_backup_dir()?)
+}
+
+fn list_backups_in(backup_dir: &std::path::Path) -> Result<Vec<BackupInfo>, String> {
+    if !backup_dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut backups = Vec::new();
+    for entry in fs::read_dir(backup_dir).map_err(|e| format!("读取备份目录失败: {e}"))? {
+        let path = entry.map_err(|e| format!("读取备份目录失败: {e}"))?.path();
+        let is_env_backup = path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .is_some_and(|n| n.starts_with("env-backup-") && n.ends_with(".json"));
+        if !is_env_backup {
+            continue;
+        }
+
+        match fs::read_to_string(&path)
+            .map_err(|e| e.to_string())
+            .and_then(|c| serde_json::from_str::<BackupInfo>(&c).map_err(|e| e.to_string()))
+        {
+            Ok(mut info) => {
+                // 以实际文件路径为准（备份目录可能被移动过）
+                info.backup_path = path.to_string_lossy().to_string();
+                backups.push(info);
+            }
+            Err(e) => log::warn!("跳过无法解析的环境变量备份 {}: {e}", path.display()),
+        }
+    }
+
+    backups.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
+    Ok(backups)
+}
+
+/// Result of editing a shell rc file with `env set/unset --write`
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RcFileEdit {
+    pub path: String,
+    /// Copy of the file taken before writing (None when nothing changed or the file was new)
+    pub backup_path: Option<String>,
+    pub changed: bool,
+}
+
+/// Check that `key` is a plain shell identifier
+pub fn validate_env_key(key: &str) -> Result<(), String> {
+    let valid = !key.is_empty()
+        && !key.starts_with(|c: char| c.is_ascii_digit())
+        && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if valid {
+        Ok(())
+    } else {
+        Err(format!("无效的环境变量名: {key}"))
+    }
+}
+
+/// `export KEY="value"`, escaping characters that stay special inside double quotes
+pub fn export_line(key: &str, value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        if matches!(c, '\\' | '"' | '$' | '`') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    format!("export {key}=\"{escaped}\"")
+}
+
+/// Set `KEY` in a shell rc file: the first assignment of the key is replaced, later ones
+/// dropped, and the export appended when there is none. Unchanged files are not rewritten.
+pub fn set_env_in_file(path: &Path, key: &str, value: &str) -> Result<RcFileEdit, String> {
+    validate_env_key(key)?;
+    let line = export_line(key, value);
+    edit_rc_file(path, key, Some(&line), &get_backup_dir()?)
+}
+
+/// Remove every assignment of `KEY` from a shell rc file
+pub fn unset_env_in_file(path: &Path, key: &str) -> Result<RcFileEdit, String> {
+    validate_env_key(key)?;
+    edit_rc_file(path, key, None, &get_backup_dir()?)
+}
+
+fn edit_rc_file(
+    path: &Path,
+    key: &str,
+    replacement: Option<&str>,
+    backup_dir: &Path,
+) -> Result<RcFileEdit, String> {
+    let content = match fs::read_to_string(path) {
+        Ok(content) => Some(content),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+        Err(e) => return Err(format!("读取文件失败 {}: {e}", path.display())),
+    };
+    let original = content.as_deref().unwrap_or_default();
+
+    let mut lines: Vec<&str> = Vec::new();
+    let mut found = false;
+    for line in original.lines() {
+        let matches =
+            super::env_checker::parse_env_assignment(line).is_some_and(|(name, _)| name == key);
+        if !matches {
+            lines.push(line);
+        } else if !found {
+            lines.extend(replacement);
+            found = true;
+        }
+    }
+    if !found {
+        lines.extend(replacement);
+    }
+
+    let mut updated = lines.join("\n");
+    let appended = replacement.is_some() && !found;
+    if !updated.is_empty() && (original.is_empty() || original.ends_with('\n') || appended) {
+        updated.push('\n');
+    }
+    let mut edit = RcFileEdit {
+        path: path.to_string_lossy().to_string(),
+        backup_path: None,
+        changed: updated != original,
+    };
+    if !edit.changed {
+        return Ok(edit);
+    }
+
+    if content.is_some() {
+        crate::dry_run::create_dir_all(backup_dir).map_err(|e| format!("创建备份目录失败: {e}"))?;
+        let name = path
+            .file_name()
+            .map(|n| n.to_string_lossy().trim_start_matches('.').to_string())
+            .unwrap_or_else(|| "rc".to_string());
+        // 精确到毫秒，避免同一秒内的多次修改覆盖最初的备份
+        let timestamp = Utc::now().format("%Y%m%d_%H%M%S_%3f");
+        let backup = backup_dir.join(format!("rc-{name}-{timestamp}.bak"));
+        crate::dry_run::copy(path, &backup)
+            .map_err(|e| format!("备份文件失败 {}: {e}", path.display()))?;
+        edit.backup_path = Some(backup.to_string_lossy().to_string());
+    }
+    // 直接写入而非原子替换，保留指向 dotfiles 仓库的符号链接
+    crate::dry_run::write(path, updated)
+        .map_err(|e| format!("写入文件失败 {}: {e}", path.display()))?;
+    Ok(edit)
+}
+
 /// Get backup directory path
 fn get_backup_dir() -> Result<PathBuf, String> {
-    let home = dirs::home_dir().ok_or("无法获取用户主目录")?;
-    Ok(home.join(".cc-switch").join("backups"))
+    Ok(crate::config::get_app_config_dir().join("backups"))
 }
 
-/// Delete a single environment variable
+/// Delete a single environment variable, returning how many entries were removed
 #[cfg(target_os = "windows")]
-fn delete_single_env(conflict: &EnvConflict) -> Result<(), String> {
+fn delete_single_env(conflict: &EnvConflict) -> Result<usize, String> {
     match conflict.source_type.as_str() {
         "system" => {
+            if crate::dry_run::skip(format_args!(
+                "delete registry value {} ({})",
+                conflict.var_name, conflict.source_path
+            )) {
+                return Ok(1);
+            }
             if conflict.source_path.contains("HKEY_CURRENT_USER") {
                 let hkcu = RegKey::predef(HKEY_CURRENT_USER)
                     .open_subkey_with_flags("Environment", KEY_ALL_ACCESS)
@@ -83,6 +240,7 @@ fn delete_single_env(conflict: &EnvConflict) -> Result<(), String> {
 
                 hkcu.delete_value(&conflict.var_name)
                     .map_err(|e| format!("删除注册表项失败: {}", e))?;
+                Ok(1)
             } else if conflict.source_path.contains("HKEY_LOCAL_MACHINE") {
                 let hklm = RegKey::predef(HKEY_LOCAL_MACHINE)
                     .open_subkey_with_flags(
@@ -93,8 +251,10 @@ fn delete_single_env(conflict: &EnvConflict) -> Result<(), String> {
 
                 hklm.delete_value(&conflict.var_name)
                     .map_err(|e| format!("删除系统注册表项失败: {}", e))?;
+                Ok(1)
+            } else {
+                Ok(0)
             }
-            Ok(())
         }
         "file" => Err("Windows 系统不应该有文件类型的环境变量".to_string()),
         _ => Err(format!("未知的环境变量来源类型: {}", conflict.source_type)),
@@ -102,7 +262,7 @@ fn delete_single_env(conflict: &EnvConflict) -> Result<(), String> {
 }
 
 #[cfg(not(target_os = "windows"))]
-fn delete_single_env(conflict: &EnvConflict) -> Result<(), String> {
+fn delete_single_env(conflict: &EnvConflict) -> Result<usize, String> {
     match conflict.source_type.as_str() {
         "file" => {
             // Parse file path and line number from source_path (format: "path:line")
@@ -118,32 +278,34 @@ fn delete_single_env(conflict: &EnvConflict) -> Result<(), String> {
                 .map_err(|e| format!("读取文件失败 {file_path}: {e}"))?;
 
             // Filter out the line containing the environment variable
-            let new_content: Vec<String> = content
+            let kept: Vec<&str> = content
                 .lines()
                 .filter(|line| {
-                    let trimmed = line.trim();
-                    let export_line = trimmed.strip_prefix("export ").unwrap_or(trimmed);
-
-                    // Check if this line sets the target variable
-                    if let Some(eq_pos) = export_line.find('=') {
-                        let var_name = export_line[..eq_pos].trim();
-                        var_name != conflict.var_name
-                    } else {
-                        true
-                    }
+                    // Same parsing as the checker, so every reported form is removed
+                    super::env_checker::parse_env_assignment(line)
+                        .is_none_or(|(var_name, _)| var_name != conflict.var_name)
                 })
-                .map(|s| s.to_string())
                 .collect();
+            // 同一文件中的重复赋值在首个冲突处一并删除，后续冲突计为 0
+            let removed = content.lines().count() - kept.len();
+            if removed == 0 {
+                return Ok(0);
+            }
+
+            let mut new_content = kept.join("\n");
+            if content.ends_with('\n') && !new_content.is_empty() {
+                new_content.push('\n');
+            }
 
             // Write back to file
-            fs::write(file_path, new_content.join("\n"))
+            crate::dry_run::write(file_path, new_content)
                 .map_err(|e| format!("写入文件失败 {file_path}: {e}"))?;
 
-            Ok(())
+            Ok(removed)
         }
         "system" => {
             // On Unix, we can't directly delete process environment variables
-            Ok(())
+            Ok(0)
         }
         _ => Err(format!("未知的环境变量来源类型: {}", conflict.source_type)),
     }
@@ -170,6 +332,12 @@ pub fn restore_from_backup(backup_path: String) -> Result<(), String> {
 fn restore_single_env(conflict: &EnvConflict) -> Result<(), String> {
     match conflict.source_type.as_str() {
         "system" => {
+            if crate::dry_run::skip(format_args!(
+                "set registry value {} ({})",
+                conflict.var_name, conflict.source_path
+            )) {
+                return Ok(());
+            }
             if conflict.source_path.contains("HKEY_CURRENT_USER") {
                 let (hkcu, _) = RegKey::predef(HKEY_CURRENT_USER)
                     .create_subkey("Environment")
@@ -217,7 +385,8 @@ fn restore_single_env(conflict: &EnvConflict) -> Result<(), String> {
             content.push_str(&export_line);
 
             // Write back to file
-            fs::write(file_path, content).map_err(|e| format!("写入文件失败 {file_path}: {e}"))?;
+            crate::dry_run::write(file_path, content)
+                .map_err(|e| format!("写入文件失败 {file_path}: {e}"))?;
 
             Ok(())
         }
@@ -237,4 +406,110 @@ mod tests {
         let backup_dir = get_backup_dir();
         assert!(backup_dir.is_ok());
     }
+
+    #[test]
+    fn test_export_line_escapes_double_quote_specials() {
+        assert_eq!(
+            export_line("TOKEN", r#"a"b$c`d\e"#),
+            r#"export TOKEN="a\"b\$c\`d\\e""#
+        );
+        assert!(validate_env_key("ANTHROPIC_BASE_URL").is_ok());
+        assert!(validate_env_key("1BAD").is_err());
+        assert!(validate_env_key("A-B").is_err());
+    }
+
+    #[test]
+    fn test_set_and_unset_env_in_file_are_idempotent() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let backups = dir.path().join("backups");
+        let rc = dir.path().join(".zshrc");
+        let set = |key: &str, value: &str| {
+            edit_rc_file(&rc, key, Some(&export_line(key, value)), &backups)
+        };
+        fs::write(
+            &rc,
+            "alias ll='ls -l'\nexport FOO=old\nFOO=dup\nexport BAR=1\n",
+        )
+        .unwrap();
+
+        let edit = set("FOO", "new value").expect("set");
+        assert!(edit.changed);
+        assert!(edit
+            .backup_path
+            .as_deref()
+            .is_some_and(|p| Path::new(p).exists()));
+        assert_eq!(
+            fs::read_to_string(&rc).unwrap(),
+            "alias ll='ls -l'\nexport FOO=\"new value\"\nexport BAR=1\n"
+        );
+
+        let again = set("FOO", "new value").expect("set again");
+        assert!(!again.changed && again.backup_path.is_none());
+
+        set("BAZ", "2").expect("append");
+        edit_rc_file(&rc, "FOO", None, &backups).expect("unset");
+        assert_eq!(
+            fs::read_to_string(&rc).unwrap(),
+            "alias ll='ls -l'\nexport BAR=1\nexport BAZ=\"2\"\n"
+        );
+        assert!(
+            !edit_rc_file(&rc, "FOO", None, &backups)
+                .expect("unset again")
+                .changed
+        );
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    #[test]
+    fn test_delete_conflicts_counts_real_removals() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let rc = dir.path().join(".bashrc");
+        fs::write(
+            &rc,
+            "export ANTHROPIC_API_KEY=a\nalias ll='ls -l'\nANTHROPIC_API_KEY=b\n",
+        )
+        .unwrap();
+        let conflict = |source_type: &str, source_path: String| EnvConflict {
+            var_name: "ANTHROPIC_API_KEY".to_string(),
+            var_value: "a".to_string(),
+            source_type: source_type.to_string(),
+            source_path,
+        };
+        let conflicts = vec![
+            conflict("file", format!("{}:1", rc.display())),
+            conflict("file", format!("{}:3", rc.display())),
+            conflict("system", "Process Environment".to_string()),
+        ];
+
+        // 两行赋值都被删除；进程环境变量无法删除，不计入
+        assert_eq!(delete_conflicts(&conflicts).expect("delete"), 2);
+        assert_eq!(fs::read_to_string(&rc).unwrap(), "alias ll='ls -l'\n");
+        assert_eq!(delete_conflicts(&conflicts).expect("delete again"), 0);
+    }
+
+    #[test]
+    fn test_list_backups_sorted_and_filtered() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        for ts in ["20240101_000000", "20250101_000000"] {
+            let info = BackupInfo {
+                backup_path: String::new(),
+                timestamp: ts.to_string(),
+                conflicts: Vec::new(),
+            };
+            fs::write(
+                dir.path().join(format!("env-backup-{ts}.json")),
+                serde_json::to_string(&info).unwrap(),
+            )
+            .unwrap();
+        }
+        fs::write(dir.path().join("backup_20250101.sql"), "-- not env").unwrap();
+        fs::write(dir.path().join("env-backup-broken.json"), "{").unwrap();
+
+        let backups = list_backups_in(dir.path()).expect("list backups");
+        let timestamps: Vec<_> = backups.iter().map(|b| b.timestamp.as_str()).collect();
+        assert_eq!(timestamps, vec!["20250101_000000", "20240101_000000"]);
+        assert!(backups[0]
+            .backup_path
+            .ends_with("env-backup-20250101_000000.json"));
+    }
 }
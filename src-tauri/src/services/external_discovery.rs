@@ -0,0 +1,137 @@
+//! 外部 Skills 目录：设置项 `skillDiscoveryCommand` 配置的命令补充 discover 结果
+//!
+//! 约定：命令经系统 shell 执行，stdin 写入查询字符串（无查询时为空）后关闭；
+//! 命令须在超时前退出并以 0 结束，stdout 输出 `DiscoverableSkill` 的 JSON 数组
+//! （`key`、`name`、`description`、`directory`、`readmeUrl`、`repoOwner`、`repoName`、
+//! `repoBranch`，其中 `key` 为 `owner/name:directory`）。条目仍从对应的 GitHub 仓库安装。
+//!
+//! 外部命令可以执行任意操作，因此只在显式传入 `--allow-external-discovery` 时调用。
+
+use std::process::Stdio;
+use std::time::Duration;
+
+use tokio::io::AsyncWriteExt;
+use tokio::process::Command;
+
+use crate::error::AppError;
+use crate::services::skill::DiscoverableSkill;
+
+/// 外部命令的最长运行时间
+pub const EXTERNAL_DISCOVERY_TIMEOUT: Duration = Duration::from_secs(30);
+
+fn failed(command: &str, reason: impl std::fmt::Display) -> AppError {
+    AppError::localized(
+        "skills.external_discovery_failed",
+        format!("外部 Skills 目录命令失败 ({command}): {reason}"),
+        format!("External skill discovery command failed ({command}): {reason}"),
+    )
+}
+
+/// 执行外部命令并解析其输出
+pub async fn run(
+    command: &str,
+    query: &str,
+    timeout: Duration,
+) -> Result<Vec<DiscoverableSkill>, AppError> {
+    let mut shell = if cfg!(windows) {
+        let mut cmd = Command::new("cmd");
+        cmd.arg("/C");
+        cmd
+    } else {
+        let mut cmd = Command::new("sh");
+        cmd.arg("-c");
+        cmd
+    };
+    let mut child = shell
+        .arg(command)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(|e| failed(command, e))?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        // 命令不读取 stdin 时写入会失败（broken pipe），不影响结果
+        let _ = stdin.write_all(query.as_bytes()).await;
+    }
+
+    // 超时后 future 被丢弃，kill_on_drop 负责结束子进程
+    let output = tokio::time::timeout(timeout, child.wait_with_output())
+        .await
+        .map_err(|_| failed(command, format!("timed out after {}s", timeout.as_secs())))?
+        .map_err(|e| failed(command, e))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(failed(
+            command,
+            format!("exited with {}: {}", output.status, stderr.trim()),
+        ));
+    }
+    parse_output(&output.stdout).map_err(|reason| failed(command, reason))
+}
+
+/// 解析并校验 stdout；返回英文的失败原因
+fn parse_output(stdout: &[u8]) -> Result<Vec<DiscoverableSkill>, String> {
+    let skills: Vec<DiscoverableSkill> = serde_json::from_slice(stdout)
+        .map_err(|e| format!("stdout is not a JSON array of skills: {e}"))?;
+    for skill in &skills {
+        let expected = format!(
+            "{}/{}:{}",
+            skill.repo_owner, skill.repo_name, skill.directory
+        );
+        if skill.repo_owner.trim().is_empty()
+            || skill.repo_name.trim().is_empty()
+            || skill.directory.trim().is_empty()
+        {
+            return Err(format!(
+                "entry '{}' needs repoOwner, repoName and directory",
+                skill.key
+            ));
+        }
+        if skill.key != expected {
+            return Err(format!("entry key '{}' should be '{expected}'", skill.key));
+        }
+    }
+    Ok(skills)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ENTRY: &str = r#"[{"key":"corp/catalog:lint","name":"Lint","description":"Internal lint rules","directory":"lint","readmeUrl":null,"repoOwner":"corp","repoName":"catalog","repoBranch":"main"}]"#;
+
+    #[test]
+    fn parse_output_checks_keys_and_required_fields() {
+        let skills = parse_output(ENTRY.as_bytes()).expect("valid output");
+        assert_eq!(skills.len(), 1);
+        assert_eq!(skills[0].repo_name, "catalog");
+
+        let wrong_key = ENTRY.replace("corp/catalog:lint", "lint");
+        assert!(parse_output(wrong_key.as_bytes())
+            .unwrap_err()
+            .contains("should be 'corp/catalog:lint'"));
+        assert!(parse_output(b"{}").is_err());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn run_passes_query_on_stdin_and_enforces_timeout() {
+        let command = format!(r#"read q; [ "$q" = "lint" ] && printf '%s' '{ENTRY}'"#);
+        let skills = run(&command, "lint", Duration::from_secs(10))
+            .await
+            .expect("command output");
+        assert_eq!(skills[0].key, "corp/catalog:lint");
+
+        let err = run(&command, "other", Duration::from_secs(10))
+            .await
+            .expect_err("non-zero exit");
+        assert!(err.to_string().contains("exited with"));
+
+        let err = run("sleep 5", "", Duration::from_millis(200))
+            .await
+            .expect_err("timeout");
+        assert!(err.to_string().contains("timed out"));
+    }
+}
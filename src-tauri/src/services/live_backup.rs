@@ -0,0 +1,195 @@
+//! 应用 live 配置文件快照（`app backup` / `app restore`）
+//!
+//! 与数据库备份相互独立：把 cc-switch 会写入的 live 文件原样复制到
+//! `~/.cc-switch/live-backups/<app>/<id>/`，并在 `manifest.json` 中记录原路径。
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use chrono::Local;
+use serde::{Deserialize, Serialize};
+
+use crate::app_config::AppType;
+use crate::error::AppError;
+
+/// 每个应用保留的快照数量
+const MAX_LIVE_BACKUPS: usize = 20;
+
+const MANIFEST_FILE: &str = "manifest.json";
+
+/// 快照中的一个文件
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LiveBackupFile {
+    /// 快照目录内的文件名
+    pub name: String,
+    /// 备份时的原路径，恢复时写回这里
+    pub path: PathBuf,
+}
+
+/// 一份 live 配置快照
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LiveBackup {
+    pub id: String,
+    pub app: String,
+    pub created_at: String,
+    pub files: Vec<LiveBackupFile>,
+    #[serde(skip)]
+    pub dir: PathBuf,
+}
+
+/// cc-switch 为该应用写入的 live 配置文件
+pub fn live_config_files(app: &AppType) -> Vec<PathBuf> {
+    match app {
+        AppType::Claude => vec![
+            crate::config::get_claude_settings_path(),
+            crate::config::get_claude_mcp_path(),
+        ],
+        AppType::Codex => vec![
+            crate::codex_config::get_codex_auth_path(),
+            crate::codex_config::get_codex_config_path(),
+        ],
+        AppType::Gemini => vec![
+            crate::gemini_config::get_gemini_env_path(),
+            crate::gemini_config::get_gemini_settings_path(),
+        ],
+    }
+}
+
+fn backups_root(app: &AppType) -> PathBuf {
+    crate::config::get_app_config_dir()
+        .join("live-backups")
+        .join(app.as_str())
+}
+
+fn not_found(app: &AppType, id: &str) -> AppError {
+    AppError::localized(
+        "app.live_backup_not_found",
+        format!("未找到 {} 的 live 配置快照: {id}", app.as_str()),
+        format!("No {} live config backup named '{id}'", app.as_str()),
+    )
+}
+
+/// 复制当前存在的 live 文件；一个都不存在时报错
+pub fn create(app: &AppType) -> Result<LiveBackup, AppError> {
+    let sources: Vec<PathBuf> = live_config_files(app)
+        .into_iter()
+        .filter(|path| path.is_file())
+        .collect();
+    if sources.is_empty() {
+        return Err(AppError::localized(
+            "app.live_config_missing",
+            format!("{} 没有可备份的 live 配置文件", app.as_str()),
+            format!("{} has no live config files to back up", app.as_str()),
+        ));
+    }
+
+    let root = backups_root(app);
+    let now = Local::now();
+    let base_id = now.format("%Y%m%d_%H%M%S").to_string();
+    let mut id = base_id.clone();
+    let mut suffix = 2;
+    while root.join(&id).exists() {
+        id = format!("{base_id}-{suffix}");
+        suffix += 1;
+    }
+    let dir = root.join(&id);
+    crate::dry_run::create_dir_all(&dir).map_err(|e| AppError::io(&dir, e))?;
+
+    let mut files = Vec::new();
+    for path in sources {
+        let mut name = path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| "file".to_string());
+        if files.iter().any(|f: &LiveBackupFile| f.name == name) {
+            name = format!("{}-{name}", files.len());
+        }
+        crate::config::copy_file(&path, &dir.join(&name))?;
+        files.push(LiveBackupFile { name, path });
+    }
+
+    let backup = LiveBackup {
+        id,
+        app: app.as_str().to_string(),
+        created_at: now.to_rfc3339(),
+        files,
+        dir,
+    };
+    crate::config::write_json_file(&backup.dir.join(MANIFEST_FILE), &backup)?;
+    cleanup(&root, MAX_LIVE_BACKUPS);
+    Ok(backup)
+}
+
+/// 某应用的全部快照（最新的在前）
+pub fn list(app: &AppType) -> Result<Vec<LiveBackup>, AppError> {
+    let root = backups_root(app);
+    if !root.is_dir() {
+        return Ok(Vec::new());
+    }
+    let mut backups: Vec<LiveBackup> = fs::read_dir(&root)
+        .map_err(|e| AppError::io(&root, e))?
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| read_manifest(&entry.path()))
+        .collect();
+    backups.sort_by(|a, b| b.created_at.cmp(&a.created_at).then(b.id.cmp(&a.id)));
+    Ok(backups)
+}
+
+fn read_manifest(dir: &Path) -> Option<LiveBackup> {
+    let mut backup: LiveBackup = crate::config::read_json_file(&dir.join(MANIFEST_FILE)).ok()?;
+    backup.dir = dir.to_path_buf();
+    Some(backup)
+}
+
+/// 把快照中的文件写回原路径；快照中没有的文件保持不变。
+/// 返回恢复前自动创建的快照（当时没有 live 文件则为 None）
+pub fn restore(app: &AppType, id: &str) -> Result<Option<LiveBackup>, AppError> {
+    let dir = backups_root(app).join(id.trim());
+    let backup = read_manifest(&dir).ok_or_else(|| not_found(app, id))?;
+    // 先读入内存：下面的恢复前快照可能触发清理，删掉最旧的快照
+    let contents = backup
+        .files
+        .iter()
+        .map(|file| {
+            let source = backup.dir.join(&file.name);
+            fs::read(&source).map_err(|e| AppError::io(&source, e))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let pre_restore = match create(app) {
+        Ok(snapshot) => Some(snapshot),
+        Err(AppError::Localized {
+            key: "app.live_config_missing",
+            ..
+        }) => None,
+        Err(e) => return Err(e),
+    };
+
+    for (file, data) in backup.files.iter().zip(contents) {
+        crate::config::atomic_write(&file.path, &data)?;
+    }
+    Ok(pre_restore)
+}
+
+fn cleanup(root: &Path, retain: usize) {
+    let Ok(entries) = fs::read_dir(root) else {
+        return;
+    };
+    let mut dirs: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.join(MANIFEST_FILE).is_file())
+        .collect();
+    if dirs.len() <= retain {
+        return;
+    }
+    // id 以时间戳开头，按名称排序即按时间排序
+    dirs.sort();
+    for dir in &dirs[..dirs.len() - retain] {
+        if let Err(e) = crate::dry_run::remove_dir_all(dir) {
+            log::warn!("Failed to remove old live backup {}: {e}", dir.display());
+        }
+    }
+}
@@ -1,10 +1,23 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
 
-use crate::app_config::{AppType, McpServer, MultiAppConfig};
+use crate::app_config::{AppType, McpApps, McpServer, MultiAppConfig};
 use crate::error::AppError;
 use crate::mcp;
+use crate::services::config::ConflictStrategy;
 use crate::store::AppState;
 
+/// 单个 MCP 服务器的导入结果（文件导入 / 片段添加）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum McpImportOutcome {
+    Added,
+    Overwritten,
+    /// 已存在且策略为 skip
+    Skipped,
+    /// 条目无效（原因）
+    Invalid(String),
+}
+
 /// MCP 相关业务逻辑（v3.7.0 统一结构）
 pub struct McpService;
 
@@ -245,6 +258,188 @@ impl McpService {
         Ok(())
     }
 
+    /// 将所有 MCP 服务器导出为 JSON（以 id 为键），返回导出数量
+    pub fn export_to_file(state: &AppState, path: &Path) -> Result<usize, AppError> {
+        let servers: BTreeMap<String, McpServer> =
+            Self::get_all_servers(state)?.into_iter().collect();
+        crate::config::write_json_file(path, &servers)?;
+        Ok(servers.len())
+    }
+
+    /// 从 `export_to_file` 生成的 JSON 导入 MCP 服务器，逐条返回结果
+    pub fn import_from_file(
+        state: &AppState,
+        path: &Path,
+        strategy: ConflictStrategy,
+    ) -> Result<Vec<(String, McpImportOutcome)>, AppError> {
+        let entries: BTreeMap<String, serde_json::Value> = crate::config::read_json_file(path)?;
+        let existing = Self::get_all_servers(state)?;
+        let mut results = Vec::new();
+
+        for (key, mut value) in entries {
+            if let Some(obj) = value.as_object_mut() {
+                obj.entry("id").or_insert_with(|| serde_json::json!(key));
+            }
+            let server = match serde_json::from_value::<McpServer>(value) {
+                Ok(server) => server,
+                Err(e) => {
+                    results.push((key, McpImportOutcome::Invalid(e.to_string())));
+                    continue;
+                }
+            };
+            if server.id.trim().is_empty() {
+                results.push((key, McpImportOutcome::Invalid("empty id".to_string())));
+                continue;
+            }
+            if let Err(e) = mcp::validate_server_spec(&server.server) {
+                results.push((server.id, McpImportOutcome::Invalid(e.to_string())));
+                continue;
+            }
+
+            let outcome = match (existing.contains_key(&server.id), strategy) {
+                (true, ConflictStrategy::Skip) => {
+                    results.push((server.id, McpImportOutcome::Skipped));
+                    continue;
+                }
+                (true, ConflictStrategy::Overwrite) => McpImportOutcome::Overwritten,
+                (false, _) => McpImportOutcome::Added,
+            };
+            let id = server.id.clone();
+            Self::upsert_server(state, server)?;
+            results.push((id, outcome));
+        }
+
+        Ok(results)
+    }
+
+    /// 解析粘贴的 MCP 定义，支持三种形态：
+    ///
+    /// - Claude 配置块 `{"mcpServers": {"<id>": {"command": ..}}}`（可直接是 `~/.claude.json`）
+    /// - 单个服务器 `{"name": "fs", "command": "npx", "args": [..], "env": {..}}`
+    /// - 完整的统一结构 `{"id": .., "name": .., "server": {..}}`
+    ///
+    /// 新条目默认仅对 `app` 启用；同一片段内 id 重复或连接定义无效时整体报错。
+    pub fn servers_from_snippet(
+        value: &serde_json::Value,
+        app: &AppType,
+    ) -> Result<Vec<McpServer>, AppError> {
+        let obj = value
+            .as_object()
+            .ok_or_else(|| AppError::InvalidInput("MCP 定义必须是 JSON 对象".to_string()))?;
+        let mut apps = McpApps::default();
+        apps.set_enabled_for(app, true);
+        let new_server = |id: &str, name: &str, spec: serde_json::Value| McpServer {
+            id: id.trim().to_string(),
+            name: name.trim().to_string(),
+            server: spec,
+            apps: apps.clone(),
+            description: None,
+            homepage: None,
+            docs: None,
+            tags: vec![],
+        };
+
+        let servers = if let Some(block) = obj.get("mcpServers") {
+            let block = block.as_object().ok_or_else(|| {
+                AppError::InvalidInput("mcpServers 必须是以 id 为键的对象".to_string())
+            })?;
+            block
+                .iter()
+                .map(|(id, spec)| new_server(id, id, spec.clone()))
+                .collect()
+        } else if obj.get("server").is_some_and(|v| v.is_object()) {
+            let server: McpServer = serde_json::from_value(value.clone())
+                .map_err(|e| AppError::InvalidInput(format!("无效的 MCP 服务器定义: {e}")))?;
+            vec![server]
+        } else {
+            let mut spec = obj.clone();
+            let id = spec
+                .remove("id")
+                .or_else(|| spec.get("name").cloned())
+                .and_then(|v| v.as_str().map(str::to_string))
+                .unwrap_or_default();
+            let name = spec
+                .remove("name")
+                .and_then(|v| v.as_str().map(str::to_string))
+                .unwrap_or_else(|| id.clone());
+            vec![new_server(&id, &name, serde_json::Value::Object(spec))]
+        };
+
+        if servers.is_empty() {
+            return Err(AppError::InvalidInput(
+                "片段中没有 MCP 服务器定义".to_string(),
+            ));
+        }
+        let mut seen = std::collections::HashSet::new();
+        for server in &servers {
+            if server.id.is_empty() {
+                return Err(AppError::InvalidInput(
+                    "MCP 服务器缺少 id（请提供 id 或 name 字段）".to_string(),
+                ));
+            }
+            if !seen.insert(server.id.as_str()) {
+                return Err(AppError::InvalidInput(format!(
+                    "MCP 服务器 id 重复: {}",
+                    server.id
+                )));
+            }
+            mcp::validate_server_spec(&server.server).map_err(|e| match e {
+                AppError::McpValidation(msg) => {
+                    AppError::McpValidation(format!("{}: {msg}", server.id))
+                }
+                other => other,
+            })?;
+        }
+        Ok(servers)
+    }
+
+    /// 写入 `servers_from_snippet` 解析出的服务器，逐条返回结果
+    ///
+    /// 未指定 `strategy` 时，只要有 id 与现有服务器冲突就整体报错（不写入任何条目），
+    /// 避免粘贴的片段静默覆盖已有配置。
+    pub fn add_from_snippet(
+        state: &AppState,
+        servers: Vec<McpServer>,
+        strategy: Option<ConflictStrategy>,
+    ) -> Result<Vec<(String, McpImportOutcome)>, AppError> {
+        let existing = Self::get_all_servers(state)?;
+        let strategy = match strategy {
+            Some(strategy) => strategy,
+            None => {
+                let conflicts: Vec<&str> = servers
+                    .iter()
+                    .filter(|s| existing.contains_key(&s.id))
+                    .map(|s| s.id.as_str())
+                    .collect();
+                if !conflicts.is_empty() {
+                    let ids = conflicts.join(", ");
+                    return Err(AppError::localized(
+                        "mcp.add_conflict",
+                        format!("MCP 服务器已存在：{ids}（使用 --on-conflict overwrite 替换，或 skip 跳过）"),
+                        format!("MCP server(s) already exist: {ids} (use --on-conflict overwrite to replace, or skip)"),
+                    ));
+                }
+                ConflictStrategy::Skip
+            }
+        };
+
+        let mut results = Vec::new();
+        for server in servers {
+            let outcome = match (existing.contains_key(&server.id), strategy) {
+                (true, ConflictStrategy::Skip) => {
+                    results.push((server.id, McpImportOutcome::Skipped));
+                    continue;
+                }
+                (true, ConflictStrategy::Overwrite) => McpImportOutcome::Overwritten,
+                (false, _) => McpImportOutcome::Added,
+            };
+            let id = server.id.clone();
+            Self::upsert_server(state, server)?;
+            results.push((id, outcome));
+        }
+        Ok(results)
+    }
+
     /// 从 Claude 导入 MCP（v3.7.0 已更新为统一结构）
     pub fn import_from_claude(state: &AppState) -> Result<usize, AppError> {
         let mut cfg = state.config.write()?;
@@ -0,0 +1,391 @@
+//! MCP 服务器连通性探测（`mcp test`）
+//!
+//! stdio：启动进程并发送 `initialize` 请求，等待带 `serverInfo` 的响应；
+//! http：POST `initialize`（兼容 JSON 与 SSE 响应）；sse：确认端点返回事件流。
+
+use std::process::Stdio;
+use std::time::{Duration, Instant};
+
+use futures::stream::{self, StreamExt};
+use serde::Serialize;
+use serde_json::{json, Value};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::process::Command;
+
+use crate::app_config::McpServer;
+
+use super::McpService;
+
+/// 探测结果分类
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum McpProbeStatus {
+    Passed,
+    /// stdio 命令不在 PATH 中
+    BinaryNotFound,
+    /// 进程已启动 / 连接已建立，但超时内没有完成握手
+    HandshakeTimeout,
+    /// 收到了响应，但不是合法的 MCP 握手结果
+    ProtocolError,
+    /// 无法启动进程或无法连接到 URL
+    ConnectionFailed,
+}
+
+impl McpProbeStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Passed => "pass",
+            Self::BinaryNotFound => "binary not found",
+            Self::HandshakeTimeout => "handshake timeout",
+            Self::ProtocolError => "protocol error",
+            Self::ConnectionFailed => "connection failed",
+        }
+    }
+}
+
+/// 单个服务器的探测结果
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct McpProbeResult {
+    pub id: String,
+    pub transport: String,
+    pub status: McpProbeStatus,
+    /// 成功时为服务器名称/版本，失败时为原因
+    pub detail: Option<String>,
+    pub latency_ms: Option<u128>,
+}
+
+type Probe = (McpProbeStatus, Option<String>);
+
+fn initialize_request() -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "initialize",
+        "params": {
+            "protocolVersion": "2025-03-26",
+            "capabilities": {},
+            "clientInfo": { "name": "cc-switch", "version": env!("CARGO_PKG_VERSION") }
+        }
+    })
+}
+
+/// 解析 `initialize` 的 JSON-RPC 响应；返回 None 表示不是对应的响应（例如通知），应继续读取
+fn parse_initialize_response(message: &Value) -> Option<Probe> {
+    if message.get("id") != Some(&json!(1)) {
+        return None;
+    }
+    if let Some(err) = message.get("error") {
+        let msg = err
+            .get("message")
+            .and_then(Value::as_str)
+            .unwrap_or("unknown error");
+        return Some((McpProbeStatus::ProtocolError, Some(msg.to_string())));
+    }
+    let info = message.get("result").and_then(|r| r.get("serverInfo"));
+    match info {
+        Some(info) => {
+            let name = info.get("name").and_then(Value::as_str).unwrap_or("?");
+            let version = info.get("version").and_then(Value::as_str).unwrap_or("");
+            let detail = format!("{name} {version}").trim().to_string();
+            Some((McpProbeStatus::Passed, Some(detail)))
+        }
+        None => Some((
+            McpProbeStatus::ProtocolError,
+            Some("initialize result has no serverInfo".to_string()),
+        )),
+    }
+}
+
+async fn probe_stdio(spec: &Value, timeout: Duration) -> Probe {
+    let command = spec.get("command").and_then(Value::as_str).unwrap_or("");
+    let Ok(program) = which::which(command) else {
+        return (
+            McpProbeStatus::BinaryNotFound,
+            Some(format!("'{command}' not found in PATH")),
+        );
+    };
+
+    let mut cmd = Command::new(program);
+    if let Some(args) = spec.get("args").and_then(Value::as_array) {
+        cmd.args(args.iter().filter_map(Value::as_str));
+    }
+    if let Some(env) = spec.get("env").and_then(Value::as_object) {
+        for (key, value) in env {
+            let value = value
+                .as_str()
+                .map(str::to_string)
+                .unwrap_or_else(|| value.to_string());
+            cmd.env(key, value);
+        }
+    }
+    if let Some(cwd) = spec.get("cwd").and_then(Value::as_str) {
+        cmd.current_dir(cwd);
+    }
+    let mut child = match cmd
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .kill_on_drop(true)
+        .spawn()
+    {
+        Ok(child) => child,
+        Err(e) => return (McpProbeStatus::ConnectionFailed, Some(e.to_string())),
+    };
+
+    let (Some(mut stdin), Some(stdout)) = (child.stdin.take(), child.stdout.take()) else {
+        return (
+            McpProbeStatus::ConnectionFailed,
+            Some("failed to open stdio pipes".to_string()),
+        );
+    };
+
+    let handshake = async move {
+        let mut line = initialize_request().to_string();
+        line.push('\n');
+        if let Err(e) = stdin.write_all(line.as_bytes()).await {
+            return (
+                McpProbeStatus::ProtocolError,
+                Some(format!("failed to write request: {e}")),
+            );
+        }
+        let _ = stdin.flush().await;
+
+        let mut lines = BufReader::new(stdout).lines();
+        loop {
+            match lines.next_line().await {
+                Ok(Some(line)) if line.trim().is_empty() => continue,
+                Ok(Some(line)) => match serde_json::from_str::<Value>(&line) {
+                    Ok(message) => {
+                        if let Some(probe) = parse_initialize_response(&message) {
+                            return probe;
+                        }
+                    }
+                    Err(_) => {
+                        let snippet: String = line.chars().take(80).collect();
+                        return (
+                            McpProbeStatus::ProtocolError,
+                            Some(format!("non-JSON output on stdout: {snippet}")),
+                        );
+                    }
+                },
+                Ok(None) => {
+                    return (
+                        McpProbeStatus::ProtocolError,
+                        Some("process exited before responding".to_string()),
+                    )
+                }
+                Err(e) => return (McpProbeStatus::ProtocolError, Some(e.to_string())),
+            }
+        }
+    };
+
+    let result = match tokio::time::timeout(timeout, handshake).await {
+        Ok(probe) => probe,
+        Err(_) => (
+            McpProbeStatus::HandshakeTimeout,
+            Some(format!(
+                "no initialize response within {}s",
+                timeout.as_secs()
+            )),
+        ),
+    };
+    let _ = child.kill().await;
+    result
+}
+
+fn request_error(err: reqwest::Error, timeout: Duration) -> Probe {
+    if err.is_timeout() {
+        (
+            McpProbeStatus::HandshakeTimeout,
+            Some(format!("no response within {}s", timeout.as_secs())),
+        )
+    } else if err.is_connect() || err.is_builder() {
+        (McpProbeStatus::ConnectionFailed, Some(err.to_string()))
+    } else {
+        (McpProbeStatus::ProtocolError, Some(err.to_string()))
+    }
+}
+
+fn with_headers(mut req: reqwest::RequestBuilder, spec: &Value) -> reqwest::RequestBuilder {
+    if let Some(headers) = spec.get("headers").and_then(Value::as_object) {
+        for (key, value) in headers {
+            if let Some(value) = value.as_str() {
+                req = req.header(key, value);
+            }
+        }
+    }
+    req
+}
+
+async fn probe_http(client: &reqwest::Client, spec: &Value, timeout: Duration) -> Probe {
+    let url = spec.get("url").and_then(Value::as_str).unwrap_or("");
+    let req = client
+        .post(url)
+        .header("Accept", "application/json, text/event-stream")
+        .json(&initialize_request());
+    let resp = match with_headers(req, spec).send().await {
+        Ok(resp) => resp,
+        Err(e) => return request_error(e, timeout),
+    };
+    let status = resp.status();
+    if !status.is_success() {
+        return (
+            McpProbeStatus::ProtocolError,
+            Some(format!("HTTP {status}")),
+        );
+    }
+    let body = match resp.text().await {
+        Ok(body) => body,
+        Err(e) => return request_error(e, timeout),
+    };
+
+    // Streamable HTTP 可能以 SSE 形式返回：取 `data:` 行中的 JSON
+    let messages = body
+        .lines()
+        .filter_map(|line| line.strip_prefix("data:"))
+        .map(str::trim)
+        .chain(std::iter::once(body.trim()))
+        .filter_map(|raw| serde_json::from_str::<Value>(raw).ok());
+    for message in messages {
+        if let Some(probe) = parse_initialize_response(&message) {
+            return probe;
+        }
+    }
+    (
+        McpProbeStatus::ProtocolError,
+        Some("response is not a JSON-RPC initialize result".to_string()),
+    )
+}
+
+async fn probe_sse(client: &reqwest::Client, spec: &Value, timeout: Duration) -> Probe {
+    let url = spec.get("url").and_then(Value::as_str).unwrap_or("");
+    let req = client.get(url).header("Accept", "text/event-stream");
+    let resp = match with_headers(req, spec).send().await {
+        Ok(resp) => resp,
+        Err(e) => return request_error(e, timeout),
+    };
+    let status = resp.status();
+    let content_type = resp
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("")
+        .to_string();
+    if !status.is_success() {
+        (
+            McpProbeStatus::ProtocolError,
+            Some(format!("HTTP {status}")),
+        )
+    } else if !content_type.starts_with("text/event-stream") {
+        (
+            McpProbeStatus::ProtocolError,
+            Some(format!("unexpected content type: {content_type}")),
+        )
+    } else {
+        (
+            McpProbeStatus::Passed,
+            Some("event stream open".to_string()),
+        )
+    }
+}
+
+impl McpService {
+    /// 并发探测一组 MCP 服务器（最多 `concurrency` 个同时进行），结果按 id 排序
+    pub async fn probe_servers(
+        servers: Vec<McpServer>,
+        timeout: Duration,
+        concurrency: usize,
+    ) -> Vec<McpProbeResult> {
+        let client = crate::http_client::client_builder()
+            .ok()
+            .and_then(|builder| builder.timeout(timeout).build().ok())
+            .unwrap_or_default();
+
+        let mut results: Vec<McpProbeResult> = stream::iter(servers)
+            .map(|server| {
+                let client = client.clone();
+                async move {
+                    let spec = &server.server;
+                    let transport = spec
+                        .get("type")
+                        .and_then(Value::as_str)
+                        .unwrap_or("stdio")
+                        .to_string();
+                    let start = Instant::now();
+                    let (status, detail) = match transport.as_str() {
+                        "http" | "sse" if crate::http_client::is_offline() => (
+                            McpProbeStatus::ConnectionFailed,
+                            Some("skipped in offline mode".to_string()),
+                        ),
+                        "http" => probe_http(&client, spec, timeout).await,
+                        "sse" => probe_sse(&client, spec, timeout).await,
+                        _ => probe_stdio(spec, timeout).await,
+                    };
+                    McpProbeResult {
+                        id: server.id,
+                        transport,
+                        latency_ms: (status == McpProbeStatus::Passed)
+                            .then(|| start.elapsed().as_millis()),
+                        status,
+                        detail,
+                    }
+                }
+            })
+            .buffer_unordered(concurrency.max(1))
+            .collect()
+            .await;
+
+        results.sort_by(|a, b| a.id.cmp(&b.id));
+        results
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_initialize_response_classifies_messages() {
+        let ok =
+            json!({"jsonrpc":"2.0","id":1,"result":{"serverInfo":{"name":"fs","version":"1.0"}}});
+        assert_eq!(
+            parse_initialize_response(&ok),
+            Some((McpProbeStatus::Passed, Some("fs 1.0".to_string())))
+        );
+
+        let err = json!({"jsonrpc":"2.0","id":1,"error":{"code":-32600,"message":"bad"}});
+        assert_eq!(
+            parse_initialize_response(&err).map(|p| p.0),
+            Some(McpProbeStatus::ProtocolError)
+        );
+
+        let notification = json!({"jsonrpc":"2.0","method":"notifications/message"});
+        assert_eq!(parse_initialize_response(&notification), None);
+    }
+
+    #[test]
+    fn missing_binary_is_reported_without_spawning() {
+        let server = McpServer {
+            id: "ghost".to_string(),
+            name: "ghost".to_string(),
+            server: json!({ "command": "cc-switch-definitely-missing-binary" }),
+            apps: Default::default(),
+            description: None,
+            homepage: None,
+            docs: None,
+            tags: vec![],
+        };
+        let results = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("runtime")
+            .block_on(McpService::probe_servers(
+                vec![server],
+                Duration::from_secs(1),
+                2,
+            ));
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].status, McpProbeStatus::BinaryNotFound);
+    }
+}
@@ -1,16 +1,29 @@
+pub mod common_config_schema;
 pub mod config;
+pub mod config_crypto;
 pub mod env_checker;
 pub mod env_manager;
+pub mod external_discovery;
+pub mod live_backup;
 pub mod local_env_check;
 pub mod mcp;
+pub mod mcp_probe;
 pub mod prompt;
 pub mod provider;
 pub mod skill;
 pub mod speedtest;
 
-pub use config::ConfigService;
-pub use mcp::McpService;
-pub use prompt::PromptService;
-pub use provider::ProviderService;
-pub use skill::SkillService;
+pub use common_config_schema::validate_common_config_snippet;
+pub use config::{BackupInfo, BackupMeta, ConfigSectionDiff, ConfigService, ConflictStrategy};
+pub use mcp::{McpImportOutcome, McpService};
+pub use mcp_probe::McpProbeStatus;
+pub use prompt::{PromptImportReport, PromptService};
+pub use provider::{
+    EndpointSelection, LiveConfigPreview, ProviderModels, ProviderService, SwitchHookOutput,
+    TemporarySwitch,
+};
+pub use skill::{
+    BulkInstallResult, InstallOptions, InstallOutcome, InstallPlan, SkillCasingFix, SkillProgress,
+    SkillReadme, SkillReadmeSource, SkillRepo, SkillService, SkillStats, SyncMethod,
+};
 pub use speedtest::{EndpointLatency, SpeedtestService};
@@ -1,4 +1,5 @@
 use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 
 use crate::app_config::AppType;
 use crate::config::write_text_file;
@@ -9,6 +10,54 @@ use crate::store::AppState;
 
 pub struct PromptService;
 
+/// `prompts import <dir>` 的结果
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PromptImportReport {
+    pub added: Vec<String>,
+    pub updated: Vec<String>,
+}
+
+/// 将提示词名称转换为安全的文件名（去掉路径分隔符与保留字符），为空时返回 `None`
+fn sanitize_file_stem(name: &str) -> Option<String> {
+    let cleaned: String = name
+        .chars()
+        .map(|c| match c {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '-',
+            c if c.is_control() => '-',
+            c => c,
+        })
+        .collect();
+    let cleaned = cleaned.trim().trim_matches('.').trim();
+    (!cleaned.is_empty()).then(|| cleaned.to_string())
+}
+
+/// 收集目录下的 `.md` 文件（跳过以 `.` 开头的文件与目录），按路径排序
+fn collect_markdown_files(
+    dir: &Path,
+    recursive: bool,
+    out: &mut Vec<PathBuf>,
+) -> Result<(), AppError> {
+    let entries = std::fs::read_dir(dir).map_err(|e| AppError::io(dir, e))?;
+    for entry in entries {
+        let entry = entry.map_err(|e| AppError::io(dir, e))?;
+        let path = entry.path();
+        if entry.file_name().to_string_lossy().starts_with('.') {
+            continue;
+        }
+        if path.is_dir() {
+            if recursive {
+                collect_markdown_files(&path, recursive, out)?;
+            }
+        } else if path
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("md"))
+        {
+            out.push(path);
+        }
+    }
+    Ok(())
+}
+
 impl PromptService {
     pub fn get_prompts(
         state: &AppState,
@@ -219,6 +268,129 @@ impl PromptService {
         Ok(id)
     }
 
+    /// 批量导入目录中的 Markdown 文件：文件名（去扩展名）作为新提示词的名称，内容作为正文。
+    ///
+    /// id 取相对路径（不含扩展名，`/` 分隔），重复导入时更新同 id 的提示词并保留其启用状态。
+    pub fn import_from_dir(
+        state: &AppState,
+        app: AppType,
+        dir: &Path,
+        recursive: bool,
+    ) -> Result<PromptImportReport, AppError> {
+        if !dir.is_dir() {
+            return Err(AppError::InvalidInput(format!(
+                "目录不存在: {}",
+                dir.display()
+            )));
+        }
+
+        let mut files = Vec::new();
+        collect_markdown_files(dir, recursive, &mut files)?;
+        files.sort();
+
+        let existing = Self::get_prompts(state, app.clone())?;
+        let now = chrono::Utc::now().timestamp();
+        let mut report = PromptImportReport::default();
+
+        for path in files {
+            let relative = path.strip_prefix(dir).unwrap_or(&path).with_extension("");
+            let id = relative
+                .components()
+                .map(|c| c.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/");
+            let name = path
+                .file_stem()
+                .map(|s| s.to_string_lossy().to_string())
+                .unwrap_or_else(|| id.clone());
+            let content = std::fs::read_to_string(&path).map_err(|e| AppError::io(&path, e))?;
+
+            let previous = existing.get(&id);
+            let prompt = Prompt {
+                id: id.clone(),
+                // 更新已有提示词时保留原名称（导出文件以 id 命名）
+                name: previous.map_or(name, |p| p.name.clone()),
+                content,
+                description: previous.and_then(|p| p.description.clone()),
+                enabled: previous.is_some_and(|p| p.enabled),
+                created_at: previous.and_then(|p| p.created_at).or(Some(now)),
+                updated_at: Some(now),
+            };
+            Self::upsert_prompt(state, app.clone(), &id, prompt)?;
+
+            if previous.is_some() {
+                report.updated.push(id);
+            } else {
+                report.added.push(id);
+            }
+        }
+
+        Ok(report)
+    }
+
+    /// 将提示词逐个写入 `<dir>/<id>.md`（文件名经过清理，重名时追加序号）。
+    ///
+    /// 未指定 `force` 时，只要有目标文件已存在就整体拒绝，不写入任何文件。
+    pub fn export_to_dir(
+        state: &AppState,
+        app: AppType,
+        dir: &Path,
+        force: bool,
+    ) -> Result<Vec<PathBuf>, AppError> {
+        let mut prompts: Vec<_> = Self::get_prompts(state, app)?.into_values().collect();
+        prompts.sort_by(|a, b| a.id.cmp(&b.id));
+
+        let mut used = std::collections::HashSet::new();
+        let mut targets = Vec::with_capacity(prompts.len());
+        for prompt in &prompts {
+            // 按 id 命名，`prompts import` 时相对路径即还原为同一 id；`/` 分隔的 id 写入子目录
+            let segments: Vec<String> = prompt
+                .id
+                .split('/')
+                .filter_map(sanitize_file_stem)
+                .collect();
+            let base = if segments.is_empty() {
+                "prompt".to_string()
+            } else {
+                segments.join("/")
+            };
+            let mut stem = base.clone();
+            let mut n = 2;
+            while !used.insert(stem.to_lowercase()) {
+                stem = format!("{base}-{n}");
+                n += 1;
+            }
+            targets.push((dir.join(format!("{stem}.md")), &prompt.content));
+        }
+
+        if !force {
+            let existing: Vec<_> = targets
+                .iter()
+                .filter(|(path, _)| path.exists())
+                .map(|(path, _)| path.display().to_string())
+                .collect();
+            if !existing.is_empty() {
+                return Err(AppError::localized(
+                    "prompts.export_exists",
+                    format!(
+                        "以下文件已存在（使用 --force 覆盖）: {}",
+                        existing.join(", ")
+                    ),
+                    format!(
+                        "Files already exist (use --force to overwrite): {}",
+                        existing.join(", ")
+                    ),
+                ));
+            }
+        }
+
+        crate::dry_run::create_dir_all(dir).map_err(|e| AppError::io(dir, e))?;
+        for (path, content) in &targets {
+            write_text_file(path, content)?;
+        }
+        Ok(targets.into_iter().map(|(path, _)| path).collect())
+    }
+
     pub fn get_current_file_content(app: AppType) -> Result<Option<String>, AppError> {
         let file_path = prompt_file_path(&app)?;
         if !file_path.exists() {
@@ -0,0 +1,72 @@
+use serde_json::{json, Value};
+
+use crate::app_config::AppType;
+use crate::error::AppError;
+use crate::provider::Provider;
+use crate::store::AppState;
+
+use super::ProviderService;
+
+/// 各应用存放 API Key 的位置：(分区, 候选字段)，首个候选为缺省写入字段
+pub(super) fn api_key_slot(app_type: &AppType) -> (&'static str, &'static [&'static str]) {
+    match app_type {
+        AppType::Claude => ("env", &["ANTHROPIC_AUTH_TOKEN", "ANTHROPIC_API_KEY"]),
+        AppType::Codex => ("auth", &["OPENAI_API_KEY"]),
+        AppType::Gemini => ("env", &["GEMINI_API_KEY", "GOOGLE_API_KEY"]),
+    }
+}
+
+impl ProviderService {
+    /// 原地替换供应商的 API Key（可为 `keyring:<名称>` 引用）；若为当前供应商，会同步写入 live 配置。
+    /// 返回被写入的字段名
+    pub fn set_api_key(
+        state: &AppState,
+        app_type: AppType,
+        provider_id: &str,
+        value: &str,
+    ) -> Result<String, AppError> {
+        let value = value.trim();
+        if value.is_empty() {
+            return Err(AppError::InvalidInput(
+                "API key must not be empty".to_string(),
+            ));
+        }
+
+        let mut provider: Provider = {
+            let config = state.config.read().map_err(AppError::from)?;
+            let manager = config
+                .get_manager(&app_type)
+                .ok_or_else(|| Self::app_not_found(&app_type))?;
+            manager.providers.get(provider_id).cloned().ok_or_else(|| {
+                AppError::localized(
+                    "provider.not_found",
+                    format!("供应商不存在: {provider_id}"),
+                    format!("Provider not found: {provider_id}"),
+                )
+            })?
+        };
+
+        let (section_name, keys) = api_key_slot(&app_type);
+        let obj = provider
+            .settings_config
+            .as_object_mut()
+            .ok_or_else(|| AppError::Config("Provider settings must be an object".to_string()))?;
+        let section = obj
+            .entry(section_name)
+            .or_insert_with(|| json!({}))
+            .as_object_mut()
+            .ok_or_else(|| {
+                AppError::Config(format!("Provider {section_name} must be an object"))
+            })?;
+        // 沿用已有字段（如 ANTHROPIC_API_KEY），否则写入缺省字段
+        let field = keys
+            .iter()
+            .find(|key| section.get(**key).is_some_and(Value::is_string))
+            .unwrap_or(&keys[0])
+            .to_string();
+        section.insert(field.clone(), json!(value));
+
+        Self::update(state, app_type, provider)?;
+        Ok(field)
+    }
+}
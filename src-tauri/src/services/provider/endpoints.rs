@@ -1,13 +1,111 @@
 use std::time::{SystemTime, UNIX_EPOCH};
 
+use serde::Serialize;
+use serde_json::{json, Value};
+
 use crate::app_config::AppType;
 use crate::error::AppError;
-use crate::provider::ProviderMeta;
+use crate::provider::{Provider, ProviderMeta};
+use crate::services::speedtest::{EndpointLatency, SpeedtestService};
 use crate::settings::CustomEndpoint;
 use crate::store::AppState;
 
 use super::ProviderService;
 
+/// Gemini 的 base URL 键（按优先级）
+const GEMINI_BASE_URL_KEYS: [&str; 3] = ["GOOGLE_GEMINI_BASE_URL", "GEMINI_BASE_URL", "BASE_URL"];
+
+/// 供应商的一个端点：主端点写在 settings_config 中，其余来自 `meta.custom_endpoints`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProviderEndpoint {
+    pub url: String,
+    /// 是否为 live 配置当前使用的端点
+    pub primary: bool,
+    pub added_at: Option<i64>,
+    pub last_used: Option<i64>,
+}
+
+/// 自动选择端点的结果
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EndpointSelection {
+    pub results: Vec<EndpointLatency>,
+    /// 延迟最低的可达端点（全部不可达时为 None）
+    pub chosen: Option<String>,
+    /// 是否因此改变了主端点
+    pub changed: bool,
+}
+
+fn normalize_url(url: &str) -> String {
+    url.trim().trim_end_matches('/').to_string()
+}
+
+/// 读取供应商配置中的主端点（Claude `ANTHROPIC_BASE_URL`、Codex `base_url`、Gemini base URL）
+fn primary_endpoint(app_type: &AppType, settings_config: &Value) -> Option<String> {
+    let env_str = |key: &str| {
+        settings_config
+            .get("env")
+            .and_then(|env| env.get(key))
+            .and_then(|v| v.as_str())
+            .filter(|s| !s.trim().is_empty())
+            .map(str::to_string)
+    };
+    match app_type {
+        AppType::Claude => env_str("ANTHROPIC_BASE_URL"),
+        AppType::Codex => settings_config
+            .get("config")
+            .and_then(|v| v.as_str())
+            .and_then(|s| s.parse::<toml_edit::DocumentMut>().ok())
+            .and_then(|doc| doc.get("base_url")?.as_str().map(str::to_string)),
+        AppType::Gemini => GEMINI_BASE_URL_KEYS.iter().find_map(|key| env_str(key)),
+    }
+}
+
+fn set_primary_endpoint(
+    app_type: &AppType,
+    settings_config: &mut Value,
+    url: &str,
+) -> Result<(), AppError> {
+    match app_type {
+        AppType::Claude => {
+            ProviderService::env_object_mut(settings_config)?
+                .insert("ANTHROPIC_BASE_URL".to_string(), json!(url));
+        }
+        AppType::Codex => {
+            let raw = settings_config
+                .get("config")
+                .and_then(|v| v.as_str())
+                .unwrap_or_default();
+            let mut doc = raw
+                .parse::<toml_edit::DocumentMut>()
+                .map_err(|e| AppError::Config(format!("Invalid Codex config.toml: {e}")))?;
+            doc["base_url"] = toml_edit::value(url);
+            let obj = settings_config.as_object_mut().ok_or_else(|| {
+                AppError::Config("Codex provider settings must be an object".to_string())
+            })?;
+            obj.insert("config".to_string(), json!(doc.to_string()));
+        }
+        AppType::Gemini => {
+            let env = ProviderService::env_object_mut(settings_config)?;
+            let key = GEMINI_BASE_URL_KEYS
+                .iter()
+                .find(|key| env.contains_key(**key))
+                .unwrap_or(&GEMINI_BASE_URL_KEYS[0]);
+            env.insert(key.to_string(), json!(url));
+        }
+    }
+    Ok(())
+}
+
+/// 从测速结果中挑出延迟最低的可达端点
+pub fn pick_fastest_endpoint(results: &[EndpointLatency]) -> Option<&EndpointLatency> {
+    results
+        .iter()
+        .filter(|r| r.error.is_none() && r.latency.is_some())
+        .min_by_key(|r| r.latency)
+}
+
 impl ProviderService {
     /// 获取自定义端点列表
     pub fn get_custom_endpoints(
@@ -128,9 +226,209 @@ impl ProviderService {
     }
 }
 
+impl ProviderService {
+    fn provider_or_not_found(
+        state: &AppState,
+        app_type: &AppType,
+        provider_id: &str,
+    ) -> Result<Provider, AppError> {
+        let cfg = state.config.read().map_err(AppError::from)?;
+        let manager = cfg
+            .get_manager(app_type)
+            .ok_or_else(|| Self::app_not_found(app_type))?;
+        manager.providers.get(provider_id).cloned().ok_or_else(|| {
+            AppError::localized(
+                "provider.not_found",
+                format!("供应商不存在: {provider_id}"),
+                format!("Provider not found: {provider_id}"),
+            )
+        })
+    }
+
+    /// 列出主端点与自定义端点（主端点在前，自定义端点按 URL 排序并去重）
+    pub fn list_endpoints(
+        state: &AppState,
+        app_type: AppType,
+        provider_id: &str,
+    ) -> Result<Vec<ProviderEndpoint>, AppError> {
+        let provider = Self::provider_or_not_found(state, &app_type, provider_id)?;
+        let primary =
+            primary_endpoint(&app_type, &provider.settings_config).map(|u| normalize_url(&u));
+        let custom = provider
+            .meta
+            .as_ref()
+            .map(|meta| meta.custom_endpoints.clone())
+            .unwrap_or_default();
+
+        let mut endpoints = Vec::new();
+        if let Some(url) = &primary {
+            let info = custom.get(url);
+            endpoints.push(ProviderEndpoint {
+                url: url.clone(),
+                primary: true,
+                added_at: info.map(|e| e.added_at),
+                last_used: info.and_then(|e| e.last_used),
+            });
+        }
+        let mut rest: Vec<_> = custom
+            .into_values()
+            .filter(|e| primary.as_deref() != Some(e.url.as_str()))
+            .collect();
+        rest.sort_by(|a, b| a.url.cmp(&b.url));
+        endpoints.extend(rest.into_iter().map(|e| ProviderEndpoint {
+            url: e.url,
+            primary: false,
+            added_at: Some(e.added_at),
+            last_used: e.last_used,
+        }));
+        Ok(endpoints)
+    }
+
+    /// 是否开启了端点自动选择（`meta.endpointAutoSelect`）
+    pub fn endpoint_auto_select(
+        state: &AppState,
+        app_type: AppType,
+        provider_id: &str,
+    ) -> Result<bool, AppError> {
+        let provider = Self::provider_or_not_found(state, &app_type, provider_id)?;
+        Ok(provider
+            .meta
+            .and_then(|meta| meta.endpoint_auto_select)
+            .unwrap_or(false))
+    }
+
+    pub fn set_endpoint_auto_select(
+        state: &AppState,
+        app_type: AppType,
+        provider_id: &str,
+        enabled: bool,
+    ) -> Result<(), AppError> {
+        let mut provider = Self::provider_or_not_found(state, &app_type, provider_id)?;
+        provider
+            .meta
+            .get_or_insert_with(ProviderMeta::default)
+            .endpoint_auto_select = Some(enabled);
+        Self::update(state, app_type, provider)?;
+        Ok(())
+    }
+
+    /// 将 `url` 设为主端点（写入 settings_config；若为当前供应商会同步 live 配置）。
+    /// 原主端点保留为自定义端点，避免丢失。
+    pub fn use_endpoint(
+        state: &AppState,
+        app_type: AppType,
+        provider_id: &str,
+        url: &str,
+    ) -> Result<(), AppError> {
+        let url = normalize_url(url);
+        let mut provider = Self::provider_or_not_found(state, &app_type, provider_id)?;
+        let previous = primary_endpoint(&app_type, &provider.settings_config);
+        set_primary_endpoint(&app_type, &mut provider.settings_config, &url)?;
+
+        let meta = provider.meta.get_or_insert_with(ProviderMeta::default);
+        for endpoint in previous
+            .iter()
+            .map(|u| normalize_url(u))
+            .chain([url.clone()])
+        {
+            meta.custom_endpoints
+                .entry(endpoint.clone())
+                .or_insert_with(|| CustomEndpoint {
+                    url: endpoint,
+                    added_at: now_millis(),
+                    last_used: None,
+                });
+        }
+        if let Some(endpoint) = meta.custom_endpoints.get_mut(&url) {
+            endpoint.last_used = Some(now_millis());
+        }
+
+        Self::update(state, app_type, provider)?;
+        Ok(())
+    }
+
+    /// 测速所有端点并切换到延迟最低的可达端点。
+    ///
+    /// 未开启 `endpointAutoSelect` 或端点少于两个时返回 `None`（不测速）。
+    pub async fn auto_select_endpoint(
+        state: &AppState,
+        app_type: AppType,
+        provider_id: &str,
+    ) -> Result<Option<EndpointSelection>, AppError> {
+        if !Self::endpoint_auto_select(state, app_type.clone(), provider_id)? {
+            return Ok(None);
+        }
+        let endpoints = Self::list_endpoints(state, app_type.clone(), provider_id)?;
+        if endpoints.len() < 2 {
+            return Ok(None);
+        }
+
+        let primary = endpoints.iter().find(|e| e.primary).map(|e| e.url.clone());
+        let urls = endpoints.into_iter().map(|e| e.url).collect();
+        let results = SpeedtestService::test_endpoints(urls, None).await?;
+        let chosen = pick_fastest_endpoint(&results).map(|r| normalize_url(&r.url));
+
+        let changed = chosen.is_some() && chosen != primary;
+        if let Some(url) = chosen.as_deref().filter(|_| changed) {
+            Self::use_endpoint(state, app_type, provider_id, url)?;
+        }
+        Ok(Some(EndpointSelection {
+            results,
+            chosen,
+            changed,
+        }))
+    }
+}
+
 fn now_millis() -> i64 {
     SystemTime::now()
         .duration_since(UNIX_EPOCH)
         .unwrap_or_default()
         .as_millis() as i64
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn latency(url: &str, ms: Option<u128>, error: Option<&str>) -> EndpointLatency {
+        EndpointLatency {
+            url: url.to_string(),
+            latency: ms,
+            status: ms.map(|_| 200),
+            error: error.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn pick_fastest_skips_unreachable_endpoints() {
+        let results = vec![
+            latency("https://a", Some(120), None),
+            latency("https://b", None, Some("timeout")),
+            latency("https://c", Some(40), None),
+        ];
+        assert_eq!(
+            pick_fastest_endpoint(&results).map(|r| r.url.as_str()),
+            Some("https://c")
+        );
+        assert!(pick_fastest_endpoint(&results[1..2]).is_none());
+    }
+
+    #[test]
+    fn primary_endpoint_round_trips_for_each_app() {
+        let mut codex = json!({ "config": "model = \"gpt\"\nbase_url = \"https://old\"\n" });
+        set_primary_endpoint(&AppType::Codex, &mut codex, "https://new").expect("set codex");
+        assert_eq!(
+            primary_endpoint(&AppType::Codex, &codex).as_deref(),
+            Some("https://new")
+        );
+        assert!(codex["config"]
+            .as_str()
+            .unwrap_or_default()
+            .contains("model = \"gpt\""));
+
+        let mut gemini = json!({ "env": { "GEMINI_BASE_URL": "https://old" } });
+        set_primary_endpoint(&AppType::Gemini, &mut gemini, "https://new").expect("set gemini");
+        assert_eq!(gemini["env"]["GEMINI_BASE_URL"], json!("https://new"));
+    }
+}
@@ -0,0 +1,94 @@
+use chrono::{DateTime, Local, NaiveDate, TimeZone};
+
+use crate::app_config::AppType;
+use crate::database::ProviderSwitchRecord;
+use crate::error::AppError;
+use crate::store::AppState;
+
+use super::ProviderService;
+
+/// 解析时间边界：RFC3339，或 `YYYY-MM-DD`（本地时区当天 00:00），返回 Unix 毫秒
+pub fn parse_time_bound(raw: &str) -> Result<i64, AppError> {
+    let raw = raw.trim();
+    if let Ok(time) = DateTime::parse_from_rfc3339(raw) {
+        return Ok(time.timestamp_millis());
+    }
+    NaiveDate::parse_from_str(raw, "%Y-%m-%d")
+        .ok()
+        .and_then(|date| date.and_hms_opt(0, 0, 0))
+        .and_then(|midnight| Local.from_local_datetime(&midnight).earliest())
+        .map(|time| time.timestamp_millis())
+        .ok_or_else(|| {
+            AppError::localized(
+                "provider.history.invalid_time",
+                format!("无效的时间 '{raw}'，应为 RFC3339 或 YYYY-MM-DD"),
+                format!("Invalid time '{raw}': expected RFC3339 or YYYY-MM-DD"),
+            )
+        })
+}
+
+impl ProviderService {
+    /// 切换记录（最新的在前），按 `[since, until)` 过滤后最多取 `limit` 条
+    pub fn switch_history(
+        state: &AppState,
+        app_type: AppType,
+        since: Option<i64>,
+        until: Option<i64>,
+        limit: Option<usize>,
+    ) -> Result<Vec<ProviderSwitchRecord>, AppError> {
+        Ok(state
+            .db
+            .get_provider_switch_log(app_type.as_str())?
+            .into_iter()
+            .filter(|r| since.is_none_or(|since| r.switched_at >= since))
+            .filter(|r| until.is_none_or(|until| r.switched_at < until))
+            .take(limit.unwrap_or(usize::MAX))
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_time_bound_accepts_rfc3339_and_plain_dates() {
+        assert_eq!(
+            parse_time_bound("2024-01-01T00:00:00Z").expect("rfc3339"),
+            1_704_067_200_000
+        );
+        let date = parse_time_bound("2024-01-01").expect("date");
+        assert!((date - 1_704_067_200_000).abs() <= 14 * 3_600_000);
+        assert!(parse_time_bound("01/02/2024").is_err());
+    }
+
+    #[test]
+    fn switch_history_filters_by_window_and_limit() {
+        let state = super::super::state_from_config(Default::default());
+        for (id, at) in [("a", 1_000), ("b", 2_000), ("c", 3_000), ("d", 4_000)] {
+            state
+                .db
+                .record_provider_switch_at("claude", id, at)
+                .expect("record");
+        }
+
+        let ids = |records: Vec<ProviderSwitchRecord>| {
+            records
+                .into_iter()
+                .map(|r| r.provider_id)
+                .collect::<Vec<_>>()
+        };
+        let window = ProviderService::switch_history(
+            &state,
+            AppType::Claude,
+            Some(2_000),
+            Some(4_000),
+            None,
+        )
+        .expect("window");
+        assert_eq!(ids(window), vec!["c", "b"]);
+        let limited = ProviderService::switch_history(&state, AppType::Claude, None, None, Some(1))
+            .expect("limit");
+        assert_eq!(ids(limited), vec!["d"]);
+    }
+}
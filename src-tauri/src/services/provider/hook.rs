@@ -0,0 +1,133 @@
+use std::process::Command;
+
+use crate::app_config::AppType;
+use crate::error::AppError;
+use crate::store::AppState;
+
+use super::ProviderService;
+
+/// post-switch 钩子的执行结果（stdout 与 stderr 合并展示）
+#[derive(Debug, Clone)]
+pub struct SwitchHookOutput {
+    pub command: String,
+    pub success: bool,
+    pub exit_code: Option<i32>,
+    pub output: String,
+}
+
+/// 替换命令模板中的 `{app}` / `{id}` 占位符（代入值经 shell 转义，不会被当作命令解析）
+fn render_switch_hook(template: &str, app_type: &AppType, provider_id: &str) -> String {
+    template
+        .replace("{app}", &shell_quote(app_type.as_str()))
+        .replace("{id}", &shell_quote(provider_id))
+}
+
+/// 将值转义为单个 shell 参数（Unix 用单引号；Windows cmd 用双引号并去掉会破坏引号的字符）
+fn shell_quote(value: &str) -> String {
+    if cfg!(windows) {
+        let cleaned: String = value
+            .chars()
+            .filter(|c| !matches!(c, '"' | '%' | '!'))
+            .collect();
+        format!("\"{cleaned}\"")
+    } else {
+        format!("'{}'", value.replace('\'', r"'\''"))
+    }
+}
+
+impl ProviderService {
+    /// 切换供应商后执行 post-switch 钩子（`run_hook` 为 false 时跳过）
+    ///
+    /// 钩子失败只体现在返回的输出里，不回滚已完成的切换。
+    pub fn switch_with_hook(
+        state: &AppState,
+        app_type: AppType,
+        provider_id: &str,
+        run_hook: bool,
+    ) -> Result<Option<SwitchHookOutput>, AppError> {
+        Self::switch(state, app_type.clone(), provider_id)?;
+        if !run_hook {
+            return Ok(None);
+        }
+        Ok(Self::run_switch_hook(&app_type, provider_id))
+    }
+
+    /// 执行已配置的 post-switch 钩子；未配置时返回 None
+    ///
+    /// 命令经系统 shell 执行，同时通过 `CC_SWITCH_APP` / `CC_SWITCH_PROVIDER_ID` 环境变量传入参数。
+    pub fn run_switch_hook(app_type: &AppType, provider_id: &str) -> Option<SwitchHookOutput> {
+        let template = crate::settings::get_post_switch_hook()?;
+        let command = render_switch_hook(&template, app_type, provider_id);
+        // 钩子可能有任意副作用，预演模式下不执行
+        if crate::dry_run::skip(format_args!("run post-switch hook: {command}")) {
+            return None;
+        }
+
+        let mut shell = if cfg!(windows) {
+            let mut cmd = Command::new("cmd");
+            cmd.arg("/C");
+            cmd
+        } else {
+            let mut cmd = Command::new("sh");
+            cmd.arg("-c");
+            cmd
+        };
+        let result = shell
+            .arg(&command)
+            .env("CC_SWITCH_APP", app_type.as_str())
+            .env("CC_SWITCH_PROVIDER_ID", provider_id)
+            .output();
+
+        Some(match result {
+            Ok(out) => {
+                let mut output = String::from_utf8_lossy(&out.stdout).to_string();
+                output.push_str(&String::from_utf8_lossy(&out.stderr));
+                SwitchHookOutput {
+                    command,
+                    success: out.status.success(),
+                    exit_code: out.status.code(),
+                    output,
+                }
+            }
+            Err(e) => {
+                log::warn!("执行 post-switch 钩子失败: {e}");
+                SwitchHookOutput {
+                    command,
+                    success: false,
+                    exit_code: None,
+                    output: e.to_string(),
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(unix)]
+    #[test]
+    fn render_switch_hook_fills_placeholders() {
+        assert_eq!(
+            render_switch_hook("notify {app} -> {id} ({id})", &AppType::Codex, "p1"),
+            "notify 'codex' -> 'p1' ('p1')"
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn render_switch_hook_quotes_shell_metacharacters() {
+        let command = render_switch_hook("echo {id}", &AppType::Claude, "x'; touch pwned; '$(id)");
+        assert_eq!(command, r"echo 'x'\''; touch pwned; '\''$(id)'");
+        let out = std::process::Command::new("sh")
+            .arg("-c")
+            .arg(&command)
+            .output()
+            .expect("run sh");
+        assert_eq!(
+            String::from_utf8_lossy(&out.stdout).trim_end(),
+            "x'; touch pwned; '$(id)"
+        );
+    }
+}
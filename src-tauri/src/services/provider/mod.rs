@@ -1,7 +1,18 @@
+mod api_key;
 mod endpoints;
 mod gemini_auth;
+mod history;
+mod hook;
 mod live;
+mod models;
+mod order;
+mod resolve;
+mod session;
 mod usage;
+mod validate;
+
+use std::collections::HashMap;
+use std::path::PathBuf;
 
 use indexmap::IndexMap;
 use serde::Deserialize;
@@ -20,9 +31,23 @@ use crate::store::AppState;
 use gemini_auth::GeminiAuthType;
 use live::LiveSnapshot;
 
+pub use endpoints::{pick_fastest_endpoint, EndpointSelection};
+pub use history::parse_time_bound;
+pub use hook::SwitchHookOutput;
+pub use models::ProviderModels;
+pub use session::TemporarySwitch;
+
 /// 供应商相关业务逻辑
 pub struct ProviderService;
 
+/// `provider switch --dry-run` 的预览结果：切换后将写入的一个 live 配置文件
+#[derive(Debug, Clone)]
+pub struct LiveConfigPreview {
+    pub path: PathBuf,
+    /// 完整文件内容（JSON / TOML / .env 文本）
+    pub content: String,
+}
+
 #[cfg(test)]
 fn state_from_config(config: MultiAppConfig) -> AppState {
     AppState {
@@ -408,6 +433,58 @@ mod tests {
         );
     }
 
+    #[test]
+    #[serial]
+    fn preview_switch_merges_common_snippet_without_writing() {
+        let temp_home = TempDir::new().expect("create temp home");
+        let _env = EnvGuard::set_home(temp_home.path());
+        std::fs::create_dir_all(crate::config::get_claude_config_dir())
+            .expect("create ~/.claude (initialized)");
+
+        let mut config = MultiAppConfig::default();
+        config.ensure_app(&AppType::Claude);
+        config.common_config_snippets.claude = Some(r#"{"includeCoAuthoredBy":false}"#.to_string());
+        let state = state_from_config(config);
+
+        for (id, url) in [("p1", "https://one.example"), ("p2", "https://two.example")] {
+            let provider = Provider::with_id(
+                id.to_string(),
+                id.to_string(),
+                json!({ "env": { "ANTHROPIC_BASE_URL": url } }),
+                None,
+            );
+            ProviderService::add(&state, AppType::Claude, provider).expect("add provider");
+        }
+        ProviderService::switch(&state, AppType::Claude, "p1").expect("switch to p1");
+
+        let settings_path = get_claude_settings_path();
+        let before = std::fs::read_to_string(&settings_path).expect("read live settings");
+
+        let previews =
+            ProviderService::preview_switch(&state, AppType::Claude, "p2").expect("preview switch");
+        assert_eq!(previews.len(), 1);
+        assert_eq!(previews[0].path, settings_path);
+
+        let preview: Value = serde_json::from_str(&previews[0].content).expect("preview json");
+        assert_eq!(
+            preview
+                .pointer("/env/ANTHROPIC_BASE_URL")
+                .and_then(Value::as_str),
+            Some("https://two.example")
+        );
+        assert_eq!(
+            preview.get("includeCoAuthoredBy").and_then(Value::as_bool),
+            Some(false)
+        );
+
+        let after = std::fs::read_to_string(&settings_path).expect("read live settings");
+        assert_eq!(before, after, "dry run must not touch the live file");
+        assert_eq!(
+            ProviderService::current(&state, AppType::Claude).expect("current"),
+            "p1"
+        );
+    }
+
     #[test]
     #[serial]
     fn common_config_snippet_can_be_disabled_per_provider_for_claude() {
@@ -1779,7 +1856,98 @@ impl ProviderService {
             };
 
             Ok(((), Some(action)))
-        })
+        })?;
+
+        // 切换历史用于排序与 `provider history`，写入失败不影响切换结果
+        if let Err(e) = state
+            .db
+            .record_provider_switch(app_type.as_str(), provider_id)
+        {
+            log::warn!("记录供应商切换历史失败: {e}");
+        }
+        Ok(())
+    }
+
+    /// 预览切换后写入的 live 配置：与 `switch` 使用同一套合并逻辑（供应商配置 + 通用配置片段），
+    /// 但不修改任何文件
+    pub fn preview_switch(
+        state: &AppState,
+        app_type: AppType,
+        provider_id: &str,
+    ) -> Result<Vec<LiveConfigPreview>, AppError> {
+        let (provider, common_config_snippet) = {
+            let config = state.config.read().map_err(AppError::from)?;
+            let provider = config
+                .get_manager(&app_type)
+                .ok_or_else(|| Self::app_not_found(&app_type))?
+                .providers
+                .get(provider_id)
+                .cloned()
+                .ok_or_else(|| {
+                    AppError::localized(
+                        "provider.not_found",
+                        format!("供应商不存在: {provider_id}"),
+                        format!("Provider not found: {provider_id}"),
+                    )
+                })?;
+            (
+                provider,
+                config.common_config_snippets.get(&app_type).cloned(),
+            )
+        };
+
+        let apply_common_config = provider
+            .meta
+            .as_ref()
+            .and_then(|meta| meta.apply_common_config)
+            .unwrap_or(true);
+        let snippet = common_config_snippet.as_deref();
+        let applied_snippet = snippet.filter(|_| apply_common_config);
+        let pretty = |value: &Value| {
+            serde_json::to_string_pretty(value).map_err(|e| AppError::JsonSerialize { source: e })
+        };
+
+        let mut previews = Vec::new();
+        match app_type {
+            AppType::Claude => {
+                let content = Self::build_claude_live(&provider, applied_snippet)?;
+                previews.push(LiveConfigPreview {
+                    path: get_claude_settings_path(),
+                    content: pretty(&content)?,
+                });
+            }
+            AppType::Codex => {
+                let (config_text, auth) =
+                    Self::build_codex_live(&provider, snippet, apply_common_config)?;
+                previews.push(LiveConfigPreview {
+                    path: get_codex_config_path(),
+                    content: config_text,
+                });
+                if let Some(auth) = auth {
+                    previews.push(LiveConfigPreview {
+                        path: get_codex_auth_path(),
+                        content: pretty(&auth)?,
+                    });
+                }
+            }
+            AppType::Gemini => {
+                use crate::gemini_config::{
+                    get_gemini_env_path, get_gemini_settings_path, serialize_env_file,
+                };
+
+                let (env_map, settings) = Self::build_gemini_live(&provider, applied_snippet)?;
+                previews.push(LiveConfigPreview {
+                    path: get_gemini_env_path(),
+                    content: serialize_env_file(&env_map),
+                });
+                previews.push(LiveConfigPreview {
+                    path: get_gemini_settings_path(),
+                    content: pretty(&settings)?,
+                });
+            }
+        }
+
+        Ok(previews)
     }
 
     /// 从 Codex 的 `config.toml` 中提取当前 provider 的“供应商片段配置”（用于写入到 CC-Switch 的 provider.settings_config.config）。
@@ -1940,7 +2108,10 @@ impl ProviderService {
                 }
 
                 let obj = current.settings_config.as_object_mut().unwrap();
-                if let Some(auth) = auth {
+                if let Some(mut auth) = auth {
+                    if let Some(stored) = obj.get("auth") {
+                        crate::keyring_store::restore_references(&mut auth, stored);
+                    }
                     obj.insert("auth".to_string(), auth);
                 }
                 if let Some(config_snippet) = config_snippet {
@@ -1957,12 +2128,31 @@ impl ProviderService {
         common_config_snippet: Option<&str>,
         apply_common_config: bool,
     ) -> Result<(), AppError> {
-        use toml_edit::{value, Item, Table};
-
         if !crate::sync_policy::should_sync_live(&AppType::Codex) {
             return Ok(());
         }
 
+        let provider = &crate::keyring_store::resolve_provider(provider)?;
+        let (config_text, auth) =
+            Self::build_codex_live(provider, common_config_snippet, apply_common_config)?;
+        crate::config::write_text_file(&get_codex_config_path(), &config_text)?;
+
+        // 只在 auth 非空时写入 auth.json（Codex 0.64+ 使用环境变量，不需要 auth.json）
+        if let Some(auth_value) = auth {
+            write_json_file(&get_codex_auth_path(), &auth_value)?;
+        }
+
+        Ok(())
+    }
+
+    /// 计算切换后的 Codex `config.toml` 文本与 `auth.json` 内容（auth 为空时返回 None），不写任何文件
+    fn build_codex_live(
+        provider: &Provider,
+        common_config_snippet: Option<&str>,
+        apply_common_config: bool,
+    ) -> Result<(String, Option<Value>), AppError> {
+        use toml_edit::{value, Item, Table};
+
         let settings = provider
             .settings_config
             .as_object()
@@ -2126,20 +2316,8 @@ impl ProviderService {
             }
         }
 
-        // 写回 config.toml
-        let new_text = doc.to_string();
-        let config_path = get_codex_config_path();
-        crate::config::write_text_file(&config_path, &new_text)?;
-
-        // 只在 auth 非空时写入 auth.json（Codex 0.64+ 使用环境变量，不需要 auth.json）
-        if !auth_is_empty {
-            if let Some(auth_value) = auth {
-                let auth_path = get_codex_auth_path();
-                write_json_file(&auth_path, auth_value)?;
-            }
-        }
-
-        Ok(())
+        let auth = if auth_is_empty { None } else { auth.cloned() };
+        Ok((doc.to_string(), auth))
     }
 
     /// 将 toml::Value 转换为 toml_edit::Value
@@ -2236,6 +2414,7 @@ impl ProviderService {
         }
         if let Some(manager) = config.get_manager_mut(&AppType::Claude) {
             if let Some(current) = manager.providers.get_mut(&current_id) {
+                crate::keyring_store::restore_references(&mut live, &current.settings_config);
                 current.settings_config = live;
             }
         }
@@ -2287,6 +2466,7 @@ impl ProviderService {
 
         if let Some(manager) = config.get_manager_mut(&AppType::Gemini) {
             if let Some(current) = manager.providers.get_mut(&current_id) {
+                crate::keyring_store::restore_references(&mut live, &current.settings_config);
                 current.settings_config = live;
             }
         }
@@ -2302,7 +2482,17 @@ impl ProviderService {
             return Ok(());
         }
 
-        let settings_path = get_claude_settings_path();
+        let provider = &crate::keyring_store::resolve_provider(provider)?;
+        let content = Self::build_claude_live(provider, common_config_snippet)?;
+        write_json_file(&get_claude_settings_path(), &content)?;
+        Ok(())
+    }
+
+    /// 计算写入 Claude `settings.json` 的内容（通用配置片段 + 供应商配置），不写文件
+    fn build_claude_live(
+        provider: &Provider,
+        common_config_snippet: Option<&str>,
+    ) -> Result<Value, AppError> {
         let mut provider_content = provider.settings_config.clone();
         let _ = Self::normalize_claude_models_in_value(&mut provider_content);
 
@@ -2321,8 +2511,7 @@ impl ProviderService {
             provider_content
         };
 
-        write_json_file(&settings_path, &content_to_write)?;
-        Ok(())
+        Ok(content_to_write)
     }
 
     pub(crate) fn write_gemini_live(
@@ -2344,10 +2533,7 @@ impl ProviderService {
         common_config_snippet: Option<&str>,
         force_sync: bool,
     ) -> Result<(), AppError> {
-        use crate::gemini_config::{
-            get_gemini_settings_path, json_to_env, validate_gemini_settings_strict,
-            write_gemini_env_atomic,
-        };
+        use crate::gemini_config::{get_gemini_settings_path, write_gemini_env_atomic};
 
         // 一次性检测认证类型，避免重复检测
         let auth_type = Self::detect_gemini_auth_type(provider);
@@ -2363,6 +2549,28 @@ impl ProviderService {
             return Ok(());
         }
 
+        let resolved = crate::keyring_store::resolve_provider(provider)?;
+        let (env_map, settings) = Self::build_gemini_live(&resolved, common_config_snippet)?;
+        write_gemini_env_atomic(&env_map)?;
+        write_json_file(&get_gemini_settings_path(), &settings)?;
+
+        match auth_type {
+            GeminiAuthType::GoogleOfficial => Self::ensure_google_oauth_security_flag(provider)?,
+            GeminiAuthType::ApiKey => Self::ensure_api_key_security_flag(provider)?,
+        }
+
+        Ok(())
+    }
+
+    /// 计算切换后的 Gemini `.env` 键值与 `settings.json` 内容，不写文件
+    fn build_gemini_live(
+        provider: &Provider,
+        common_config_snippet: Option<&str>,
+    ) -> Result<(HashMap<String, String>, Value), AppError> {
+        use crate::gemini_config::{
+            get_gemini_settings_path, json_to_env, validate_gemini_settings_strict,
+        };
+
         let provider_content = provider.settings_config.clone();
         let content_to_write = if let Some(snippet) = common_config_snippet {
             let snippet = snippet.trim();
@@ -2432,30 +2640,14 @@ impl ProviderService {
             }
         }
 
-        match auth_type {
-            GeminiAuthType::GoogleOfficial => {
-                // Google 官方使用 OAuth，清空 env
-                env_map.clear();
-                write_gemini_env_atomic(&env_map)?;
-            }
-            GeminiAuthType::ApiKey => {
-                // API Key 供应商（所有第三方服务）
-                // 统一处理：验证配置 + 写入 .env 文件
-                validate_gemini_settings_strict(&content_to_write)?;
-                write_gemini_env_atomic(&env_map)?;
-            }
-        }
-
-        if let Some(config_value) = config_to_write {
-            write_json_file(&settings_path, &config_value)?;
+        match Self::detect_gemini_auth_type(provider) {
+            // Google 官方使用 OAuth，清空 env
+            GeminiAuthType::GoogleOfficial => env_map.clear(),
+            // API Key 供应商（所有第三方服务）需通过严格校验
+            GeminiAuthType::ApiKey => validate_gemini_settings_strict(&content_to_write)?,
         }
 
-        match auth_type {
-            GeminiAuthType::GoogleOfficial => Self::ensure_google_oauth_security_flag(provider)?,
-            GeminiAuthType::ApiKey => Self::ensure_api_key_security_flag(provider)?,
-        }
-
-        Ok(())
+        Ok((env_map, config_to_write.unwrap_or_else(|| json!({}))))
     }
 
     fn write_live_snapshot(
@@ -2623,6 +2815,50 @@ impl ProviderService {
 
         state.save()
     }
+
+    /// 删除某应用下的全部供应商，返回删除数量。
+    ///
+    /// 当前供应商正应用于 live 配置，除非 `force`，否则拒绝删除；强制时会先清空 current 指针。
+    pub fn delete_all(state: &AppState, app_type: AppType, force: bool) -> Result<usize, AppError> {
+        let (ids, current) = {
+            let config = state.config.read().map_err(AppError::from)?;
+            let manager = config
+                .get_manager(&app_type)
+                .ok_or_else(|| Self::app_not_found(&app_type))?;
+            (
+                manager.providers.keys().cloned().collect::<Vec<_>>(),
+                manager.current.clone(),
+            )
+        };
+
+        if ids.contains(&current) {
+            if !force {
+                return Err(AppError::localized(
+                    "provider.delete_all.current",
+                    format!("供应商 '{current}' 正在使用中，使用 --force 才能一并删除"),
+                    format!(
+                        "Provider '{current}' is currently in use; pass --force to delete it as well"
+                    ),
+                ));
+            }
+
+            {
+                let mut config = state.config.write().map_err(AppError::from)?;
+                let manager = config
+                    .get_manager_mut(&app_type)
+                    .ok_or_else(|| Self::app_not_found(&app_type))?;
+                manager.current.clear();
+            }
+            state.save()?;
+        }
+
+        for id in &ids {
+            Self::delete(state, app_type.clone(), id)?;
+        }
+        Self::clear_temporary_switch(&app_type)?;
+
+        Ok(ids.len())
+    }
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -0,0 +1,158 @@
+use serde_json::{json, Value};
+
+use crate::app_config::AppType;
+use crate::error::AppError;
+use crate::provider::Provider;
+use crate::store::AppState;
+
+use super::ProviderService;
+
+/// 供应商模型映射（与 deeplink 携带的字段一致）
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ProviderModels {
+    pub model: Option<String>,
+    pub haiku_model: Option<String>,
+    pub sonnet_model: Option<String>,
+    pub opus_model: Option<String>,
+}
+
+impl ProviderModels {
+    pub fn is_empty(&self) -> bool {
+        self.model.is_none()
+            && self.haiku_model.is_none()
+            && self.sonnet_model.is_none()
+            && self.opus_model.is_none()
+    }
+
+    fn has_tier_models(&self) -> bool {
+        self.haiku_model.is_some() || self.sonnet_model.is_some() || self.opus_model.is_some()
+    }
+
+    /// 从供应商配置中读取当前模型映射
+    pub fn from_settings(app_type: &AppType, settings_config: &Value) -> Self {
+        let env_str = |key: &str| {
+            settings_config
+                .get("env")
+                .and_then(|env| env.get(key))
+                .and_then(|v| v.as_str())
+                .map(|s| s.to_string())
+        };
+
+        match app_type {
+            AppType::Claude => Self {
+                model: env_str("ANTHROPIC_MODEL"),
+                haiku_model: env_str("ANTHROPIC_DEFAULT_HAIKU_MODEL"),
+                sonnet_model: env_str("ANTHROPIC_DEFAULT_SONNET_MODEL"),
+                opus_model: env_str("ANTHROPIC_DEFAULT_OPUS_MODEL"),
+            },
+            AppType::Codex => Self {
+                model: settings_config
+                    .get("config")
+                    .and_then(|v| v.as_str())
+                    .and_then(|s| s.parse::<toml_edit::DocumentMut>().ok())
+                    .and_then(|doc| doc.get("model").and_then(|v| v.as_str()).map(String::from)),
+                ..Self::default()
+            },
+            AppType::Gemini => Self {
+                model: env_str("GEMINI_MODEL"),
+                ..Self::default()
+            },
+        }
+    }
+}
+
+impl ProviderService {
+    /// 原地修改供应商的模型字段；若为当前供应商，会同步写入 live 配置
+    pub fn set_models(
+        state: &AppState,
+        app_type: AppType,
+        provider_id: &str,
+        models: &ProviderModels,
+    ) -> Result<Provider, AppError> {
+        if models.is_empty() {
+            return Err(AppError::InvalidInput(
+                "At least one of --model/--haiku/--sonnet/--opus is required".to_string(),
+            ));
+        }
+        if !matches!(app_type, AppType::Claude) && models.has_tier_models() {
+            return Err(AppError::InvalidInput(format!(
+                "--haiku/--sonnet/--opus are only supported for Claude (got {})",
+                app_type.as_str()
+            )));
+        }
+
+        let mut provider = {
+            let config = state.config.read().map_err(AppError::from)?;
+            let manager = config
+                .get_manager(&app_type)
+                .ok_or_else(|| Self::app_not_found(&app_type))?;
+            manager.providers.get(provider_id).cloned().ok_or_else(|| {
+                AppError::localized(
+                    "provider.not_found",
+                    format!("供应商不存在: {provider_id}"),
+                    format!("Provider not found: {provider_id}"),
+                )
+            })?
+        };
+
+        match app_type {
+            AppType::Claude => {
+                let env = Self::env_object_mut(&mut provider.settings_config)?;
+                for (key, value) in [
+                    ("ANTHROPIC_MODEL", &models.model),
+                    ("ANTHROPIC_DEFAULT_HAIKU_MODEL", &models.haiku_model),
+                    ("ANTHROPIC_DEFAULT_SONNET_MODEL", &models.sonnet_model),
+                    ("ANTHROPIC_DEFAULT_OPUS_MODEL", &models.opus_model),
+                ] {
+                    if let Some(value) = value {
+                        env.insert(key.to_string(), json!(value));
+                    }
+                }
+            }
+            AppType::Codex => {
+                if let Some(model) = &models.model {
+                    let raw = provider
+                        .settings_config
+                        .get("config")
+                        .and_then(|v| v.as_str())
+                        .unwrap_or_default();
+                    let mut doc = raw
+                        .parse::<toml_edit::DocumentMut>()
+                        .map_err(|e| AppError::Config(format!("Invalid Codex config.toml: {e}")))?;
+                    doc["model"] = toml_edit::value(model.as_str());
+                    let obj = provider.settings_config.as_object_mut().ok_or_else(|| {
+                        AppError::Config("Codex provider settings must be an object".to_string())
+                    })?;
+                    obj.insert("config".to_string(), json!(doc.to_string()));
+                }
+            }
+            AppType::Gemini => {
+                if let Some(model) = &models.model {
+                    let env = Self::env_object_mut(&mut provider.settings_config)?;
+                    env.insert("GEMINI_MODEL".to_string(), json!(model));
+                }
+            }
+        }
+
+        Self::update(state, app_type.clone(), provider)?;
+
+        // update() 会归一化 Claude 模型键，因此返回持久化后的结果
+        let config = state.config.read().map_err(AppError::from)?;
+        config
+            .get_manager(&app_type)
+            .and_then(|manager| manager.providers.get(provider_id).cloned())
+            .ok_or_else(|| Self::app_not_found(&app_type))
+    }
+
+    pub(super) fn env_object_mut(
+        settings_config: &mut Value,
+    ) -> Result<&mut serde_json::Map<String, Value>, AppError> {
+        let obj = settings_config
+            .as_object_mut()
+            .ok_or_else(|| AppError::Config("Provider settings must be an object".to_string()))?;
+        obj.entry("env")
+            .or_insert_with(|| json!({}))
+            .as_object_mut()
+            .ok_or_else(|| AppError::Config("Provider env must be an object".to_string()))
+    }
+}
@@ -0,0 +1,69 @@
+use indexmap::IndexMap;
+
+use crate::app_config::AppType;
+use crate::error::AppError;
+use crate::provider::Provider;
+use crate::store::AppState;
+
+use super::ProviderService;
+
+impl ProviderService {
+    /// 默认显示顺序：有 sort_index 的在前（升序），其余按创建时间
+    pub fn display_order(providers: &IndexMap<String, Provider>) -> Vec<String> {
+        let mut ids: Vec<&String> = providers.keys().collect();
+        ids.sort_by(|a, b| {
+            let (pa, pb) = (&providers[*a], &providers[*b]);
+            match (pa.sort_index, pb.sort_index) {
+                (Some(idx_a), Some(idx_b)) => idx_a.cmp(&idx_b),
+                (Some(_), None) => std::cmp::Ordering::Less,
+                (None, Some(_)) => std::cmp::Ordering::Greater,
+                (None, None) => pa.created_at.cmp(&pb.created_at),
+            }
+        });
+        ids.into_iter().cloned().collect()
+    }
+
+    /// 持久化自定义显示顺序：`ids` 依次排在最前，未列出的供应商按原顺序追加。
+    /// 返回最终顺序
+    pub fn reorder(
+        state: &AppState,
+        app_type: AppType,
+        ids: &[String],
+    ) -> Result<Vec<String>, AppError> {
+        let order = {
+            let mut config = state.config.write().map_err(AppError::from)?;
+            let manager = config
+                .get_manager_mut(&app_type)
+                .ok_or_else(|| Self::app_not_found(&app_type))?;
+
+            let mut order: Vec<String> = Vec::with_capacity(manager.providers.len());
+            for id in ids.iter().map(|id| id.trim()).filter(|id| !id.is_empty()) {
+                if !manager.providers.contains_key(id) {
+                    return Err(AppError::localized(
+                        "provider.not_found",
+                        format!("供应商不存在: {id}"),
+                        format!("Provider not found: {id}"),
+                    ));
+                }
+                if !order.iter().any(|existing| existing == id) {
+                    order.push(id.to_string());
+                }
+            }
+            for id in Self::display_order(&manager.providers) {
+                if !order.contains(&id) {
+                    order.push(id);
+                }
+            }
+
+            for (position, id) in order.iter().enumerate() {
+                if let Some(provider) = manager.providers.get_mut(id) {
+                    provider.sort_index = Some(position);
+                }
+            }
+            order
+        };
+
+        state.save()?;
+        Ok(order)
+    }
+}
@@ -0,0 +1,56 @@
+use crate::app_config::AppType;
+use crate::error::AppError;
+use crate::store::AppState;
+
+use super::ProviderService;
+
+impl ProviderService {
+    /// 将命令行输入解析为供应商 id：优先精确匹配 id，其次匹配唯一的名称（不区分大小写）。
+    ///
+    /// 多个供应商同名时报错并列出候选 id。
+    pub fn resolve_provider(
+        state: &AppState,
+        app_type: AppType,
+        input: &str,
+    ) -> Result<String, AppError> {
+        let input = input.trim();
+        let config = state.config.read().map_err(AppError::from)?;
+        let manager = config
+            .get_manager(&app_type)
+            .ok_or_else(|| Self::app_not_found(&app_type))?;
+
+        if manager.providers.contains_key(input) {
+            return Ok(input.to_string());
+        }
+
+        let matches: Vec<&String> = Self::display_order(&manager.providers)
+            .iter()
+            .filter_map(|id| manager.providers.get_key_value(id))
+            .filter(|(_, provider)| provider.name.trim().eq_ignore_ascii_case(input))
+            .map(|(id, _)| id)
+            .collect();
+        match matches.as_slice() {
+            [id] => Ok(id.to_string()),
+            [] => Err(AppError::localized(
+                "provider.not_found",
+                format!("供应商不存在: {input}（{}）", app_type.as_str()),
+                format!(
+                    "Provider not found: {input} (no id or name matches for {})",
+                    app_type.as_str()
+                ),
+            )),
+            ids => {
+                let ids = ids
+                    .iter()
+                    .map(|id| id.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                Err(AppError::localized(
+                    "provider.ambiguous_name",
+                    format!("名称 '{input}' 对应多个供应商，请改用 id: {ids}"),
+                    format!("Name '{input}' matches several providers; use an id instead: {ids}"),
+                ))
+            }
+        }
+    }
+}
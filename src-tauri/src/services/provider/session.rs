@@ -0,0 +1,116 @@
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use crate::app_config::AppType;
+use crate::config::{delete_file, get_app_config_dir, read_json_file, write_json_file};
+use crate::error::AppError;
+use crate::store::AppState;
+
+use super::ProviderService;
+
+/// 临时切换记录（每个应用一条），用于 `provider restore-session` 回滚
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TemporarySwitch {
+    /// 临时切换前的供应商（为空表示之前没有当前供应商）
+    pub previous_id: String,
+    pub temporary_id: String,
+    pub created_at: i64,
+}
+
+fn marker_path() -> PathBuf {
+    get_app_config_dir().join("temporary-switch.json")
+}
+
+fn load_markers() -> Result<BTreeMap<String, TemporarySwitch>, AppError> {
+    let path = marker_path();
+    if !path.exists() {
+        return Ok(BTreeMap::new());
+    }
+    read_json_file(&path)
+}
+
+fn save_markers(markers: &BTreeMap<String, TemporarySwitch>) -> Result<(), AppError> {
+    let path = marker_path();
+    if markers.is_empty() {
+        return delete_file(&path);
+    }
+    write_json_file(&path, markers)
+}
+
+impl ProviderService {
+    /// 切换供应商并记录切换前的供应商；连续临时切换时保留最初的供应商
+    pub fn switch_temporary(
+        state: &AppState,
+        app_type: AppType,
+        provider_id: &str,
+    ) -> Result<TemporarySwitch, AppError> {
+        let mut markers = load_markers()?;
+        let previous_id = match markers.get(app_type.as_str()) {
+            Some(existing) => existing.previous_id.clone(),
+            None => Self::current(state, app_type.clone())?,
+        };
+
+        Self::switch(state, app_type.clone(), provider_id)?;
+
+        let marker = TemporarySwitch {
+            previous_id,
+            temporary_id: provider_id.to_string(),
+            created_at: chrono::Utc::now().timestamp_millis(),
+        };
+        markers.insert(app_type.as_str().to_string(), marker.clone());
+        save_markers(&markers)?;
+        Ok(marker)
+    }
+
+    /// 当前应用的临时切换记录
+    pub fn temporary_switch(app_type: &AppType) -> Result<Option<TemporarySwitch>, AppError> {
+        Ok(load_markers()?.remove(app_type.as_str()))
+    }
+
+    /// 丢弃临时切换记录（例如用户随后做了永久切换）
+    pub fn clear_temporary_switch(app_type: &AppType) -> Result<(), AppError> {
+        let mut markers = load_markers()?;
+        if markers.remove(app_type.as_str()).is_some() {
+            save_markers(&markers)?;
+        }
+        Ok(())
+    }
+
+    /// 回滚临时切换；返回恢复到的供应商 ID（无记录时返回 None）
+    pub fn restore_session(
+        state: &AppState,
+        app_type: AppType,
+    ) -> Result<Option<String>, AppError> {
+        let mut markers = load_markers()?;
+        let Some(marker) = markers.get(app_type.as_str()).cloned() else {
+            return Ok(None);
+        };
+
+        if !marker.previous_id.is_empty()
+            && marker.previous_id != Self::current(state, app_type.clone())?
+        {
+            let exists = Self::list(state, app_type.clone())?.contains_key(&marker.previous_id);
+            if !exists {
+                return Err(AppError::localized(
+                    "provider.restore_session_missing",
+                    format!(
+                        "无法恢复：临时切换前的供应商已不存在: {}",
+                        marker.previous_id
+                    ),
+                    format!(
+                        "Cannot restore: provider '{}' (active before the temporary switch) no longer exists",
+                        marker.previous_id
+                    ),
+                ));
+            }
+            Self::switch(state, app_type.clone(), &marker.previous_id)?;
+        }
+
+        markers.remove(app_type.as_str());
+        save_markers(&markers)?;
+        Ok(Some(marker.previous_id))
+    }
+}
@@ -188,7 +188,7 @@ impl ProviderService {
     }
 
     fn extract_api_key(provider: &Provider, app_type: &AppType) -> Result<String, AppError> {
-        match app_type {
+        let key = match app_type {
             AppType::Claude => {
                 let env = provider
                     .settings_config
@@ -251,10 +251,15 @@ impl ProviderService {
                     )
                 })
             }
-        }
+        }?;
+        // 支持 keyring:<名称> 引用
+        crate::keyring_store::resolve_secret(&key)
     }
 
-    fn extract_base_url(provider: &Provider, app_type: &AppType) -> Result<String, AppError> {
+    pub(super) fn extract_base_url(
+        provider: &Provider,
+        app_type: &AppType,
+    ) -> Result<String, AppError> {
         match app_type {
             AppType::Claude => provider
                 .settings_config
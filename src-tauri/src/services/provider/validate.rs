@@ -0,0 +1,139 @@
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::app_config::AppType;
+use crate::deeplink::validate_url;
+use crate::error::AppError;
+use crate::provider::Provider;
+use crate::store::AppState;
+
+use super::api_key::api_key_slot;
+use super::gemini_auth::GeminiAuthType;
+use super::{ProviderModels, ProviderService};
+
+/// `provider validate` 中单个供应商的检查结果
+#[derive(Debug, Clone, Serialize)]
+pub struct ProviderValidation {
+    pub id: String,
+    pub name: String,
+    pub issues: Vec<String>,
+}
+
+impl ProviderValidation {
+    pub fn is_valid(&self) -> bool {
+        self.issues.is_empty()
+    }
+}
+
+impl ProviderService {
+    /// 按显示顺序检查某应用下的全部供应商
+    pub fn validate_providers(
+        state: &AppState,
+        app_type: AppType,
+    ) -> Result<Vec<ProviderValidation>, AppError> {
+        let config = state.config.read().map_err(AppError::from)?;
+        let manager = config
+            .get_manager(&app_type)
+            .ok_or_else(|| Self::app_not_found(&app_type))?;
+        Ok(Self::display_order(&manager.providers)
+            .into_iter()
+            .map(|id| {
+                let provider = &manager.providers[&id];
+                ProviderValidation {
+                    name: provider.name.clone(),
+                    issues: Self::validate_provider(provider, &app_type),
+                    id,
+                }
+            })
+            .collect())
+    }
+
+    /// 语义检查：端点非空且为合法 http(s) URL、存在 token（或 keyring 引用）、
+    /// Codex 须设置 model。官方登录类供应商不需要端点与 token
+    pub fn validate_provider(provider: &Provider, app_type: &AppType) -> Vec<String> {
+        let mut issues = Vec::new();
+        let login_based = provider.category.as_deref() == Some("official")
+            || (*app_type == AppType::Gemini
+                && matches!(
+                    Self::detect_gemini_auth_type(provider),
+                    GeminiAuthType::GoogleOfficial
+                ));
+
+        if !login_based {
+            match Self::extract_base_url(provider, app_type) {
+                Ok(url) if url.trim().is_empty() => issues.push("endpoint is empty".to_string()),
+                Ok(url) => {
+                    if let Err(e) = validate_url(url.trim(), "base_url") {
+                        issues.push(e.to_string());
+                    }
+                }
+                Err(_) => issues.push("endpoint is missing".to_string()),
+            }
+
+            let (section, keys) = api_key_slot(app_type);
+            let has_token = provider
+                .settings_config
+                .get(section)
+                .and_then(Value::as_object)
+                .is_some_and(|section| {
+                    keys.iter().any(|key| {
+                        section
+                            .get(*key)
+                            .and_then(Value::as_str)
+                            .is_some_and(|v| !v.trim().is_empty())
+                    })
+                });
+            if !has_token {
+                issues.push(format!("token is missing (expected {section}.{})", keys[0]));
+            }
+        }
+
+        if *app_type == AppType::Codex
+            && ProviderModels::from_settings(app_type, &provider.settings_config)
+                .model
+                .is_none_or(|m| m.trim().is_empty())
+        {
+            issues.push("model is missing from config.toml".to_string());
+        }
+
+        issues
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn provider(settings: Value) -> Provider {
+        Provider::with_id("p".to_string(), "P".to_string(), settings, None)
+    }
+
+    #[test]
+    fn validate_provider_reports_endpoint_token_and_model_issues() {
+        let ok = provider(json!({"env": {
+            "ANTHROPIC_BASE_URL": "https://api.example.com",
+            "ANTHROPIC_AUTH_TOKEN": "keyring:claude-prod"
+        }}));
+        assert!(ProviderService::validate_provider(&ok, &AppType::Claude).is_empty());
+
+        let bad = provider(json!({"env": {"ANTHROPIC_BASE_URL": "ftp://example.com"}}));
+        let issues = ProviderService::validate_provider(&bad, &AppType::Claude);
+        assert_eq!(issues.len(), 2, "{issues:?}");
+        assert!(issues[0].contains("scheme"));
+        assert!(issues[1].contains("ANTHROPIC_AUTH_TOKEN"));
+
+        let codex = provider(json!({
+            "auth": {"OPENAI_API_KEY": "sk-test"},
+            "config": "base_url = \"https://api.example.com/v1\"\n"
+        }));
+        assert_eq!(
+            ProviderService::validate_provider(&codex, &AppType::Codex),
+            vec!["model is missing from config.toml".to_string()]
+        );
+
+        let mut official = provider(json!({"env": {}}));
+        official.category = Some("official".to_string());
+        assert!(ProviderService::validate_provider(&official, &AppType::Claude).is_empty());
+    }
+}
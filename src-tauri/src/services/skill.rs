@@ -3,14 +3,18 @@
 //! v3.10.0+ 统一管理架构（与上游一致）：
 //! - SSOT（单一事实源）：`~/.cc-switch/skills/`
 //! - 数据库存储安装记录、启用状态与仓库列表（`~/.cc-switch/cc-switch.db`）
+//! - 仓库解压缓存（`~/.cc-switch/cache/skill-repos/`），命中时安装无需联网
 
 use chrono::{DateTime, Utc};
 use futures::future::join_all;
+use futures::stream::{self, StreamExt};
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
 use tokio::time::timeout;
 
 use crate::app_config::AppType;
@@ -18,13 +22,39 @@ pub use crate::app_config::{InstalledSkill, SkillApps, UnmanagedSkill};
 use crate::config::get_app_config_dir;
 use crate::database::Database;
 use crate::error::{format_skill_error, AppError};
+use crate::util::archive::{self, ArchiveKind, ExtractLimits};
 
 const SKILLS_INDEX_VERSION: u32 = 1;
 
+/// Setting key marking the one-time directory casing migration as done.
+const CASING_MIGRATION_KEY: &str = "skills_casing_normalized";
+
+/// Base delay for exponential backoff between download retries.
+const DOWNLOAD_BACKOFF_BASE_MS: u64 = 500;
+/// Upper bound for a single backoff delay (including an honored `Retry-After`).
+const DOWNLOAD_BACKOFF_MAX_MS: u64 = 30_000;
+/// Size guard for skill archives downloaded from an arbitrary URL.
+const MAX_SKILL_ARCHIVE_BYTES: u64 = 200 * 1024 * 1024;
+/// Entry and extracted-size limits for those archives.
+const SKILL_ARCHIVE_LIMITS: ExtractLimits = ExtractLimits {
+    max_entries: 20_000,
+    max_bytes: 1024 * 1024 * 1024,
+};
+
 fn default_skills_index_version() -> u32 {
     SKILLS_INDEX_VERSION
 }
 
+/// A fetched skill source: the temp download dir to remove afterwards (None when served
+/// from the repo cache) and the skill dir inside it.
+type FetchedSource = (Option<PathBuf>, PathBuf);
+
+/// Serializes the index / SSOT phase of installs running concurrently in one process.
+fn install_lock() -> &'static futures::lock::Mutex<()> {
+    static LOCK: std::sync::OnceLock<futures::lock::Mutex<()>> = std::sync::OnceLock::new();
+    LOCK.get_or_init(|| futures::lock::Mutex::new(()))
+}
+
 // ============================================================================
 // Legacy (v2) store structures - kept for backward compatibility
 // ============================================================================
@@ -40,6 +70,9 @@ pub struct SkillRepo {
     pub branch: String,
     /// 是否启用
     pub enabled: bool,
+    /// 私有仓库的访问令牌引用（`keyring:<名称>`），令牌本身只保存在系统钥匙串
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token: Option<String>,
 }
 
 /// Legacy install state: directory -> installed timestamp (Claude-only era).
@@ -72,24 +105,28 @@ impl Default for SkillStore {
                     name: "skills".to_string(),
                     branch: "main".to_string(),
                     enabled: true,
+                    token: None,
                 },
                 SkillRepo {
                     owner: "ComposioHQ".to_string(),
                     name: "awesome-claude-skills".to_string(),
                     branch: "master".to_string(),
                     enabled: true,
+                    token: None,
                 },
                 SkillRepo {
                     owner: "cexll".to_string(),
                     name: "myclaude".to_string(),
                     branch: "master".to_string(),
                     enabled: true,
+                    token: None,
                 },
                 SkillRepo {
                     owner: "JimLiu".to_string(),
                     name: "baoyu-skills".to_string(),
                     branch: "main".to_string(),
                     enabled: true,
+                    token: None,
                 },
             ],
         }
@@ -113,6 +150,89 @@ pub enum SyncMethod {
     Copy,
 }
 
+/// SSOT 中没有索引记录的孤立目录（`skills prune` 使用）
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OrphanedSkillDir {
+    pub directory: String,
+    pub path: PathBuf,
+    pub size_bytes: u64,
+    /// 仍有符号链接指向该目录的应用；这些目录不会被删除
+    pub referenced_by: Vec<AppType>,
+}
+
+/// `skills stats` summary.
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SkillStats {
+    pub installed: usize,
+    /// Installed skills enabled per app (keyed by app name).
+    pub enabled_by_app: BTreeMap<String, usize>,
+    pub from_repo: usize,
+    /// Installed from a non-GitHub archive URL.
+    pub from_archive: usize,
+    /// Imported from app dirs or otherwise without a remote source.
+    pub local: usize,
+    pub ssot_bytes: u64,
+    pub repos_configured: usize,
+    pub repos_enabled: usize,
+}
+
+/// Progress event of a long-running skill operation (`skills install --progress json`).
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum SkillProgress {
+    Start {
+        skill: String,
+    },
+    /// Repo download progress; `pct` is absent when the server sends no length.
+    Download {
+        skill: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pct: Option<u8>,
+        bytes: u64,
+    },
+    Done {
+        skill: String,
+        directory: String,
+        outcome: InstallOutcome,
+    },
+    Failed {
+        skill: String,
+        error: String,
+    },
+}
+
+/// Receives [`SkillProgress`] events; called from the install tasks as they proceed.
+pub type ProgressSink = Arc<dyn Fn(SkillProgress) + Send + Sync>;
+
+/// Without a content length, a download event is reported every this many bytes.
+const PROGRESS_BYTES_STEP: u64 = 256 * 1024;
+
+/// Error key for a downloaded archive that does not match `--sha256`.
+const CHECKSUM_MISMATCH_KEY: &str = "skills.checksum_mismatch";
+
+/// Error key for an HTTP 401 on a repo download (missing, expired or under-scoped token).
+const UNAUTHORIZED_KEY: &str = "skills.repo_unauthorized";
+
+/// `skills normalize`: one group of names that differ only by case.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SkillCasingFix {
+    /// Directory name kept in the index, the SSOT and app dirs.
+    pub canonical: String,
+    /// Other spellings found (index keys, SSOT or app dirs), consolidated into `canonical`.
+    pub merged: Vec<String>,
+    /// Duplicate index records folded into the canonical one.
+    pub records_merged: usize,
+    /// Apps whose skill dir was re-synced under the canonical name.
+    pub resynced: Vec<AppType>,
+    /// SSOT spellings whose content differs from the kept copy (distinct skills, not duplicates).
+    pub differing: Vec<String>,
+    /// Where removed directories were moved; nothing is deleted without a backup.
+    pub backup_dir: Option<PathBuf>,
+}
+
 /// skills.json (SSOT index; no DB).
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -186,6 +306,66 @@ pub struct Skill {
     pub repo_branch: Option<String>,
 }
 
+/// Failure of a single archive download attempt.
+enum DownloadFailure {
+    /// Non-success HTTP status (429/5xx are retried, everything else is permanent).
+    Status {
+        status: u16,
+        retry_after: Option<std::time::Duration>,
+    },
+    /// Request-level failure; timeouts and connection errors are retried.
+    Network { message: String, transient: bool },
+    /// Anything after the download itself (bad archive, IO) is never retried.
+    Other(AppError),
+}
+
+impl DownloadFailure {
+    fn is_transient(&self) -> bool {
+        match self {
+            Self::Status { status, .. } => *status == 429 || (500..600).contains(status),
+            Self::Network { transient, .. } => *transient,
+            Self::Other(_) => false,
+        }
+    }
+
+    fn from_reqwest(err: reqwest::Error) -> Self {
+        Self::Network {
+            transient: err.is_timeout() || err.is_connect() || err.is_body(),
+            message: crate::http_client::describe_error(&err),
+        }
+    }
+
+    fn into_app_error(self, attempts: u32) -> AppError {
+        match self {
+            Self::Status { status: 401, .. } => AppError::localized(
+                UNAUTHORIZED_KEY,
+                "下载失败: HTTP 401，仓库令牌缺失、已过期或无权访问；可用 `cc-switch skills repo add <owner/name> --private` 重新设置",
+                "Download failed: HTTP 401, the repo token is missing, expired or lacks access; reset it with `cc-switch skills repo add <owner/name> --private`",
+            ),
+            Self::Status { status, .. } => {
+                let status = status.to_string();
+                let attempts = attempts.to_string();
+                AppError::Message(format_skill_error(
+                    "DOWNLOAD_FAILED",
+                    &[("status", status.as_str()), ("attempts", attempts.as_str())],
+                    match status.as_str() {
+                        "403" => Some("http403"),
+                        "404" => Some("http404"),
+                        "429" => Some("http429"),
+                        _ => Some("checkNetwork"),
+                    },
+                ))
+            }
+            Self::Network { message, .. } => AppError::localized(
+                "skills.download_failed",
+                format!("下载失败（共尝试 {attempts} 次）: {message}"),
+                format!("Download failed after {attempts} attempt(s): {message}"),
+            ),
+            Self::Other(err) => err,
+        }
+    }
+}
+
 /// Skill metadata extracted from SKILL.md YAML front matter.
 #[derive(Debug, Clone, Deserialize)]
 pub struct SkillMetadata {
@@ -193,19 +373,140 @@ pub struct SkillMetadata {
     pub description: Option<String>,
 }
 
+/// Options for [`SkillService::install_with_options`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct InstallOptions {
+    /// On a directory conflict with another repo's skill, install as `<directory>-<owner>`.
+    pub force: bool,
+    /// Require `SKILL.md` with parseable front matter and a `name`.
+    pub strict: bool,
+    /// Only stage into the SSOT: record the skill with every app disabled and skip syncing.
+    pub no_enable: bool,
+}
+
+/// A resolved install spec from [`SkillService::plan_install`].
+#[derive(Debug, Clone)]
+pub struct InstallPlan {
+    pub spec: String,
+    /// SSOT directory the skill would be installed under.
+    pub directory: String,
+    /// `directory` is `<directory>-<owner>` because of a conflict (`force`).
+    pub renamed: bool,
+    discoverable: DiscoverableSkill,
+}
+
+/// What [`SkillService::install_with_outcome`] did.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum InstallOutcome {
+    /// Freshly installed under its own directory name.
+    Installed,
+    /// Installed as `<directory>-<owner>` because of a directory conflict (`force`).
+    Renamed,
+    /// Already installed; only enabled and synced for the app.
+    AlreadyInstalled,
+}
+
+/// Per-spec result of [`SkillService::install_many`].
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BulkInstallResult {
+    pub spec: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub directory: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub outcome: Option<InstallOutcome>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Where [`SkillService::read_readme`] found the document.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SkillReadmeSource {
+    /// Installed copy in the SSOT.
+    Installed,
+    /// Cached repo archive.
+    Cache,
+    /// Fetched from `raw.githubusercontent.com`.
+    Remote,
+}
+
+/// A skill's `SKILL.md` (or `README.md`) content.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SkillReadme {
+    pub key: String,
+    pub file_name: String,
+    pub source: SkillReadmeSource,
+    pub readme_url: Option<String>,
+    pub content: String,
+}
+
+/// Repo manifest shared by `skills repos import` / `export`.
+///
+/// JSON files may also be a bare array of repos; TOML files use `[[repos]]` tables.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RepoManifest {
+    pub repos: Vec<SkillRepo>,
+}
+
+/// Result of [`SkillService::import_repos`], as `owner/name` lists.
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RepoImportReport {
+    pub added: Vec<String>,
+    /// Already configured; branch or enabled flag taken from the manifest.
+    pub merged: Vec<String>,
+    pub unchanged: Vec<String>,
+    /// Listed more than once in the manifest (the last entry wins).
+    pub duplicates: Vec<String>,
+}
+
+/// Result of [`SkillService::test_repo`].
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RepoTestReport {
+    pub owner: String,
+    pub name: String,
+    pub branch: String,
+    /// Scanned from the repo cache instead of a fresh download.
+    pub from_cache: bool,
+    pub skills: Vec<DiscoverableSkill>,
+}
+
+/// Doc files tried in order when reading a skill's readme.
+const README_FILES: [&str; 2] = ["SKILL.md", "README.md"];
+
 // ============================================================================
 // SkillService
 // ============================================================================
 
 pub struct SkillService {
     http_client: Client,
+    /// 单个仓库下载（含重试）的总超时
+    download_timeout: std::time::Duration,
+    progress: Option<ProgressSink>,
+    /// Expected SHA-256 of downloaded archives (lowercase hex).
+    expected_sha256: Option<String>,
+    /// Query for the external discovery command; `None` keeps it disabled.
+    external_query: Option<String>,
 }
 
 impl SkillService {
     pub fn new() -> Result<Self, AppError> {
-        let http_client = Client::builder()
-            .user_agent("cc-switch")
-            .timeout(std::time::Duration::from_secs(10))
+        Self::with_timeout(None)
+    }
+
+    /// `timeout_secs` 覆盖本次的 HTTP 请求与仓库下载超时；未指定时使用设置中的值
+    pub fn with_timeout(timeout_secs: Option<u64>) -> Result<Self, AppError> {
+        let http_timeout =
+            timeout_secs.unwrap_or_else(crate::settings::get_skill_http_timeout_secs);
+        let download_timeout =
+            timeout_secs.unwrap_or_else(crate::settings::get_skill_download_timeout_secs);
+
+        let http_client = crate::http_client::client_builder()?
+            .timeout(std::time::Duration::from_secs(http_timeout))
             .build()
             .map_err(|e| {
                 AppError::localized(
@@ -215,7 +516,73 @@ impl SkillService {
                 )
             })?;
 
-        Ok(Self { http_client })
+        Ok(Self {
+            http_client,
+            download_timeout: std::time::Duration::from_secs(download_timeout),
+            progress: None,
+            expected_sha256: None,
+            external_query: None,
+        })
+    }
+
+    /// Report progress events of installs and repo downloads to `sink`.
+    pub fn with_progress(mut self, sink: ProgressSink) -> Self {
+        self.progress = Some(sink);
+        self
+    }
+
+    /// Require the installed skill's archive to match `sha256` before extraction. Installs
+    /// then always download instead of using the repo cache; repos downloaded only to
+    /// resolve the spec are not checked.
+    pub fn with_sha256(mut self, sha256: &str) -> Result<Self, AppError> {
+        self.expected_sha256 = Some(crate::util::hash::normalize_sha256(sha256)?);
+        Ok(self)
+    }
+
+    /// Merge the `skillDiscoveryCommand` catalog into discovery, passing `query` on its stdin.
+    pub fn with_external_discovery(mut self, query: Option<&str>) -> Self {
+        self.external_query = Some(query.unwrap_or_default().trim().to_string());
+        self
+    }
+
+    async fn discover_external(&self) -> Result<Vec<DiscoverableSkill>, AppError> {
+        let Some(query) = &self.external_query else {
+            return Ok(Vec::new());
+        };
+        let command = crate::settings::get_skill_discovery_command().ok_or_else(|| {
+            AppError::localized(
+                "skills.external_discovery_unset",
+                "未配置外部 Skills 目录命令；先执行 `cc-switch settings set skillDiscoveryCommand <command>`",
+                "No external discovery command configured; set one with `cc-switch settings set skillDiscoveryCommand <command>`",
+            )
+        })?;
+        super::external_discovery::run(
+            &command,
+            query,
+            super::external_discovery::EXTERNAL_DISCOVERY_TIMEOUT,
+        )
+        .await
+    }
+
+    fn verify_checksum(&self, bytes: &[u8]) -> Result<(), AppError> {
+        let Some(expected) = &self.expected_sha256 else {
+            return Ok(());
+        };
+        let actual = crate::util::hash::compute_sha256_hex(bytes);
+        if &actual == expected {
+            return Ok(());
+        }
+        Err(AppError::localized(
+            CHECKSUM_MISMATCH_KEY,
+            format!("压缩包校验失败：期望 SHA-256 {expected}，实际为 {actual}"),
+            format!("Checksum mismatch: expected SHA-256 {expected}, got {actual}"),
+        ))
+    }
+
+    fn report(&self, event: SkillProgress) {
+        if let Some(sink) = &self.progress {
+            sink(event);
+        }
     }
 
     // ---------------------------------------------------------------------
@@ -224,7 +591,7 @@ impl SkillService {
 
     pub fn get_ssot_dir() -> Result<PathBuf, AppError> {
         let dir = get_app_config_dir().join("skills");
-        fs::create_dir_all(&dir).map_err(|e| AppError::io(&dir, e))?;
+        crate::dry_run::create_dir_all(&dir).map_err(|e| AppError::io(&dir, e))?;
         Ok(dir)
     }
 
@@ -316,6 +683,7 @@ impl SkillService {
 
     pub fn migrate_ssot_if_pending(index: &mut SkillsIndex) -> Result<usize, AppError> {
         if !index.ssot_migration_pending {
+            Self::report_casing_if_pending()?;
             return Ok(0);
         }
 
@@ -464,6 +832,7 @@ impl SkillService {
                             description,
                             directory: directory.clone(),
                             readme_url: None,
+                            source_url: None,
                             repo_owner: None,
                             repo_name: None,
                             repo_branch: None,
@@ -482,23 +851,284 @@ impl SkillService {
         Ok(created)
     }
 
+    // ---------------------------------------------------------------------
+    // Directory casing (index keys / SSOT / app dirs differing only by case)
+    // ---------------------------------------------------------------------
+
+    /// One-time casing check, run after the SSOT migration has completed.
+    ///
+    /// Only reports: this runs implicitly (even from `skills list`), while consolidation
+    /// moves directories away, so that is left to an explicit `skills normalize`.
+    fn report_casing_if_pending() -> Result<(), AppError> {
+        let db = Database::init()?;
+        if db.get_setting(CASING_MIGRATION_KEY)?.is_some() {
+            return Ok(());
+        }
+        match Self::normalize_casing(true) {
+            Ok(fixes) => {
+                for fix in &fixes {
+                    log::warn!(
+                        "Skill 目录仅大小写不同: {} / {}（运行 `cc-switch skills normalize` 统一）",
+                        fix.canonical,
+                        fix.merged.join(", ")
+                    );
+                }
+            }
+            Err(e) => log::warn!("Skill 目录大小写检查失败: {e}"),
+        }
+        db.set_setting(CASING_MIGRATION_KEY, "true")?;
+        Ok(())
+    }
+
+    /// Consolidate skills whose index key, SSOT dir and app dir names differ only by case.
+    ///
+    /// The canonical name is the index key that has an SSOT dir (earliest install first).
+    /// Duplicate records are merged into it, the SSOT dir is renamed to it, stale spellings
+    /// are removed from enabled apps and the skill is re-synced. Removed SSOT dirs and real
+    /// (non-symlink) app dirs are moved to `backups/skills-casing-<time>/` rather than deleted.
+    pub fn normalize_casing(dry_run: bool) -> Result<Vec<SkillCasingFix>, AppError> {
+        let mut index = Self::load_index()?;
+        let ssot_dir = Self::get_ssot_dir()?;
+        let ssot_names = Self::dir_entry_names(&ssot_dir)?;
+        let mut app_names = Vec::new();
+        for app in [AppType::Claude, AppType::Codex, AppType::Gemini] {
+            let dir = Self::get_app_skills_dir(&app)?;
+            let names = Self::dir_entry_names(&dir)?;
+            app_names.push((app, dir, names));
+        }
+
+        let mut groups: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        for directory in index.skills.keys() {
+            groups
+                .entry(directory.to_lowercase())
+                .or_default()
+                .push(directory.clone());
+        }
+
+        let db = Database::init()?;
+        let backup_root = get_app_config_dir().join("backups").join(format!(
+            "skills-casing-{}",
+            Utc::now().format("%Y%m%d_%H%M%S")
+        ));
+        let mut fixes = Vec::new();
+        for (lower, mut keys) in groups {
+            let matches = |name: &&String| name.to_lowercase() == lower;
+            let ssot_variants: Vec<String> = ssot_names.iter().filter(matches).cloned().collect();
+            keys.sort_by_key(|key| {
+                (
+                    !ssot_variants.contains(key),
+                    index.skills[key].installed_at,
+                    key.clone(),
+                )
+            });
+            let canonical = keys[0].clone();
+
+            let mut apps = SkillApps::default();
+            for key in &keys {
+                apps.merge_enabled(&index.skills[key].apps);
+            }
+            let mut spellings: BTreeSet<String> = keys.iter().cloned().collect();
+            spellings.extend(ssot_variants.iter().cloned());
+            for (app, _, names) in &app_names {
+                if apps.is_enabled_for(app) {
+                    spellings.extend(names.iter().filter(matches).cloned());
+                }
+            }
+            if spellings.len() < 2 {
+                continue;
+            }
+
+            // SSOT: keep the canonical dir (or the first variant) and set the others aside.
+            // Distinct listing entries are distinct dirs even on case-insensitive
+            // filesystems, so moving them never touches the kept one.
+            let keep = if ssot_variants.contains(&canonical) {
+                Some(canonical.clone())
+            } else {
+                ssot_variants.first().cloned()
+            };
+            let removed: Vec<&String> = ssot_variants
+                .iter()
+                .filter(|v| Some(*v) != keep.as_ref())
+                .collect();
+
+            let mut fix = SkillCasingFix {
+                canonical: canonical.clone(),
+                merged: spellings.into_iter().filter(|n| *n != canonical).collect(),
+                records_merged: keys.len() - 1,
+                resynced: Vec::new(),
+                differing: keep
+                    .as_ref()
+                    .map(|kept| {
+                        removed
+                            .iter()
+                            .filter(|v| {
+                                !Self::dirs_identical(&ssot_dir.join(kept), &ssot_dir.join(v))
+                            })
+                            .map(|v| v.to_string())
+                            .collect()
+                    })
+                    .unwrap_or_default(),
+                backup_dir: None,
+            };
+            if dry_run {
+                fixes.push(fix);
+                continue;
+            }
+
+            // Index: fold duplicates into the canonical record.
+            let mut record = index
+                .skills
+                .remove(&canonical)
+                .ok_or_else(|| AppError::Message(format!("未找到已安装的 Skill: {canonical}")))?;
+            for key in &keys[1..] {
+                if let Some(duplicate) = index.skills.remove(key) {
+                    db.delete_skill(&duplicate.id)?;
+                }
+            }
+            record.apps = apps;
+            record.directory = canonical.clone();
+
+            for variant in removed {
+                let dest = backup_root.join("skills").join(variant);
+                Self::move_to_backup(&ssot_dir.join(variant), &dest)?;
+                fix.backup_dir = Some(backup_root.clone());
+            }
+            if let Some(keep) = keep.filter(|k| *k != canonical) {
+                Self::rename_case(&ssot_dir, &keep, &canonical)?;
+            }
+
+            // App dirs: set stale spellings aside (symlinks carry no data and are just
+            // removed), then re-sync under the canonical name.
+            for (app, dir, names) in &app_names {
+                if !record.apps.is_enabled_for(app) {
+                    continue;
+                }
+                for name in names.iter().filter(matches).filter(|n| **n != canonical) {
+                    let path = dir.join(name);
+                    if Self::is_symlink(&path) {
+                        Self::remove_path(&path)?;
+                    } else {
+                        let dest = backup_root.join(app.as_str()).join(name);
+                        Self::move_to_backup(&path, &dest)?;
+                        fix.backup_dir = Some(backup_root.clone());
+                    }
+                }
+                if ssot_dir.join(&canonical).exists() {
+                    Self::sync_to_app_dir(&canonical, app, index.sync_method)?;
+                    fix.resynced.push(app.clone());
+                }
+            }
+
+            db.save_skill(&record)?;
+            index.skills.insert(canonical, record);
+            fixes.push(fix);
+        }
+        Ok(fixes)
+    }
+
+    /// Whether two directory trees have the same entry names and byte-identical files.
+    fn dirs_identical(a: &Path, b: &Path) -> bool {
+        let entries = |dir: &Path| -> Option<BTreeMap<std::ffi::OsString, PathBuf>> {
+            fs::read_dir(dir)
+                .ok()?
+                .map(|entry| entry.ok().map(|e| (e.file_name(), e.path())))
+                .collect()
+        };
+        let (Some(left), Some(right)) = (entries(a), entries(b)) else {
+            return false;
+        };
+        left.len() == right.len()
+            && left
+                .iter()
+                .zip(right.iter())
+                .all(|((name_a, a), (name_b, b))| {
+                    name_a == name_b
+                        && if a.is_dir() {
+                            b.is_dir() && Self::dirs_identical(a, b)
+                        } else {
+                            matches!((fs::read(a), fs::read(b)), (Ok(x), Ok(y)) if x == y)
+                        }
+                })
+    }
+
+    /// Move `path` to the backup location `dest`, copying when a rename is not possible
+    /// (e.g. app dirs on another filesystem).
+    fn move_to_backup(path: &Path, dest: &Path) -> Result<(), AppError> {
+        if let Some(parent) = dest.parent() {
+            crate::dry_run::create_dir_all(parent).map_err(|e| AppError::io(parent, e))?;
+        }
+        if crate::dry_run::rename(path, dest).is_ok() {
+            return Ok(());
+        }
+        if path.is_dir() {
+            Self::copy_dir_recursive(path, dest)?;
+        } else {
+            crate::dry_run::copy(path, dest).map_err(|e| AppError::io(dest, e))?;
+        }
+        Self::remove_path(path)
+    }
+
+    /// Non-hidden entry names of `dir` as stored on disk (empty when missing).
+    fn dir_entry_names(dir: &Path) -> Result<Vec<String>, AppError> {
+        if !dir.exists() {
+            return Ok(Vec::new());
+        }
+        let mut names = Vec::new();
+        for entry in fs::read_dir(dir).map_err(|e| AppError::io(dir, e))? {
+            let entry = entry.map_err(|e| AppError::io(dir, e))?;
+            let name = entry.file_name().to_string_lossy().to_string();
+            if !name.starts_with('.') {
+                names.push(name);
+            }
+        }
+        Ok(names)
+    }
+
+    /// Rename `from` to `to` inside `dir` via a temporary name, so case-only renames
+    /// also work on case-insensitive filesystems.
+    fn rename_case(dir: &Path, from: &str, to: &str) -> Result<(), AppError> {
+        let source = dir.join(from);
+        let temp = dir.join(format!(".{to}.casing"));
+        let dest = dir.join(to);
+        crate::dry_run::rename(&source, &temp).map_err(|e| AppError::io(&source, e))?;
+        crate::dry_run::rename(&temp, &dest).map_err(|e| AppError::io(&dest, e))
+    }
+
     // ---------------------------------------------------------------------
     // Sync / remove (file operations)
     // ---------------------------------------------------------------------
 
-    #[cfg(unix)]
-    fn create_symlink(src: &Path, dest: &Path) -> Result<(), AppError> {
-        std::os::unix::fs::symlink(src, dest).map_err(|e| AppError::IoContext {
-            context: format!("创建符号链接失败 ({} -> {})", src.display(), dest.display()),
-            source: e,
-        })
+    fn try_symlink(src: &Path, dest: &Path) -> std::io::Result<()> {
+        crate::dry_run::symlink_dir(src, dest)
+    }
+
+    /// Windows 未开启开发者模式且非管理员时，symlink_dir 返回 ERROR_PRIVILEGE_NOT_HELD (1314)
+    fn is_symlink_privilege_error(err: &std::io::Error) -> bool {
+        cfg!(windows) && err.raw_os_error() == Some(1314)
     }
 
-    #[cfg(windows)]
     fn create_symlink(src: &Path, dest: &Path) -> Result<(), AppError> {
-        std::os::windows::fs::symlink_dir(src, dest).map_err(|e| AppError::IoContext {
-            context: format!("创建符号链接失败 ({} -> {})", src.display(), dest.display()),
-            source: e,
+        Self::try_symlink(src, dest).map_err(|e| {
+            if Self::is_symlink_privilege_error(&e) {
+                AppError::localized(
+                    "skill.symlink_privilege",
+                    format!(
+                        "创建符号链接需要管理员权限 ({} -> {})。请在 Windows 设置中开启“开发者模式”，或以管理员身份运行，或改用同步方式 copy/auto",
+                        src.display(),
+                        dest.display()
+                    ),
+                    format!(
+                        "Creating symlinks requires elevated privileges ({} -> {}). Enable Developer Mode in Windows Settings, run as administrator, or switch the sync method to copy/auto",
+                        src.display(),
+                        dest.display()
+                    ),
+                )
+            } else {
+                AppError::IoContext {
+                    context: format!("创建符号链接失败 ({} -> {})", src.display(), dest.display()),
+                    source: e,
+                }
+            }
         })
     }
 
@@ -510,6 +1140,9 @@ impl SkillService {
 
     fn remove_path(path: &Path) -> Result<(), AppError> {
         if Self::is_symlink(path) {
+            if crate::dry_run::skip(format_args!("remove link {}", path.display())) {
+                return Ok(());
+            }
             #[cfg(unix)]
             fs::remove_file(path).map_err(|e| AppError::io(path, e))?;
             #[cfg(windows)]
@@ -518,9 +1151,9 @@ impl SkillService {
         }
 
         if path.is_dir() {
-            fs::remove_dir_all(path).map_err(|e| AppError::io(path, e))?;
+            crate::dry_run::remove_dir_all(path).map_err(|e| AppError::io(path, e))?;
         } else if path.exists() {
-            fs::remove_file(path).map_err(|e| AppError::io(path, e))?;
+            crate::dry_run::remove_file(path).map_err(|e| AppError::io(path, e))?;
         }
         Ok(())
     }
@@ -532,7 +1165,8 @@ impl SkillService {
     ) -> Result<(), AppError> {
         let ssot_dir = Self::get_ssot_dir()?;
         let source = ssot_dir.join(directory);
-        if !source.exists() {
+        // 预演模式下刚“安装”的 Skill 并未真正复制到 SSOT
+        if !source.exists() && !crate::dry_run::is_dry_run() {
             return Err(AppError::Message(format!(
                 "Skill 不存在于 SSOT: {directory}"
             )));
@@ -540,7 +1174,7 @@ impl SkillService {
 
         let app_dir = Self::get_app_skills_dir(app)?;
         // D5: allow creating target app dirs during skills sync.
-        fs::create_dir_all(&app_dir).map_err(|e| AppError::io(&app_dir, e))?;
+        crate::dry_run::create_dir_all(&app_dir).map_err(|e| AppError::io(&app_dir, e))?;
 
         let dest = app_dir.join(directory);
         if dest.exists() || Self::is_symlink(&dest) {
@@ -548,8 +1182,17 @@ impl SkillService {
         }
 
         match method {
-            SyncMethod::Auto => match Self::create_symlink(&source, &dest) {
+            SyncMethod::Auto => match Self::try_symlink(&source, &dest) {
                 Ok(()) => Ok(()),
+                Err(err) if Self::is_symlink_privilege_error(&err) => {
+                    static PRIVILEGE_FALLBACK_LOGGED: AtomicBool = AtomicBool::new(false);
+                    if !PRIVILEGE_FALLBACK_LOGGED.swap(true, Ordering::Relaxed) {
+                        log::info!(
+                            "当前用户无创建符号链接的权限（未开启开发者模式），Skills 将以文件复制方式同步"
+                        );
+                    }
+                    Self::copy_dir_recursive(&source, &dest)
+                }
                 Err(err) => {
                     log::warn!(
                         "Symlink 创建失败，将回退到文件复制: {} -> {}. 错误: {err}",
@@ -574,9 +1217,17 @@ impl SkillService {
     }
 
     pub fn sync_to_app(index: &SkillsIndex, app: &AppType) -> Result<(), AppError> {
+        Self::sync_to_app_with_method(index, app, index.sync_method)
+    }
+
+    fn sync_to_app_with_method(
+        index: &SkillsIndex,
+        app: &AppType,
+        method: SyncMethod,
+    ) -> Result<(), AppError> {
         for skill in index.skills.values() {
             if skill.apps.is_enabled_for(app) {
-                Self::sync_to_app_dir(&skill.directory, app, index.sync_method)?;
+                Self::sync_to_app_dir(&skill.directory, app, method)?;
             }
         }
         Ok(())
@@ -595,14 +1246,23 @@ impl SkillService {
     }
 
     pub fn sync_all_enabled(app: Option<&AppType>) -> Result<(), AppError> {
+        Self::sync_all_enabled_with_method(app, None)
+    }
+
+    /// 同步已启用的 Skills；`method` 仅对本次生效，不修改持久化的 sync_method
+    pub fn sync_all_enabled_with_method(
+        app: Option<&AppType>,
+        method: Option<SyncMethod>,
+    ) -> Result<(), AppError> {
         let mut index = Self::load_index()?;
         let _ = Self::migrate_ssot_if_pending(&mut index)?;
+        let method = method.unwrap_or(index.sync_method);
 
         match app {
-            Some(app) => Self::sync_to_app(&index, app)?,
+            Some(app) => Self::sync_to_app_with_method(&index, app, method)?,
             None => {
                 for app in [AppType::Claude, AppType::Codex, AppType::Gemini] {
-                    Self::sync_to_app(&index, &app)?;
+                    Self::sync_to_app_with_method(&index, &app, method)?;
                 }
             }
         }
@@ -610,11 +1270,60 @@ impl SkillService {
         Ok(())
     }
 
+    /// SSOT 内路径所属的 Skill 目录名（第一级子目录；隐藏目录如暂存区返回 None）
+    pub fn ssot_skill_of(path: &Path) -> Result<Option<String>, AppError> {
+        let ssot_dir = Self::get_ssot_dir()?;
+        let first = path
+            .strip_prefix(&ssot_dir)
+            .ok()
+            .and_then(|rel| rel.components().next())
+            .map(|c| c.as_os_str().to_string_lossy().to_string());
+        Ok(first.filter(|name| !name.starts_with('.')))
+    }
+
+    /// `skills watch`：SSOT 中某个 Skill 变化后刷新已启用应用里的副本
+    ///
+    /// 复制方式重新复制；已存在的符号链接本身指向 SSOT，只确认链接仍在。
+    /// 返回 `(应用, 是否重新同步)`；未被索引管理的目录返回空列表。
+    pub fn resync_skill(
+        directory: &str,
+        app: Option<&AppType>,
+    ) -> Result<Vec<(AppType, bool)>, AppError> {
+        let index = Self::load_index()?;
+        let Some(record) = index.skills.get(directory) else {
+            return Ok(Vec::new());
+        };
+        if !Self::get_ssot_dir()?.join(directory).exists() {
+            return Ok(Vec::new());
+        }
+
+        let mut results = Vec::new();
+        for candidate in [AppType::Claude, AppType::Codex, AppType::Gemini] {
+            if app.is_some_and(|a| *a != candidate) || !record.apps.is_enabled_for(&candidate) {
+                continue;
+            }
+            let dest = Self::get_app_skills_dir(&candidate)?.join(directory);
+            if Self::is_symlink(&dest) && dest.exists() {
+                results.push((candidate, false));
+                continue;
+            }
+            Self::sync_to_app_dir(directory, &candidate, index.sync_method)?;
+            results.push((candidate, true));
+        }
+        Ok(results)
+    }
+
     pub fn list_installed() -> Result<Vec<InstalledSkill>, AppError> {
         let mut index = Self::load_index()?;
         let _ = Self::migrate_ssot_if_pending(&mut index)?;
         let mut skills: Vec<InstalledSkill> = index.skills.values().cloned().collect();
-        skills.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
+        // 名称相同时按目录排序，保证 `skills list --offset` 分页稳定
+        skills.sort_by(|a, b| {
+            a.name
+                .to_lowercase()
+                .cmp(&b.name.to_lowercase())
+                .then_with(|| a.directory.cmp(&b.directory))
+        });
         Ok(skills)
     }
 
@@ -622,6 +1331,39 @@ impl SkillService {
         Ok(Self::load_index()?.repos)
     }
 
+    /// Aggregate installed skills, repos and SSOT disk usage.
+    pub fn stats() -> Result<SkillStats, AppError> {
+        let skills = Self::list_installed()?;
+        let repos = Self::list_repos()?;
+
+        let mut stats = SkillStats {
+            installed: skills.len(),
+            ssot_bytes: Self::dir_size(&Self::get_ssot_dir()?),
+            repos_configured: repos.len(),
+            repos_enabled: repos.iter().filter(|r| r.enabled).count(),
+            ..SkillStats::default()
+        };
+        for app in [AppType::Claude, AppType::Codex, AppType::Gemini] {
+            let enabled = skills
+                .iter()
+                .filter(|s| s.apps.is_enabled_for(&app))
+                .count();
+            stats
+                .enabled_by_app
+                .insert(app.as_str().to_string(), enabled);
+        }
+        for skill in &skills {
+            if skill.repo_owner.is_some() && skill.repo_name.is_some() {
+                stats.from_repo += 1;
+            } else if skill.source_url.is_some() {
+                stats.from_archive += 1;
+            } else {
+                stats.local += 1;
+            }
+        }
+        Ok(stats)
+    }
+
     pub fn get_sync_method() -> Result<SyncMethod, AppError> {
         Ok(crate::settings::get_skill_sync_method())
     }
@@ -630,13 +1372,17 @@ impl SkillService {
         crate::settings::set_skill_sync_method(method)
     }
 
-    pub fn upsert_repo(repo: SkillRepo) -> Result<(), AppError> {
+    pub fn upsert_repo(mut repo: SkillRepo) -> Result<(), AppError> {
         let mut index = Self::load_index()?;
         if let Some(pos) = index
             .repos
             .iter()
             .position(|r| r.owner == repo.owner && r.name == repo.name)
         {
+            // 未提供令牌时保留已保存的引用
+            if repo.token.is_none() {
+                repo.token = index.repos[pos].token.take();
+            }
             index.repos[pos] = repo;
         } else {
             index.repos.push(repo);
@@ -645,37 +1391,353 @@ impl SkillService {
         Ok(())
     }
 
-    pub fn remove_repo(owner: &str, name: &str) -> Result<(), AppError> {
-        let db = Database::init()?;
-        db.delete_skill_repo(owner, name)
+    /// 已跟踪仓库当前记录的分支；未跟踪时返回 `None`
+    pub fn tracked_branch(owner: &str, name: &str) -> Result<Option<String>, AppError> {
+        Ok(Self::load_index()?
+            .repos
+            .into_iter()
+            .find(|r| r.owner == owner && r.name == name)
+            .map(|r| r.branch))
     }
 
-    fn resolve_directory_from_input(index: &SkillsIndex, input: &str) -> Option<String> {
-        let trimmed = input.trim();
-        if trimmed.is_empty() {
-            return None;
+    /// 添加仓库；未指定分支时探测 `main` / `master` 中实际存在的默认分支并持久化。
+    ///
+    /// 显式指定的分支保持不变；重新添加已跟踪的仓库且未指定分支时沿用已记录的分支。
+    /// 探测失败（如离线）时回退为 `main`，下载时仍会按 main/master 兜底。
+    pub async fn add_repo(&self, mut repo: SkillRepo) -> Result<SkillRepo, AppError> {
+        if repo.branch.trim().is_empty() {
+            if let Some(branch) = Self::tracked_branch(&repo.owner, &repo.name)? {
+                repo.branch = branch;
+            }
         }
-
-        // Prefer exact directory match.
-        if index.skills.contains_key(trimmed) {
-            return Some(trimmed.to_string());
+        if repo.branch.trim().is_empty() {
+            repo.branch = match self.probe_default_branch(&repo).await {
+                Some(branch) => branch,
+                None => {
+                    log::warn!(
+                        "无法探测 {}/{} 的默认分支，使用 main",
+                        repo.owner,
+                        repo.name
+                    );
+                    "main".to_string()
+                }
+            };
         }
+        Self::upsert_repo(repo.clone())?;
+        Ok(repo)
+    }
 
-        // Case-insensitive directory match.
-        let trimmed_lower = trimmed.to_lowercase();
-        if let Some((dir, _)) = index
-            .skills
-            .iter()
-            .find(|(dir, _)| dir.to_lowercase() == trimmed_lower)
-        {
-            return Some(dir.clone());
+    /// Check that a repo is reachable and list the skills it contains, without adding it.
+    ///
+    /// Uses the repo cache when present (unless `refresh`); otherwise downloads into a temp
+    /// dir that is discarded afterwards. An empty branch is probed like [`Self::add_repo`].
+    pub async fn test_repo(
+        &self,
+        mut repo: SkillRepo,
+        refresh: bool,
+    ) -> Result<RepoTestReport, AppError> {
+        if repo.branch.trim().is_empty() {
+            repo.branch = self
+                .probe_default_branch(&repo)
+                .await
+                .unwrap_or_else(|| "main".to_string());
         }
 
-        // Match by id.
-        if let Some((dir, _)) = index
-            .skills
-            .iter()
-            .find(|(_, s)| s.id.eq_ignore_ascii_case(trimmed))
+        let cache = Self::repo_cache_dir(&repo);
+        let from_cache = !refresh && cache.is_dir();
+        let mut skills = if from_cache {
+            Self::scan_repo_skills(&repo, &cache)?
+        } else {
+            self.download_and_scan_repo(&repo, false).await?
+        };
+        skills.sort_by(|a, b| a.directory.cmp(&b.directory));
+
+        Ok(RepoTestReport {
+            owner: repo.owner,
+            name: repo.name,
+            branch: repo.branch,
+            from_cache,
+            skills,
+        })
+    }
+
+    /// 通过对归档地址发送 HEAD 请求，返回第一个存在的默认分支
+    async fn probe_default_branch(&self, repo: &SkillRepo) -> Option<String> {
+        if crate::http_client::is_offline() {
+            return None;
+        }
+        let token = Self::resolve_repo_token(repo).unwrap_or_else(|e| {
+            log::debug!("读取仓库令牌失败: {e}");
+            None
+        });
+        for branch in ["main", "master"] {
+            let url = Self::archive_url(repo, branch, token.is_some());
+            let mut request = self.http_client.head(&url);
+            if let Some(token) = &token {
+                request = request.bearer_auth(token);
+            }
+            match request.send().await {
+                Ok(resp) if resp.status().is_success() => return Some(branch.to_string()),
+                Ok(resp) => log::debug!("探测分支 {branch} 失败: HTTP {}", resp.status()),
+                Err(e) => log::debug!("探测分支 {branch} 失败: {e}"),
+            }
+        }
+        None
+    }
+
+    /// 启用/禁用仓库（保留仓库及其分支设置，禁用后不再参与 discover）
+    pub fn set_repo_enabled(owner: &str, name: &str, enabled: bool) -> Result<SkillRepo, AppError> {
+        let db = Database::init()?;
+        let Some(mut repo) = db
+            .get_skill_repos()?
+            .into_iter()
+            .find(|r| r.owner.eq_ignore_ascii_case(owner) && r.name.eq_ignore_ascii_case(name))
+        else {
+            return Err(AppError::localized(
+                "skill.repo_not_found",
+                format!("未找到 Skill 仓库: {owner}/{name}"),
+                format!("Skill repo not found: {owner}/{name}"),
+            ));
+        };
+        repo.enabled = enabled;
+        db.save_skill_repo(&repo)?;
+        Ok(repo)
+    }
+
+    pub fn remove_repo(owner: &str, name: &str) -> Result<(), AppError> {
+        let db = Database::init()?;
+        let stored = db
+            .get_skill_repos()?
+            .into_iter()
+            .find(|r| r.owner == owner && r.name == name);
+        db.delete_skill_repo(owner, name)?;
+
+        // 只清理由 `--private` 写入的钥匙串条目，手动引用的条目可能被其他地方共用
+        let own_entry = Self::repo_token_name(owner, name);
+        if stored
+            .and_then(|r| r.token)
+            .is_some_and(|t| crate::keyring_store::parse_reference(&t) == Some(own_entry.as_str()))
+        {
+            if let Err(e) = crate::keyring_store::delete_secret(&own_entry) {
+                log::warn!("删除仓库令牌失败: {e}");
+            }
+        }
+        Ok(())
+    }
+
+    /// Keychain entry that `skills repo add --private` stores a repo's token under.
+    pub fn repo_token_name(owner: &str, name: &str) -> String {
+        format!("skill-repo/{owner}/{name}")
+    }
+
+    /// Store a private repo's token in the system keychain and return its `keyring:` reference.
+    pub fn store_repo_token(owner: &str, name: &str, token: &str) -> Result<String, AppError> {
+        let entry = Self::repo_token_name(owner, name);
+        crate::keyring_store::store_secret(&entry, token)?;
+        Ok(crate::keyring_store::reference(&entry))
+    }
+
+    /// Resolve a repo's token from the keychain.
+    ///
+    /// Falls back to the stored repo's reference, since installs and `repo test` build a
+    /// `SkillRepo` from discovery results or the command line without one.
+    fn resolve_repo_token(repo: &SkillRepo) -> Result<Option<String>, AppError> {
+        let reference = match &repo.token {
+            Some(reference) => Some(reference.clone()),
+            None => Database::init()?
+                .get_skill_repos()?
+                .into_iter()
+                .find(|r| {
+                    r.owner.eq_ignore_ascii_case(&repo.owner)
+                        && r.name.eq_ignore_ascii_case(&repo.name)
+                })
+                .and_then(|r| r.token),
+        };
+        reference
+            .as_deref()
+            .map(crate::keyring_store::resolve_secret)
+            .transpose()
+    }
+
+    /// Branch archive URL; private repos go through the API, which accepts a bearer token.
+    fn archive_url(repo: &SkillRepo, branch: &str, private: bool) -> String {
+        if private {
+            format!(
+                "https://api.github.com/repos/{}/{}/zipball/{branch}",
+                repo.owner, repo.name
+            )
+        } else {
+            format!(
+                "https://github.com/{}/{}/archive/refs/heads/{branch}.zip",
+                repo.owner, repo.name
+            )
+        }
+    }
+
+    /// Read a repo manifest: TOML for `.toml` files, JSON (object or bare array) otherwise.
+    pub fn read_repo_manifest(path: &Path) -> Result<Vec<SkillRepo>, AppError> {
+        let content = fs::read_to_string(path).map_err(|e| AppError::io(path, e))?;
+        let invalid = |reason: String| {
+            AppError::localized(
+                "skills.repo_manifest_invalid",
+                format!("无效的仓库清单 {}: {reason}", path.display()),
+                format!("Invalid repo manifest {}: {reason}", path.display()),
+            )
+        };
+        let is_toml = path
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("toml"));
+        let repos = if is_toml {
+            toml::from_str::<RepoManifest>(&content)
+                .map_err(|e| invalid(e.to_string()))?
+                .repos
+        } else {
+            match serde_json::from_str::<Vec<SkillRepo>>(&content) {
+                Ok(repos) => repos,
+                Err(_) => {
+                    serde_json::from_str::<RepoManifest>(&content)
+                        .map_err(|e| invalid(e.to_string()))?
+                        .repos
+                }
+            }
+        };
+
+        for (i, repo) in repos.iter().enumerate() {
+            let valid =
+                |part: &str| !part.trim().is_empty() && !part.contains(['/', ' ', '\t', '@', ':']);
+            if !valid(&repo.owner) || !valid(&repo.name) || repo.branch.trim().is_empty() {
+                return Err(invalid(format!(
+                    "entry {} ({}/{}@{}) needs a non-empty owner, name and branch",
+                    i + 1,
+                    repo.owner,
+                    repo.name,
+                    repo.branch
+                )));
+            }
+        }
+        Ok(repos)
+    }
+
+    /// Write all configured repos as a manifest (TOML for `.toml`, pretty JSON otherwise).
+    pub fn export_repo_manifest(path: &Path) -> Result<usize, AppError> {
+        let manifest = RepoManifest {
+            repos: Self::list_repos()?,
+        };
+        let is_toml = path
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("toml"));
+        let content = if is_toml {
+            toml::to_string_pretty(&manifest)
+                .map_err(|e| AppError::Message(format!("序列化仓库清单失败: {e}")))?
+        } else {
+            serde_json::to_string_pretty(&manifest)
+                .map_err(|e| AppError::JsonSerialize { source: e })?
+        };
+        crate::config::atomic_write(path, content.as_bytes())?;
+        Ok(manifest.repos.len())
+    }
+
+    /// Upsert manifest repos (matched by owner/name, case-insensitively); nothing is removed.
+    pub fn import_repos(repos: Vec<SkillRepo>) -> Result<RepoImportReport, AppError> {
+        // 令牌只能以钥匙串引用出现；有明文令牌时整体拒绝，不写入任何仓库
+        let plaintext: Vec<String> = repos
+            .iter()
+            .filter(|r| {
+                r.token
+                    .as_deref()
+                    .is_some_and(|t| crate::keyring_store::parse_reference(t).is_none())
+            })
+            .map(|r| format!("{}/{}", r.owner, r.name))
+            .collect();
+        if !plaintext.is_empty() {
+            return Err(AppError::localized(
+                "skills.repo_token_plaintext",
+                format!(
+                    "仓库清单包含明文令牌（{}）；只接受 keyring:<名称> 引用，请用 `skills repos add --private` 保存令牌",
+                    plaintext.join(", ")
+                ),
+                format!(
+                    "Repo manifest has plaintext tokens ({}); only keyring:<name> references are accepted, store tokens with `skills repos add --private`",
+                    plaintext.join(", ")
+                ),
+            ));
+        }
+
+        let db = Database::init()?;
+        let existing = db.get_skill_repos()?;
+        let key = |r: &SkillRepo| format!("{}/{}", r.owner, r.name);
+        let mut report = RepoImportReport::default();
+
+        // Keep the last entry per repo, in first-seen order.
+        let mut unique: Vec<SkillRepo> = Vec::new();
+        for repo in repos {
+            let repo_key = key(&repo);
+            match unique
+                .iter_mut()
+                .find(|r| key(r).eq_ignore_ascii_case(&repo_key))
+            {
+                Some(slot) => {
+                    report.duplicates.push(repo_key);
+                    *slot = repo;
+                }
+                None => unique.push(repo),
+            }
+        }
+
+        for mut repo in unique {
+            match existing.iter().find(|r| {
+                r.owner.eq_ignore_ascii_case(&repo.owner) && r.name.eq_ignore_ascii_case(&repo.name)
+            }) {
+                Some(current)
+                    if current.branch == repo.branch
+                        && current.enabled == repo.enabled
+                        && (repo.token.is_none() || repo.token == current.token) =>
+                {
+                    report.unchanged.push(key(current));
+                    continue;
+                }
+                Some(current) => {
+                    // Keep the stored spelling so the row is updated rather than duplicated.
+                    repo.owner = current.owner.clone();
+                    repo.name = current.name.clone();
+                    if repo.token.is_none() {
+                        repo.token = current.token.clone();
+                    }
+                    report.merged.push(key(&repo));
+                }
+                None => report.added.push(key(&repo)),
+            }
+            db.save_skill_repo(&repo)?;
+        }
+        Ok(report)
+    }
+
+    fn resolve_directory_from_input(index: &SkillsIndex, input: &str) -> Option<String> {
+        let trimmed = input.trim();
+        if trimmed.is_empty() {
+            return None;
+        }
+
+        // Prefer exact directory match.
+        if index.skills.contains_key(trimmed) {
+            return Some(trimmed.to_string());
+        }
+
+        // Case-insensitive directory match.
+        let trimmed_lower = trimmed.to_lowercase();
+        if let Some((dir, _)) = index
+            .skills
+            .iter()
+            .find(|(dir, _)| dir.to_lowercase() == trimmed_lower)
+        {
+            return Some(dir.clone());
+        }
+
+        // Match by id.
+        if let Some((dir, _)) = index
+            .skills
+            .iter()
+            .find(|(_, s)| s.id.eq_ignore_ascii_case(trimmed))
         {
             return Some(dir.clone());
         }
@@ -707,6 +1769,12 @@ impl SkillService {
     }
 
     pub fn uninstall(directory_or_id: &str) -> Result<(), AppError> {
+        Self::uninstall_with_options(directory_or_id, false)
+    }
+
+    /// Uninstall a skill; with `keep_ssot`, the SSOT copy is left in place so the skill
+    /// shows up again in `scan-unmanaged` and can be re-imported later.
+    pub fn uninstall_with_options(directory_or_id: &str, keep_ssot: bool) -> Result<(), AppError> {
         let index = Self::load_index()?;
         let Some(dir) = Self::resolve_directory_from_input(&index, directory_or_id) else {
             return Err(AppError::Message(format!(
@@ -729,8 +1797,8 @@ impl SkillService {
         // Remove from SSOT.
         let ssot_dir = Self::get_ssot_dir()?;
         let ssot_path = ssot_dir.join(&dir);
-        if ssot_path.exists() {
-            fs::remove_dir_all(&ssot_path).map_err(|e| AppError::io(&ssot_path, e))?;
+        if !keep_ssot && ssot_path.exists() {
+            crate::dry_run::remove_dir_all(&ssot_path).map_err(|e| AppError::io(&ssot_path, e))?;
         }
 
         let db = Database::init()?;
@@ -739,6 +1807,32 @@ impl SkillService {
     }
 
     pub async fn install(&self, spec: &str, app: &AppType) -> Result<InstalledSkill, AppError> {
+        self.install_with_options(spec, app, InstallOptions::default())
+            .await
+    }
+
+    /// Install a skill; with `force`, a directory conflict with another repo's skill is
+    /// resolved by installing under `<directory>-<owner>` instead of failing. With
+    /// `strict`, the skill's `SKILL.md` metadata must validate before anything is copied.
+    pub async fn install_with_options(
+        &self,
+        spec: &str,
+        app: &AppType,
+        options: InstallOptions,
+    ) -> Result<InstalledSkill, AppError> {
+        self.install_with_outcome(spec, app, options)
+            .await
+            .map(|(installed, _)| installed)
+    }
+
+    /// Resolve a spec and its install directory without downloading or writing anything.
+    /// The plan can be shown to the user and then passed to [`Self::install_planned`], so
+    /// the spec (and any network discovery) is resolved only once.
+    pub async fn plan_install(
+        &self,
+        spec: &str,
+        options: InstallOptions,
+    ) -> Result<InstallPlan, AppError> {
         let spec = spec.trim();
         if spec.is_empty() {
             return Err(AppError::InvalidInput("Skill 不能为空".to_string()));
@@ -748,43 +1842,357 @@ impl SkillService {
         let _ = Self::migrate_ssot_if_pending(&mut index)?;
 
         // Resolve spec to a discoverable skill.
-        let discoverable = self.resolve_install_spec(&index, spec).await?;
+        let discoverable = self
+            .resolve_install_spec(&index, spec, options.force)
+            .await?;
+        let directory = Self::resolve_install_directory(&index, &discoverable, options.force)?;
+        let renamed = directory != Self::skill_directory_name(&discoverable);
+        Ok(InstallPlan {
+            spec: spec.to_string(),
+            directory,
+            renamed,
+            discoverable,
+        })
+    }
 
-        // Directory install name is always the last segment.
-        let install_name = Path::new(&discoverable.directory)
-            .file_name()
-            .map(|s| s.to_string_lossy().to_string())
-            .unwrap_or_else(|| discoverable.directory.clone());
+    /// Like [`Self::install_with_options`], also reporting whether the skill was freshly
+    /// installed, renamed, or already installed.
+    pub async fn install_with_outcome(
+        &self,
+        spec: &str,
+        app: &AppType,
+        options: InstallOptions,
+    ) -> Result<(InstalledSkill, InstallOutcome), AppError> {
+        let plan = self.plan_install(spec, options).await?;
+        self.install_planned(plan, app, options).await
+    }
+
+    /// Install a skill resolved by [`Self::plan_install`]. The directory is re-checked under
+    /// the install lock, so a plan that went stale in the meantime still fails safely.
+    pub async fn install_planned(
+        &self,
+        plan: InstallPlan,
+        app: &AppType,
+        options: InstallOptions,
+    ) -> Result<(InstalledSkill, InstallOutcome), AppError> {
+        let InstallPlan {
+            spec,
+            directory: planned,
+            discoverable,
+            ..
+        } = plan;
+        let spec = spec.as_str();
+
+        // Path of the skill inside the source repo (leaf name, or relative path when nested
+        // skills share a leaf name).
+        let source_name = discoverable.directory.clone();
+
+        // Fetch the source outside the install lock so bulk installs download in parallel;
+        // skipped when the skill is already on disk.
+        let index = Self::load_index()?;
+        let mut fetched = if index.skills.contains_key(&planned)
+            || Self::get_ssot_dir()?.join(&planned).exists()
+        {
+            None
+        } else {
+            Some(
+                self.fetch_skill_source(&discoverable, &source_name, spec)
+                    .await?,
+            )
+        };
+        let discard_fetched = |fetched: &Option<FetchedSource>| {
+            if let Some((Some(temp_dir), _)) = fetched {
+                let _ = fs::remove_dir_all(temp_dir);
+            }
+        };
+
+        // Index and SSOT changes are serialized: concurrent installs would otherwise pick the
+        // same directory or save over each other's index.
+        let _guard = install_lock().lock().await;
+        let mut index = Self::load_index()?;
+        let install_name =
+            match Self::resolve_install_directory(&index, &discoverable, options.force) {
+                Ok(name) => name,
+                Err(e) => {
+                    discard_fetched(&fetched);
+                    return Err(e);
+                }
+            };
 
-        // Conflict check (directory collisions across repos).
         if let Some(existing) = index.skills.get(&install_name) {
-            let same_repo = existing.repo_owner.as_deref()
-                == Some(discoverable.repo_owner.as_str())
-                && existing.repo_name.as_deref() == Some(discoverable.repo_name.as_str());
-            if !same_repo
-                && (existing.repo_owner.is_some()
-                    || existing.repo_name.is_some()
-                    || existing.id.starts_with("local:"))
-            {
-                let existing_repo = format!(
-                    "{}/{}",
-                    existing.repo_owner.as_deref().unwrap_or("unknown"),
-                    existing.repo_name.as_deref().unwrap_or("unknown")
+            discard_fetched(&fetched);
+            if options.no_enable {
+                return Ok((existing.clone(), InstallOutcome::AlreadyInstalled));
+            }
+            // Already installed: just enable current app and sync.
+            let mut updated = existing.clone();
+            updated.apps.set_enabled_for(app, true);
+            index.skills.insert(install_name.clone(), updated.clone());
+            Self::save_index(&index)?;
+            Self::sync_to_app_dir(&install_name, app, index.sync_method)?;
+            return Ok((updated, InstallOutcome::AlreadyInstalled));
+        }
+        let outcome = if install_name == Self::skill_directory_name(&discoverable) {
+            InstallOutcome::Installed
+        } else {
+            InstallOutcome::Renamed
+        };
+
+        // Ensure SSOT dir and install files.
+        let ssot_dir = Self::get_ssot_dir()?;
+        let dest = ssot_dir.join(&install_name);
+        if !dest.exists() {
+            // The snapshot said the skill was on disk but it is gone now: fetch under the lock.
+            if fetched.is_none() {
+                fetched = Some(
+                    self.fetch_skill_source(&discoverable, &source_name, spec)
+                        .await?,
                 );
-                let new_repo = format!("{}/{}", discoverable.repo_owner, discoverable.repo_name);
+            }
+            let source = fetched
+                .as_ref()
+                .map(|(_, source)| source.clone())
+                .unwrap_or_default();
+            let cleanup = || discard_fetched(&fetched);
 
+            if !source.exists() {
+                cleanup();
+                let source_path_string = source.display().to_string();
+                return Err(AppError::Message(format_skill_error(
+                    "SKILL_DIR_NOT_FOUND",
+                    &[("path", source_path_string.as_str())],
+                    Some("checkRepoUrl"),
+                )));
+            }
+
+            if options.strict {
+                if let Err(e) = Self::validate_skill_metadata(&source) {
+                    cleanup();
+                    return Err(e);
+                }
+            }
+
+            let copied = Self::copy_dir_recursive(&source, &dest);
+            cleanup();
+            copied?;
+        } else {
+            discard_fetched(&fetched);
+            if options.strict {
+                Self::validate_skill_metadata(&dest)?;
+            }
+        }
+
+        let installed = InstalledSkill {
+            id: discoverable.key.clone(),
+            name: discoverable.name.clone(),
+            description: if discoverable.description.trim().is_empty() {
+                None
+            } else {
+                Some(discoverable.description.clone())
+            },
+            directory: install_name.clone(),
+            readme_url: discoverable.readme_url.clone(),
+            source_url: None,
+            repo_owner: Some(discoverable.repo_owner.clone()),
+            repo_name: Some(discoverable.repo_name.clone()),
+            repo_branch: Some(discoverable.repo_branch.clone()),
+            apps: Self::initial_apps(app, options),
+            installed_at: Utc::now().timestamp(),
+        };
+
+        index.skills.insert(install_name.clone(), installed.clone());
+        Self::save_index(&index)?;
+        if !options.no_enable {
+            Self::sync_to_app_dir(&install_name, app, index.sync_method)?;
+        }
+
+        Ok((installed, outcome))
+    }
+
+    /// Locate `source_name` in the cached repo archive, or download the repo (the cache is
+    /// skipped when a checksum must be verified). Returns the temp dir to clean up, if any,
+    /// and the skill source dir.
+    async fn fetch_skill_source(
+        &self,
+        discoverable: &DiscoverableSkill,
+        source_name: &str,
+        spec: &str,
+    ) -> Result<FetchedSource, AppError> {
+        let repo = SkillRepo {
+            owner: discoverable.repo_owner.clone(),
+            name: discoverable.repo_name.clone(),
+            branch: discoverable.repo_branch.clone(),
+            enabled: true,
+            token: None,
+        };
+
+        let cache = Self::repo_cache_dir(&repo);
+        if cache.is_dir() && self.expected_sha256.is_none() {
+            if let Some(source) = Self::find_skill_dir_in_repo(&cache, source_name)? {
+                return Ok((None, source));
+            }
+        }
+
+        let timeout_secs = self.download_timeout.as_secs().to_string();
+        let temp_dir = timeout(
+            self.download_timeout,
+            self.download_repo(&repo, Some(spec), true),
+        )
+        .await
+        .map_err(|_| {
+            AppError::Message(format_skill_error(
+                "DOWNLOAD_TIMEOUT",
+                &[
+                    ("owner", repo.owner.as_str()),
+                    ("name", repo.name.as_str()),
+                    ("timeout", timeout_secs.as_str()),
+                ],
+                Some("checkNetwork"),
+            ))
+        })??;
+        Self::store_repo_cache(&repo, &temp_dir);
+
+        let source = Self::find_skill_dir_in_repo(&temp_dir, source_name)?.ok_or_else(|| {
+            let _ = fs::remove_dir_all(&temp_dir);
+            AppError::Message(format_skill_error(
+                "SKILL_DIR_NOT_FOUND",
+                &[("directory", source_name)],
+                Some("checkRepoUrl"),
+            ))
+        })?;
+        Ok((Some(temp_dir), source))
+    }
+
+    /// Read a skill list file: one spec per line; blank lines and `#` comments are skipped,
+    /// duplicates are dropped.
+    pub fn read_spec_list(path: &Path) -> Result<Vec<String>, AppError> {
+        let content = fs::read_to_string(path).map_err(|e| AppError::io(path, e))?;
+        let mut seen = HashSet::new();
+        Ok(content
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .filter(|line| seen.insert(line.to_string()))
+            .map(str::to_string)
+            .collect())
+    }
+
+    /// Install several specs with at most `parallel` installs in flight; a failure is
+    /// recorded for that spec and does not stop the others. Results keep the input order.
+    pub async fn install_many(
+        &self,
+        specs: Vec<String>,
+        app: &AppType,
+        options: InstallOptions,
+        parallel: usize,
+    ) -> Vec<BulkInstallResult> {
+        let mut results: Vec<(usize, BulkInstallResult)> =
+            stream::iter(specs.into_iter().enumerate())
+                .map(|(position, spec)| async move {
+                    (position, self.install_one(spec, app, options).await)
+                })
+                .buffer_unordered(parallel.max(1))
+                .collect()
+                .await;
+
+        results.sort_by_key(|(position, _)| *position);
+        results.into_iter().map(|(_, result)| result).collect()
+    }
+
+    /// One entry of [`Self::install_many`], reporting start / done / failed events.
+    async fn install_one(
+        &self,
+        spec: String,
+        app: &AppType,
+        options: InstallOptions,
+    ) -> BulkInstallResult {
+        self.report(SkillProgress::Start {
+            skill: spec.clone(),
+        });
+        match self.install_with_outcome(&spec, app, options).await {
+            Ok((installed, outcome)) => {
+                self.report(SkillProgress::Done {
+                    skill: spec.clone(),
+                    directory: installed.directory.clone(),
+                    outcome,
+                });
+                BulkInstallResult {
+                    spec,
+                    directory: Some(installed.directory),
+                    outcome: Some(outcome),
+                    error: None,
+                }
+            }
+            Err(e) => {
+                self.report(SkillProgress::Failed {
+                    skill: spec.clone(),
+                    error: e.to_string(),
+                });
+                BulkInstallResult {
+                    spec,
+                    directory: None,
+                    outcome: None,
+                    error: Some(e.to_string()),
+                }
+            }
+        }
+    }
+
+    /// Install `directory` from a release archive (`.zip`, `.tar.gz` / `.tgz`) at `url`.
+    ///
+    /// The skill is recorded without repo info and with `source_url` set, so it is never
+    /// resolved through repo discovery.
+    pub async fn install_from_url(
+        &self,
+        url: &str,
+        directory: &str,
+        app: &AppType,
+        options: InstallOptions,
+    ) -> Result<InstalledSkill, AppError> {
+        let url = url.trim();
+        let directory = directory.trim().trim_matches('/');
+        if !(url.starts_with("https://") || url.starts_with("http://")) {
+            return Err(AppError::InvalidInput(format!(
+                "Skill 压缩包地址必须是 http(s) URL: {url}"
+            )));
+        }
+        if directory.is_empty() {
+            return Err(AppError::InvalidInput("Skill 目录不能为空".to_string()));
+        }
+
+        let mut index = Self::load_index()?;
+        let _ = Self::migrate_ssot_if_pending(&mut index)?;
+
+        let install_name = Path::new(directory)
+            .file_name()
+            .map(|s| s.to_string_lossy().to_string())
+            .unwrap_or_else(|| directory.to_string());
+
+        // `force` re-downloads and replaces whatever is installed under the directory.
+        if let Some(existing) = index.skills.get(&install_name).filter(|_| !options.force) {
+            if existing.source_url.as_deref() != Some(url) {
+                let existing_repo = match (&existing.repo_owner, &existing.repo_name) {
+                    (Some(owner), Some(name)) => format!("{owner}/{name}"),
+                    _ => existing
+                        .source_url
+                        .clone()
+                        .unwrap_or_else(|| existing.id.clone()),
+                };
                 return Err(AppError::Message(format_skill_error(
                     "SKILL_DIRECTORY_CONFLICT",
                     &[
                         ("directory", install_name.as_str()),
                         ("existing_repo", existing_repo.as_str()),
-                        ("new_repo", new_repo.as_str()),
+                        ("new_repo", url),
                     ],
                     Some("uninstallFirst"),
                 )));
             }
 
-            // Already installed: just enable current app and sync.
+            if options.no_enable {
+                return Ok(existing.clone());
+            }
+            // Already installed from the same archive: just enable current app and sync.
             let mut updated = existing.clone();
             updated.apps.set_enabled_for(app, true);
             index.skills.insert(install_name.clone(), updated.clone());
@@ -793,107 +2201,577 @@ impl SkillService {
             return Ok(updated);
         }
 
-        // Ensure SSOT dir and install files.
-        let ssot_dir = Self::get_ssot_dir()?;
-        let dest = ssot_dir.join(&install_name);
-        if !dest.exists() {
-            let repo = SkillRepo {
-                owner: discoverable.repo_owner.clone(),
-                name: discoverable.repo_name.clone(),
-                branch: discoverable.repo_branch.clone(),
-                enabled: true,
-            };
+        // Unmanaged files already in the SSOT are only replaced with `force`.
+        let dest = Self::get_ssot_dir()?.join(&install_name);
+        if dest.exists() && !options.force {
+            return Err(AppError::localized(
+                "skills.directory_exists",
+                format!("SSOT 中已存在目录 {}，使用 --force 覆盖", dest.display()),
+                format!(
+                    "{} already exists in the SSOT; use --force to replace it",
+                    dest.display()
+                ),
+            ));
+        }
 
-            let temp_dir = timeout(
-                std::time::Duration::from_secs(60),
-                self.download_repo(&repo),
-            )
+        let timeout_secs = self.download_timeout.as_secs().to_string();
+        let temp_dir = timeout(self.download_timeout, self.download_archive(url))
             .await
             .map_err(|_| {
                 AppError::Message(format_skill_error(
                     "DOWNLOAD_TIMEOUT",
                     &[
-                        ("owner", repo.owner.as_str()),
-                        ("name", repo.name.as_str()),
-                        ("timeout", "60"),
+                        ("owner", url),
+                        ("name", directory),
+                        ("timeout", timeout_secs.as_str()),
                     ],
                     Some("checkNetwork"),
                 ))
             })??;
 
-            let source =
-                Self::find_skill_dir_in_repo(&temp_dir, &install_name)?.ok_or_else(|| {
-                    let _ = fs::remove_dir_all(&temp_dir);
-                    AppError::Message(format_skill_error(
-                        "SKILL_DIR_NOT_FOUND",
-                        &[("directory", install_name.as_str())],
-                        Some("checkRepoUrl"),
-                    ))
-                })?;
+        let result = (|| {
+            let source = Self::find_skill_dir_in_repo(&temp_dir, directory)?.ok_or_else(|| {
+                AppError::Message(format_skill_error(
+                    "SKILL_DIR_NOT_FOUND",
+                    &[("directory", directory)],
+                    Some("checkRepoUrl"),
+                ))
+            })?;
+            if options.strict {
+                Self::validate_skill_metadata(&source)?;
+            }
+            let meta = Self::parse_skill_metadata_static(&source.join("SKILL.md"))?;
+
+            Self::remove_path(&dest)?;
+            Self::copy_dir_recursive(&source, &dest)?;
+            Ok::<_, AppError>(meta)
+        })();
+        let _ = fs::remove_dir_all(&temp_dir);
+        let meta = result?;
+
+        let mut installed = InstalledSkill {
+            id: format!("url:{install_name}"),
+            name: meta.name.unwrap_or_else(|| install_name.clone()),
+            description: meta.description.filter(|d| !d.trim().is_empty()),
+            directory: install_name.clone(),
+            readme_url: None,
+            source_url: Some(url.to_string()),
+            repo_owner: None,
+            repo_name: None,
+            repo_branch: None,
+            apps: Self::initial_apps(app, options),
+            installed_at: Utc::now().timestamp(),
+        };
+        // A forced reinstall keeps the apps the replaced skill was enabled for.
+        if let Some(previous) = index.skills.get(&install_name) {
+            installed.apps.merge_enabled(&previous.apps);
+        }
+
+        let replaced = index
+            .skills
+            .insert(install_name.clone(), installed.clone())
+            .is_some();
+        Self::save_index(&index)?;
+        if !options.no_enable {
+            Self::sync_to_app_dir(&install_name, app, index.sync_method)?;
+        }
+        if replaced {
+            Self::resync_skill(&install_name, None)?;
+        }
+
+        Ok(installed)
+    }
+
+    /// App flags for a freshly installed skill: only `app`, or none when staging.
+    fn initial_apps(app: &AppType, options: InstallOptions) -> SkillApps {
+        if options.no_enable {
+            SkillApps::default()
+        } else {
+            SkillApps::only(app)
+        }
+    }
+
+    fn skill_directory_name(discoverable: &DiscoverableSkill) -> String {
+        Path::new(&discoverable.directory)
+            .file_name()
+            .map(|s| s.to_string_lossy().to_string())
+            .unwrap_or_else(|| discoverable.directory.clone())
+    }
+
+    /// Returns the repo label of an installed skill occupying `directory` when it
+    /// comes from a different repo than `discoverable`.
+    fn conflicting_repo(
+        index: &SkillsIndex,
+        directory: &str,
+        discoverable: &DiscoverableSkill,
+    ) -> Option<String> {
+        let existing = index.skills.get(directory)?;
+        let same_repo = existing.repo_owner.as_deref() == Some(discoverable.repo_owner.as_str())
+            && existing.repo_name.as_deref() == Some(discoverable.repo_name.as_str());
+        if same_repo {
+            // Nested skills of one repo may share a leaf directory name.
+            let nested = |key: &str| key.split_once(':').is_some_and(|(_, p)| p.contains('/'));
+            let distinct_nested = !existing.id.eq_ignore_ascii_case(&discoverable.key)
+                && (nested(&existing.id) || nested(&discoverable.key));
+            return distinct_nested.then(|| existing.id.clone());
+        }
+        if let Some(source_url) = &existing.source_url {
+            return Some(source_url.clone());
+        }
+        if existing.repo_owner.is_none()
+            && existing.repo_name.is_none()
+            && !existing.id.starts_with("local:")
+        {
+            return None;
+        }
+
+        Some(format!(
+            "{}/{}",
+            existing.repo_owner.as_deref().unwrap_or("unknown"),
+            existing.repo_name.as_deref().unwrap_or("unknown")
+        ))
+    }
+
+    /// Resolve the SSOT directory name used to install `discoverable`.
+    ///
+    /// Directory collisions across repos fail with `SKILL_DIRECTORY_CONFLICT` unless
+    /// `force` is set, in which case the skill is installed as `<directory>-<owner>`.
+    /// A previous forced install under the alternate name is always reused.
+    pub fn resolve_install_directory(
+        index: &SkillsIndex,
+        discoverable: &DiscoverableSkill,
+        force: bool,
+    ) -> Result<String, AppError> {
+        let directory = Self::skill_directory_name(discoverable);
+        let alternate = format!("{directory}-{}", discoverable.repo_owner);
+
+        let conflict = match Self::conflicting_repo(index, &directory, discoverable) {
+            None => return Ok(directory),
+            Some(existing_repo) => existing_repo,
+        };
+
+        let alternate_taken_by_same_repo = index.skills.contains_key(&alternate)
+            && Self::conflicting_repo(index, &alternate, discoverable).is_none();
+        if alternate_taken_by_same_repo {
+            return Ok(alternate);
+        }
+
+        let (directory, existing_repo) = if force {
+            match Self::conflicting_repo(index, &alternate, discoverable) {
+                None => return Ok(alternate),
+                Some(existing_repo) => (alternate, existing_repo),
+            }
+        } else {
+            (directory, conflict)
+        };
+
+        let new_repo = format!("{}/{}", discoverable.repo_owner, discoverable.repo_name);
+        Err(AppError::Message(format_skill_error(
+            "SKILL_DIRECTORY_CONFLICT",
+            &[
+                ("directory", directory.as_str()),
+                ("existing_repo", existing_repo.as_str()),
+                ("new_repo", new_repo.as_str()),
+            ],
+            Some("uninstallFirst"),
+        )))
+    }
+
+    /// Resolve a spec without the network when possible: installed skills present in the
+    /// SSOT first, then cached repo archives, and only on a miss `discover_available`.
+    /// `refresh` (`--force`) skips both local tiers so discovery re-downloads the repos and
+    /// replaces their caches; offline it falls back to the caches.
+    async fn resolve_install_spec(
+        &self,
+        index: &SkillsIndex,
+        spec: &str,
+        refresh: bool,
+    ) -> Result<DiscoverableSkill, AppError> {
+        if !refresh || crate::http_client::is_offline() {
+            if let Some(found) = Self::installed_as_discoverable(index, spec) {
+                return Ok(found);
+            }
+
+            if let Some(found) =
+                Self::match_install_spec(Self::cached_repo_skills(&index.repos), spec)?
+            {
+                return Ok(found);
+            }
+        }
+
+        crate::http_client::ensure_online("skills install")?;
+        let discoverable = self.discover_available(index.repos.clone()).await?;
+        Self::match_install_spec(discoverable, spec)?
+            .ok_or_else(|| AppError::Message(format!("未找到可安装的 Skill: {spec}")))
+    }
+
+    fn match_install_spec(
+        discoverable: Vec<DiscoverableSkill>,
+        spec: &str,
+    ) -> Result<Option<DiscoverableSkill>, AppError> {
+        // If the user provides full key (owner/name:dir), match by key.
+        if let Some(found) = discoverable.iter().find(|s| s.key == spec) {
+            return Ok(Some(found.clone()));
+        }
+
+        // `owner/name:path/to/dir` for a skill whose leaf name is unique in its repo.
+        if let Some((repo, path)) = spec.split_once(':') {
+            if let Some((_, leaf)) = path.trim_matches('/').rsplit_once('/') {
+                let leaf_key = format!("{repo}:{leaf}");
+                if let Some(found) = discoverable.iter().find(|s| s.key == leaf_key) {
+                    return Ok(Some(found.clone()));
+                }
+            }
+        }
+
+        // Otherwise treat as directory name or leaf of a nested path (may be ambiguous).
+        let mut matches: Vec<DiscoverableSkill> = discoverable
+            .into_iter()
+            .filter(|s| {
+                s.directory.eq_ignore_ascii_case(spec)
+                    || Self::skill_directory_name(s).eq_ignore_ascii_case(spec)
+            })
+            .collect();
+
+        match matches.len() {
+            0 => Ok(None),
+            1 => Ok(matches.pop()),
+            _ => Err(AppError::Message(format!(
+                "Skill 名称不唯一，请使用完整 key（owner/name:directory）: {spec}"
+            ))),
+        }
+    }
+
+    /// An installed repo skill whose files are still in the SSOT, matched by key or
+    /// install directory.
+    fn installed_as_discoverable(index: &SkillsIndex, spec: &str) -> Option<DiscoverableSkill> {
+        let skill = index
+            .skills
+            .values()
+            .find(|s| s.id == spec || s.directory.eq_ignore_ascii_case(spec))?;
+        let (_, source) = skill.id.split_once(':')?;
+        if !Self::get_ssot_dir().ok()?.join(&skill.directory).exists() {
+            return None;
+        }
+
+        Some(DiscoverableSkill {
+            key: skill.id.clone(),
+            name: skill.name.clone(),
+            description: skill.description.clone().unwrap_or_default(),
+            directory: source.to_string(),
+            readme_url: skill.readme_url.clone(),
+            repo_owner: skill.repo_owner.clone()?,
+            repo_name: skill.repo_name.clone()?,
+            repo_branch: skill
+                .repo_branch
+                .clone()
+                .unwrap_or_else(|| "main".to_string()),
+        })
+    }
+
+    // ---------------------------------------------------------------------
+    // Readme
+    // ---------------------------------------------------------------------
+
+    /// Read a skill's `SKILL.md` (or `README.md`): from the SSOT when installed, then the
+    /// repo cache, and only then over HTTP (bounded by the download timeout). A failed
+    /// fetch reports the `readme_url` so the user can open it in a browser instead.
+    pub async fn read_readme(&self, spec: &str) -> Result<SkillReadme, AppError> {
+        let index = Self::load_index()?;
+
+        if let Some(skill) = index
+            .skills
+            .values()
+            .find(|s| s.id == spec || s.directory.eq_ignore_ascii_case(spec))
+        {
+            let dir = Self::get_ssot_dir()?.join(&skill.directory);
+            if let Some((file_name, content)) = Self::read_readme_in(&dir)? {
+                return Ok(SkillReadme {
+                    key: skill.id.clone(),
+                    file_name,
+                    source: SkillReadmeSource::Installed,
+                    readme_url: skill.readme_url.clone(),
+                    content,
+                });
+            }
+        }
+
+        let skill = match Self::match_install_spec(Self::cached_repo_skills(&index.repos), spec)? {
+            Some(found) => found,
+            None => {
+                crate::http_client::ensure_online("skills info --readme")?;
+                let discoverable = self.discover_available(index.repos.clone()).await?;
+                Self::match_install_spec(discoverable, spec)?
+                    .ok_or_else(|| AppError::Message(format!("未找到 Skill: {spec}")))?
+            }
+        };
+
+        if let Some(readme) = Self::read_cached_readme(&skill)? {
+            return Ok(readme);
+        }
+        self.fetch_remote_readme(&skill).await
+    }
+
+    /// First existing file of [`README_FILES`] in `dir`.
+    fn read_readme_in(dir: &Path) -> Result<Option<(String, String)>, AppError> {
+        for name in README_FILES {
+            let path = dir.join(name);
+            if path.is_file() {
+                let content = fs::read_to_string(&path).map_err(|e| AppError::io(&path, e))?;
+                return Ok(Some((name.to_string(), content)));
+            }
+        }
+        Ok(None)
+    }
+
+    fn read_cached_readme(skill: &DiscoverableSkill) -> Result<Option<SkillReadme>, AppError> {
+        let cache = Self::repo_cache_dir(&SkillRepo {
+            owner: skill.repo_owner.clone(),
+            name: skill.repo_name.clone(),
+            branch: skill.repo_branch.clone(),
+            enabled: true,
+            token: None,
+        });
+        if !cache.is_dir() {
+            return Ok(None);
+        }
+        let Some(dir) = Self::find_skill_dir_in_repo(&cache, &skill.directory)? else {
+            return Ok(None);
+        };
+        Ok(
+            Self::read_readme_in(&dir)?.map(|(file_name, content)| SkillReadme {
+                key: skill.key.clone(),
+                file_name,
+                source: SkillReadmeSource::Cache,
+                readme_url: skill.readme_url.clone(),
+                content,
+            }),
+        )
+    }
+
+    /// Path of the skill dir inside its repo, derived from the `readme_url` tree link.
+    fn repo_relative_path(skill: &DiscoverableSkill) -> Option<String> {
+        let prefix = format!(
+            "https://github.com/{}/{}/tree/{}/",
+            skill.repo_owner, skill.repo_name, skill.repo_branch
+        );
+        skill
+            .readme_url
+            .as_deref()?
+            .strip_prefix(&prefix)
+            .map(|path| path.trim_matches('/').to_string())
+    }
+
+    async fn fetch_remote_readme(
+        &self,
+        skill: &DiscoverableSkill,
+    ) -> Result<SkillReadme, AppError> {
+        crate::http_client::ensure_online("skills info --readme")?;
+        let fallback = skill.readme_url.clone().unwrap_or_else(|| {
+            format!(
+                "https://github.com/{}/{}",
+                skill.repo_owner, skill.repo_name
+            )
+        });
+        let failed = |reason: String| {
+            AppError::localized(
+                "skills.readme_fetch_failed",
+                format!(
+                    "获取 {} 的文档失败: {reason}\n可在浏览器中查看: {fallback}",
+                    skill.key
+                ),
+                format!(
+                    "Failed to fetch the docs of {}: {reason}\nView them in a browser: {fallback}",
+                    skill.key
+                ),
+            )
+        };
+
+        let path = Self::repo_relative_path(skill).unwrap_or_else(|| skill.directory.clone());
+        let base = format!(
+            "https://raw.githubusercontent.com/{}/{}/{}/{}",
+            skill.repo_owner, skill.repo_name, skill.repo_branch, path
+        );
+        let fetch = async {
+            for name in README_FILES {
+                let url = format!("{base}/{name}");
+                let response = self
+                    .http_client
+                    .get(&url)
+                    .send()
+                    .await
+                    .map_err(|e| crate::http_client::describe_error(&e))?;
+                let status = response.status();
+                if status == reqwest::StatusCode::NOT_FOUND {
+                    continue;
+                }
+                if !status.is_success() {
+                    return Err(format!("{url}: HTTP {}", status.as_u16()));
+                }
+                let content = response
+                    .text()
+                    .await
+                    .map_err(|e| crate::http_client::describe_error(&e))?;
+                return Ok(Some((name.to_string(), content)));
+            }
+            Ok(None)
+        };
+
+        let (file_name, content) = timeout(self.download_timeout, fetch)
+            .await
+            .map_err(|_| {
+                failed(format!(
+                    "timed out after {}s",
+                    self.download_timeout.as_secs()
+                ))
+            })?
+            .map_err(failed)?
+            .ok_or_else(|| failed(format!("no {} found", README_FILES.join(" or "))))?;
+
+        Ok(SkillReadme {
+            key: skill.key.clone(),
+            file_name,
+            source: SkillReadmeSource::Remote,
+            readme_url: skill.readme_url.clone(),
+            content,
+        })
+    }
+
+    // ---------------------------------------------------------------------
+    // Repo cache
+    // ---------------------------------------------------------------------
+
+    /// 仓库解压缓存：`~/.cc-switch/cache/skill-repos/<owner>/<name>@<branch>/`
+    pub fn repo_cache_dir(repo: &SkillRepo) -> PathBuf {
+        crate::config::get_app_cache_dir()
+            .join("skill-repos")
+            .join(&repo.owner)
+            .join(format!("{}@{}", repo.name, repo.branch))
+    }
+
+    /// 用刚下载的仓库内容替换缓存（失败只记录日志，不影响主流程）
+    fn store_repo_cache(repo: &SkillRepo, extracted: &Path) {
+        let cache = Self::repo_cache_dir(repo);
+        let result =
+            Self::remove_path(&cache).and_then(|_| Self::copy_dir_recursive(extracted, &cache));
+        if let Err(e) = result {
+            log::warn!("缓存仓库 {}/{} 失败: {}", repo.owner, repo.name, e);
+            let _ = fs::remove_dir_all(&cache);
+        }
+    }
+
+    /// 重新下载仓库并替换其缓存，返回其中的 Skill 数量
+    pub async fn refresh_repo_cache(&self, repo: &SkillRepo) -> Result<usize, AppError> {
+        crate::http_client::ensure_online("skills repos refresh")?;
+        Ok(self.download_and_scan_repo(repo, true).await?.len())
+    }
+
+    /// 从已缓存的启用仓库中列出 Skills（不访问网络）
+    fn cached_repo_skills(repos: &[SkillRepo]) -> Vec<DiscoverableSkill> {
+        let mut skills = Vec::new();
+        for repo in repos.iter().filter(|r| r.enabled) {
+            let cache = Self::repo_cache_dir(repo);
+            if !cache.is_dir() {
+                continue;
+            }
+            match Self::scan_repo_skills(repo, &cache) {
+                Ok(found) => skills.extend(found),
+                Err(e) => log::warn!("读取仓库缓存 {}/{} 失败: {}", repo.owner, repo.name, e),
+            }
+        }
+        Self::deduplicate_discoverable(&mut skills);
+        skills
+    }
+
+    // ---------------------------------------------------------------------
+    // Prune orphaned SSOT dirs
+    // ---------------------------------------------------------------------
+
+    /// 列出 SSOT 中未被 skills.json 引用的目录（安装中断或手动改动索引后残留）
+    pub fn find_orphaned_ssot_dirs() -> Result<Vec<OrphanedSkillDir>, AppError> {
+        let mut index = Self::load_index()?;
+        let _ = Self::migrate_ssot_if_pending(&mut index)?;
 
-            if !source.exists() {
-                let _ = fs::remove_dir_all(&temp_dir);
-                let source_path_string = source.display().to_string();
-                return Err(AppError::Message(format_skill_error(
-                    "SKILL_DIR_NOT_FOUND",
-                    &[("path", source_path_string.as_str())],
-                    Some("checkRepoUrl"),
-                )));
+        let ssot = Self::get_ssot_dir()?;
+        let mut orphans = Vec::new();
+        for entry in fs::read_dir(&ssot).map_err(|e| AppError::io(&ssot, e))? {
+            let entry = entry.map_err(|e| AppError::io(&ssot, e))?;
+            let path = entry.path();
+            if !path.is_dir() {
+                continue;
+            }
+            let directory = entry.file_name().to_string_lossy().to_string();
+            if directory.starts_with('.') {
+                continue;
             }
 
-            Self::copy_dir_recursive(&source, &dest)?;
-            let _ = fs::remove_dir_all(&temp_dir);
-        }
+            // 嵌套安装目录（owner 前缀的相对路径）也算作引用了顶层目录
+            let nested_prefix = format!("{directory}/");
+            let indexed = index
+                .skills
+                .values()
+                .any(|s| s.directory == directory || s.directory.starts_with(&nested_prefix));
+            if indexed {
+                continue;
+            }
 
-        let installed = InstalledSkill {
-            id: discoverable.key.clone(),
-            name: discoverable.name.clone(),
-            description: if discoverable.description.trim().is_empty() {
-                None
-            } else {
-                Some(discoverable.description.clone())
-            },
-            directory: install_name.clone(),
-            readme_url: discoverable.readme_url.clone(),
-            repo_owner: Some(discoverable.repo_owner.clone()),
-            repo_name: Some(discoverable.repo_name.clone()),
-            repo_branch: Some(discoverable.repo_branch.clone()),
-            apps: SkillApps::only(app),
-            installed_at: Utc::now().timestamp(),
-        };
+            let mut referenced_by = Vec::new();
+            for app in [AppType::Claude, AppType::Codex, AppType::Gemini] {
+                if Self::app_links_to(&app, &directory, &path) {
+                    referenced_by.push(app);
+                }
+            }
 
-        index.skills.insert(install_name.clone(), installed.clone());
-        Self::save_index(&index)?;
-        Self::sync_to_app_dir(&install_name, app, index.sync_method)?;
+            orphans.push(OrphanedSkillDir {
+                size_bytes: Self::dir_size(&path),
+                directory,
+                path,
+                referenced_by,
+            });
+        }
 
-        Ok(installed)
+        orphans.sort_by(|a, b| a.directory.cmp(&b.directory));
+        Ok(orphans)
     }
 
-    async fn resolve_install_spec(
-        &self,
-        index: &SkillsIndex,
-        spec: &str,
-    ) -> Result<DiscoverableSkill, AppError> {
-        // If the user provides full key (owner/name:dir), match by key.
-        let discoverable = self.discover_available(index.repos.clone()).await?;
-
-        if let Some(found) = discoverable.iter().find(|s| s.key == spec) {
-            return Ok(found.clone());
+    /// 删除孤立目录；仍被应用目录引用时拒绝删除
+    pub fn remove_orphaned_ssot_dir(orphan: &OrphanedSkillDir) -> Result<(), AppError> {
+        if !orphan.referenced_by.is_empty() {
+            return Err(AppError::Message(format!(
+                "Skill 目录仍被应用引用，跳过删除: {}",
+                orphan.directory
+            )));
         }
+        crate::dry_run::remove_dir_all(&orphan.path).map_err(|e| AppError::io(&orphan.path, e))
+    }
 
-        // Otherwise treat as directory name (may be ambiguous).
-        let matches: Vec<DiscoverableSkill> = discoverable
-            .into_iter()
-            .filter(|s| s.directory.eq_ignore_ascii_case(spec))
-            .collect();
-
-        match matches.len() {
-            0 => Err(AppError::Message(format!("未找到可安装的 Skill: {spec}"))),
-            1 => Ok(matches[0].clone()),
-            _ => Err(AppError::Message(format!(
-                "Skill 名称不唯一，请使用完整 key（owner/name:directory）: {spec}"
-            ))),
+    /// 应用 skills 目录中同名条目是否为指向 `target` 的符号链接
+    fn app_links_to(app: &AppType, directory: &str, target: &Path) -> bool {
+        let Ok(app_dir) = Self::get_app_skills_dir(app) else {
+            return false;
+        };
+        let link = app_dir.join(directory);
+        if !Self::is_symlink(&link) {
+            return false;
         }
+        match (fs::canonicalize(&link), fs::canonicalize(target)) {
+            (Ok(a), Ok(b)) => a == b,
+            _ => false,
+        }
+    }
+
+    fn dir_size(path: &Path) -> u64 {
+        let Ok(entries) = fs::read_dir(path) else {
+            return 0;
+        };
+        entries
+            .flatten()
+            .map(|entry| match entry.metadata() {
+                Ok(meta) if meta.is_dir() => Self::dir_size(&entry.path()),
+                Ok(meta) => meta.len(),
+                Err(_) => 0,
+            })
+            .sum()
     }
 
     // ---------------------------------------------------------------------
@@ -930,18 +2808,7 @@ impl SkillService {
                     continue;
                 }
 
-                let skill_md = path.join("SKILL.md");
-                let (name, description) = if skill_md.exists() {
-                    match Self::parse_skill_metadata_static(&skill_md) {
-                        Ok(meta) => (
-                            meta.name.unwrap_or_else(|| dir_name.clone()),
-                            meta.description,
-                        ),
-                        Err(_) => (dir_name.clone(), None),
-                    }
-                } else {
-                    (dir_name.clone(), None)
-                };
+                let (name, description) = Self::dir_metadata(&path, &dir_name);
 
                 let app_str = match app {
                     AppType::Claude => "claude",
@@ -961,9 +2828,38 @@ impl SkillService {
             }
         }
 
+        // Dirs left only in the SSOT (e.g. after `uninstall --keep-ssot`).
+        for orphan in Self::find_orphaned_ssot_dirs()? {
+            let (name, description) = Self::dir_metadata(&orphan.path, &orphan.directory);
+            unmanaged
+                .entry(orphan.directory.clone())
+                .and_modify(|s| s.found_in.push("ssot".to_string()))
+                .or_insert(UnmanagedSkill {
+                    directory: orphan.directory,
+                    name,
+                    description,
+                    found_in: vec!["ssot".to_string()],
+                });
+        }
+
         Ok(unmanaged.into_values().collect())
     }
 
+    /// Name and description from a skill dir's `SKILL.md`, falling back to the dir name.
+    fn dir_metadata(path: &Path, dir_name: &str) -> (String, Option<String>) {
+        let skill_md = path.join("SKILL.md");
+        if !skill_md.exists() {
+            return (dir_name.to_string(), None);
+        }
+        match Self::parse_skill_metadata_static(&skill_md) {
+            Ok(meta) => (
+                meta.name.unwrap_or_else(|| dir_name.to_string()),
+                meta.description,
+            ),
+            Err(_) => (dir_name.to_string(), None),
+        }
+    }
+
     pub fn import_from_apps(directories: Vec<String>) -> Result<Vec<InstalledSkill>, AppError> {
         let mut index = Self::load_index()?;
         let ssot_dir = Self::get_ssot_dir()?;
@@ -985,25 +2881,18 @@ impl SkillService {
                 }
             }
 
+            let dest = ssot_dir.join(&dir_name);
+            // Not in any app dir: adopt a dir left in the SSOT (enabled for no app).
+            if source_path.is_none() && dest.is_dir() {
+                source_path = Some(dest.clone());
+            }
             let Some(source) = source_path else { continue };
 
-            let dest = ssot_dir.join(&dir_name);
             if !dest.exists() {
                 Self::copy_dir_recursive(&source, &dest)?;
             }
 
-            let skill_md = dest.join("SKILL.md");
-            let (name, description) = if skill_md.exists() {
-                match Self::parse_skill_metadata_static(&skill_md) {
-                    Ok(meta) => (
-                        meta.name.unwrap_or_else(|| dir_name.clone()),
-                        meta.description,
-                    ),
-                    Err(_) => (dir_name.clone(), None),
-                }
-            } else {
-                (dir_name.clone(), None)
-            };
+            let (name, description) = Self::dir_metadata(&dest, &dir_name);
 
             let mut apps = SkillApps::default();
             for app in &found_in {
@@ -1019,6 +2908,7 @@ impl SkillService {
                     description: description.clone(),
                     directory: dir_name.clone(),
                     readme_url: None,
+                    source_url: None,
                     repo_owner: None,
                     repo_name: None,
                     repo_branch: None,
@@ -1050,6 +2940,24 @@ impl SkillService {
         repos: Vec<SkillRepo>,
     ) -> Result<Vec<DiscoverableSkill>, AppError> {
         let enabled_repos: Vec<SkillRepo> = repos.into_iter().filter(|r| r.enabled).collect();
+        let external = self.discover_external().await?;
+
+        // 离线模式只读仓库缓存；一个缓存都没有时直接报错，而不是静默返回空列表
+        if crate::http_client::is_offline() {
+            if !enabled_repos.is_empty()
+                && !enabled_repos
+                    .iter()
+                    .any(|repo| Self::repo_cache_dir(repo).is_dir())
+            {
+                crate::http_client::ensure_online("skills discover")?;
+            }
+            let mut skills = Self::cached_repo_skills(&enabled_repos);
+            skills.extend(external);
+            Self::deduplicate_discoverable(&mut skills);
+            skills.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
+            return Ok(skills);
+        }
+
         let tasks = enabled_repos
             .iter()
             .map(|repo| self.fetch_repo_skills(repo));
@@ -1062,6 +2970,7 @@ impl SkillService {
                 Err(e) => log::warn!("获取仓库 {}/{} 技能失败: {}", repo.owner, repo.name, e),
             }
         }
+        skills.extend(external);
 
         Self::deduplicate_discoverable(&mut skills);
         skills.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
@@ -1074,11 +2983,13 @@ impl SkillService {
         let discoverable = self.discover_available(index.repos.clone()).await?;
         let installed_dirs: HashSet<String> =
             index.skills.keys().map(|s| s.to_lowercase()).collect();
+        let installed_ids: HashSet<String> = index.skills.values().map(|s| s.id.clone()).collect();
 
         let mut out: Vec<Skill> = discoverable
             .into_iter()
             .map(|d| {
-                let installed = installed_dirs.contains(&d.directory.to_lowercase());
+                let installed = installed_dirs.contains(&d.directory.to_lowercase())
+                    || installed_ids.contains(&d.key);
                 Skill {
                     key: d.key,
                     name: d.name,
@@ -1170,7 +3081,18 @@ impl SkillService {
         &self,
         repo: &SkillRepo,
     ) -> Result<Vec<DiscoverableSkill>, AppError> {
-        let temp_dir = timeout(std::time::Duration::from_secs(60), self.download_repo(repo))
+        self.download_and_scan_repo(repo, true).await
+    }
+
+    /// Download a repo into a temp dir and scan it; `store_cache` replaces the repo cache
+    /// with the fresh copy.
+    async fn download_and_scan_repo(
+        &self,
+        repo: &SkillRepo,
+        store_cache: bool,
+    ) -> Result<Vec<DiscoverableSkill>, AppError> {
+        let timeout_secs = self.download_timeout.as_secs().to_string();
+        let temp_dir = timeout(self.download_timeout, self.download_repo(repo, None, false))
             .await
             .map_err(|_| {
                 AppError::Message(format_skill_error(
@@ -1178,14 +3100,35 @@ impl SkillService {
                     &[
                         ("owner", repo.owner.as_str()),
                         ("name", repo.name.as_str()),
-                        ("timeout", "60"),
+                        ("timeout", timeout_secs.as_str()),
                     ],
                     Some("checkNetwork"),
                 ))
             })??;
 
+        let skills = Self::scan_repo_skills(repo, &temp_dir);
+        if store_cache && skills.is_ok() {
+            Self::store_repo_cache(repo, &temp_dir);
+        }
+        let _ = fs::remove_dir_all(&temp_dir);
+        skills
+    }
+
+    /// 扫描已解压仓库（下载的临时目录或缓存）中的 Skills
+    fn scan_repo_skills(repo: &SkillRepo, root: &Path) -> Result<Vec<DiscoverableSkill>, AppError> {
         let mut skills = Vec::new();
-        let skill_dirs = Self::scan_skill_dirs(&temp_dir)?;
+        let skill_dirs = Self::scan_skill_dirs(root)?;
+
+        // Leaf names used by more than one skill dir are keyed by relative path instead.
+        let mut leaf_counts: HashMap<String, usize> = HashMap::new();
+        for path in &skill_dirs {
+            if let Some(leaf) = path.file_name() {
+                *leaf_counts
+                    .entry(leaf.to_string_lossy().to_lowercase())
+                    .or_default() += 1;
+            }
+        }
+
         for path in skill_dirs {
             let skill_md = path.join("SKILL.md");
             if !skill_md.exists() {
@@ -1200,21 +3143,29 @@ impl SkillService {
                 },
             };
 
-            let directory = path
+            let leaf = path
                 .file_name()
                 .map(|s| s.to_string_lossy().to_string())
                 .unwrap_or_default();
-            if directory.is_empty() {
+            if leaf.is_empty() {
                 continue;
             }
 
-            let relative = path.strip_prefix(&temp_dir).unwrap_or(&path);
+            let relative = path.strip_prefix(root).unwrap_or(&path);
             let relative_path = relative.to_string_lossy().replace('\\', "/");
             let readme_path = if relative_path.trim().is_empty() {
-                directory.clone()
+                leaf.clone()
             } else {
                 relative_path
             };
+            let directory = if leaf_counts
+                .get(&leaf.to_lowercase())
+                .is_some_and(|count| *count > 1)
+            {
+                readme_path.clone()
+            } else {
+                leaf
+            };
 
             skills.push(DiscoverableSkill {
                 key: format!("{}/{}:{}", repo.owner, repo.name, directory),
@@ -1231,7 +3182,6 @@ impl SkillService {
             });
         }
 
-        let _ = fs::remove_dir_all(&temp_dir);
         Ok(skills)
     }
 
@@ -1262,24 +3212,88 @@ impl SkillService {
     }
 
     fn parse_skill_metadata_static(path: &Path) -> Result<SkillMetadata, AppError> {
+        match Self::parse_front_matter(path)? {
+            Some(Ok(meta)) => Ok(meta),
+            Some(Err(e)) => {
+                log::warn!("解析 {} 的 front matter 失败: {e}", path.display());
+                Ok(SkillMetadata {
+                    name: None,
+                    description: None,
+                })
+            }
+            None => Ok(SkillMetadata {
+                name: None,
+                description: None,
+            }),
+        }
+    }
+
+    /// Read the YAML front matter of a `SKILL.md`: `None` when there is no front matter,
+    /// `Some(Err)` with the YAML error when it does not parse.
+    fn parse_front_matter(
+        path: &Path,
+    ) -> Result<Option<Result<SkillMetadata, serde_yaml::Error>>, AppError> {
         let content = fs::read_to_string(path).map_err(|e| AppError::io(path, e))?;
         let content = content.trim_start_matches('\u{feff}');
         let parts: Vec<&str> = content.splitn(3, "---").collect();
         if parts.len() < 3 {
-            return Ok(SkillMetadata {
-                name: None,
-                description: None,
-            });
+            return Ok(None);
         }
-        let front_matter = parts[1].trim();
-        let meta: SkillMetadata = serde_yaml::from_str(front_matter).unwrap_or(SkillMetadata {
-            name: None,
-            description: None,
-        });
+        Ok(Some(serde_yaml::from_str(parts[1].trim())))
+    }
+
+    /// Strict validation of a skill directory: `SKILL.md` must exist, start with YAML
+    /// front matter that parses, and declare a non-empty `name`.
+    pub fn validate_skill_metadata(skill_dir: &Path) -> Result<SkillMetadata, AppError> {
+        let skill_md = skill_dir.join("SKILL.md");
+        let path_str = skill_md.display().to_string();
+        if !skill_md.is_file() {
+            return Err(AppError::Message(format_skill_error(
+                "SKILL_MD_MISSING",
+                &[("path", path_str.as_str())],
+                Some("checkSkillMd"),
+            )));
+        }
+
+        let meta = match Self::parse_front_matter(&skill_md)? {
+            None => {
+                return Err(AppError::Message(format_skill_error(
+                    "SKILL_FRONT_MATTER_MISSING",
+                    &[("path", path_str.as_str())],
+                    Some("checkSkillMd"),
+                )))
+            }
+            Some(Err(e)) => {
+                let detail = e.to_string();
+                return Err(AppError::Message(format_skill_error(
+                    "SKILL_FRONT_MATTER_INVALID",
+                    &[("path", path_str.as_str()), ("error", detail.as_str())],
+                    Some("checkSkillMd"),
+                )));
+            }
+            Some(Ok(meta)) => meta,
+        };
+
+        if meta.name.as_deref().is_none_or(|n| n.trim().is_empty()) {
+            return Err(AppError::Message(format_skill_error(
+                "SKILL_NAME_MISSING",
+                &[("path", path_str.as_str())],
+                Some("checkSkillMd"),
+            )));
+        }
+
         Ok(meta)
     }
 
-    async fn download_repo(&self, repo: &SkillRepo) -> Result<PathBuf, AppError> {
+    /// `skill` names the install the download belongs to, for progress events. `checked`
+    /// applies `--sha256` to this download; discovery downloads of other repos are unchecked.
+    async fn download_repo(
+        &self,
+        repo: &SkillRepo,
+        skill: Option<&str>,
+        checked: bool,
+    ) -> Result<PathBuf, AppError> {
+        crate::http_client::ensure_online("downloading a skill repo")?;
         let temp_dir = tempfile::tempdir().map_err(|e| {
             AppError::localized(
                 "skills.tempdir_failed",
@@ -1287,8 +3301,8 @@ impl SkillService {
                 format!("Failed to create temp dir: {e}"),
             )
         })?;
+        // 成功后才 keep；失败或被取消（future 被丢弃）时随 TempDir 一起删除
         let temp_path = temp_dir.path().to_path_buf();
-        let _ = temp_dir.keep();
 
         let branches = if repo.branch.trim().is_empty() {
             vec!["main", "master"]
@@ -1296,15 +3310,34 @@ impl SkillService {
             vec![repo.branch.as_str(), "main", "master"]
         };
 
+        let token = Self::resolve_repo_token(repo)?;
+        let max_retries = crate::settings::get_skill_download_retries();
         let mut last_error: Option<AppError> = None;
         for branch in branches {
-            let url = format!(
-                "https://github.com/{}/{}/archive/refs/heads/{}.zip",
-                repo.owner, repo.name, branch
-            );
-
-            match self.download_and_extract(&url, &temp_path).await {
-                Ok(()) => return Ok(temp_path),
+            let url = Self::archive_url(repo, branch, token.is_some());
+
+            match self
+                .download_with_retry(
+                    &url,
+                    &temp_path,
+                    max_retries,
+                    skill,
+                    token.as_deref(),
+                    checked,
+                )
+                .await
+            {
+                Ok(()) => {
+                    let _ = temp_dir.keep();
+                    return Ok(temp_path);
+                }
+                // 校验失败说明该分支的压缩包不可信，401 换分支也无济于事，不再尝试其他分支
+                Err(
+                    e @ AppError::Localized {
+                        key: CHECKSUM_MISMATCH_KEY | UNAUTHORIZED_KEY,
+                        ..
+                    },
+                ) => return Err(e),
                 Err(e) => {
                     last_error = Some(e);
                     continue;
@@ -1321,37 +3354,265 @@ impl SkillService {
         }))
     }
 
-    async fn download_and_extract(&self, url: &str, dest: &Path) -> Result<(), AppError> {
-        let response = self.http_client.get(url).send().await.map_err(|e| {
-            AppError::localized(
-                "skills.download_failed",
-                format!("下载失败: {e}"),
-                format!("Download failed: {e}"),
-            )
-        })?;
+    /// Download a release archive into a fresh temp dir and extract it there.
+    ///
+    /// The body is streamed to disk and aborted once it exceeds `MAX_SKILL_ARCHIVE_BYTES`.
+    async fn download_archive(&self, url: &str) -> Result<PathBuf, AppError> {
+        use std::io::Write;
+
+        crate::http_client::ensure_online("downloading a skill archive")?;
+
+        let path = url
+            .split(['?', '#'])
+            .next()
+            .unwrap_or(url)
+            .to_ascii_lowercase();
+        let is_zip = path.ends_with(".zip");
+        if !is_zip && !path.ends_with(".tar.gz") && !path.ends_with(".tgz") {
+            return Err(AppError::localized(
+                "skills.archive_unsupported",
+                format!("不支持的压缩包格式（仅支持 .zip / .tar.gz / .tgz）: {url}"),
+                format!("Unsupported archive format (expected .zip, .tar.gz or .tgz): {url}"),
+            ));
+        }
 
+        let mut response = self.http_client.get(url).send().await.map_err(|e| {
+            AppError::Message(format!(
+                "下载失败: {url}: {}",
+                crate::http_client::describe_error(&e)
+            ))
+        })?;
         if !response.status().is_success() {
-            let status = response.status().as_u16().to_string();
-            return Err(AppError::Message(format_skill_error(
-                "DOWNLOAD_FAILED",
-                &[("status", status.as_str())],
-                match status.as_str() {
-                    "403" => Some("http403"),
-                    "404" => Some("http404"),
-                    "429" => Some("http429"),
-                    _ => Some("checkNetwork"),
-                },
+            return Err(AppError::Message(format!(
+                "下载失败: {url}: HTTP {}",
+                response.status().as_u16()
             )));
         }
+        let too_large = || {
+            AppError::localized(
+                "skills.archive_too_large",
+                format!(
+                    "压缩包超过大小上限 {} MB: {url}",
+                    MAX_SKILL_ARCHIVE_BYTES / 1024 / 1024
+                ),
+                format!(
+                    "Archive exceeds the {} MB size limit: {url}",
+                    MAX_SKILL_ARCHIVE_BYTES / 1024 / 1024
+                ),
+            )
+        };
+        if response
+            .content_length()
+            .is_some_and(|len| len > MAX_SKILL_ARCHIVE_BYTES)
+        {
+            return Err(too_large());
+        }
 
-        let bytes = response.bytes().await.map_err(|e| {
+        let temp_dir = tempfile::tempdir().map_err(|e| {
             AppError::localized(
-                "skills.download_failed",
-                format!("读取下载内容失败: {e}"),
-                format!("Failed to read download bytes: {e}"),
+                "skills.tempdir_failed",
+                format!("创建临时目录失败: {e}"),
+                format!("Failed to create temp dir: {e}"),
             )
         })?;
+        let archive_path = temp_dir.path().join(if is_zip {
+            "archive.zip"
+        } else {
+            "archive.tar.gz"
+        });
+        {
+            let mut file =
+                fs::File::create(&archive_path).map_err(|e| AppError::io(&archive_path, e))?;
+            let mut written: u64 = 0;
+            while let Some(chunk) = response
+                .chunk()
+                .await
+                .map_err(|e| AppError::Message(format!("下载失败: {url}: {e}")))?
+            {
+                written += chunk.len() as u64;
+                if written > MAX_SKILL_ARCHIVE_BYTES {
+                    return Err(too_large());
+                }
+                file.write_all(&chunk)
+                    .map_err(|e| AppError::io(&archive_path, e))?;
+            }
+        }
+
+        if self.expected_sha256.is_some() {
+            let bytes = fs::read(&archive_path).map_err(|e| AppError::io(&archive_path, e))?;
+            self.verify_checksum(&bytes)?;
+        }
+
+        let out_dir = temp_dir.path().join("extracted");
+        fs::create_dir_all(&out_dir).map_err(|e| AppError::io(&out_dir, e))?;
+        let kind = if is_zip {
+            ArchiveKind::Zip
+        } else {
+            ArchiveKind::TarGz
+        };
+        archive::extract_file(&archive_path, kind, &out_dir, SKILL_ARCHIVE_LIMITS)?;
+        let _ = fs::remove_file(&archive_path);
+
+        Ok(temp_dir.keep())
+    }
+
+    /// Download with exponential backoff on transient failures (timeouts, 429, 5xx).
+    async fn download_with_retry(
+        &self,
+        url: &str,
+        dest: &Path,
+        max_retries: u32,
+        skill: Option<&str>,
+        token: Option<&str>,
+        checked: bool,
+    ) -> Result<(), AppError> {
+        let mut attempt = 0u32;
+        loop {
+            attempt += 1;
+            let failure = match self
+                .download_and_extract(url, dest, skill, token, checked)
+                .await
+            {
+                Ok(()) => return Ok(()),
+                Err(failure) => failure,
+            };
 
+            let Some(delay) = Self::retry_delay(&failure, attempt, max_retries) else {
+                return Err(failure.into_app_error(attempt));
+            };
+            log::warn!(
+                "下载失败，将在 {}ms 后重试（第 {attempt}/{max_retries} 次重试）: {url}",
+                delay.as_millis()
+            );
+            tokio::time::sleep(delay).await;
+        }
+    }
+
+    /// Delay before retrying after the `attempt`-th failed try, or `None` to give up:
+    /// permanent failures and exhausted retries stop, an honored `Retry-After` is capped.
+    fn retry_delay(
+        failure: &DownloadFailure,
+        attempt: u32,
+        max_retries: u32,
+    ) -> Option<std::time::Duration> {
+        if !failure.is_transient() || attempt > max_retries {
+            return None;
+        }
+        let retry_after = match failure {
+            DownloadFailure::Status { retry_after, .. } => *retry_after,
+            _ => None,
+        };
+        Some(
+            retry_after
+                .map(|d| d.min(std::time::Duration::from_millis(DOWNLOAD_BACKOFF_MAX_MS)))
+                .unwrap_or_else(|| Self::backoff_delay(attempt)),
+        )
+    }
+
+    /// Exponential backoff with "equal jitter": half fixed, half random.
+    fn backoff_delay(attempt: u32) -> std::time::Duration {
+        let exp = DOWNLOAD_BACKOFF_BASE_MS
+            .saturating_mul(1u64 << attempt.saturating_sub(1).min(16))
+            .min(DOWNLOAD_BACKOFF_MAX_MS);
+        let nanos = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| u64::from(d.subsec_nanos()))
+            .unwrap_or(0);
+        let half = exp / 2;
+        std::time::Duration::from_millis(half + nanos % (half + 1))
+    }
+
+    fn parse_retry_after(headers: &reqwest::header::HeaderMap) -> Option<std::time::Duration> {
+        let value = headers.get(reqwest::header::RETRY_AFTER)?.to_str().ok()?;
+        Self::parse_retry_after_value(value, Utc::now())
+    }
+
+    /// `Retry-After` is either delay-seconds or an HTTP-date; a date in the past means now.
+    fn parse_retry_after_value(value: &str, now: DateTime<Utc>) -> Option<std::time::Duration> {
+        let value = value.trim();
+        if let Ok(secs) = value.parse::<u64>() {
+            return Some(std::time::Duration::from_secs(secs));
+        }
+        let at = DateTime::parse_from_rfc2822(value).ok()?;
+        Some(
+            (at.with_timezone(&Utc) - now)
+                .to_std()
+                .unwrap_or(std::time::Duration::ZERO),
+        )
+    }
+
+    async fn download_and_extract(
+        &self,
+        url: &str,
+        dest: &Path,
+        skill: Option<&str>,
+        token: Option<&str>,
+        checked: bool,
+    ) -> Result<(), DownloadFailure> {
+        let mut request = self.http_client.get(url);
+        if let Some(token) = token {
+            request = request.bearer_auth(token);
+        }
+        let mut response = request
+            .send()
+            .await
+            .map_err(DownloadFailure::from_reqwest)?;
+
+        if !response.status().is_success() {
+            return Err(DownloadFailure::Status {
+                status: response.status().as_u16(),
+                retry_after: Self::parse_retry_after(response.headers()),
+            });
+        }
+
+        let (Some(skill), Some(_)) = (skill, &self.progress) else {
+            let bytes = response
+                .bytes()
+                .await
+                .map_err(DownloadFailure::from_reqwest)?;
+            if checked {
+                self.verify_checksum(&bytes)
+                    .map_err(DownloadFailure::Other)?;
+            }
+            return Self::extract_archive(bytes, dest).map_err(DownloadFailure::Other);
+        };
+
+        // Stream the body so progress can be reported per chunk.
+        let total = response.content_length().filter(|len| *len > 0);
+        let mut bytes = Vec::new();
+        let mut last_pct = None;
+        let mut last_reported = 0u64;
+        while let Some(chunk) = response
+            .chunk()
+            .await
+            .map_err(DownloadFailure::from_reqwest)?
+        {
+            bytes.extend_from_slice(&chunk);
+            let received = bytes.len() as u64;
+            let pct = total.map(|total| (received.saturating_mul(100) / total).min(100) as u8);
+            let due = match pct {
+                Some(_) => pct != last_pct,
+                None => received - last_reported >= PROGRESS_BYTES_STEP,
+            };
+            if due {
+                last_pct = pct;
+                last_reported = received;
+                self.report(SkillProgress::Download {
+                    skill: skill.to_string(),
+                    pct,
+                    bytes: received,
+                });
+            }
+        }
+
+        if checked {
+            self.verify_checksum(&bytes)
+                .map_err(DownloadFailure::Other)?;
+        }
+        Self::extract_archive(bytes, dest).map_err(DownloadFailure::Other)
+    }
+
+    fn extract_archive(bytes: impl AsRef<[u8]>, dest: &Path) -> Result<(), AppError> {
         let cursor = std::io::Cursor::new(bytes);
         let mut archive = zip::ZipArchive::new(cursor).map_err(|e| {
             AppError::localized(
@@ -1447,29 +3708,47 @@ impl SkillService {
             }
         }
 
+        // Deterministic order: shallower paths first, then lexicographic.
+        results.sort_by(|a, b| {
+            a.components()
+                .count()
+                .cmp(&b.components().count())
+                .then_with(|| a.cmp(b))
+        });
         Ok(results)
     }
 
+    /// Locate a skill dir inside an extracted repo. `directory` is either a leaf name or a
+    /// relative path (`path/to/foo`); relative paths match exactly, leaf names match the
+    /// first (shallowest, then lexicographic) dir with that name.
     fn find_skill_dir_in_repo(root: &Path, directory: &str) -> Result<Option<PathBuf>, AppError> {
-        let target = directory.trim();
+        let target = directory.trim().trim_matches('/');
         if target.is_empty() {
             return Ok(None);
         }
 
+        let by_path = target.contains('/');
         let mut matches = Vec::new();
         for dir in Self::scan_skill_dirs(root)? {
-            let name = dir
-                .file_name()
-                .map(|s| s.to_string_lossy().to_string())
-                .unwrap_or_default();
-            if name.eq_ignore_ascii_case(target) {
+            let candidate = if by_path {
+                dir.strip_prefix(root)
+                    .unwrap_or(&dir)
+                    .to_string_lossy()
+                    .replace('\\', "/")
+            } else {
+                dir.file_name()
+                    .map(|s| s.to_string_lossy().to_string())
+                    .unwrap_or_default()
+            };
+            if candidate.eq_ignore_ascii_case(target) {
                 matches.push(dir);
             }
         }
 
         if matches.len() > 1 {
             log::warn!(
-                "发现多个同名 skill 目录 '{target}'，将使用第一个匹配项（共 {} 个）",
+                "发现多个同名 skill 目录 '{target}'，将使用 {}（共 {} 个，可使用 owner/name:path/to/{target} 指定）",
+                matches[0].display(),
                 matches.len()
             );
         }
@@ -1478,6 +3757,13 @@ impl SkillService {
     }
 
     fn copy_dir_recursive(src: &Path, dest: &Path) -> Result<(), AppError> {
+        if crate::dry_run::skip(format_args!(
+            "copy directory {} -> {}",
+            src.display(),
+            dest.display()
+        )) {
+            return Ok(());
+        }
         fs::create_dir_all(dest).map_err(|e| AppError::io(dest, e))?;
         for entry in fs::read_dir(src).map_err(|e| AppError::io(src, e))? {
             let entry = entry.map_err(|e| AppError::io(src, e))?;
@@ -1493,3 +3779,124 @@ impl SkillService {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_skill(root: &Path, rel: &str) {
+        let dir = root.join(rel);
+        fs::create_dir_all(&dir).expect("create skill dir");
+        fs::write(dir.join("SKILL.md"), "---\nname: x\n---\n").expect("write SKILL.md");
+    }
+
+    #[test]
+    fn find_skill_dir_matches_nested_paths_deterministically() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        let root = temp.path();
+        write_skill(root, "b/tools/foo");
+        write_skill(root, "a/foo");
+        write_skill(root, "bar");
+
+        let by_path = SkillService::find_skill_dir_in_repo(root, "b/tools/foo")
+            .expect("scan")
+            .expect("nested path should match");
+        assert_eq!(by_path, root.join("b/tools/foo"));
+
+        // Leaf lookups pick the shallowest match regardless of read_dir order.
+        let by_leaf = SkillService::find_skill_dir_in_repo(root, "foo")
+            .expect("scan")
+            .expect("leaf should match");
+        assert_eq!(by_leaf, root.join("a/foo"));
+
+        assert!(SkillService::find_skill_dir_in_repo(root, "c/foo")
+            .expect("scan")
+            .is_none());
+    }
+
+    #[test]
+    fn retry_delay_retries_transient_failures_with_capped_backoff() {
+        use std::time::Duration;
+
+        let status = |status: u16, retry_after: Option<Duration>| DownloadFailure::Status {
+            status,
+            retry_after,
+        };
+
+        // Permanent failures and exhausted retries give up.
+        assert!(SkillService::retry_delay(&status(404, None), 1, 3).is_none());
+        assert!(SkillService::retry_delay(&status(503, None), 4, 3).is_none());
+        let bad_archive = DownloadFailure::Other(AppError::Message("bad zip".to_string()));
+        assert!(SkillService::retry_delay(&bad_archive, 1, 3).is_none());
+
+        // Backoff doubles per attempt with equal jitter, capped at the maximum.
+        let first = SkillService::retry_delay(&status(500, None), 1, 3).expect("retry");
+        assert!(
+            (Duration::from_millis(DOWNLOAD_BACKOFF_BASE_MS / 2)
+                ..=Duration::from_millis(DOWNLOAD_BACKOFF_BASE_MS))
+                .contains(&first),
+            "{first:?}"
+        );
+        let timeout = DownloadFailure::Network {
+            message: "timed out".to_string(),
+            transient: true,
+        };
+        let late = SkillService::retry_delay(&timeout, 30, 30).expect("retry");
+        assert!(late <= Duration::from_millis(DOWNLOAD_BACKOFF_MAX_MS));
+        assert!(late >= Duration::from_millis(DOWNLOAD_BACKOFF_MAX_MS / 2));
+
+        // An honored Retry-After replaces the backoff but is capped too.
+        let delay = SkillService::retry_delay(&status(429, Some(Duration::from_secs(2))), 1, 3);
+        assert_eq!(delay, Some(Duration::from_secs(2)));
+        let delay = SkillService::retry_delay(&status(429, Some(Duration::from_secs(3600))), 1, 3);
+        assert_eq!(delay, Some(Duration::from_millis(DOWNLOAD_BACKOFF_MAX_MS)));
+    }
+
+    #[test]
+    fn retry_after_accepts_seconds_and_http_dates() {
+        use std::time::Duration;
+
+        let now = DateTime::parse_from_rfc3339("2015-10-21T07:28:00Z")
+            .expect("now")
+            .with_timezone(&Utc);
+        assert_eq!(
+            SkillService::parse_retry_after_value(" 120 ", now),
+            Some(Duration::from_secs(120))
+        );
+        assert_eq!(
+            SkillService::parse_retry_after_value("Wed, 21 Oct 2015 07:28:30 GMT", now),
+            Some(Duration::from_secs(30))
+        );
+        assert_eq!(
+            SkillService::parse_retry_after_value("Wed, 21 Oct 2015 07:00:00 GMT", now),
+            Some(Duration::ZERO)
+        );
+        assert!(SkillService::parse_retry_after_value("soon", now).is_none());
+    }
+
+    #[test]
+    fn verify_checksum_rejects_mismatched_archives() {
+        let archive = b"PK fake archive";
+        let hash = crate::util::hash::compute_sha256_hex(archive);
+        let service = SkillService::new()
+            .expect("service")
+            .with_sha256(&hash.to_uppercase())
+            .expect("valid hash");
+        assert!(service.verify_checksum(archive).is_ok());
+
+        let err = service
+            .verify_checksum(b"tampered")
+            .expect_err("mismatch should fail");
+        assert!(matches!(
+            err,
+            AppError::Localized {
+                key: CHECKSUM_MISMATCH_KEY,
+                ..
+            }
+        ));
+        assert!(SkillService::new()
+            .expect("service")
+            .with_sha256("not-a-hash")
+            .is_err());
+    }
+}
@@ -96,7 +96,8 @@ impl SpeedtestService {
     }
 
     fn build_client(timeout_secs: u64) -> Result<Client, AppError> {
-        Client::builder()
+        crate::http_client::ensure_online("speedtest")?;
+        crate::http_client::apply_proxy(Client::builder())?
             .timeout(Duration::from_secs(timeout_secs))
             .redirect(reqwest::redirect::Policy::limited(5))
             .user_agent("cc-switch-speedtest/1.0")
@@ -4,6 +4,7 @@ use std::fs;
 use std::path::PathBuf;
 use std::sync::{OnceLock, RwLock};
 
+use crate::app_config::AppType;
 use crate::error::AppError;
 
 /// 自定义端点配置
@@ -30,6 +31,26 @@ pub struct SecuritySettings {
     pub auth: Option<SecurityAuthSettings>,
 }
 
+/// `cc-switch update` 跟踪的发布渠道
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum UpdateChannel {
+    /// 仅正式版（GitHub `releases/latest`）
+    #[default]
+    Stable,
+    /// 包含预发布版本，取语义版本最高者
+    Prerelease,
+}
+
+impl UpdateChannel {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            UpdateChannel::Stable => "stable",
+            UpdateChannel::Prerelease => "prerelease",
+        }
+    }
+}
+
 /// 应用设置结构，允许覆盖默认配置目录
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -49,12 +70,43 @@ pub struct AppSettings {
     pub gemini_config_dir: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub language: Option<String>,
+    /// 各应用的强调色（颜色名或 #rrggbb），未设置时使用内置默认色
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub theme_color_claude: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub theme_color_codex: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub theme_color_gemini: Option<String>,
     /// 是否开机自启
     #[serde(default)]
     pub launch_on_startup: bool,
     /// Skills 同步方式（auto|symlink|copy）
     #[serde(default)]
     pub skill_sync_method: crate::services::skill::SyncMethod,
+    /// Skills 仓库下载的最大重试次数（仅针对超时、429、5xx 等临时错误）
+    #[serde(default = "default_skill_download_retries")]
+    pub skill_download_retries: u32,
+    /// Skills 仓库下载（含重试）的总超时（秒）
+    #[serde(default = "default_skill_download_timeout_secs")]
+    pub skill_download_timeout_secs: u64,
+    /// Skills 单次 HTTP 请求超时（秒）
+    #[serde(default = "default_skill_http_timeout_secs")]
+    pub skill_http_timeout_secs: u64,
+    /// 外部 Skills 目录命令（stdin 为查询，stdout 为 JSON 数组），需 `--allow-external-discovery`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub skill_discovery_command: Option<String>,
+    /// 切换供应商后执行的命令模板（支持 `{app}` / `{id}` 占位符）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub post_switch_hook: Option<String>,
+    /// 出站 HTTP 代理（URL，或 `off` 禁用代理），未设置时遵循环境变量
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub proxy: Option<String>,
+    /// `cc-switch update` 默认使用的发布渠道
+    #[serde(default)]
+    pub update_channel: UpdateChannel,
+    /// 自更新使用的发布站点（GitHub Enterprise 或内部镜像），未设置时为 github.com
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub release_host: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub security: Option<SecuritySettings>,
     /// Claude 自定义端点列表
@@ -73,6 +125,18 @@ fn default_minimize_to_tray_on_close() -> bool {
     true
 }
 
+fn default_skill_download_retries() -> u32 {
+    3
+}
+
+fn default_skill_download_timeout_secs() -> u64 {
+    60
+}
+
+fn default_skill_http_timeout_secs() -> u64 {
+    10
+}
+
 impl Default for AppSettings {
     fn default() -> Self {
         Self {
@@ -83,8 +147,19 @@ impl Default for AppSettings {
             codex_config_dir: None,
             gemini_config_dir: None,
             language: None,
+            theme_color_claude: None,
+            theme_color_codex: None,
+            theme_color_gemini: None,
             launch_on_startup: false,
             skill_sync_method: crate::services::skill::SyncMethod::default(),
+            skill_download_retries: default_skill_download_retries(),
+            skill_download_timeout_secs: default_skill_download_timeout_secs(),
+            skill_http_timeout_secs: default_skill_http_timeout_secs(),
+            skill_discovery_command: None,
+            post_switch_hook: None,
+            proxy: None,
+            update_channel: UpdateChannel::default(),
+            release_host: None,
             security: None,
             custom_endpoints_claude: HashMap::new(),
             custom_endpoints_codex: HashMap::new(),
@@ -94,12 +169,9 @@ impl Default for AppSettings {
 
 impl AppSettings {
     fn settings_path() -> PathBuf {
-        // settings.json 必须使用固定路径，不能被 app_config_dir 覆盖
-        // 否则会造成循环依赖：读取 settings 需要知道路径，但路径在 settings 中
-        dirs::home_dir()
-            .expect("无法获取用户主目录")
-            .join(".cc-switch")
-            .join("settings.json")
+        // settings.json 不能由 settings 内的字段决定路径（否则循环依赖），
+        // 但跟随 --config-dir / CC_SWITCH_CONFIG_DIR 这类进程级覆盖
+        crate::config::get_app_config_dir().join("settings.json")
     }
 
     fn normalize_paths(&mut self) {
@@ -160,12 +232,12 @@ impl AppSettings {
         let path = Self::settings_path();
 
         if let Some(parent) = path.parent() {
-            fs::create_dir_all(parent).map_err(|e| AppError::io(parent, e))?;
+            crate::dry_run::create_dir_all(parent).map_err(|e| AppError::io(parent, e))?;
         }
 
         let json = serde_json::to_string_pretty(&normalized)
             .map_err(|e| AppError::JsonSerialize { source: e })?;
-        fs::write(&path, json).map_err(|e| AppError::io(&path, e))?;
+        crate::dry_run::write(&path, json).map_err(|e| AppError::io(&path, e))?;
         Ok(())
     }
 }
@@ -175,7 +247,7 @@ fn settings_store() -> &'static RwLock<AppSettings> {
     STORE.get_or_init(|| RwLock::new(AppSettings::load()))
 }
 
-fn resolve_override_path(raw: &str) -> PathBuf {
+pub(crate) fn resolve_override_path(raw: &str) -> PathBuf {
     if raw == "~" {
         if let Some(home) = dirs::home_dir() {
             return home;
@@ -193,6 +265,11 @@ fn resolve_override_path(raw: &str) -> PathBuf {
     PathBuf::from(raw)
 }
 
+/// settings.json 的路径
+pub fn get_settings_path() -> PathBuf {
+    AppSettings::settings_path()
+}
+
 pub fn get_settings() -> AppSettings {
     settings_store().read().expect("读取设置锁失败").clone()
 }
@@ -251,6 +328,88 @@ pub fn get_gemini_override_dir() -> Option<PathBuf> {
         .map(|p| resolve_override_path(p))
 }
 
+/// 获取指定应用的配置目录覆盖（已展开 `~`）
+pub fn get_app_override_dir(app: &AppType) -> Option<PathBuf> {
+    match app {
+        AppType::Claude => get_claude_override_dir(),
+        AppType::Codex => get_codex_override_dir(),
+        AppType::Gemini => get_gemini_override_dir(),
+    }
+}
+
+/// 指定应用的自定义主题色（原始字符串）
+pub fn get_theme_color(app: &AppType) -> Option<String> {
+    let settings = settings_store().read().ok()?;
+    match app {
+        AppType::Claude => settings.theme_color_claude.clone(),
+        AppType::Codex => settings.theme_color_codex.clone(),
+        AppType::Gemini => settings.theme_color_gemini.clone(),
+    }
+}
+
+/// 设置或清除（`None`）指定应用的主题色
+pub fn set_theme_color(app: &AppType, color: Option<String>) -> Result<(), AppError> {
+    let mut settings = get_settings();
+    match app {
+        AppType::Claude => settings.theme_color_claude = color,
+        AppType::Codex => settings.theme_color_codex = color,
+        AppType::Gemini => settings.theme_color_gemini = color,
+    }
+    update_settings(settings)
+}
+
+/// 设置或清除（`None`）指定应用的配置目录覆盖
+pub fn set_app_override_dir(app: &AppType, dir: Option<String>) -> Result<(), AppError> {
+    let mut settings = get_settings();
+    match app {
+        AppType::Claude => settings.claude_config_dir = dir,
+        AppType::Codex => settings.codex_config_dir = dir,
+        AppType::Gemini => settings.gemini_config_dir = dir,
+    }
+    update_settings(settings)
+}
+
+pub fn get_post_switch_hook() -> Option<String> {
+    settings_store()
+        .read()
+        .ok()?
+        .post_switch_hook
+        .clone()
+        .filter(|cmd| !cmd.trim().is_empty())
+}
+
+/// 设置或清除（`None`）post-switch 钩子
+pub fn set_post_switch_hook(command: Option<String>) -> Result<(), AppError> {
+    let mut settings = get_settings();
+    settings.post_switch_hook = command;
+    update_settings(settings)
+}
+
+pub fn get_proxy() -> Option<String> {
+    settings_store()
+        .read()
+        .ok()?
+        .proxy
+        .clone()
+        .filter(|p| !p.trim().is_empty())
+}
+
+pub fn get_update_channel() -> UpdateChannel {
+    settings_store()
+        .read()
+        .map(|s| s.update_channel)
+        .unwrap_or_default()
+}
+
+pub fn get_release_host() -> Option<String> {
+    settings_store()
+        .read()
+        .ok()?
+        .release_host
+        .clone()
+        .filter(|host| !host.trim().is_empty())
+}
+
 pub fn get_skill_sync_method() -> crate::services::skill::SyncMethod {
     settings_store()
         .read()
@@ -263,3 +422,287 @@ pub fn set_skill_sync_method(method: crate::services::skill::SyncMethod) -> Resu
     settings.skill_sync_method = method;
     update_settings(settings)
 }
+
+pub fn get_skill_download_retries() -> u32 {
+    settings_store()
+        .read()
+        .map(|s| s.skill_download_retries)
+        .unwrap_or_else(|_| default_skill_download_retries())
+}
+
+pub fn get_skill_download_timeout_secs() -> u64 {
+    settings_store()
+        .read()
+        .map(|s| s.skill_download_timeout_secs)
+        .unwrap_or_else(|_| default_skill_download_timeout_secs())
+}
+
+pub fn get_skill_http_timeout_secs() -> u64 {
+    settings_store()
+        .read()
+        .map(|s| s.skill_http_timeout_secs)
+        .unwrap_or_else(|_| default_skill_http_timeout_secs())
+}
+
+pub fn get_skill_discovery_command() -> Option<String> {
+    settings_store()
+        .read()
+        .ok()?
+        .skill_discovery_command
+        .clone()
+        .filter(|cmd| !cmd.trim().is_empty())
+}
+
+/// 可通过 `cc-switch settings` 查看/修改的设置项（settings.json 中的 camelCase 键名）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SettingKey {
+    Language,
+    SkillSyncMethod,
+    SkillDownloadRetries,
+    SkillDownloadTimeoutSecs,
+    SkillHttpTimeoutSecs,
+    SkillDiscoveryCommand,
+    Proxy,
+    UpdateChannel,
+    ReleaseHost,
+    EnableClaudePluginIntegration,
+    ClaudeConfigDir,
+    CodexConfigDir,
+    GeminiConfigDir,
+    PostSwitchHook,
+    ThemeColorClaude,
+    ThemeColorCodex,
+    ThemeColorGemini,
+}
+
+impl SettingKey {
+    pub const ALL: [SettingKey; 17] = [
+        SettingKey::Language,
+        SettingKey::SkillSyncMethod,
+        SettingKey::SkillDownloadRetries,
+        SettingKey::SkillDownloadTimeoutSecs,
+        SettingKey::SkillHttpTimeoutSecs,
+        SettingKey::SkillDiscoveryCommand,
+        SettingKey::Proxy,
+        SettingKey::UpdateChannel,
+        SettingKey::ReleaseHost,
+        SettingKey::EnableClaudePluginIntegration,
+        SettingKey::ClaudeConfigDir,
+        SettingKey::CodexConfigDir,
+        SettingKey::GeminiConfigDir,
+        SettingKey::PostSwitchHook,
+        SettingKey::ThemeColorClaude,
+        SettingKey::ThemeColorCodex,
+        SettingKey::ThemeColorGemini,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SettingKey::Language => "language",
+            SettingKey::SkillSyncMethod => "skillSyncMethod",
+            SettingKey::SkillDownloadRetries => "skillDownloadRetries",
+            SettingKey::SkillDownloadTimeoutSecs => "skillDownloadTimeoutSecs",
+            SettingKey::SkillHttpTimeoutSecs => "skillHttpTimeoutSecs",
+            SettingKey::SkillDiscoveryCommand => "skillDiscoveryCommand",
+            SettingKey::Proxy => "proxy",
+            SettingKey::UpdateChannel => "updateChannel",
+            SettingKey::ReleaseHost => "releaseHost",
+            SettingKey::EnableClaudePluginIntegration => "enableClaudePluginIntegration",
+            SettingKey::ClaudeConfigDir => "claudeConfigDir",
+            SettingKey::CodexConfigDir => "codexConfigDir",
+            SettingKey::GeminiConfigDir => "geminiConfigDir",
+            SettingKey::PostSwitchHook => "postSwitchHook",
+            SettingKey::ThemeColorClaude => "themeColorClaude",
+            SettingKey::ThemeColorCodex => "themeColorCodex",
+            SettingKey::ThemeColorGemini => "themeColorGemini",
+        }
+    }
+
+    /// 取值说明（用于列表展示和校验失败提示）
+    pub fn value_hint(&self) -> &'static str {
+        match self {
+            SettingKey::Language => "en|zh",
+            SettingKey::SkillSyncMethod => "auto|symlink|copy",
+            SettingKey::SkillDownloadRetries => "0-10",
+            SettingKey::SkillDownloadTimeoutSecs | SettingKey::SkillHttpTimeoutSecs => "1-3600",
+            SettingKey::SkillDiscoveryCommand | SettingKey::PostSwitchHook => {
+                "<command> (empty to clear)"
+            }
+            SettingKey::Proxy => "<url>|off (empty to clear)",
+            SettingKey::UpdateChannel => "stable|prerelease",
+            SettingKey::ReleaseHost => "<https url> (empty for github.com)",
+            SettingKey::EnableClaudePluginIntegration => "true|false",
+            SettingKey::ClaudeConfigDir
+            | SettingKey::CodexConfigDir
+            | SettingKey::GeminiConfigDir => "<path> (empty to clear)",
+            SettingKey::ThemeColorClaude
+            | SettingKey::ThemeColorCodex
+            | SettingKey::ThemeColorGemini => "<color name>|#rrggbb (empty to clear)",
+        }
+    }
+
+    pub fn parse(raw: &str) -> Result<Self, AppError> {
+        let raw = raw.trim();
+        Self::ALL
+            .into_iter()
+            .find(|key| key.as_str().eq_ignore_ascii_case(raw))
+            .ok_or_else(|| {
+                let valid = Self::ALL.map(|key| key.as_str()).join(", ");
+                AppError::localized(
+                    "settings.unknown_key",
+                    format!("未知的设置项: {raw}（可用: {valid}）"),
+                    format!("Unknown setting: {raw} (valid keys: {valid})"),
+                )
+            })
+    }
+
+    /// 以字符串形式读取当前值（未设置时为空字符串）
+    pub fn get(&self, settings: &AppSettings) -> String {
+        match self {
+            SettingKey::Language => settings.language.clone().unwrap_or_default(),
+            SettingKey::SkillSyncMethod => match settings.skill_sync_method {
+                crate::services::skill::SyncMethod::Auto => "auto",
+                crate::services::skill::SyncMethod::Symlink => "symlink",
+                crate::services::skill::SyncMethod::Copy => "copy",
+            }
+            .to_string(),
+            SettingKey::SkillDownloadRetries => settings.skill_download_retries.to_string(),
+            SettingKey::SkillDownloadTimeoutSecs => {
+                settings.skill_download_timeout_secs.to_string()
+            }
+            SettingKey::SkillHttpTimeoutSecs => settings.skill_http_timeout_secs.to_string(),
+            SettingKey::SkillDiscoveryCommand => {
+                settings.skill_discovery_command.clone().unwrap_or_default()
+            }
+            SettingKey::Proxy => settings.proxy.clone().unwrap_or_default(),
+            SettingKey::UpdateChannel => settings.update_channel.as_str().to_string(),
+            SettingKey::ReleaseHost => settings.release_host.clone().unwrap_or_default(),
+            SettingKey::EnableClaudePluginIntegration => {
+                settings.enable_claude_plugin_integration.to_string()
+            }
+            SettingKey::ClaudeConfigDir => settings.claude_config_dir.clone().unwrap_or_default(),
+            SettingKey::CodexConfigDir => settings.codex_config_dir.clone().unwrap_or_default(),
+            SettingKey::GeminiConfigDir => settings.gemini_config_dir.clone().unwrap_or_default(),
+            SettingKey::PostSwitchHook => settings.post_switch_hook.clone().unwrap_or_default(),
+            SettingKey::ThemeColorClaude => settings.theme_color_claude.clone().unwrap_or_default(),
+            SettingKey::ThemeColorCodex => settings.theme_color_codex.clone().unwrap_or_default(),
+            SettingKey::ThemeColorGemini => settings.theme_color_gemini.clone().unwrap_or_default(),
+        }
+    }
+
+    /// 校验并写入新值（仅修改内存中的副本，持久化由调用方负责）
+    pub fn set(&self, settings: &mut AppSettings, raw: &str) -> Result<(), AppError> {
+        let value = raw.trim();
+        let invalid = || {
+            AppError::InvalidInput(format!(
+                "Invalid value for {}: '{value}' (expected {})",
+                self.as_str(),
+                self.value_hint()
+            ))
+        };
+
+        match self {
+            SettingKey::Language => {
+                if !matches!(value, "en" | "zh") {
+                    return Err(invalid());
+                }
+                settings.language = Some(value.to_string());
+            }
+            SettingKey::SkillSyncMethod => {
+                settings.skill_sync_method =
+                    <crate::services::skill::SyncMethod as clap::ValueEnum>::from_str(value, true)
+                        .map_err(|_| invalid())?;
+            }
+            SettingKey::SkillDownloadRetries => {
+                let retries: u32 = value.parse().map_err(|_| invalid())?;
+                if retries > 10 {
+                    return Err(invalid());
+                }
+                settings.skill_download_retries = retries;
+            }
+            SettingKey::SkillDownloadTimeoutSecs | SettingKey::SkillHttpTimeoutSecs => {
+                let secs: u64 = value.parse().map_err(|_| invalid())?;
+                if !(1..=3600).contains(&secs) {
+                    return Err(invalid());
+                }
+                if *self == SettingKey::SkillDownloadTimeoutSecs {
+                    settings.skill_download_timeout_secs = secs;
+                } else {
+                    settings.skill_http_timeout_secs = secs;
+                }
+            }
+            SettingKey::SkillDiscoveryCommand => {
+                settings.skill_discovery_command = (!value.is_empty()).then(|| value.to_string());
+            }
+            SettingKey::Proxy => {
+                settings.proxy = if value.is_empty() {
+                    None
+                } else if value.eq_ignore_ascii_case(crate::http_client::PROXY_OFF) {
+                    Some(crate::http_client::PROXY_OFF.to_string())
+                } else {
+                    crate::http_client::parse_proxy_url(value)?;
+                    Some(value.to_string())
+                };
+            }
+            SettingKey::UpdateChannel => {
+                settings.update_channel = <UpdateChannel as clap::ValueEnum>::from_str(value, true)
+                    .map_err(|_| invalid())?;
+            }
+            SettingKey::ReleaseHost => {
+                settings.release_host = if value.is_empty() {
+                    None
+                } else {
+                    Some(crate::cli::commands::update::parse_release_host(value)?)
+                };
+            }
+            SettingKey::EnableClaudePluginIntegration => {
+                settings.enable_claude_plugin_integration = match value.to_lowercase().as_str() {
+                    "true" | "1" | "on" | "yes" => true,
+                    "false" | "0" | "off" | "no" => false,
+                    _ => return Err(invalid()),
+                };
+            }
+            SettingKey::ClaudeConfigDir => {
+                settings.claude_config_dir = Some(value.to_string());
+            }
+            SettingKey::CodexConfigDir => {
+                settings.codex_config_dir = Some(value.to_string());
+            }
+            SettingKey::GeminiConfigDir => {
+                settings.gemini_config_dir = Some(value.to_string());
+            }
+            SettingKey::PostSwitchHook => {
+                settings.post_switch_hook = (!value.is_empty()).then(|| value.to_string());
+            }
+            SettingKey::ThemeColorClaude
+            | SettingKey::ThemeColorCodex
+            | SettingKey::ThemeColorGemini => {
+                let color = if value.is_empty() {
+                    None
+                } else if crate::cli::ui::colors::parse_theme_color(value).is_some() {
+                    Some(value.to_string())
+                } else {
+                    return Err(invalid());
+                };
+                match self {
+                    SettingKey::ThemeColorClaude => settings.theme_color_claude = color,
+                    SettingKey::ThemeColorCodex => settings.theme_color_codex = color,
+                    _ => settings.theme_color_gemini = color,
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// 读取指定设置项的当前值
+pub fn get_setting_value(key: SettingKey) -> String {
+    key.get(&get_settings())
+}
+
+/// 校验并持久化指定设置项
+pub fn set_setting_value(key: SettingKey, raw: &str) -> Result<(), AppError> {
+    let mut settings = get_settings();
+    key.set(&mut settings, raw)?;
+    update_settings(settings)
+}
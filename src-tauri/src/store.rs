@@ -13,6 +13,9 @@ pub struct AppState {
 impl AppState {
     /// 创建新的应用状态
     pub fn try_new() -> Result<Self, AppError> {
+        // CLI 入口已加锁；库调用方按已设置的策略在此获取（重复调用或只读时为空操作）
+        crate::instance_lock::acquire_instance_lock()?;
+
         let app_config_dir = crate::config::get_app_config_dir();
         let db_path = app_config_dir.join("cc-switch.db");
         let config_path = app_config_dir.join("config.json");
@@ -83,6 +86,13 @@ impl AppState {
         })
     }
 
+    /// 从 SQLite 重新加载内存中的 config 快照（用于绕过 AppState 直接写库之后）。
+    pub fn reload(&self) -> Result<(), AppError> {
+        let fresh = export_db_to_multi_app_config(&self.db)?;
+        *self.config.write().map_err(AppError::from)? = fresh;
+        Ok(())
+    }
+
     /// 将内存中的 config 快照持久化到 SQLite（SSOT）。
     pub fn save(&self) -> Result<(), AppError> {
         let config = self.config.read().map_err(AppError::from)?;
@@ -90,7 +100,7 @@ impl AppState {
     }
 }
 
-fn export_db_to_multi_app_config(db: &Database) -> Result<MultiAppConfig, AppError> {
+pub(crate) fn export_db_to_multi_app_config(db: &Database) -> Result<MultiAppConfig, AppError> {
     use crate::app_config::AppType;
     use crate::provider::ProviderManager;
 
@@ -193,6 +203,17 @@ fn persist_multi_app_config_to_db(db: &Database, config: &MultiAppConfig) -> Res
     Ok(())
 }
 
+/// 旧版 skills.json 的 index 版本；没有 version 字段的旧版 SkillStore 视为 0
+pub(crate) fn legacy_skills_index_version(path: &Path) -> Result<u32, AppError> {
+    let raw = std::fs::read_to_string(path).map_err(|e| AppError::io(path, e))?;
+    let value: serde_json::Value = serde_json::from_str(raw.trim_start_matches('\u{feff}'))
+        .map_err(|e| AppError::json(path, e))?;
+    Ok(value
+        .get("version")
+        .and_then(|v| v.as_u64())
+        .map_or(0, |v| v as u32))
+}
+
 fn load_skills_index_for_migration(
     path: &Path,
 ) -> Result<crate::services::skill::SkillsIndex, AppError> {
@@ -233,6 +254,7 @@ fn load_skills_index_for_migration(
             description: None,
             directory: directory.clone(),
             readme_url: None,
+            source_url: None,
             repo_owner: None,
             repo_name: None,
             repo_branch: None,
@@ -245,7 +267,7 @@ fn load_skills_index_for_migration(
     Ok(index)
 }
 
-fn archive_legacy_file(path: &Path, suffix: &str) -> Result<Option<PathBuf>, AppError> {
+pub(crate) fn archive_legacy_file(path: &Path, suffix: &str) -> Result<Option<PathBuf>, AppError> {
     if !path.exists() {
         return Ok(None);
     }
@@ -263,6 +285,6 @@ fn archive_legacy_file(path: &Path, suffix: &str) -> Result<Option<PathBuf>, App
         counter += 1;
     }
 
-    std::fs::rename(path, &candidate).map_err(|e| AppError::io(path, e))?;
+    crate::dry_run::rename(path, &candidate).map_err(|e| AppError::io(path, e))?;
     Ok(Some(candidate))
 }
@@ -219,7 +219,8 @@ struct RequestConfig {
 async fn send_http_request(config: &RequestConfig, timeout_secs: u64) -> Result<String, AppError> {
     // 约束超时范围，防止异常配置导致长时间阻塞
     let timeout = timeout_secs.clamp(2, 30);
-    let client = Client::builder()
+    crate::http_client::ensure_online("usage query")?;
+    let client = crate::http_client::apply_proxy(Client::builder())?
         .timeout(Duration::from_secs(timeout))
         .build()
         .map_err(|e| {
@@ -0,0 +1,387 @@
+//! 进程内解压 ZIP / tar.gz / tar.xz / tar.zst（不调用系统 `tar`）
+//!
+//! 只解出普通文件和目录；越出目标目录的路径、符号链接与设备文件一律跳过。
+//! 条目数与解压后总大小设有上限，防止压缩炸弹。
+
+use std::fs;
+use std::io::{self, BufReader, Read, Seek};
+use std::path::{Component, Path, PathBuf};
+
+use crate::error::AppError;
+
+/// 支持的压缩包格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArchiveKind {
+    Zip,
+    TarGz,
+    TarXz,
+    TarZst,
+}
+
+impl ArchiveKind {
+    /// 先按扩展名判断，再回退到文件头魔数
+    pub fn detect(archive: &Path) -> Result<Self, AppError> {
+        let name = archive
+            .file_name()
+            .map(|n| n.to_string_lossy().to_ascii_lowercase())
+            .unwrap_or_default();
+        let by_ext = [
+            (".zip", Self::Zip),
+            (".tar.gz", Self::TarGz),
+            (".tgz", Self::TarGz),
+            (".tar.xz", Self::TarXz),
+            (".txz", Self::TarXz),
+            (".tar.zst", Self::TarZst),
+            (".tzst", Self::TarZst),
+        ];
+        if let Some((_, kind)) = by_ext.iter().find(|(ext, _)| name.ends_with(ext)) {
+            return Ok(*kind);
+        }
+
+        let mut magic = [0u8; 6];
+        let read = fs::File::open(archive)
+            .and_then(|mut f| f.read(&mut magic))
+            .map_err(|e| AppError::io(archive, e))?;
+        Self::from_magic(&magic[..read]).ok_or_else(|| {
+            let ext = archive
+                .extension()
+                .map(|e| format!(".{}", e.to_string_lossy()))
+                .unwrap_or_else(|| "(none)".to_string());
+            AppError::Message(format!(
+                "Unsupported archive format: {ext} ({})",
+                archive.display()
+            ))
+        })
+    }
+
+    fn from_magic(magic: &[u8]) -> Option<Self> {
+        if magic.starts_with(b"PK\x03\x04") {
+            Some(Self::Zip)
+        } else if magic.starts_with(&[0x1f, 0x8b]) {
+            Some(Self::TarGz)
+        } else if magic.starts_with(&[0xfd, b'7', b'z', b'X', b'Z', 0x00]) {
+            Some(Self::TarXz)
+        } else if magic.starts_with(&[0x28, 0xb5, 0x2f, 0xfd]) {
+            Some(Self::TarZst)
+        } else {
+            None
+        }
+    }
+}
+
+/// 解压上限：条目数（含目录）与解压后的总字节数
+#[derive(Debug, Clone, Copy)]
+pub struct ExtractLimits {
+    pub max_entries: usize,
+    pub max_bytes: u64,
+}
+
+/// 把 `archive` 解压到 `dest`（需已存在）
+pub fn extract_file(
+    archive: &Path,
+    kind: ArchiveKind,
+    dest: &Path,
+    limits: ExtractLimits,
+) -> Result<(), AppError> {
+    let file = fs::File::open(archive).map_err(|e| AppError::io(archive, e))?;
+    let reader = BufReader::new(file);
+    match kind {
+        ArchiveKind::Zip => extract_zip(reader, dest, limits),
+        ArchiveKind::TarGz => extract_tar(flate2::read::GzDecoder::new(reader), dest, limits),
+        ArchiveKind::TarXz => extract_tar(xz2::read::XzDecoder::new(reader), dest, limits),
+        ArchiveKind::TarZst => {
+            let decoder = zstd::stream::read::Decoder::with_buffer(reader)
+                .map_err(|e| invalid_archive("zstd", e))?;
+            extract_tar(decoder, dest, limits)
+        }
+    }
+}
+
+fn extract_zip(
+    reader: impl Read + Seek,
+    dest: &Path,
+    limits: ExtractLimits,
+) -> Result<(), AppError> {
+    let mut archive = zip::ZipArchive::new(reader).map_err(|e| invalid_archive("ZIP", e))?;
+    let mut budget = Budget::new(limits);
+
+    for i in 0..archive.len() {
+        budget.count_entry()?;
+        let mut file = archive.by_index(i).map_err(|e| invalid_archive("ZIP", e))?;
+        let Some(relative) = file.enclosed_name().and_then(|p| safe_relative(&p)) else {
+            continue;
+        };
+        let target = dest.join(relative);
+        if file.is_dir() {
+            fs::create_dir_all(&target).map_err(|e| AppError::io(&target, e))?;
+        } else if file.is_file() {
+            let mode = file.unix_mode();
+            budget.write_file(&mut file, &target)?;
+            apply_mode(&target, mode);
+        }
+    }
+    Ok(())
+}
+
+fn extract_tar(reader: impl Read, dest: &Path, limits: ExtractLimits) -> Result<(), AppError> {
+    let mut archive = tar::Archive::new(reader);
+    let mut budget = Budget::new(limits);
+
+    let entries = archive.entries().map_err(|e| invalid_archive("tar", e))?;
+    for entry in entries {
+        budget.count_entry()?;
+        let mut entry = entry.map_err(|e| invalid_archive("tar", e))?;
+        let path = entry
+            .path()
+            .map_err(|e| invalid_archive("tar", e))?
+            .into_owned();
+        let Some(relative) = safe_relative(&path) else {
+            continue;
+        };
+        let target = dest.join(relative);
+        let entry_type = entry.header().entry_type();
+        if entry_type.is_dir() {
+            fs::create_dir_all(&target).map_err(|e| AppError::io(&target, e))?;
+        } else if entry_type.is_file() {
+            let mode = entry.header().mode().ok();
+            budget.write_file(&mut entry, &target)?;
+            apply_mode(&target, mode);
+        }
+    }
+    Ok(())
+}
+
+/// 归档内路径转为相对路径；含 `..`、根目录或盘符时返回 None
+fn safe_relative(path: &Path) -> Option<PathBuf> {
+    let mut relative = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::Normal(part) => relative.push(part),
+            Component::CurDir => {}
+            _ => return None,
+        }
+    }
+    (!relative.as_os_str().is_empty()).then_some(relative)
+}
+
+struct Budget {
+    limits: ExtractLimits,
+    entries: usize,
+    bytes: u64,
+}
+
+impl Budget {
+    fn new(limits: ExtractLimits) -> Self {
+        Self {
+            limits,
+            entries: 0,
+            bytes: 0,
+        }
+    }
+
+    fn count_entry(&mut self) -> Result<(), AppError> {
+        self.entries += 1;
+        if self.entries > self.limits.max_entries {
+            return Err(AppError::localized(
+                "archive.too_many_entries",
+                format!("压缩包条目超过上限 {}", self.limits.max_entries),
+                format!("Archive has more than {} entries", self.limits.max_entries),
+            ));
+        }
+        Ok(())
+    }
+
+    /// 写出一个文件；按实际解压字节计数，不信任头部声明的大小
+    fn write_file(&mut self, reader: &mut impl Read, target: &Path) -> Result<(), AppError> {
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent).map_err(|e| AppError::io(parent, e))?;
+        }
+        let remaining = self.limits.max_bytes.saturating_sub(self.bytes);
+        let mut out = fs::File::create(target).map_err(|e| AppError::io(target, e))?;
+        let written = io::copy(&mut reader.take(remaining + 1), &mut out)
+            .map_err(|e| AppError::io(target, e))?;
+        if written > remaining {
+            return Err(AppError::localized(
+                "archive.too_large",
+                format!(
+                    "解压后的内容超过上限 {} MB",
+                    self.limits.max_bytes / 1024 / 1024
+                ),
+                format!(
+                    "Extracted content exceeds the {} MB limit",
+                    self.limits.max_bytes / 1024 / 1024
+                ),
+            ));
+        }
+        self.bytes += written;
+        Ok(())
+    }
+}
+
+fn invalid_archive(format: &str, e: impl std::fmt::Display) -> AppError {
+    AppError::localized(
+        "archive.invalid",
+        format!("{format} 压缩包损坏: {e}"),
+        format!("Invalid {format} archive: {e}"),
+    )
+}
+
+/// 保留可执行位等权限（只取低 9 位）
+#[cfg(unix)]
+fn apply_mode(path: &Path, mode: Option<u32>) {
+    use std::os::unix::fs::PermissionsExt;
+    if let Some(mode) = mode {
+        let _ = fs::set_permissions(path, fs::Permissions::from_mode(mode & 0o777));
+    }
+}
+
+#[cfg(not(unix))]
+fn apply_mode(_path: &Path, _mode: Option<u32>) {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    const LIMITS: ExtractLimits = ExtractLimits {
+        max_entries: 16,
+        max_bytes: 1024,
+    };
+
+    fn tar_gz(files: &[(&str, &[u8])]) -> Vec<u8> {
+        let encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::fast());
+        let mut builder = tar::Builder::new(encoder);
+        for (path, data) in files {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(data.len() as u64);
+            header.set_mode(0o644);
+            header.set_entry_type(tar::EntryType::Regular);
+            // set_path 会拒绝 `..`，直接写入名字字段以构造恶意条目
+            header.as_gnu_mut().unwrap().name[..path.len()].copy_from_slice(path.as_bytes());
+            header.set_cksum();
+            builder.append(&header, *data).unwrap();
+        }
+        builder.into_inner().unwrap().finish().unwrap()
+    }
+
+    #[test]
+    fn tar_gz_extracts_files_and_skips_escaping_paths() {
+        let dir = tempfile::tempdir().unwrap();
+        let bytes = tar_gz(&[
+            ("pack/foo/SKILL.md", &b"# Foo"[..]),
+            ("../escape.txt", &b"nope"[..]),
+        ]);
+
+        extract_tar(
+            flate2::read::GzDecoder::new(Cursor::new(bytes)),
+            dir.path(),
+            LIMITS,
+        )
+        .unwrap();
+
+        assert_eq!(
+            fs::read_to_string(dir.path().join("pack/foo/SKILL.md")).unwrap(),
+            "# Foo"
+        );
+        assert!(!dir.path().parent().unwrap().join("escape.txt").exists());
+    }
+
+    #[test]
+    fn extraction_stops_at_size_and_entry_limits() {
+        let dir = tempfile::tempdir().unwrap();
+        let big = vec![b'x'; 2048];
+        let err = extract_tar(
+            flate2::read::GzDecoder::new(Cursor::new(tar_gz(&[("big.bin", &big[..])]))),
+            dir.path(),
+            LIMITS,
+        )
+        .unwrap_err();
+        assert_eq!(err.code(), "ARCHIVE_TOO_LARGE");
+
+        let names: Vec<String> = (0..20).map(|i| format!("f{i}.txt")).collect();
+        let files: Vec<(&str, &[u8])> = names.iter().map(|n| (n.as_str(), &b"x"[..])).collect();
+        let err = extract_tar(
+            flate2::read::GzDecoder::new(Cursor::new(tar_gz(&files))),
+            dir.path(),
+            LIMITS,
+        )
+        .unwrap_err();
+        assert_eq!(err.code(), "ARCHIVE_TOO_MANY_ENTRIES");
+    }
+
+    #[test]
+    fn detect_uses_extension_then_magic() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let detect = |name: &str, bytes: &[u8]| {
+            let path = dir.path().join(name);
+            fs::write(&path, bytes).expect("write archive");
+            ArchiveKind::detect(&path)
+        };
+
+        assert_eq!(detect("a.tar.gz", b"").unwrap(), ArchiveKind::TarGz);
+        assert_eq!(detect("a.tar.xz", b"").unwrap(), ArchiveKind::TarXz);
+        assert_eq!(detect("a.TAR.ZST", b"").unwrap(), ArchiveKind::TarZst);
+        assert_eq!(detect("a.zip", b"").unwrap(), ArchiveKind::Zip);
+        assert_eq!(
+            detect("asset", &[0x28, 0xb5, 0x2f, 0xfd, 0, 0]).unwrap(),
+            ArchiveKind::TarZst
+        );
+
+        let err = detect("a.rar", b"Rar!").unwrap_err().to_string();
+        assert!(err.contains(".rar"), "{err}");
+    }
+
+    #[test]
+    fn xz_and_zstd_tarballs_extract_in_process() {
+        use std::io::Write;
+
+        let tar_bytes = {
+            let mut builder = tar::Builder::new(Vec::new());
+            let mut header = tar::Header::new_gnu();
+            header.set_size(3);
+            header.set_mode(0o755);
+            builder
+                .append_data(&mut header, "cc-switch", &b"bin"[..])
+                .unwrap();
+            builder.into_inner().unwrap()
+        };
+
+        let mut xz = xz2::write::XzEncoder::new(Vec::new(), 1);
+        xz.write_all(&tar_bytes).unwrap();
+        let zst = zstd::stream::encode_all(Cursor::new(&tar_bytes), 1).unwrap();
+
+        for (name, bytes) in [("a.tar.xz", xz.finish().unwrap()), ("a.tar.zst", zst)] {
+            let dir = tempfile::tempdir().unwrap();
+            let archive = dir.path().join(name);
+            fs::write(&archive, bytes).unwrap();
+            let out = dir.path().join("out");
+            fs::create_dir_all(&out).unwrap();
+
+            let kind = ArchiveKind::detect(&archive).unwrap();
+            extract_file(&archive, kind, &out, LIMITS).unwrap();
+            assert_eq!(fs::read(out.join("cc-switch")).unwrap(), b"bin", "{name}");
+        }
+    }
+
+    #[test]
+    fn zip_extracts_regular_files() {
+        use std::io::Write;
+
+        let mut writer = zip::ZipWriter::new(Cursor::new(Vec::new()));
+        writer
+            .start_file(
+                "pack/bar/SKILL.md",
+                zip::write::SimpleFileOptions::default(),
+            )
+            .unwrap();
+        writer.write_all(b"# Bar").unwrap();
+        let bytes = writer.finish().unwrap().into_inner();
+
+        let dir = tempfile::tempdir().unwrap();
+        extract_zip(Cursor::new(bytes), dir.path(), LIMITS).unwrap();
+        assert_eq!(
+            fs::read_to_string(dir.path().join("pack/bar/SKILL.md")).unwrap(),
+            "# Bar"
+        );
+    }
+}
@@ -0,0 +1,44 @@
+//! SHA-256 摘要（小写十六进制）
+
+use sha2::{Digest, Sha256};
+
+use crate::error::AppError;
+
+pub fn compute_sha256_hex(bytes: &[u8]) -> String {
+    Sha256::digest(bytes)
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect()
+}
+
+/// 规范化用户给出的摘要：去空白、转小写，并要求为 64 位十六进制
+pub fn normalize_sha256(raw: &str) -> Result<String, AppError> {
+    let hash = raw.trim().to_ascii_lowercase();
+    if hash.len() != 64 || !hash.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(AppError::localized(
+            "hash.invalid_sha256",
+            format!("无效的 SHA-256 值 '{raw}'：应为 64 位十六进制"),
+            format!("Invalid SHA-256 '{raw}': expected 64 hex characters"),
+        ));
+    }
+    Ok(hash)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sha256_hex_matches_known_vector_and_validates_input() {
+        assert_eq!(
+            compute_sha256_hex(b"abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+        let upper = "BA7816BF8F01CFEA414140DE5DAE2223B00361A396177A9CB410FF61F20015AD";
+        assert_eq!(
+            normalize_sha256(upper).expect("valid"),
+            compute_sha256_hex(b"abc")
+        );
+        assert!(normalize_sha256("abc123").is_err());
+    }
+}
@@ -0,0 +1,4 @@
+//! 多处共用的小工具
+
+pub mod archive;
+pub mod hash;
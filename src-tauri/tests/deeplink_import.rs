@@ -1,5 +1,8 @@
 use base64::prelude::*;
-use cc_switch_lib::{import_provider_from_deeplink, parse_deeplink_url, AppType, MultiAppConfig};
+use cc_switch_lib::{
+    import_mcp_from_deeplink, import_provider_from_deeplink, import_skill_from_deeplink,
+    parse_deeplink_url, AppType, MultiAppConfig, SkillRepo, SkillService,
+};
 
 #[path = "support.rs"]
 mod support;
@@ -145,3 +148,142 @@ fn deeplink_import_rejects_non_http_endpoints_from_config() {
         "expected scheme validation error, got {err:?}"
     );
 }
+
+#[test]
+fn deeplink_parse_rejects_unsafe_config_payloads() {
+    let base = "ccswitch://v1/import?resource=provider&app=claude&name=Bad";
+
+    let err = parse_deeplink_url(&format!(
+        "{base}&configUrl=http%3A%2F%2Fexample.com%2Fc.json"
+    ))
+    .expect_err("plain http configUrl should be rejected");
+    assert!(err.to_string().contains("must be https"), "got {err:?}");
+
+    let not_json = BASE64_URL_SAFE_NO_PAD.encode(b"not json at all");
+    let err = parse_deeplink_url(&format!("{base}&config={not_json}&configFormat=json"))
+        .expect_err("config that is not JSON should be rejected");
+    assert!(err.to_string().contains("not valid JSON"), "got {err:?}");
+
+    let valid = BASE64_URL_SAFE_NO_PAD.encode(br#"{"env":{}}"#);
+    let err = parse_deeplink_url(&format!("{base}&config={valid}&configFormat=yaml"))
+        .expect_err("unknown configFormat should be rejected");
+    assert!(err.to_string().contains("configFormat"), "got {err:?}");
+
+    let oversized = "A".repeat(64 * 1024 + 1);
+    let err = parse_deeplink_url(&format!("{base}&config={oversized}"))
+        .expect_err("oversized config should be rejected");
+    assert!(err.to_string().contains("exceeds"), "got {err:?}");
+
+    let err = parse_deeplink_url(&format!("{base}&apiKey=sk%20with%20spaces"))
+        .expect_err("apiKey with whitespace should be rejected");
+    assert!(err.to_string().contains("apiKey"), "got {err:?}");
+}
+
+#[test]
+fn deeplink_import_mcp_server_enables_requested_apps() {
+    let _guard = lock_test_mutex();
+    reset_test_fs();
+    let home = ensure_test_home();
+
+    let url = "ccswitch://v1/import?resource=mcp&name=Fetch&command=uvx&args=mcp-server-fetch%2C--verbose&env=LOG_LEVEL%3Ddebug&apps=claude%2Ccodex";
+    let request = parse_deeplink_url(url).expect("parse mcp deeplink");
+    assert_eq!(request.resource, "mcp");
+
+    let mut config = MultiAppConfig::default();
+    config.mcp.servers = Some(Default::default());
+    let state = state_from_config(config);
+
+    let id = import_mcp_from_deeplink(&state, request.clone()).expect("import mcp server");
+    assert_eq!(id, "fetch");
+
+    {
+        let guard = state.config.read().expect("read config");
+        let server = guard
+            .mcp
+            .servers
+            .as_ref()
+            .and_then(|servers| servers.get("fetch"))
+            .expect("mcp server created via deeplink");
+        assert_eq!(server.name, "Fetch");
+        assert_eq!(server.server["command"], "uvx");
+        assert_eq!(
+            server.server["args"],
+            serde_json::json!(["mcp-server-fetch", "--verbose"])
+        );
+        assert_eq!(server.server["env"]["LOG_LEVEL"], "debug");
+        assert!(server.apps.claude && server.apps.codex && !server.apps.gemini);
+    }
+    let codex_config = std::fs::read_to_string(home.join(".codex").join("config.toml"))
+        .expect("codex config.toml created even without an existing ~/.codex");
+    assert!(
+        codex_config.contains("[mcp_servers.fetch]"),
+        "{codex_config}"
+    );
+
+    let err = import_mcp_from_deeplink(&state, request)
+        .expect_err("importing the same server twice should fail");
+    assert!(err.to_string().contains("already exists"), "got {err:?}");
+
+    let err = parse_deeplink_url("ccswitch://v1/import?resource=mcp&name=Fetch")
+        .expect_err("command or config is required");
+    assert!(err.to_string().contains("'command'"), "got {err:?}");
+
+    let err =
+        parse_deeplink_url("ccswitch://v1/import?resource=mcp&name=Fetch&command=uvx&apps=vscode")
+            .expect_err("unknown apps should be rejected");
+    assert!(err.to_string().contains("vscode"), "got {err:?}");
+}
+
+#[test]
+fn deeplink_parse_skill_resource_validates_repo_and_apps() {
+    let request = parse_deeplink_url(
+        "ccswitch://v1/import?resource=skill&repo=owner%2Fskills&directory=tools%2Fpdf&branch=dev&apps=claude%2Ccodex",
+    )
+    .expect("parse skill deeplink");
+    assert_eq!(request.resource, "skill");
+    assert_eq!(request.repo.as_deref(), Some("owner/skills"));
+    assert_eq!(request.directory.as_deref(), Some("tools/pdf"));
+    assert_eq!(request.branch.as_deref(), Some("dev"));
+    assert_eq!(request.apps.as_deref(), Some("claude,codex"));
+
+    let err = parse_deeplink_url("ccswitch://v1/import?resource=skill&repo=owner&directory=pdf")
+        .expect_err("repo without name should be rejected");
+    assert!(err.to_string().contains("owner/name"), "got {err:?}");
+
+    let err = parse_deeplink_url("ccswitch://v1/import?resource=skill&repo=owner%2Fskills")
+        .expect_err("missing directory should be rejected");
+    assert!(err.to_string().contains("directory"), "got {err:?}");
+
+    let err = parse_deeplink_url(
+        "ccswitch://v1/import?resource=skill&repo=owner%2Fskills&directory=pdf&apps=cursor",
+    )
+    .expect_err("unknown apps should be rejected");
+    assert!(err.to_string().contains("cursor"), "got {err:?}");
+}
+
+#[test]
+fn deeplink_skill_import_refuses_to_switch_tracked_branch() {
+    let _guard = lock_test_mutex();
+    reset_test_fs();
+    let _home = ensure_test_home();
+
+    SkillService::upsert_repo(SkillRepo {
+        owner: "owner".to_string(),
+        name: "skills".to_string(),
+        branch: "stable".to_string(),
+        enabled: true,
+        token: None,
+    })
+    .expect("track repo");
+
+    let request = parse_deeplink_url(
+        "ccswitch://v1/import?resource=skill&repo=owner%2Fskills&directory=pdf&branch=dev",
+    )
+    .expect("parse skill deeplink");
+    let err = futures::executor::block_on(import_skill_from_deeplink(request))
+        .expect_err("branch switch via deeplink is refused");
+    assert_eq!(err.code(), "DEEPLINK_SKILL_BRANCH_MISMATCH");
+
+    let branch = SkillService::tracked_branch("owner", "skills").expect("tracked branch");
+    assert_eq!(branch.as_deref(), Some("stable"));
+}
@@ -0,0 +1,54 @@
+//! `--dry-run-all` 是进程级开关，单独放在一个测试二进制中，避免影响其他测试
+
+use serde_json::json;
+
+use cc_switch_lib::{
+    enable_dry_run, get_claude_settings_path, AppState, AppType, Provider, ProviderService,
+};
+
+#[path = "support.rs"]
+mod support;
+use support::{ensure_test_home, lock_test_mutex, reset_test_fs};
+
+fn claude_provider(id: &str, token: &str) -> Provider {
+    Provider::with_id(
+        id.to_string(),
+        id.to_uppercase(),
+        json!({ "env": { "ANTHROPIC_AUTH_TOKEN": token } }),
+        None,
+    )
+}
+
+#[test]
+fn dry_run_all_switch_leaves_database_and_live_config_untouched() {
+    let _guard = lock_test_mutex();
+    reset_test_fs();
+    let home = ensure_test_home();
+    std::fs::create_dir_all(home.join(".claude")).expect("create ~/.claude");
+
+    {
+        let state = AppState::try_new().expect("create state");
+        ProviderService::add(&state, AppType::Claude, claude_provider("p1", "one"))
+            .expect("add p1");
+        ProviderService::add(&state, AppType::Claude, claude_provider("p2", "two"))
+            .expect("add p2");
+    }
+    let db_path = home.join(".cc-switch").join("cc-switch.db");
+    let db_before = std::fs::read(&db_path).expect("read database");
+    let live_before = std::fs::read_to_string(get_claude_settings_path()).ok();
+
+    enable_dry_run();
+    let state = AppState::try_new().expect("create dry-run state");
+    ProviderService::switch(&state, AppType::Claude, "p2").expect("dry-run switch");
+
+    // 进程内状态照常更新，磁盘上的数据库与 live 配置保持原样
+    assert_eq!(
+        ProviderService::current(&state, AppType::Claude).expect("current"),
+        "p2"
+    );
+    assert_eq!(std::fs::read(&db_path).expect("read database"), db_before);
+    assert_eq!(
+        std::fs::read_to_string(get_claude_settings_path()).ok(),
+        live_before
+    );
+}
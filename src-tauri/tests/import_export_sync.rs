@@ -3,8 +3,9 @@ use serde_json::json;
 use std::{fs, path::Path};
 
 use cc_switch_lib::{
-    get_claude_settings_path, read_json_file, AppError, AppType, ConfigService, Database,
-    MultiAppConfig, Provider, ProviderMeta,
+    get_claude_settings_path, get_codex_auth_path, get_codex_config_path, live_backup,
+    read_json_file, AppError, AppType, BackupMeta, ConfigService, ConflictStrategy, Database,
+    McpApps, McpServer, MultiAppConfig, Provider, ProviderMeta, SkillService,
 };
 
 #[path = "support.rs"]
@@ -786,6 +787,341 @@ fn create_backup_generates_snapshot_file() {
     );
 }
 
+#[test]
+fn live_backup_round_trips_app_config_files() {
+    let _guard = lock_test_mutex();
+    reset_test_fs();
+    let _home = ensure_test_home();
+
+    let auth_path = get_codex_auth_path();
+    let config_path = get_codex_config_path();
+    fs::create_dir_all(auth_path.parent().expect("codex dir")).expect("create codex dir");
+    fs::write(&auth_path, r#"{"OPENAI_API_KEY":"sk-original"}"#).expect("write auth");
+    fs::write(&config_path, "model = \"gpt-5\"\n").expect("write config");
+
+    let backup = live_backup::create(&AppType::Codex).expect("create live backup");
+    assert_eq!(backup.files.len(), 2);
+
+    fs::write(&auth_path, r#"{"OPENAI_API_KEY":"sk-broken"}"#).expect("clobber auth");
+    fs::write(&config_path, "model = \"oops\"\n").expect("clobber config");
+
+    let pre_restore = live_backup::restore(&AppType::Codex, &backup.id)
+        .expect("restore")
+        .expect("pre-restore snapshot");
+    assert_eq!(
+        fs::read_to_string(&auth_path).expect("read auth"),
+        r#"{"OPENAI_API_KEY":"sk-original"}"#
+    );
+    assert_eq!(
+        fs::read_to_string(&config_path).expect("read config"),
+        "model = \"gpt-5\"\n"
+    );
+
+    let listed = live_backup::list(&AppType::Codex).expect("list");
+    assert_eq!(listed.len(), 2);
+    assert!(listed.iter().any(|b| b.id == pre_restore.id));
+    assert!(live_backup::list(&AppType::Gemini)
+        .expect("list")
+        .is_empty());
+    assert!(live_backup::restore(&AppType::Codex, "missing").is_err());
+}
+
+#[test]
+fn backup_metadata_is_listed_and_filterable_by_tag() {
+    let _guard = lock_test_mutex();
+    reset_test_fs();
+    let home = ensure_test_home();
+    let db_path = home.join(".cc-switch").join("cc-switch.db");
+
+    let state = state_from_config(MultiAppConfig::default());
+    state.save().expect("persist db");
+
+    let meta = BackupMeta {
+        description: Some("before 3.0 upgrade".to_string()),
+        tags: vec!["pre-upgrade".to_string()],
+    };
+    let tagged =
+        ConfigService::create_backup_with_meta(&db_path, Some("tagged".to_string()), false, &meta)
+            .expect("tagged backup");
+    let plain =
+        ConfigService::create_backup(&db_path, Some("plain".to_string())).expect("plain backup");
+
+    let backups_dir = home.join(".cc-switch").join("backups");
+    assert!(backups_dir.join(format!("{tagged}.meta.json")).exists());
+    assert!(
+        !backups_dir.join(format!("{plain}.meta.json")).exists(),
+        "metadata-less backups should not get a sidecar"
+    );
+
+    let backups = ConfigService::list_backups(&db_path).expect("list backups");
+    let listed = backups
+        .iter()
+        .find(|b| b.id == tagged)
+        .expect("tagged listed");
+    assert_eq!(listed.meta, meta);
+    assert!(listed.label().contains("[pre-upgrade] before 3.0 upgrade"));
+
+    let plain_info = backups
+        .iter()
+        .find(|b| b.id == plain)
+        .expect("plain listed");
+    assert_eq!(plain_info.meta, BackupMeta::default());
+
+    let filtered: Vec<_> = backups
+        .iter()
+        .filter(|b| b.meta.has_tag("Pre-Upgrade"))
+        .map(|b| b.id.as_str())
+        .collect();
+    assert_eq!(filtered, vec![tagged.as_str()]);
+}
+
+#[test]
+fn compressed_backup_is_listed_and_restorable() {
+    let _guard = lock_test_mutex();
+    reset_test_fs();
+    let home = ensure_test_home();
+    let db_path = home.join(".cc-switch").join("cc-switch.db");
+
+    let mut config = MultiAppConfig::default();
+    {
+        let manager = config
+            .get_manager_mut(&AppType::Claude)
+            .expect("claude manager");
+        manager.current = "p1".to_string();
+        manager.providers.insert(
+            "p1".to_string(),
+            Provider::with_id(
+                "p1".to_string(),
+                "Test Claude".to_string(),
+                json!({ "env": { "ANTHROPIC_AUTH_TOKEN": "test-key" } }),
+                None,
+            ),
+        );
+    }
+    let state = state_from_config(config);
+    state.save().expect("persist db");
+
+    let backup_id =
+        ConfigService::create_backup_with_options(&db_path, None, true).expect("compressed backup");
+    let backup_path = home
+        .join(".cc-switch")
+        .join("backups")
+        .join(format!("{backup_id}.sql.gz"));
+    let raw = fs::read(&backup_path).expect("read compressed backup");
+    assert_eq!(&raw[..2], &[0x1f, 0x8b], "backup should be gzip data");
+
+    let backups = ConfigService::list_backups(&db_path).expect("list backups");
+    let listed = backups
+        .iter()
+        .find(|b| b.id == backup_id)
+        .expect("compressed backup should be listed");
+    assert!(listed.compressed);
+
+    let p2 = Provider::with_id(
+        "p2".to_string(),
+        "Other".to_string(),
+        json!({ "env": {} }),
+        None,
+    );
+    state
+        .db
+        .save_provider(AppType::Claude.as_str(), &p2)
+        .expect("save p2");
+    state
+        .db
+        .set_current_provider(AppType::Claude.as_str(), "p2")
+        .expect("switch to p2");
+
+    ConfigService::restore_from_backup_id(&backup_id, &state).expect("restore compressed");
+    let current = state
+        .db
+        .get_current_provider(AppType::Claude.as_str())
+        .expect("read current");
+    assert_eq!(current.as_deref(), Some("p1"));
+}
+
+#[test]
+fn merge_import_upserts_without_deleting_local_entries() {
+    let _guard = lock_test_mutex();
+    reset_test_fs();
+    let home = ensure_test_home();
+
+    let provider = |id: &str, name: &str| {
+        Provider::with_id(
+            id.to_string(),
+            name.to_string(),
+            json!({ "env": { "ANTHROPIC_AUTH_TOKEN": "test-key" } }),
+            None,
+        )
+    };
+    let mut config = MultiAppConfig::default();
+    {
+        let manager = config
+            .get_manager_mut(&AppType::Claude)
+            .expect("claude manager");
+        manager.current = "p1".to_string();
+        manager
+            .providers
+            .insert("p1".to_string(), provider("p1", "One"));
+        manager
+            .providers
+            .insert("shared".to_string(), provider("shared", "Shared v1"));
+    }
+    let state = state_from_config(config);
+    state.save().expect("persist db");
+
+    let export_path = home.join("merge-source.sql");
+    ConfigService::export_config_to_path(&export_path).expect("export");
+
+    let claude = AppType::Claude.as_str();
+    state.db.delete_provider(claude, "p1").expect("delete p1");
+    state
+        .db
+        .save_provider(claude, &provider("shared", "Shared local"))
+        .expect("edit shared");
+    state
+        .db
+        .save_provider(claude, &provider("local", "Local only"))
+        .expect("add local");
+    state.reload().expect("reload");
+
+    let preview = ConfigService::merge_import_from_path(
+        &export_path,
+        None,
+        ConflictStrategy::Skip,
+        true,
+        &state,
+    )
+    .expect("dry-run merge");
+    let providers = preview
+        .sections
+        .iter()
+        .find(|s| s.section == "providers:claude")
+        .expect("providers section");
+    assert_eq!(providers.added, vec!["p1".to_string()]);
+    assert_eq!(providers.skipped, vec!["shared".to_string()]);
+    assert!(preview.backup_id.is_none());
+    assert!(state
+        .db
+        .get_all_providers(claude)
+        .expect("providers")
+        .get("p1")
+        .is_none());
+
+    let report = ConfigService::merge_import_from_path(
+        &export_path,
+        None,
+        ConflictStrategy::Overwrite,
+        false,
+        &state,
+    )
+    .expect("merge");
+    let providers = report
+        .sections
+        .iter()
+        .find(|s| s.section == "providers:claude")
+        .expect("providers section");
+    assert_eq!(providers.updated, vec!["shared".to_string()]);
+    assert!(report.backup_id.is_some());
+
+    let merged = state.db.get_all_providers(claude).expect("providers");
+    assert!(merged.contains_key("p1"));
+    assert!(merged.contains_key("local"), "local-only entry is kept");
+    assert_eq!(
+        merged.get("shared").map(|p| p.name.as_str()),
+        Some("Shared v1")
+    );
+}
+
+#[test]
+fn merge_import_syncs_mcp_servers_and_skips_skills_without_files() {
+    let _guard = lock_test_mutex();
+    reset_test_fs();
+    let home = ensure_test_home();
+
+    for name in ["present", "ghost"] {
+        let dir = home.join(".claude").join("skills").join(name);
+        fs::create_dir_all(&dir).expect("create skill dir");
+        fs::write(
+            dir.join("SKILL.md"),
+            format!("---\nname: {name}\ndescription: test\n---\n"),
+        )
+        .expect("write SKILL.md");
+    }
+    SkillService::import_from_apps(vec!["present".to_string(), "ghost".to_string()])
+        .expect("import skills");
+    let skill_ids: Vec<(String, String)> = SkillService::list_installed()
+        .expect("list skills")
+        .into_iter()
+        .map(|s| (s.directory, s.id))
+        .collect();
+    let id_of = |dir: &str| {
+        skill_ids
+            .iter()
+            .find(|(d, _)| d == dir)
+            .map(|(_, id)| id.clone())
+            .expect("skill id")
+    };
+
+    let mut config = MultiAppConfig::default();
+    config.mcp.servers = Some(std::collections::HashMap::from([(
+        "fetch".to_string(),
+        McpServer {
+            id: "fetch".to_string(),
+            name: "Fetch".to_string(),
+            server: json!({ "type": "stdio", "command": "uvx" }),
+            apps: McpApps {
+                claude: true,
+                codex: false,
+                gemini: false,
+                opencode: false,
+            },
+            description: None,
+            homepage: None,
+            docs: None,
+            tags: Vec::new(),
+        },
+    )]));
+    let state = state_from_config(config);
+    state.save().expect("persist db");
+    let export_path = home.join("merge-source.sql");
+    ConfigService::export_config_to_path(&export_path).expect("export");
+
+    // 本机：记录全部删除，且 ghost 的 SSOT 目录已不存在
+    for (_, id) in &skill_ids {
+        state.db.delete_skill(id).expect("delete skill");
+    }
+    state.db.delete_mcp_server("fetch").expect("delete mcp");
+    state.reload().expect("reload");
+    let ssot = SkillService::get_ssot_dir().expect("ssot dir");
+    fs::remove_dir_all(ssot.join("ghost")).expect("remove ghost dir");
+
+    let report = ConfigService::merge_import_from_path(
+        &export_path,
+        None,
+        ConflictStrategy::Skip,
+        false,
+        &state,
+    )
+    .expect("merge");
+    let skills = report
+        .sections
+        .iter()
+        .find(|s| s.section == "skills")
+        .expect("skills section");
+    assert_eq!(skills.added, vec![id_of("present")]);
+    assert_eq!(skills.missing, vec![id_of("ghost")]);
+    assert!(state
+        .db
+        .get_installed_skill(&id_of("ghost"))
+        .expect("query")
+        .is_none());
+
+    let claude_mcp: serde_json::Value =
+        read_json_file(&home.join(".claude.json")).expect("claude mcp config written");
+    assert_eq!(claude_mcp["mcpServers"]["fetch"]["command"], "uvx");
+}
+
 #[test]
 fn create_backup_retains_only_latest_entries() {
     let _guard = lock_test_mutex();
@@ -919,6 +1255,69 @@ fn import_config_from_path_overwrites_state_and_creates_backup() {
     );
 }
 
+#[test]
+fn preview_import_reports_section_diff_without_modifying_db() {
+    let _guard = lock_test_mutex();
+    reset_test_fs();
+    let home = ensure_test_home();
+
+    let mut config = MultiAppConfig::default();
+    {
+        let manager = config
+            .get_manager_mut(&AppType::Claude)
+            .expect("claude manager");
+        manager.current = "p-old".to_string();
+        for id in ["p-old", "p-same"] {
+            let mut provider = Provider::with_id(id.to_string(), id.to_string(), json!({}), None);
+            // 仅时间戳不同的条目不算变更
+            provider.created_at = Some(1);
+            provider.updated_at = Some(1);
+            manager.providers.insert(id.to_string(), provider);
+        }
+    }
+    let app_state = state_from_config(config);
+    app_state.save().expect("persist initial db");
+
+    let import_path = home.join(".cc-switch").join("preview.sql");
+    let import_db = Database::memory().expect("create import db");
+    for (id, settings) in [("p-same", json!({})), ("p-new", json!({ "env": {} }))] {
+        let provider = Provider::with_id(id.to_string(), id.to_string(), settings, None);
+        import_db
+            .save_provider(AppType::Claude.as_str(), &provider)
+            .expect("seed provider");
+    }
+    import_db
+        .set_current_provider(AppType::Claude.as_str(), "p-new")
+        .expect("seed current");
+    import_db
+        .export_sql(&import_path)
+        .expect("export import sql");
+
+    let sections = ConfigService::preview_import_from_path(&import_path, None, &app_state)
+        .expect("preview should succeed");
+    let claude = sections
+        .iter()
+        .find(|s| s.section == "providers:claude")
+        .expect("claude providers section");
+    assert_eq!((claude.current, claude.incoming), (2, 2));
+    assert_eq!(claude.added, vec!["p-new".to_string()]);
+    assert_eq!(claude.removed, vec!["p-old".to_string()]);
+    assert_eq!(
+        claude.changed,
+        vec!["(current: p-old -> p-new)".to_string()]
+    );
+    assert!(sections
+        .iter()
+        .filter(|s| s.section != "providers:claude")
+        .all(|s| s.is_unchanged()));
+
+    let current = app_state
+        .db
+        .get_current_provider(AppType::Claude.as_str())
+        .expect("read current provider from db");
+    assert_eq!(current.as_deref(), Some("p-old"), "dry run must not import");
+}
+
 #[test]
 fn import_config_from_path_invalid_json_returns_error() {
     let _guard = lock_test_mutex();
@@ -1135,3 +1534,176 @@ fn export_config_to_file_returns_error_when_path_invalid() {
         "expected invalid export path message, got {err}"
     );
 }
+
+#[test]
+fn migrate_legacy_config_merges_new_entries_and_archives_source() {
+    let _guard = lock_test_mutex();
+    reset_test_fs();
+    let home = ensure_test_home();
+
+    let mut config = MultiAppConfig::default();
+    {
+        let manager = config
+            .get_manager_mut(&AppType::Claude)
+            .expect("claude manager");
+        manager.providers.insert(
+            "shared".to_string(),
+            Provider::with_id(
+                "shared".to_string(),
+                "Current Name".to_string(),
+                json!({"env": {}}),
+                None,
+            ),
+        );
+        manager.current = "shared".to_string();
+    }
+    let state = state_from_config(config);
+    state.save().expect("persist initial config");
+
+    let legacy_path = home.join("legacy-config.json");
+    let legacy = json!({
+        "version": 2,
+        "claude": {
+            "providers": {
+                "shared": {"id": "shared", "name": "Legacy Name", "settingsConfig": {"env": {}}},
+                "legacy-only": {"id": "legacy-only", "name": "Legacy Only", "settingsConfig": {"env": {}}}
+            },
+            "current": "legacy-only"
+        }
+    });
+    fs::write(&legacy_path, serde_json::to_string(&legacy).unwrap()).expect("write legacy");
+
+    let report = ConfigService::migrate_legacy_config(&legacy_path, ConflictStrategy::Skip, &state)
+        .expect("migrate legacy config");
+    assert_eq!(report.providers, 1);
+    assert_eq!(report.skipped, 1);
+    assert!(!legacy_path.exists(), "source should be archived");
+    assert!(report.archived_to.as_deref().is_some_and(Path::exists));
+
+    let guard = state.config.read().expect("read config");
+    let manager = guard.get_manager(&AppType::Claude).expect("claude manager");
+    assert_eq!(manager.providers["shared"].name, "Current Name");
+    assert!(manager.providers.contains_key("legacy-only"));
+    assert_eq!(
+        manager.current, "shared",
+        "current provider must not change"
+    );
+}
+
+#[test]
+fn diff_from_default_lists_only_changed_sections_and_keys() {
+    let _guard = lock_test_mutex();
+    reset_test_fs();
+    let _home = ensure_test_home();
+
+    let untouched =
+        ConfigService::diff_from_default(&MultiAppConfig::default()).expect("diff fresh default");
+    assert!(
+        untouched.is_empty(),
+        "fresh default should have no deviations"
+    );
+
+    let mut config = MultiAppConfig::default();
+    {
+        let manager = config
+            .get_manager_mut(&AppType::Claude)
+            .expect("claude manager");
+        manager.providers.insert(
+            "work".to_string(),
+            Provider::with_id(
+                "work".to_string(),
+                "Work".to_string(),
+                json!({"env": {"ANTHROPIC_AUTH_TOKEN": "sk-work"}}),
+                None,
+            ),
+        );
+        manager.current = "work".to_string();
+    }
+
+    let deviations = ConfigService::diff_from_default(&config).expect("diff config");
+    assert_eq!(deviations.sections.len(), 1);
+    assert_eq!(deviations.sections[0].section, "providers:claude");
+    assert_eq!(deviations.sections[0].added, vec!["work".to_string()]);
+
+    let claude = &deviations.config["claude"];
+    assert_eq!(claude["current"], "work");
+    assert_eq!(claude["providers"]["work"]["name"], "Work");
+    assert!(deviations.config.get("codex").is_none());
+    assert!(deviations.config.get("mcp").is_none());
+    assert_eq!(deviations.skills, json!({}));
+}
+
+#[test]
+fn config_upgrade_reports_then_applies_pending_migrations() {
+    let _guard = lock_test_mutex();
+    reset_test_fs();
+    let home = ensure_test_home();
+
+    let config_dir = home.join(".cc-switch");
+    fs::create_dir_all(&config_dir).expect("create config dir");
+    let skills_path = config_dir.join("skills.json");
+    fs::write(&skills_path, r#"{"version": 0, "skills": {}}"#).expect("write skills.json");
+
+    let pending = ConfigService::upgrade(false).expect("check upgrade");
+    assert_eq!(pending.database_version, None);
+    assert_eq!(pending.skills_index_version, Some(0));
+    assert!(pending.is_pending() && !pending.applied);
+    assert!(
+        !config_dir.join("cc-switch.db").exists(),
+        "check must not create the database"
+    );
+
+    let applied = ConfigService::upgrade(true).expect("apply upgrade");
+    assert!(applied.applied);
+    assert!(!skills_path.exists(), "legacy skills.json is archived");
+    assert!(config_dir.join("skills.json.migrated").exists());
+
+    let after = ConfigService::upgrade(true).expect("re-run upgrade");
+    assert_eq!(after.database_version, Some(after.target_version));
+    assert!(!after.is_pending() && !after.applied);
+    assert!(after.backup_path.is_none());
+}
+
+#[test]
+fn opening_an_older_database_backs_it_up_before_migrating() {
+    let _guard = lock_test_mutex();
+    reset_test_fs();
+    let home = ensure_test_home();
+
+    let db_path = home.join(".cc-switch").join("cc-switch.db");
+    drop(Database::init().expect("create database"));
+    let downgrade = |version: i32| {
+        let conn = rusqlite::Connection::open(&db_path).expect("open db");
+        conn.pragma_update(None, "user_version", version)
+            .expect("set user_version");
+    };
+    let backups = || -> Vec<std::path::PathBuf> {
+        fs::read_dir(home.join(".cc-switch").join("backups"))
+            .map(|entries| {
+                entries
+                    .filter_map(|e| e.ok().map(|e| e.path()))
+                    .filter(|p| p.to_string_lossy().contains("db_pre_upgrade_v8_"))
+                    .collect()
+            })
+            .unwrap_or_default()
+    };
+
+    // 隐式迁移（任何命令打开数据库时）同样先备份
+    downgrade(8);
+    drop(Database::init().expect("implicit migration"));
+    assert_eq!(backups().len(), 1, "implicit migration takes a backup");
+
+    downgrade(8);
+    let report = ConfigService::upgrade(true).expect("apply upgrade");
+    assert_eq!(report.database_version, Some(8));
+    let backup = report
+        .backup_path
+        .expect("explicit upgrade reports the backup");
+    assert!(backup.exists());
+    assert_eq!(
+        ConfigService::upgrade(false)
+            .expect("check")
+            .database_version,
+        Some(report.target_version)
+    );
+}
@@ -3,8 +3,8 @@ use std::{collections::HashMap, fs};
 use serde_json::json;
 
 use cc_switch_lib::{
-    get_claude_mcp_path, get_claude_settings_path, AppError, AppState, AppType, McpApps, McpServer,
-    McpService, MultiAppConfig, ProviderService,
+    get_claude_mcp_path, get_claude_settings_path, AppError, AppState, AppType, ConflictStrategy,
+    McpApps, McpImportOutcome, McpServer, McpService, MultiAppConfig, ProviderService,
 };
 
 #[path = "support.rs"]
@@ -507,3 +507,164 @@ fn upsert_server_disables_app_removes_from_gemini_live() {
         "upsert with Gemini disabled should remove it from ~/.gemini/settings.json, got: {settings_text}"
     );
 }
+
+#[test]
+fn mcp_export_import_round_trip_respects_conflict_strategy() {
+    let _guard = lock_test_mutex();
+    reset_test_fs();
+    let home = ensure_test_home();
+
+    let server = |id: &str, command: &str| McpServer {
+        id: id.to_string(),
+        name: id.to_string(),
+        server: json!({"type": "stdio", "command": command}),
+        apps: McpApps::default(),
+        description: None,
+        homepage: None,
+        docs: None,
+        tags: Vec::new(),
+    };
+
+    let mut config = MultiAppConfig::default();
+    config.mcp.servers = Some(HashMap::from([
+        ("alpha".to_string(), server("alpha", "echo")),
+        ("beta".to_string(), server("beta", "cat")),
+    ]));
+    let state = state_from_config(config);
+
+    let export_path = home.join("mcp-export.json");
+    let count = McpService::export_to_file(&state, &export_path).expect("export servers");
+    assert_eq!(count, 2);
+
+    // Target state: "alpha" already exists with a different command.
+    let mut target = MultiAppConfig::default();
+    target.mcp.servers = Some(HashMap::from([(
+        "alpha".to_string(),
+        server("alpha", "local"),
+    )]));
+    let target_state = state_from_config(target);
+
+    let results = McpService::import_from_file(&target_state, &export_path, ConflictStrategy::Skip)
+        .expect("import servers");
+    assert_eq!(
+        results,
+        vec![
+            ("alpha".to_string(), McpImportOutcome::Skipped),
+            ("beta".to_string(), McpImportOutcome::Added),
+        ]
+    );
+    let servers = McpService::get_all_servers(&target_state).expect("servers");
+    assert_eq!(servers["alpha"].server["command"], "local");
+    assert_eq!(servers["beta"].server["command"], "cat");
+
+    let results =
+        McpService::import_from_file(&target_state, &export_path, ConflictStrategy::Overwrite)
+            .expect("import servers");
+    assert_eq!(
+        results[0],
+        ("alpha".to_string(), McpImportOutcome::Overwritten)
+    );
+    let servers = McpService::get_all_servers(&target_state).expect("servers");
+    assert_eq!(servers["alpha"].server["command"], "echo");
+
+    fs::write(
+        &export_path,
+        r#"{"broken": {"name": "broken", "server": {"type": "stdio"}, "apps": {}}}"#,
+    )
+    .expect("write invalid export");
+    let results = McpService::import_from_file(&target_state, &export_path, ConflictStrategy::Skip)
+        .expect("import servers");
+    assert!(matches!(results[0].1, McpImportOutcome::Invalid(_)));
+}
+
+#[test]
+fn servers_from_snippet_accepts_single_and_claude_block_shapes() {
+    let single = json!({
+        "name": "fs",
+        "command": "npx",
+        "args": ["-y", "@modelcontextprotocol/server-filesystem"],
+        "env": { "ROOT": "/tmp" }
+    });
+    let servers =
+        McpService::servers_from_snippet(&single, &AppType::Codex).expect("parse single server");
+    assert_eq!(servers.len(), 1);
+    assert_eq!(servers[0].id, "fs");
+    assert_eq!(servers[0].server["command"], "npx");
+    assert!(servers[0].server.get("name").is_none());
+    assert!(servers[0].apps.codex && !servers[0].apps.claude);
+
+    let block = json!({
+        "mcpServers": {
+            "fetch": { "command": "uvx", "args": ["mcp-server-fetch"] },
+            "remote": { "type": "http", "url": "https://mcp.example.com" }
+        }
+    });
+    let mut ids: Vec<_> = McpService::servers_from_snippet(&block, &AppType::Claude)
+        .expect("parse mcpServers block")
+        .into_iter()
+        .map(|s| s.id)
+        .collect();
+    ids.sort();
+    assert_eq!(ids, vec!["fetch", "remote"]);
+
+    let missing_command = json!({ "name": "broken", "args": [] });
+    assert!(McpService::servers_from_snippet(&missing_command, &AppType::Claude).is_err());
+    let missing_id = json!({ "command": "npx" });
+    assert!(McpService::servers_from_snippet(&missing_id, &AppType::Claude).is_err());
+}
+
+#[test]
+fn add_from_snippet_refuses_conflicts_without_strategy() {
+    let _guard = lock_test_mutex();
+    reset_test_fs();
+    let _home = ensure_test_home();
+
+    let mut config = MultiAppConfig::default();
+    config.mcp.servers = Some(HashMap::from([(
+        "fs".to_string(),
+        McpServer {
+            id: "fs".to_string(),
+            name: "fs".to_string(),
+            server: json!({"type": "stdio", "command": "local"}),
+            apps: McpApps::default(),
+            description: None,
+            homepage: None,
+            docs: None,
+            tags: Vec::new(),
+        },
+    )]));
+    let state = state_from_config(config);
+
+    let snippet = json!({
+        "mcpServers": {
+            "fs": { "command": "npx" },
+            "fetch": { "command": "uvx" }
+        }
+    });
+    let parse = || McpService::servers_from_snippet(&snippet, &AppType::Claude).expect("parse");
+
+    let err = McpService::add_from_snippet(&state, parse(), None).expect_err("conflict");
+    assert_eq!(err.code(), "MCP_ADD_CONFLICT");
+    let servers = McpService::get_all_servers(&state).expect("servers");
+    assert_eq!(servers.len(), 1, "nothing is written on conflict");
+    assert_eq!(servers["fs"].server["command"], "local");
+
+    let mut results = McpService::add_from_snippet(&state, parse(), Some(ConflictStrategy::Skip))
+        .expect("add with skip");
+    results.sort_by(|a, b| a.0.cmp(&b.0));
+    assert_eq!(
+        results,
+        vec![
+            ("fetch".to_string(), McpImportOutcome::Added),
+            ("fs".to_string(), McpImportOutcome::Skipped),
+        ]
+    );
+    let servers = McpService::get_all_servers(&state).expect("servers");
+    assert_eq!(servers["fs"].server["command"], "local");
+
+    let results = McpService::add_from_snippet(&state, parse(), Some(ConflictStrategy::Overwrite))
+        .expect("add with overwrite");
+    assert!(results.contains(&("fs".to_string(), McpImportOutcome::Overwritten)));
+    let servers = McpService::get_all_servers(&state).expect("servers");
+    assert_eq!(servers["fs"].server["command"], "npx");
+}
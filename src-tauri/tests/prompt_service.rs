@@ -0,0 +1,105 @@
+use std::fs;
+
+use cc_switch_lib::{AppType, MultiAppConfig, PromptService};
+
+#[path = "support.rs"]
+mod support;
+use support::{ensure_test_home, lock_test_mutex, reset_test_fs, state_from_config};
+
+#[test]
+fn import_from_dir_adds_then_updates_markdown_prompts() {
+    let _guard = lock_test_mutex();
+    reset_test_fs();
+    let home = ensure_test_home();
+
+    let dir = home.join(".cc-switch").join("prompt-import");
+    fs::create_dir_all(dir.join("nested")).expect("create prompt dirs");
+    fs::write(dir.join("review.md"), "Review carefully").expect("write review.md");
+    fs::write(dir.join(".draft.md"), "hidden").expect("write hidden file");
+    fs::write(dir.join("notes.txt"), "not markdown").expect("write txt");
+    fs::write(dir.join("nested").join("deep.md"), "Nested").expect("write nested");
+
+    let state = state_from_config(MultiAppConfig::default());
+
+    let report = PromptService::import_from_dir(&state, AppType::Claude, &dir, false)
+        .expect("import flat dir");
+    assert_eq!(report.added, vec!["review".to_string()]);
+    assert!(report.updated.is_empty());
+
+    fs::write(dir.join("review.md"), "Review even more carefully").expect("rewrite review.md");
+    let report = PromptService::import_from_dir(&state, AppType::Claude, &dir, true)
+        .expect("import recursively");
+    assert_eq!(report.added, vec!["nested/deep".to_string()]);
+    assert_eq!(report.updated, vec!["review".to_string()]);
+
+    let prompts = PromptService::get_prompts(&state, AppType::Claude).expect("get prompts");
+    assert_eq!(prompts.len(), 2);
+    assert_eq!(prompts["review"].name, "review");
+    assert_eq!(prompts["review"].content, "Review even more carefully");
+    assert_eq!(prompts["nested/deep"].name, "deep");
+}
+
+#[test]
+fn export_to_dir_round_trips_and_refuses_overwrite() {
+    let _guard = lock_test_mutex();
+    reset_test_fs();
+    let home = ensure_test_home();
+
+    let source = home.join(".cc-switch").join("prompt-src");
+    fs::create_dir_all(&source).expect("create source dir");
+    let content = "# Title\n\n  indented line\ntrailing spaces  \n";
+    fs::write(source.join("style guide.md"), content).expect("write prompt");
+
+    let state = state_from_config(MultiAppConfig::default());
+    PromptService::import_from_dir(&state, AppType::Claude, &source, false).expect("import");
+
+    let target = home.join(".cc-switch").join("prompt-out");
+    let written =
+        PromptService::export_to_dir(&state, AppType::Claude, &target, false).expect("export");
+    assert_eq!(written, vec![target.join("style guide.md")]);
+    assert_eq!(
+        fs::read_to_string(&written[0]).expect("read exported"),
+        content
+    );
+
+    assert!(PromptService::export_to_dir(&state, AppType::Claude, &target, false).is_err());
+    PromptService::export_to_dir(&state, AppType::Claude, &target, true).expect("force export");
+}
+
+#[test]
+fn export_to_dir_names_files_by_id_so_import_keeps_ids() {
+    let _guard = lock_test_mutex();
+    reset_test_fs();
+    let home = ensure_test_home();
+
+    let mut config = MultiAppConfig::default();
+    config.prompts = serde_json::from_value(serde_json::json!({
+        "claude": { "prompts": {
+            "p-1700000000": { "id": "p-1700000000", "name": "Style Guide", "content": "Be terse" },
+            "team/review": { "id": "team/review", "name": "Review", "content": "Check tests" }
+        }}
+    }))
+    .expect("prompt fixture");
+    let state = state_from_config(config);
+
+    let target = home.join(".cc-switch").join("prompt-out");
+    let written =
+        PromptService::export_to_dir(&state, AppType::Claude, &target, false).expect("export");
+    assert_eq!(
+        written,
+        vec![
+            target.join("p-1700000000.md"),
+            target.join("team").join("review.md")
+        ]
+    );
+
+    let report =
+        PromptService::import_from_dir(&state, AppType::Claude, &target, true).expect("re-import");
+    assert!(report.added.is_empty(), "no duplicates: {:?}", report.added);
+    assert_eq!(report.updated.len(), 2);
+
+    let prompts = PromptService::get_prompts(&state, AppType::Claude).expect("get prompts");
+    assert_eq!(prompts.len(), 2);
+    assert_eq!(prompts["p-1700000000"].name, "Style Guide");
+    assert_eq!(prompts["team/review"].content, "Check tests");
+}
@@ -3,7 +3,7 @@ use std::collections::HashMap;
 
 use cc_switch_lib::{
     get_claude_settings_path, read_json_file, write_codex_live_atomic, AppError, AppType, McpApps,
-    McpServer, MultiAppConfig, Provider, ProviderMeta, ProviderService,
+    McpServer, MultiAppConfig, Provider, ProviderMeta, ProviderModels, ProviderService,
 };
 
 #[path = "support.rs"]
@@ -794,3 +794,376 @@ fn provider_service_delete_current_provider_returns_error() {
         other => panic!("expected Config error, got {other:?}"),
     }
 }
+
+#[test]
+fn provider_service_set_models_updates_current_claude_live_config() {
+    let _guard = lock_test_mutex();
+    reset_test_fs();
+    let _home = ensure_test_home();
+
+    let settings_path = get_claude_settings_path();
+    if let Some(parent) = settings_path.parent() {
+        std::fs::create_dir_all(parent).expect("create claude settings dir");
+    }
+
+    let mut config = MultiAppConfig::default();
+    {
+        let manager = config
+            .get_manager_mut(&AppType::Claude)
+            .expect("claude manager");
+        manager.current = "p1".to_string();
+        manager.providers.insert(
+            "p1".to_string(),
+            Provider::with_id(
+                "p1".to_string(),
+                "P1".to_string(),
+                json!({
+                    "env": {
+                        "ANTHROPIC_AUTH_TOKEN": "token",
+                        "ANTHROPIC_MODEL": "old-model",
+                        "ANTHROPIC_DEFAULT_HAIKU_MODEL": "old-haiku"
+                    }
+                }),
+                None,
+            ),
+        );
+    }
+
+    let state = state_from_config(config);
+
+    let err =
+        ProviderService::set_models(&state, AppType::Claude, "p1", &ProviderModels::default())
+            .expect_err("empty model mapping should be rejected");
+    assert!(matches!(err, AppError::InvalidInput(_)), "got {err:?}");
+
+    let updated = ProviderService::set_models(
+        &state,
+        AppType::Claude,
+        "p1",
+        &ProviderModels {
+            model: Some("new-model".to_string()),
+            opus_model: Some("new-opus".to_string()),
+            ..ProviderModels::default()
+        },
+    )
+    .expect("set models should succeed");
+
+    let mapping = ProviderModels::from_settings(&AppType::Claude, &updated.settings_config);
+    assert_eq!(mapping.model.as_deref(), Some("new-model"));
+    assert_eq!(mapping.haiku_model.as_deref(), Some("old-haiku"));
+    assert_eq!(mapping.opus_model.as_deref(), Some("new-opus"));
+
+    let live: serde_json::Value = read_json_file(&settings_path).expect("read claude live");
+    assert_eq!(
+        live.pointer("/env/ANTHROPIC_MODEL")
+            .and_then(|v| v.as_str()),
+        Some("new-model"),
+        "current provider change should be written to live settings"
+    );
+    assert_eq!(
+        live.pointer("/env/ANTHROPIC_DEFAULT_OPUS_MODEL")
+            .and_then(|v| v.as_str()),
+        Some("new-opus")
+    );
+}
+
+#[test]
+fn provider_service_temporary_switch_restores_original_provider() {
+    let _guard = lock_test_mutex();
+    reset_test_fs();
+    let _home = ensure_test_home();
+
+    let settings_path = get_claude_settings_path();
+    if let Some(parent) = settings_path.parent() {
+        std::fs::create_dir_all(parent).expect("create claude settings dir");
+    }
+    std::fs::write(
+        &settings_path,
+        serde_json::to_string_pretty(&json!({ "env": { "ANTHROPIC_API_KEY": "a-key" } }))
+            .expect("serialize live"),
+    )
+    .expect("seed claude live config");
+
+    let mut config = MultiAppConfig::default();
+    {
+        let manager = config
+            .get_manager_mut(&AppType::Claude)
+            .expect("claude manager");
+        manager.current = "a".to_string();
+        for id in ["a", "b", "c"] {
+            manager.providers.insert(
+                id.to_string(),
+                Provider::with_id(
+                    id.to_string(),
+                    id.to_uppercase(),
+                    json!({ "env": { "ANTHROPIC_API_KEY": format!("{id}-key") } }),
+                    None,
+                ),
+            );
+        }
+    }
+    let state = state_from_config(config);
+
+    ProviderService::switch_temporary(&state, AppType::Claude, "b").expect("temporary switch");
+    let marker = ProviderService::switch_temporary(&state, AppType::Claude, "c")
+        .expect("second temporary switch");
+    assert_eq!(
+        marker.previous_id, "a",
+        "chained temporary switches keep the original provider"
+    );
+    assert_eq!(
+        ProviderService::current(&state, AppType::Claude).expect("current"),
+        "c"
+    );
+
+    let restored =
+        ProviderService::restore_session(&state, AppType::Claude).expect("restore session");
+    assert_eq!(restored.as_deref(), Some("a"));
+    assert_eq!(
+        ProviderService::current(&state, AppType::Claude).expect("current"),
+        "a"
+    );
+    assert!(ProviderService::temporary_switch(&AppType::Claude)
+        .expect("read marker")
+        .is_none());
+    assert_eq!(
+        ProviderService::restore_session(&state, AppType::Claude).expect("restore again"),
+        None,
+        "nothing left to restore"
+    );
+}
+
+#[test]
+fn provider_service_delete_all_requires_force_for_current_provider() {
+    let _guard = lock_test_mutex();
+    reset_test_fs();
+    ensure_test_home();
+
+    let mut config = MultiAppConfig::default();
+    {
+        let manager = config
+            .get_manager_mut(&AppType::Claude)
+            .expect("claude manager");
+        manager.current = "current".to_string();
+        for id in ["current", "spare"] {
+            manager.providers.insert(
+                id.to_string(),
+                Provider::with_id(
+                    id.to_string(),
+                    id.to_string(),
+                    json!({ "env": { "ANTHROPIC_API_KEY": format!("{id}-key") } }),
+                    None,
+                ),
+            );
+        }
+    }
+    let app_state = state_from_config(config);
+
+    let err = ProviderService::delete_all(&app_state, AppType::Claude, false)
+        .expect_err("deleting the current provider without --force should fail");
+    assert!(matches!(err, AppError::Localized { .. }), "got {err:?}");
+    assert_eq!(
+        ProviderService::list(&app_state, AppType::Claude)
+            .expect("list providers")
+            .len(),
+        2,
+        "nothing should be deleted when refusing"
+    );
+
+    let removed =
+        ProviderService::delete_all(&app_state, AppType::Claude, true).expect("force delete all");
+    assert_eq!(removed, 2);
+
+    let guard = app_state.config.read().expect("read config");
+    let manager = guard.get_manager(&AppType::Claude).expect("claude manager");
+    assert!(manager.providers.is_empty());
+    assert!(manager.current.is_empty(), "current pointer cleared");
+}
+
+#[test]
+fn use_endpoint_updates_primary_keeps_previous_and_syncs_live() {
+    let _guard = lock_test_mutex();
+    reset_test_fs();
+    let _home = ensure_test_home();
+
+    let settings_path = get_claude_settings_path();
+    if let Some(parent) = settings_path.parent() {
+        std::fs::create_dir_all(parent).expect("create claude settings dir");
+    }
+
+    let mut config = MultiAppConfig::default();
+    {
+        let manager = config
+            .get_manager_mut(&AppType::Claude)
+            .expect("claude manager");
+        manager.current = "relay".to_string();
+        manager.providers.insert(
+            "relay".to_string(),
+            Provider::with_id(
+                "relay".to_string(),
+                "Relay".to_string(),
+                json!({
+                    "env": {
+                        "ANTHROPIC_AUTH_TOKEN": "key",
+                        "ANTHROPIC_BASE_URL": "https://a.example.com"
+                    }
+                }),
+                None,
+            ),
+        );
+    }
+    let state = state_from_config(config);
+    ProviderService::switch(&state, AppType::Claude, "relay").expect("initial switch");
+
+    ProviderService::add_custom_endpoint(
+        &state,
+        AppType::Claude,
+        "relay",
+        "https://b.example.com/".to_string(),
+    )
+    .expect("add endpoint");
+    ProviderService::use_endpoint(&state, AppType::Claude, "relay", "https://b.example.com")
+        .expect("use endpoint");
+
+    let endpoints =
+        ProviderService::list_endpoints(&state, AppType::Claude, "relay").expect("list");
+    let urls: Vec<_> = endpoints
+        .iter()
+        .map(|e| (e.url.as_str(), e.primary))
+        .collect();
+    assert_eq!(
+        urls,
+        vec![
+            ("https://b.example.com", true),
+            ("https://a.example.com", false)
+        ]
+    );
+
+    let live: serde_json::Value = read_json_file(&settings_path).expect("read live settings");
+    assert_eq!(
+        live["env"]["ANTHROPIC_BASE_URL"],
+        json!("https://b.example.com"),
+        "current provider's live config should follow the chosen endpoint"
+    );
+
+    assert!(!ProviderService::endpoint_auto_select(&state, AppType::Claude, "relay").unwrap());
+    ProviderService::set_endpoint_auto_select(&state, AppType::Claude, "relay", true)
+        .expect("enable auto-select");
+    assert!(ProviderService::endpoint_auto_select(&state, AppType::Claude, "relay").unwrap());
+}
+
+#[test]
+fn provider_service_set_api_key_keeps_existing_field_and_stores_reference() {
+    let _guard = lock_test_mutex();
+    reset_test_fs();
+    let _home = ensure_test_home();
+
+    let mut config = MultiAppConfig::default();
+    {
+        let manager = config
+            .get_manager_mut(&AppType::Claude)
+            .expect("claude manager");
+        manager.current = "p1".to_string();
+        for (id, field) in [("p1", "ANTHROPIC_AUTH_TOKEN"), ("p2", "ANTHROPIC_API_KEY")] {
+            manager.providers.insert(
+                id.to_string(),
+                Provider::with_id(
+                    id.to_string(),
+                    id.to_uppercase(),
+                    json!({ "env": { field: "inline-token" } }),
+                    None,
+                ),
+            );
+        }
+    }
+    let state = state_from_config(config);
+
+    let field = ProviderService::set_api_key(&state, AppType::Claude, "p2", "keyring:claude-p2")
+        .expect("set key reference");
+    assert_eq!(field, "ANTHROPIC_API_KEY");
+
+    let providers = ProviderService::list(&state, AppType::Claude).expect("list providers");
+    let env = &providers["p2"].settings_config["env"];
+    assert_eq!(env["ANTHROPIC_API_KEY"], "keyring:claude-p2");
+    assert!(env.get("ANTHROPIC_AUTH_TOKEN").is_none());
+
+    let err = ProviderService::set_api_key(&state, AppType::Claude, "p2", "  ")
+        .expect_err("empty key should be rejected");
+    assert!(matches!(err, AppError::InvalidInput(_)), "got {err:?}");
+}
+
+#[test]
+fn provider_service_reorder_pins_listed_ids_and_keeps_the_rest() {
+    let _guard = lock_test_mutex();
+    reset_test_fs();
+    let _home = ensure_test_home();
+
+    let mut config = MultiAppConfig::default();
+    {
+        let manager = config
+            .get_manager_mut(&AppType::Claude)
+            .expect("claude manager");
+        for (created_at, id) in ["a", "b", "c", "d"].into_iter().enumerate() {
+            let mut provider = Provider::with_id(
+                id.to_string(),
+                id.to_uppercase(),
+                json!({ "env": {} }),
+                None,
+            );
+            provider.created_at = Some(created_at as i64);
+            manager.providers.insert(id.to_string(), provider);
+        }
+    }
+    let state = state_from_config(config);
+
+    let order = ProviderService::reorder(
+        &state,
+        AppType::Claude,
+        &["c".to_string(), "a".to_string(), "c".to_string()],
+    )
+    .expect("reorder");
+    assert_eq!(order, vec!["c", "a", "b", "d"]);
+
+    let providers = ProviderService::list(&state, AppType::Claude).expect("list providers");
+    assert_eq!(ProviderService::display_order(&providers), order);
+    assert_eq!(providers["d"].sort_index, Some(3));
+
+    let err = ProviderService::reorder(&state, AppType::Claude, &["missing".to_string()])
+        .expect_err("unknown id is rejected");
+    assert!(err.to_string().contains("missing"), "{err}");
+}
+
+#[test]
+fn provider_service_resolve_provider_accepts_id_or_unique_name() {
+    let _guard = lock_test_mutex();
+    reset_test_fs();
+    let _home = ensure_test_home();
+
+    let mut config = MultiAppConfig::default();
+    {
+        let manager = config
+            .get_manager_mut(&AppType::Claude)
+            .expect("claude manager");
+        for (id, name) in [("p1", "Work"), ("p2", "Mirror"), ("p3", "mirror")] {
+            manager.providers.insert(
+                id.to_string(),
+                Provider::with_id(id.to_string(), name.to_string(), json!({ "env": {} }), None),
+            );
+        }
+    }
+    let state = state_from_config(config);
+
+    let resolve = |input: &str| ProviderService::resolve_provider(&state, AppType::Claude, input);
+    assert_eq!(resolve("p2").expect("exact id"), "p2");
+    assert_eq!(resolve("work").expect("case-insensitive name"), "p1");
+
+    let err = resolve("MIRROR").expect_err("ambiguous name");
+    let message = err.to_string();
+    assert!(
+        message.contains("p2") && message.contains("p3"),
+        "{message}"
+    );
+
+    let err = resolve("missing").expect_err("unknown input");
+    assert!(err.to_string().contains("missing"), "{err}");
+}
@@ -0,0 +1,182 @@
+use cc_switch_lib::{
+    get_claude_settings_path, get_setting_value, set_app_override_dir, set_setting_value, AppError,
+    AppType, SettingKey,
+};
+
+#[path = "support.rs"]
+mod support;
+use support::{ensure_test_home, lock_test_mutex, reset_test_fs};
+
+#[test]
+fn settings_registry_validates_and_persists_known_keys() {
+    let _guard = lock_test_mutex();
+    reset_test_fs();
+    let home = ensure_test_home();
+
+    let key = SettingKey::parse("skillsyncmethod").expect("key lookup is case-insensitive");
+    assert_eq!(key, SettingKey::SkillSyncMethod);
+    assert_eq!(get_setting_value(key), "auto");
+
+    set_setting_value(key, "copy").expect("set valid sync method");
+    assert_eq!(get_setting_value(key), "copy");
+
+    let err = set_setting_value(key, "hardlink").expect_err("invalid value should be rejected");
+    assert!(matches!(err, AppError::InvalidInput(_)), "got {err:?}");
+    assert_eq!(get_setting_value(key), "copy", "value unchanged on error");
+
+    let err = set_setting_value(SettingKey::SkillDownloadRetries, "99")
+        .expect_err("out of range retries should be rejected");
+    assert!(matches!(err, AppError::InvalidInput(_)), "got {err:?}");
+
+    let saved = std::fs::read_to_string(home.join(".cc-switch").join("settings.json"))
+        .expect("settings.json should be written");
+    assert!(saved.contains("\"skillSyncMethod\": \"copy\""), "{saved}");
+}
+
+#[test]
+fn settings_registry_rejects_unknown_key_with_valid_list() {
+    let err = SettingKey::parse("cacheTtl").expect_err("unknown key should be rejected");
+    let message = err.to_string();
+    for key in SettingKey::ALL {
+        assert!(
+            message.contains(key.as_str()),
+            "missing {key:?} in: {message}"
+        );
+    }
+}
+
+#[test]
+fn app_override_dir_is_used_for_claude_paths_until_cleared() {
+    let _guard = lock_test_mutex();
+    reset_test_fs();
+    let home = ensure_test_home();
+
+    let custom = home.join("custom-claude");
+    set_app_override_dir(&AppType::Claude, Some(custom.display().to_string()))
+        .expect("set override");
+    assert_eq!(
+        get_setting_value(SettingKey::ClaudeConfigDir),
+        custom.display().to_string()
+    );
+    assert!(
+        get_claude_settings_path().starts_with(&custom),
+        "claude settings path should follow the override"
+    );
+
+    set_app_override_dir(&AppType::Claude, None).expect("clear override");
+    assert_eq!(get_setting_value(SettingKey::ClaudeConfigDir), "");
+    assert!(get_claude_settings_path().starts_with(home.join(".claude")));
+}
+
+#[test]
+fn settings_registry_validates_skill_timeouts() {
+    let _guard = lock_test_mutex();
+    reset_test_fs();
+    ensure_test_home();
+
+    assert_eq!(
+        get_setting_value(SettingKey::SkillDownloadTimeoutSecs),
+        "60"
+    );
+    assert_eq!(get_setting_value(SettingKey::SkillHttpTimeoutSecs), "10");
+
+    set_setting_value(SettingKey::SkillDownloadTimeoutSecs, "300").expect("set download timeout");
+    assert_eq!(
+        get_setting_value(SettingKey::SkillDownloadTimeoutSecs),
+        "300"
+    );
+    assert_eq!(
+        get_setting_value(SettingKey::SkillHttpTimeoutSecs),
+        "10",
+        "http timeout is independent"
+    );
+
+    let err = set_setting_value(SettingKey::SkillHttpTimeoutSecs, "0")
+        .expect_err("zero timeout should be rejected");
+    assert!(matches!(err, AppError::InvalidInput(_)), "got {err:?}");
+}
+
+#[test]
+fn settings_registry_validates_proxy_urls() {
+    let _guard = lock_test_mutex();
+    reset_test_fs();
+    ensure_test_home();
+
+    set_setting_value(SettingKey::Proxy, "http://[::1]:3128").expect("ipv6 proxy url");
+    assert_eq!(get_setting_value(SettingKey::Proxy), "http://[::1]:3128");
+
+    set_setting_value(SettingKey::Proxy, "OFF").expect("disable proxy");
+    assert_eq!(get_setting_value(SettingKey::Proxy), "off");
+
+    set_setting_value(SettingKey::Proxy, "ftp://proxy.local")
+        .expect_err("unsupported scheme should be rejected");
+    assert_eq!(get_setting_value(SettingKey::Proxy), "off");
+
+    set_setting_value(SettingKey::Proxy, "").expect("clear proxy");
+    assert_eq!(get_setting_value(SettingKey::Proxy), "");
+}
+
+#[test]
+fn settings_registry_validates_update_channel() {
+    let _guard = lock_test_mutex();
+    reset_test_fs();
+    ensure_test_home();
+
+    assert_eq!(get_setting_value(SettingKey::UpdateChannel), "stable");
+    set_setting_value(SettingKey::UpdateChannel, "Prerelease").expect("set channel");
+    assert_eq!(get_setting_value(SettingKey::UpdateChannel), "prerelease");
+
+    let err = set_setting_value(SettingKey::UpdateChannel, "nightly")
+        .expect_err("unknown channel should be rejected");
+    assert!(matches!(err, AppError::InvalidInput(_)), "got {err:?}");
+}
+
+#[test]
+fn settings_registry_exposes_post_switch_hook() {
+    let _guard = lock_test_mutex();
+    reset_test_fs();
+    ensure_test_home();
+
+    let key = SettingKey::parse("postSwitchHook").expect("hook is a registered setting");
+    assert_eq!(get_setting_value(key), "");
+    set_setting_value(key, "  notify {app} {id}  ").expect("set hook");
+    assert_eq!(get_setting_value(key), "notify {app} {id}");
+    set_setting_value(key, "").expect("clear hook");
+    assert_eq!(get_setting_value(key), "");
+}
+
+#[test]
+fn settings_registry_validates_release_host() {
+    let _guard = lock_test_mutex();
+    reset_test_fs();
+    ensure_test_home();
+
+    set_setting_value(SettingKey::ReleaseHost, "https://ghe.example.com/").expect("set host");
+    assert_eq!(
+        get_setting_value(SettingKey::ReleaseHost),
+        "https://ghe.example.com"
+    );
+
+    set_setting_value(SettingKey::ReleaseHost, "mirror.local")
+        .expect_err("host without scheme should be rejected");
+    set_setting_value(SettingKey::ReleaseHost, "").expect("clear host");
+    assert_eq!(get_setting_value(SettingKey::ReleaseHost), "");
+}
+
+#[test]
+fn theme_color_keys_validate_colors_and_clear_on_empty() {
+    let _guard = lock_test_mutex();
+    reset_test_fs();
+    let _home = ensure_test_home();
+
+    let key = SettingKey::parse("themeColorCodex").expect("theme color key is registered");
+    set_setting_value(key, "#ff8000").expect("hex color");
+    assert_eq!(get_setting_value(key), "#ff8000");
+
+    let err = set_setting_value(key, "chartreuse").expect_err("unknown color is rejected");
+    assert!(matches!(err, AppError::InvalidInput(_)), "got {err:?}");
+    assert_eq!(get_setting_value(key), "#ff8000");
+
+    set_setting_value(key, "").expect("empty clears the color");
+    assert_eq!(get_setting_value(key), "");
+}
@@ -0,0 +1,40 @@
+use std::process::Command;
+
+/// `skills install --from-file --progress json` 的 stdout 必须逐行都是 JSON（供包装脚本解析）
+#[test]
+fn progress_json_stdout_is_json_lines_only() {
+    let home = std::env::temp_dir().join("cc-switch-progress-json-home");
+    let _ = std::fs::remove_dir_all(&home);
+    std::fs::create_dir_all(&home).expect("create isolated home");
+    let list = home.join("skills.txt");
+    std::fs::write(
+        &list,
+        "# offline: every spec fails to resolve\nnobody/missing:first\nnobody/missing:second\n",
+    )
+    .expect("write spec list");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_cc-switch"))
+        .env("HOME", &home)
+        .env("USERPROFILE", &home)
+        .args(["--offline", "skills", "install", "--from-file"])
+        .arg(&list)
+        .args(["--progress", "json"])
+        .output()
+        .expect("run cc-switch");
+
+    assert!(!output.status.success(), "failed installs exit non-zero");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let events: Vec<serde_json::Value> = stdout
+        .lines()
+        .map(|line| {
+            serde_json::from_str(line)
+                .unwrap_or_else(|e| panic!("stdout line is not JSON ({e}): {line:?}"))
+        })
+        .collect();
+    let summary = events.last().expect("at least the summary event");
+    assert_eq!(summary["event"], "summary");
+    assert_eq!(summary["total"], 2);
+    assert_eq!(summary["failed"], 2);
+
+    let _ = std::fs::remove_dir_all(&home);
+}
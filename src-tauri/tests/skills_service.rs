@@ -1,4 +1,7 @@
-use cc_switch_lib::{Database, SkillService};
+use cc_switch_lib::{
+    AppType, Database, InstallOptions, InstallOutcome, SkillProgress, SkillReadmeSource, SkillRepo,
+    SkillService, SyncMethod,
+};
 
 #[path = "support.rs"]
 mod support;
@@ -130,3 +133,954 @@ fn pending_migration_with_existing_managed_list_does_not_claim_unmanaged_skills(
         "unmanaged skill should remain unmanaged (not added to db)"
     );
 }
+
+#[test]
+fn strict_metadata_validation_reports_skill_md_problems() {
+    let _guard = lock_test_mutex();
+    reset_test_fs();
+    let home = ensure_test_home();
+    let base = home.join("strict-skills");
+
+    let valid = base.join("valid");
+    write_skill_md(&valid, "Valid Skill", "does things");
+    let meta = SkillService::validate_skill_metadata(&valid).expect("valid skill passes");
+    assert_eq!(meta.name.as_deref(), Some("Valid Skill"));
+
+    let missing = base.join("missing");
+    std::fs::create_dir_all(&missing).expect("create dir");
+    let err = SkillService::validate_skill_metadata(&missing).expect_err("no SKILL.md");
+    assert!(err.to_string().contains("SKILL_MD_MISSING"), "got {err}");
+
+    let broken = base.join("broken");
+    std::fs::create_dir_all(&broken).expect("create dir");
+    std::fs::write(broken.join("SKILL.md"), "---\nname: [unclosed\n---\n").expect("write");
+    let err = SkillService::validate_skill_metadata(&broken).expect_err("bad yaml");
+    assert!(
+        err.to_string().contains("SKILL_FRONT_MATTER_INVALID"),
+        "got {err}"
+    );
+
+    let unnamed = base.join("unnamed");
+    write_skill_md(&unnamed, "", "no name");
+    let err = SkillService::validate_skill_metadata(&unnamed).expect_err("empty name");
+    assert!(err.to_string().contains("SKILL_NAME_MISSING"), "got {err}");
+}
+
+#[test]
+fn sync_method_override_applies_to_single_run_only() {
+    let _guard = lock_test_mutex();
+    reset_test_fs();
+    let home = ensure_test_home();
+
+    write_skill_md(
+        &home.join(".claude").join("skills").join("copy-skill"),
+        "Copy Skill",
+        "Synced by copy",
+    );
+    SkillService::import_from_apps(vec!["copy-skill".to_string()]).expect("import copy-skill");
+    SkillService::set_sync_method(SyncMethod::Symlink).expect("set stored sync method");
+
+    let installed = SkillService::list_installed().expect("list installed");
+    assert_eq!(installed.len(), 1);
+    SkillService::toggle_app("copy-skill", &AppType::Gemini, true).expect("enable for gemini");
+    let gemini_skill = SkillService::get_app_skills_dir(&AppType::Gemini)
+        .expect("gemini skills dir")
+        .join("copy-skill");
+    std::fs::remove_file(&gemini_skill)
+        .or_else(|_| std::fs::remove_dir_all(&gemini_skill))
+        .expect("remove synced gemini skill");
+
+    SkillService::sync_all_enabled_with_method(Some(&AppType::Gemini), Some(SyncMethod::Copy))
+        .expect("sync with copy override");
+
+    let meta = std::fs::symlink_metadata(&gemini_skill).expect("gemini skill synced");
+    assert!(
+        !meta.file_type().is_symlink() && meta.is_dir(),
+        "override should copy the skill directory"
+    );
+    assert!(gemini_skill.join("SKILL.md").exists());
+    assert_eq!(
+        SkillService::get_sync_method().expect("get stored sync method"),
+        SyncMethod::Symlink,
+        "stored sync method must not change"
+    );
+}
+
+#[test]
+fn prune_finds_orphaned_ssot_dirs_and_keeps_linked_ones() {
+    let _guard = lock_test_mutex();
+    reset_test_fs();
+    let home = ensure_test_home();
+
+    write_skill_md(
+        &home.join(".claude").join("skills").join("kept-skill"),
+        "Kept Skill",
+        "Indexed",
+    );
+    SkillService::import_from_apps(vec!["kept-skill".to_string()]).expect("import kept-skill");
+
+    let ssot = SkillService::get_ssot_dir().expect("ssot dir");
+    write_skill_md(&ssot.join("orphan-skill"), "Orphan", "Interrupted install");
+    write_skill_md(&ssot.join("linked-orphan"), "Linked", "Still symlinked");
+    #[cfg(unix)]
+    std::os::unix::fs::symlink(
+        ssot.join("linked-orphan"),
+        home.join(".claude").join("skills").join("linked-orphan"),
+    )
+    .expect("symlink linked-orphan");
+
+    let orphans = SkillService::find_orphaned_ssot_dirs().expect("find orphans");
+    let names: Vec<_> = orphans.iter().map(|o| o.directory.as_str()).collect();
+    assert_eq!(names, vec!["linked-orphan", "orphan-skill"]);
+    assert!(orphans.iter().all(|o| o.size_bytes > 0));
+
+    let orphan = orphans
+        .iter()
+        .find(|o| o.directory == "orphan-skill")
+        .expect("orphan-skill listed");
+    SkillService::remove_orphaned_ssot_dir(orphan).expect("remove orphan");
+    assert!(!ssot.join("orphan-skill").exists());
+    assert!(ssot.join("kept-skill").exists(), "indexed skill untouched");
+
+    #[cfg(unix)]
+    {
+        let linked = orphans
+            .iter()
+            .find(|o| o.directory == "linked-orphan")
+            .expect("linked-orphan listed");
+        assert_eq!(linked.referenced_by, vec![AppType::Claude]);
+        assert!(SkillService::remove_orphaned_ssot_dir(linked).is_err());
+        assert!(ssot.join("linked-orphan").exists());
+    }
+}
+
+#[test]
+fn set_repo_enabled_toggles_flag_and_keeps_repo() {
+    let _guard = lock_test_mutex();
+    reset_test_fs();
+    let _home = ensure_test_home();
+
+    let repo = SkillService::list_repos()
+        .expect("list repos")
+        .into_iter()
+        .next()
+        .expect("default repos present");
+
+    let disabled =
+        SkillService::set_repo_enabled(&repo.owner, &repo.name, false).expect("disable repo");
+    assert!(!disabled.enabled);
+    assert_eq!(disabled.branch, repo.branch);
+
+    let stored = SkillService::list_repos()
+        .expect("list repos")
+        .into_iter()
+        .find(|r| r.owner == repo.owner && r.name == repo.name)
+        .expect("repo still listed after disable");
+    assert!(!stored.enabled);
+
+    SkillService::set_repo_enabled(&repo.owner, &repo.name, true).expect("enable repo");
+    assert!(SkillService::list_repos()
+        .expect("list repos")
+        .iter()
+        .any(|r| r.owner == repo.owner && r.name == repo.name && r.enabled));
+
+    let err = SkillService::set_repo_enabled("nobody", "missing", false)
+        .expect_err("unknown repo should fail");
+    assert!(err.to_string().contains("nobody/missing"));
+}
+
+#[test]
+fn install_resolves_from_repo_cache_without_network() {
+    let _guard = lock_test_mutex();
+    reset_test_fs();
+    let home = ensure_test_home();
+
+    let repo = SkillService::list_repos()
+        .expect("list repos")
+        .into_iter()
+        .find(|r| r.enabled)
+        .expect("enabled default repo");
+    let cached_skill = SkillService::repo_cache_dir(&repo)
+        .join("skills")
+        .join("offline-skill");
+    write_skill_md(&cached_skill, "Offline Skill", "Installed from cache");
+
+    let spec = format!("{}/{}:offline-skill", repo.owner, repo.name);
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .expect("build runtime");
+    let service = SkillService::new().expect("create skill service");
+
+    let installed = runtime
+        .block_on(service.install(&spec, &AppType::Claude))
+        .expect("install from cache");
+    assert_eq!(installed.directory, "offline-skill");
+    assert_eq!(installed.name, "Offline Skill");
+    assert!(home
+        .join(".cc-switch")
+        .join("skills")
+        .join("offline-skill")
+        .join("SKILL.md")
+        .exists());
+
+    // Reinstalling by directory name resolves from the SSOT record.
+    let again = runtime
+        .block_on(service.install("offline-skill", &AppType::Codex))
+        .expect("reinstall from ssot");
+    assert!(again.apps.claude && again.apps.codex);
+
+    let (_, outcome) = runtime
+        .block_on(service.install_with_outcome(
+            "offline-skill",
+            &AppType::Gemini,
+            InstallOptions::default(),
+        ))
+        .expect("install already installed skill");
+    assert_eq!(outcome, InstallOutcome::AlreadyInstalled);
+}
+
+#[test]
+fn install_no_enable_stages_in_ssot_without_syncing() {
+    let _guard = lock_test_mutex();
+    reset_test_fs();
+    let home = ensure_test_home();
+
+    let repo = SkillService::list_repos()
+        .expect("list repos")
+        .into_iter()
+        .find(|r| r.enabled)
+        .expect("enabled default repo");
+    let cached_skill = SkillService::repo_cache_dir(&repo)
+        .join("skills")
+        .join("staged-skill");
+    write_skill_md(&cached_skill, "Staged Skill", "Not active yet");
+
+    let spec = format!("{}/{}:staged-skill", repo.owner, repo.name);
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .expect("build runtime");
+    let service = SkillService::new().expect("create skill service");
+    let options = InstallOptions {
+        no_enable: true,
+        ..Default::default()
+    };
+
+    let (installed, outcome) = runtime
+        .block_on(service.install_with_outcome(&spec, &AppType::Claude, options))
+        .expect("stage skill");
+    assert_eq!(outcome, InstallOutcome::Installed);
+    assert!(!installed.apps.claude && !installed.apps.codex && !installed.apps.gemini);
+    assert!(home
+        .join(".cc-switch")
+        .join("skills")
+        .join("staged-skill")
+        .join("SKILL.md")
+        .exists());
+    let claude_dir = home.join(".claude").join("skills").join("staged-skill");
+    assert!(!claude_dir.exists(), "staged skill must not be synced");
+
+    SkillService::toggle_app("staged-skill", &AppType::Claude, true).expect("activate");
+    assert!(claude_dir.join("SKILL.md").exists());
+}
+
+#[test]
+fn readme_is_read_from_repo_cache_then_ssot_without_network() {
+    let _guard = lock_test_mutex();
+    reset_test_fs();
+    let _home = ensure_test_home();
+
+    let repo = SkillService::list_repos()
+        .expect("list repos")
+        .into_iter()
+        .find(|r| r.enabled)
+        .expect("enabled default repo");
+    let cached_skill = SkillService::repo_cache_dir(&repo)
+        .join("skills")
+        .join("doc-skill");
+    write_skill_md(&cached_skill, "Doc Skill", "Has docs");
+
+    let spec = format!("{}/{}:doc-skill", repo.owner, repo.name);
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .expect("build runtime");
+    let service = SkillService::new().expect("create skill service");
+
+    let cached = runtime
+        .block_on(service.read_readme(&spec))
+        .expect("read readme from cache");
+    assert_eq!(cached.source, SkillReadmeSource::Cache);
+    assert_eq!(cached.file_name, "SKILL.md");
+    assert!(cached.content.contains("# Doc Skill"));
+    assert!(cached
+        .readme_url
+        .as_deref()
+        .is_some_and(|url| url.ends_with("/skills/doc-skill")));
+
+    runtime
+        .block_on(service.install(&spec, &AppType::Claude))
+        .expect("install from cache");
+    let installed = runtime
+        .block_on(service.read_readme("doc-skill"))
+        .expect("read readme from ssot");
+    assert_eq!(installed.source, SkillReadmeSource::Installed);
+    assert_eq!(installed.key, spec);
+}
+
+#[test]
+fn offline_mode_uses_repo_cache_and_fails_fast_otherwise() {
+    let _guard = lock_test_mutex();
+    reset_test_fs();
+    let _home = ensure_test_home();
+
+    let repo = SkillService::list_repos()
+        .expect("list repos")
+        .into_iter()
+        .find(|r| r.enabled)
+        .expect("enabled default repo");
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .expect("build runtime");
+    let service = SkillService::new().expect("create skill service");
+
+    std::env::set_var("CC_SWITCH_OFFLINE", "1");
+    let uncached = runtime.block_on(service.discover_available(vec![repo.clone()]));
+    let missing = runtime.block_on(service.install("not-cached-skill", &AppType::Claude));
+
+    write_skill_md(
+        &SkillService::repo_cache_dir(&repo)
+            .join("skills")
+            .join("cached-skill"),
+        "Cached Skill",
+        "Available offline",
+    );
+    let cached = runtime.block_on(service.discover_available(vec![repo.clone()]));
+    std::env::remove_var("CC_SWITCH_OFFLINE");
+
+    let err = uncached.expect_err("discover without cache fails in offline mode");
+    assert_eq!(err.code(), "NETWORK_OFFLINE");
+    let err = missing.expect_err("install of an uncached skill fails in offline mode");
+    assert_eq!(err.code(), "NETWORK_OFFLINE");
+    let cached = cached.expect("discover from cache in offline mode");
+    assert_eq!(cached.len(), 1);
+    assert_eq!(cached[0].directory, "cached-skill");
+}
+
+#[test]
+fn force_install_offline_falls_back_to_repo_cache_and_refresh_needs_network() {
+    let _guard = lock_test_mutex();
+    reset_test_fs();
+    let _home = ensure_test_home();
+
+    let repo = SkillService::list_repos()
+        .expect("list repos")
+        .into_iter()
+        .find(|r| r.enabled)
+        .expect("enabled default repo");
+    write_skill_md(
+        &SkillService::repo_cache_dir(&repo)
+            .join("skills")
+            .join("forced-skill"),
+        "Forced Skill",
+        "Cached copy",
+    );
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .expect("build runtime");
+    let service = SkillService::new().expect("create skill service");
+    let options = InstallOptions {
+        force: true,
+        ..Default::default()
+    };
+
+    std::env::set_var("CC_SWITCH_OFFLINE", "1");
+    let installed =
+        runtime.block_on(service.install_with_options("forced-skill", &AppType::Claude, options));
+    let refreshed = runtime.block_on(service.refresh_repo_cache(&repo));
+    std::env::remove_var("CC_SWITCH_OFFLINE");
+
+    let installed = installed.expect("--force offline still installs from the cache");
+    assert_eq!(installed.directory, "forced-skill");
+    let err = refreshed.expect_err("refresh cannot run offline");
+    assert_eq!(err.code(), "NETWORK_OFFLINE");
+}
+
+#[test]
+fn force_install_renames_on_directory_conflict_with_another_repo() {
+    let _guard = lock_test_mutex();
+    reset_test_fs();
+    let home = ensure_test_home();
+
+    let first = SkillService::list_repos()
+        .expect("list repos")
+        .into_iter()
+        .find(|r| r.enabled)
+        .expect("enabled default repo");
+    let second = SkillRepo {
+        owner: "other".to_string(),
+        name: "skill-pack".to_string(),
+        branch: "main".to_string(),
+        enabled: true,
+        token: None,
+    };
+    SkillService::upsert_repo(second.clone()).expect("add second repo");
+    for (repo, description) in [(&first, "First copy"), (&second, "Second copy")] {
+        write_skill_md(
+            &SkillService::repo_cache_dir(repo)
+                .join("skills")
+                .join("shared"),
+            "Shared",
+            description,
+        );
+    }
+
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .expect("build runtime");
+    let service = SkillService::new().expect("create skill service");
+    let first_spec = format!("{}/{}:shared", first.owner, first.name);
+    let second_spec = "other/skill-pack:shared";
+    let force = InstallOptions {
+        force: true,
+        ..Default::default()
+    };
+
+    runtime
+        .block_on(service.install(&first_spec, &AppType::Claude))
+        .expect("install first copy");
+    let err = runtime
+        .block_on(service.install(second_spec, &AppType::Claude))
+        .expect_err("conflict without --force");
+    assert_eq!(err.code(), "SKILL_DIRECTORY_CONFLICT");
+
+    // 离线时 --force 仍从仓库缓存解析，避免测试访问网络
+    std::env::set_var("CC_SWITCH_OFFLINE", "1");
+    let forced =
+        runtime.block_on(service.install_with_outcome(second_spec, &AppType::Claude, force));
+    std::env::remove_var("CC_SWITCH_OFFLINE");
+    let (renamed, outcome) = forced.expect("--force installs under the alternate name");
+    assert_eq!(outcome, InstallOutcome::Renamed);
+    assert_eq!(renamed.directory, "shared-other");
+    assert_eq!(renamed.id, second_spec);
+
+    let ssot = home.join(".cc-switch").join("skills");
+    let read = |dir: &str| std::fs::read_to_string(ssot.join(dir).join("SKILL.md")).unwrap();
+    assert!(read("shared").contains("First copy"));
+    assert!(read("shared-other").contains("Second copy"));
+    assert!(home
+        .join(".claude")
+        .join("skills")
+        .join("shared-other")
+        .join("SKILL.md")
+        .exists());
+
+    // The alternate directory is recorded, so later installs reuse it without --force.
+    let (again, outcome) = runtime
+        .block_on(service.install_with_outcome(
+            second_spec,
+            &AppType::Codex,
+            InstallOptions::default(),
+        ))
+        .expect("reinstall renamed skill");
+    assert_eq!(outcome, InstallOutcome::AlreadyInstalled);
+    assert_eq!(again.directory, "shared-other");
+    let installed = SkillService::list_installed().expect("list installed");
+    assert_eq!(installed.iter().filter(|s| s.name == "Shared").count(), 2);
+}
+
+#[test]
+fn test_repo_scans_cache_without_adding_the_repo() {
+    let _guard = lock_test_mutex();
+    reset_test_fs();
+    let _home = ensure_test_home();
+
+    let repo = SkillRepo {
+        owner: "someone".to_string(),
+        name: "skill-pack".to_string(),
+        branch: "dev".to_string(),
+        enabled: true,
+        token: None,
+    };
+    let cache = SkillService::repo_cache_dir(&repo);
+    write_skill_md(&cache.join("beta"), "Beta", "Second");
+    write_skill_md(&cache.join("nested").join("alpha"), "Alpha", "First");
+
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .expect("build runtime");
+    let service = SkillService::new().expect("create skill service");
+    let report = runtime
+        .block_on(service.test_repo(repo, false))
+        .expect("test cached repo");
+
+    assert!(report.from_cache);
+    assert_eq!(report.branch, "dev");
+    let dirs: Vec<_> = report.skills.iter().map(|s| s.directory.as_str()).collect();
+    assert_eq!(dirs, vec!["alpha", "beta"]);
+    assert!(!SkillService::list_repos()
+        .expect("list repos")
+        .iter()
+        .any(|r| r.owner == "someone"));
+}
+
+#[test]
+fn repo_token_reference_survives_upsert_and_manifest_import() {
+    let _guard = lock_test_mutex();
+    reset_test_fs();
+    let home = ensure_test_home();
+
+    let reference = format!(
+        "keyring:{}",
+        SkillService::repo_token_name("team", "private-skills")
+    );
+    SkillService::upsert_repo(SkillRepo {
+        owner: "team".to_string(),
+        name: "private-skills".to_string(),
+        branch: "main".to_string(),
+        enabled: true,
+        token: Some(reference.clone()),
+    })
+    .expect("add private repo");
+
+    // Re-adding without a token (e.g. via deeplink) keeps the stored reference.
+    SkillService::upsert_repo(SkillRepo {
+        owner: "team".to_string(),
+        name: "private-skills".to_string(),
+        branch: "dev".to_string(),
+        enabled: true,
+        token: None,
+    })
+    .expect("re-add repo");
+
+    let manifest = home.join("repos.json");
+    std::fs::write(
+        &manifest,
+        r#"[{"owner":"team","name":"private-skills","branch":"stable","enabled":true}]"#,
+    )
+    .expect("write manifest");
+    let repos = SkillService::read_repo_manifest(&manifest).expect("read manifest");
+    let report = SkillService::import_repos(repos).expect("import repos");
+    assert_eq!(report.merged, vec!["team/private-skills".to_string()]);
+
+    let stored = SkillService::list_repos()
+        .expect("list repos")
+        .into_iter()
+        .find(|r| r.name == "private-skills")
+        .expect("private repo listed");
+    assert_eq!(stored.branch, "stable");
+    assert_eq!(stored.token.as_deref(), Some(reference.as_str()));
+}
+
+#[test]
+fn repo_manifest_import_reports_added_merged_and_duplicates() {
+    let _guard = lock_test_mutex();
+    reset_test_fs();
+    let home = ensure_test_home();
+
+    let existing = SkillService::list_repos()
+        .expect("list repos")
+        .into_iter()
+        .next()
+        .expect("default repo");
+    let manifest = home.join("repos.json");
+    std::fs::write(
+        &manifest,
+        serde_json::json!([
+            { "owner": "team", "name": "skills", "branch": "main", "enabled": true },
+            { "owner": existing.owner, "name": existing.name, "branch": "dev", "enabled": true },
+            { "owner": "team", "name": "skills", "branch": "stable", "enabled": false }
+        ])
+        .to_string(),
+    )
+    .expect("write manifest");
+
+    let repos = SkillService::read_repo_manifest(&manifest).expect("read manifest");
+    let report = SkillService::import_repos(repos).expect("import repos");
+    assert_eq!(report.added, vec!["team/skills".to_string()]);
+    assert_eq!(
+        report.merged,
+        vec![format!("{}/{}", existing.owner, existing.name)]
+    );
+    assert_eq!(report.duplicates, vec!["team/skills".to_string()]);
+
+    let imported = SkillService::list_repos()
+        .expect("list repos")
+        .into_iter()
+        .find(|r| r.owner == "team")
+        .expect("imported repo");
+    assert_eq!(imported.branch, "stable");
+    assert!(!imported.enabled);
+
+    let exported = home.join("repos.toml");
+    let count = SkillService::export_repo_manifest(&exported).expect("export manifest");
+    let round_trip = SkillService::read_repo_manifest(&exported).expect("read toml manifest");
+    assert_eq!(round_trip.len(), count);
+    let again = SkillService::import_repos(round_trip).expect("re-import");
+    assert!(again.added.is_empty() && again.merged.is_empty());
+
+    std::fs::write(
+        &manifest,
+        r#"[{"owner":"","name":"x","branch":"main","enabled":true}]"#,
+    )
+    .expect("write invalid manifest");
+    assert!(SkillService::read_repo_manifest(&manifest).is_err());
+
+    std::fs::write(
+        &manifest,
+        r#"[{"owner":"corp","name":"private","branch":"main","enabled":true,"token":"ghp_plain"}]"#,
+    )
+    .expect("write manifest with plaintext token");
+    let repos = SkillService::read_repo_manifest(&manifest).expect("read manifest");
+    let err = SkillService::import_repos(repos).expect_err("plaintext tokens are rejected");
+    assert_eq!(err.code(), "SKILLS_REPO_TOKEN_PLAINTEXT");
+    assert!(!err.to_string().contains("ghp_plain"));
+    assert!(SkillService::list_repos()
+        .expect("list repos")
+        .iter()
+        .all(|r| r.owner != "corp"));
+}
+
+#[test]
+fn install_from_url_rejects_unsupported_sources() {
+    let _guard = lock_test_mutex();
+    reset_test_fs();
+    let _home = ensure_test_home();
+
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .expect("build runtime");
+    let service = SkillService::new().expect("create skill service");
+
+    let err = runtime
+        .block_on(service.install_from_url(
+            "https://example.com/skills.rar",
+            "my-skill",
+            &AppType::Claude,
+            InstallOptions::default(),
+        ))
+        .expect_err("unsupported archive format should fail before download");
+    assert!(err.to_string().contains("skills.rar"), "{err}");
+
+    let err = runtime
+        .block_on(service.install_from_url(
+            "ftp://example.com/skills.zip",
+            "my-skill",
+            &AppType::Claude,
+            InstallOptions::default(),
+        ))
+        .expect_err("non-http url should be rejected");
+    assert!(err.to_string().contains("ftp://"), "{err}");
+}
+
+#[test]
+fn install_from_url_keeps_existing_ssot_dirs_without_force() {
+    let _guard = lock_test_mutex();
+    reset_test_fs();
+    let _home = ensure_test_home();
+
+    let ssot = SkillService::get_ssot_dir().expect("ssot dir");
+    write_skill_md(&ssot.join("my-skill"), "Mine", "Hand-written");
+
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .expect("build runtime");
+    let service = SkillService::new().expect("create skill service");
+    let err = runtime
+        .block_on(service.install_from_url(
+            "https://example.com/skills.tar.gz",
+            "pack/my-skill",
+            &AppType::Claude,
+            InstallOptions::default(),
+        ))
+        .expect_err("an unmanaged SSOT dir is not replaced without --force");
+    assert_eq!(err.code(), "SKILLS_DIRECTORY_EXISTS");
+    assert!(
+        std::fs::read_to_string(ssot.join("my-skill").join("SKILL.md"))
+            .expect("files kept")
+            .contains("Hand-written")
+    );
+}
+
+#[test]
+fn resync_skill_refreshes_copies_for_enabled_apps() {
+    let _guard = lock_test_mutex();
+    reset_test_fs();
+    let home = ensure_test_home();
+
+    write_skill_md(
+        &home.join(".claude").join("skills").join("watched"),
+        "Watched",
+        "v1",
+    );
+    SkillService::import_from_apps(vec!["watched".to_string()]).expect("import watched");
+    SkillService::set_sync_method(SyncMethod::Copy).expect("use copy sync");
+    SkillService::toggle_app("watched", &AppType::Gemini, true).expect("enable for gemini");
+
+    let ssot = SkillService::get_ssot_dir().expect("ssot dir");
+    let edited = ssot.join("watched").join("notes.md");
+    std::fs::write(&edited, "edited").expect("edit skill in SSOT");
+    assert_eq!(
+        SkillService::ssot_skill_of(&edited).expect("resolve skill"),
+        Some("watched".to_string())
+    );
+
+    let results = SkillService::resync_skill("watched", Some(&AppType::Gemini)).expect("resync");
+    assert_eq!(results, vec![(AppType::Gemini, true)]);
+    let copied = SkillService::get_app_skills_dir(&AppType::Gemini)
+        .expect("gemini skills dir")
+        .join("watched")
+        .join("notes.md");
+    assert_eq!(std::fs::read_to_string(copied).expect("copied"), "edited");
+
+    assert!(SkillService::resync_skill("unknown", None)
+        .expect("unmanaged dir")
+        .is_empty());
+}
+
+#[test]
+fn stats_counts_sources_apps_and_repos() {
+    let _guard = lock_test_mutex();
+    reset_test_fs();
+    let home = ensure_test_home();
+
+    for dir in ["one", "two"] {
+        write_skill_md(
+            &home.join(".claude").join("skills").join(dir),
+            dir,
+            "local skill",
+        );
+    }
+    SkillService::import_from_apps(vec!["one".to_string(), "two".to_string()])
+        .expect("import local skills");
+    SkillService::toggle_app("two", &AppType::Gemini, true).expect("enable for gemini");
+
+    let repos = SkillService::list_repos().expect("list repos");
+    let stats = SkillService::stats().expect("stats");
+
+    assert_eq!(stats.installed, 2);
+    assert_eq!(stats.local, 2);
+    assert_eq!(stats.from_repo, 0);
+    assert_eq!(stats.enabled_by_app["claude"], 2);
+    assert_eq!(stats.enabled_by_app["gemini"], 1);
+    assert_eq!(stats.enabled_by_app["codex"], 0);
+    assert!(stats.ssot_bytes > 0);
+    assert_eq!(stats.repos_configured, repos.len());
+    assert_eq!(
+        stats.repos_enabled,
+        repos.iter().filter(|r| r.enabled).count()
+    );
+}
+
+#[test]
+fn uninstall_keep_ssot_leaves_files_for_scan_and_reimport() {
+    let _guard = lock_test_mutex();
+    reset_test_fs();
+    let home = ensure_test_home();
+
+    write_skill_md(
+        &home.join(".claude").join("skills").join("soft"),
+        "Soft",
+        "kept on uninstall",
+    );
+    SkillService::import_from_apps(vec!["soft".to_string()]).expect("import soft");
+    SkillService::uninstall_with_options("soft", true).expect("uninstall keeping ssot");
+
+    let ssot = SkillService::get_ssot_dir().expect("ssot dir").join("soft");
+    assert!(ssot.join("SKILL.md").exists());
+    assert!(!home.join(".claude").join("skills").join("soft").exists());
+    assert!(SkillService::list_installed()
+        .expect("list installed")
+        .is_empty());
+
+    let unmanaged = SkillService::scan_unmanaged().expect("scan unmanaged");
+    let soft = unmanaged
+        .iter()
+        .find(|s| s.directory == "soft")
+        .expect("ssot-only dir is reported");
+    assert_eq!(soft.found_in, vec!["ssot".to_string()]);
+    assert_eq!(soft.name, "Soft");
+
+    let imported = SkillService::import_from_apps(vec!["soft".to_string()]).expect("reimport");
+    assert_eq!(imported.len(), 1);
+    assert!(imported[0].apps.is_empty());
+}
+
+#[test]
+fn install_many_continues_past_failures_and_keeps_order() {
+    let _guard = lock_test_mutex();
+    reset_test_fs();
+    let home = ensure_test_home();
+
+    let repo = SkillService::list_repos()
+        .expect("list repos")
+        .into_iter()
+        .find(|r| r.enabled)
+        .expect("enabled default repo");
+    let cache = SkillService::repo_cache_dir(&repo).join("skills");
+    write_skill_md(&cache.join("bulk-a"), "Bulk A", "first");
+    write_skill_md(&cache.join("bulk-b"), "Bulk B", "second");
+
+    let list = home.join("skills.txt");
+    std::fs::write(
+        &list,
+        format!(
+            "# team skills\n{owner}/{name}:bulk-a\n\nmissing/repo:nope\n{owner}/{name}:bulk-b\n{owner}/{name}:bulk-a\n",
+            owner = repo.owner,
+            name = repo.name
+        ),
+    )
+    .expect("write list");
+    let specs = SkillService::read_spec_list(&list).expect("read list");
+    assert_eq!(specs.len(), 3);
+
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .expect("build runtime");
+    let events = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+    let sink_events = events.clone();
+    let service = SkillService::new()
+        .expect("create skill service")
+        .with_progress(std::sync::Arc::new(move |event: SkillProgress| {
+            let name = serde_json::to_value(&event).expect("event json")["event"]
+                .as_str()
+                .expect("event tag")
+                .to_string();
+            sink_events.lock().expect("events lock").push(name);
+        }));
+    // 离线模式：缓存中的 spec 可安装，未知仓库快速失败
+    std::env::set_var("CC_SWITCH_OFFLINE", "1");
+    let results = runtime.block_on(service.install_many(
+        specs,
+        &AppType::Claude,
+        InstallOptions::default(),
+        2,
+    ));
+    std::env::remove_var("CC_SWITCH_OFFLINE");
+
+    assert_eq!(results.len(), 3);
+    assert_eq!(results[0].directory.as_deref(), Some("bulk-a"));
+    assert!(results[1].error.is_some());
+    assert_eq!(results[2].directory.as_deref(), Some("bulk-b"));
+    assert_eq!(results[2].outcome, Some(InstallOutcome::Installed));
+    let events = events.lock().expect("events lock").clone();
+    assert_eq!(events.iter().filter(|e| *e == "start").count(), 3);
+    assert_eq!(events.iter().filter(|e| *e == "done").count(), 2);
+    assert_eq!(events.iter().filter(|e| *e == "failed").count(), 1);
+    assert_eq!(
+        SkillService::list_installed()
+            .expect("list installed")
+            .len(),
+        2
+    );
+}
+
+#[test]
+fn normalize_casing_merges_records_and_resyncs_canonical_dir() {
+    let _guard = lock_test_mutex();
+    reset_test_fs();
+    let home = ensure_test_home();
+
+    write_skill_md(
+        &home.join(".claude").join("skills").join("Review"),
+        "Review",
+        "upper",
+    );
+    write_skill_md(
+        &home.join(".codex").join("skills").join("review"),
+        "Review",
+        "lower",
+    );
+    SkillService::import_from_apps(vec!["Review".to_string(), "review".to_string()])
+        .expect("import both spellings");
+
+    let planned = SkillService::normalize_casing(true).expect("dry run");
+    assert_eq!(planned.len(), 1);
+    assert_eq!(planned[0].canonical, "Review");
+    assert_eq!(planned[0].merged, vec!["review".to_string()]);
+    assert_eq!(planned[0].differing, vec!["review".to_string()]);
+    assert!(planned[0].backup_dir.is_none());
+    assert!(home.join(".codex").join("skills").join("review").exists());
+
+    let fixes = SkillService::normalize_casing(false).expect("normalize");
+    assert_eq!(fixes[0].records_merged, 1);
+    assert_eq!(fixes[0].resynced, vec![AppType::Claude, AppType::Codex]);
+    let backup = fixes[0]
+        .backup_dir
+        .clone()
+        .expect("removed dirs are backed up");
+    let saved = std::fs::read_to_string(backup.join("skills").join("review").join("SKILL.md"))
+        .expect("backed-up variant");
+    assert!(saved.contains("lower"));
+
+    let installed = SkillService::list_installed().expect("list installed");
+    assert_eq!(installed.len(), 1);
+    assert_eq!(installed[0].directory, "Review");
+    assert!(installed[0].apps.claude && installed[0].apps.codex);
+    let ssot = SkillService::get_ssot_dir().expect("ssot dir");
+    assert!(ssot.join("Review").exists() && !ssot.join("review").exists());
+    assert!(home.join(".codex").join("skills").join("Review").exists());
+    assert!(!home.join(".codex").join("skills").join("review").exists());
+    assert!(SkillService::normalize_casing(false)
+        .expect("second run")
+        .is_empty());
+}
+
+#[test]
+fn config_dir_flag_and_env_relocate_skill_state() {
+    let _guard = lock_test_mutex();
+    reset_test_fs();
+    let home = ensure_test_home();
+    let env_dir = home.join("alt-env");
+    let flag_dir = home.join("alt-flag");
+    for dir in [&env_dir, &flag_dir] {
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    // CC_SWITCH_CONFIG_DIR moves the database and SSOT out of ~/.cc-switch.
+    std::env::set_var("CC_SWITCH_CONFIG_DIR", &env_dir);
+    let ssot = SkillService::get_ssot_dir();
+    let repos_in_env_dir = SkillService::list_repos();
+    std::env::remove_var("CC_SWITCH_CONFIG_DIR");
+    assert_eq!(ssot.expect("ssot dir"), env_dir.join("skills"));
+    assert!(!repos_in_env_dir.expect("list repos").is_empty());
+    assert!(env_dir.join("cc-switch.db").exists());
+    assert!(!home.join(".cc-switch").join("cc-switch.db").exists());
+
+    // --config-dir wins over the environment variable.
+    let output = std::process::Command::new(env!("CARGO_BIN_EXE_cc-switch"))
+        .env("HOME", home)
+        .env("USERPROFILE", home)
+        .env("CC_SWITCH_CONFIG_DIR", &env_dir)
+        .arg("--offline")
+        .arg("--config-dir")
+        .arg(&flag_dir)
+        .args(["skills", "repos", "add", "someone/skill-pack@dev"])
+        .output()
+        .expect("run cc-switch");
+    assert!(
+        output.status.success(),
+        "repos add failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(flag_dir.join("cc-switch.db").exists());
+
+    let tracked = |dir: &std::path::Path| {
+        std::env::set_var("CC_SWITCH_CONFIG_DIR", dir);
+        let branch = SkillService::tracked_branch("someone", "skill-pack");
+        std::env::remove_var("CC_SWITCH_CONFIG_DIR");
+        branch.expect("tracked branch")
+    };
+    assert_eq!(tracked(&flag_dir).as_deref(), Some("dev"));
+    assert_eq!(tracked(&env_dir), None);
+
+    reset_test_fs();
+}